@@ -60,6 +60,8 @@ enum Action {
   ExportDecisionTable(String, String),
   /// Recognize decision table.
   RecognizeDecisionTable(String),
+  /// Query decision table.
+  QueryDecisionTable(String, String),
   /// Parse `DMN` model`.
   ParseDmnModel(String, String),
   /// Evaluate `DMN` model`.
@@ -70,6 +72,10 @@ enum Action {
   ExportDmnModel(String, String),
   /// Start `dmntk` as a service.
   StartService(Option<String>, Option<String>, Option<String>),
+  /// Start `dmntk` as a language server.
+  StartLanguageServer,
+  /// Start interactive `FEEL` shell.
+  StartRepl,
   /// Generate examples.
   GenerateExamples,
   /// Do nothing, no action was specified.
@@ -115,6 +121,10 @@ pub async fn do_action() -> std::io::Result<()> {
       recognize_decision_table(&dectab_file_name);
       Ok(())
     }
+    Action::QueryDecisionTable(output, dectab_file_name) => {
+      query_decision_table(&output, &dectab_file_name);
+      Ok(())
+    }
     Action::ParseDmnModel(dmn_file_name, color) => {
       parse_dmn_model(&dmn_file_name, &color);
       Ok(())
@@ -132,6 +142,8 @@ pub async fn do_action() -> std::io::Result<()> {
       Ok(())
     }
     Action::StartService(opt_host, opt_port, opt_dir) => dmntk_server::start_server(opt_host, opt_port, opt_dir).await,
+    Action::StartLanguageServer => crate::lsp::run_language_server(),
+    Action::StartRepl => crate::repl::run_repl(),
     Action::GenerateExamples => {
       generate_examples();
       Ok(())
@@ -187,11 +199,16 @@ fn get_matches() -> ArgMatches {
       .arg(arg!(<HTML_FILE>).help("Output HTML file").required(true).index(2)))
     .subcommand(App::new("rdt").about("Recognize Decision Table").display_order(14)
       .arg(arg!(<DECTAB_FILE>).help("File containing decision table to be recognized").required(true).index(1)))
+    .subcommand(App::new("qdt").about("Query Decision Table").display_order(18)
+      .arg(arg!(-o --output).help("FEEL expression of the output, for which input conditions are searched").required(true).takes_value(true).display_order(1))
+      .arg(arg!(<DECTAB_FILE>).help("File containing decision table to be queried").required(true).index(1)))
     .subcommand(App::new("srv").about("Run DMNTK as a service").display_order(1)
       .arg(arg!(-H --host).help("Host name").takes_value(true).display_order(1))
       .arg(arg!(-P --port).help("Port number").takes_value(true).display_order(2))
       .arg(arg!(-D --dir).help("Directory where DMN files are searched").takes_value(true).display_order(3)))
     .subcommand(App::new("exs").about("Generate examples in current directory").display_order(15))
+    .subcommand(App::new("lsp").about("Run DMNTK as a language server over standard input and output").display_order(16))
+    .subcommand(App::new("repl").about("Run interactive FEEL shell").display_order(17))
     .get_matches()
 }
 
@@ -259,6 +276,13 @@ fn get_cli_action() -> Action {
   if let Some(matches) = matches.subcommand_matches("rdt") {
     return Action::RecognizeDecisionTable(matches.value_of("DECTAB_FILE").unwrap_or("unknown.dtb").to_string());
   }
+  // query decision table subcommand
+  if let Some(matches) = matches.subcommand_matches("qdt") {
+    return Action::QueryDecisionTable(
+      matches.value_of("output").unwrap_or("null").to_string(),
+      matches.value_of("DECTAB_FILE").unwrap_or("unknown.dtb").to_string(),
+    );
+  }
   // parse DMN model subcommand
   if let Some(matches) = matches.subcommand_matches("pdm") {
     return Action::ParseDmnModel(
@@ -298,6 +322,14 @@ fn get_cli_action() -> Action {
       matches.value_of("dir").map(|dir| dir.to_string()),
     );
   }
+  // start language server subcommand
+  if let Some(_matches) = matches.subcommand_matches("lsp") {
+    return Action::StartLanguageServer;
+  }
+  // start interactive shell subcommand
+  if let Some(_matches) = matches.subcommand_matches("repl") {
+    return Action::StartRepl;
+  }
   // generate examples
   if let Some(_matches) = matches.subcommand_matches("exs") {
     return Action::GenerateExamples;
@@ -510,6 +542,42 @@ fn recognize_decision_table(dtb_file_name: &str) {
   }
 }
 
+/// Prints the input conditions under which the decision table loaded from text file produces specified output.
+fn query_decision_table(output: &str, dectab_file_name: &str) {
+  let output = match dmntk_feel_parser::parse_expression(&Scope::default(), output, false) {
+    Ok(node) => match dmntk_evaluator::evaluate(&Scope::default(), &node) {
+      Ok(output) => output,
+      Err(reason) => {
+        println!("evaluating output failed with reason: {}", reason);
+        return;
+      }
+    },
+    Err(reason) => {
+      println!("parsing output failed with reason: {}", reason);
+      return;
+    }
+  };
+  let dtb_file_content = match std::fs::read_to_string(dectab_file_name) {
+    Ok(dtb_file_content) => dtb_file_content,
+    Err(reason) => {
+      println!("loading decision table file `{}` failed with reason: {}", dectab_file_name, reason);
+      return;
+    }
+  };
+  let decision_table = match dmntk_recognizer::build(&dtb_file_content) {
+    Ok(decision_table) => decision_table,
+    Err(reason) => {
+      println!("building decision table failed with reason: {}", reason);
+      return;
+    }
+  };
+  match dmntk_evaluator::query_decision_table(&decision_table, &output) {
+    Ok(rule_conditions) if rule_conditions.is_empty() => println!("no rules produce output {}", output),
+    Ok(rule_conditions) => rule_conditions.iter().for_each(|rule_conditions| println!("{}", rule_conditions)),
+    Err(reason) => println!("querying decision table failed with reason: {}", reason),
+  }
+}
+
 /// Parses `DMN` model loaded from XML file.
 fn parse_dmn_model(dmn_file_name: &str, color: &str) {
   let use_color = color.to_lowercase() != "never";
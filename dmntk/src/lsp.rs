@@ -0,0 +1,637 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Language server for `FEEL` expressions, context files and `DMN` models.
+//!
+//! The server implements the subset of the Language Server Protocol
+//! (diagnostics, completion, hover and go to definition) over standard input and output.
+
+use dmntk_feel::bif::bif_signatures;
+use dmntk_feel::context::FeelContext;
+use dmntk_feel::{FeelType, Scope, Span};
+use dmntk_model::model::{Definitions, DmnElement, NamedElement, RequiredVariable};
+use serde_json::{json, Value as JsonValue};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::rc::Rc;
+
+/// LSP diagnostic severity: error.
+const SEVERITY_ERROR: u8 = 1;
+/// LSP diagnostic severity: warning.
+const SEVERITY_WARNING: u8 = 2;
+/// LSP completion item kind: function.
+const COMPLETION_KIND_FUNCTION: u8 = 3;
+/// LSP completion item kind: variable.
+const COMPLETION_KIND_VARIABLE: u8 = 6;
+/// JSON-RPC error code: method not found.
+const METHOD_NOT_FOUND: i32 = -32601;
+
+/// Kinds of documents handled by the language server.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum DocumentKind {
+  /// `FEEL` expression (`.feel` file).
+  Feel,
+  /// `FEEL` context (`.ctx` file).
+  Context,
+  /// `DMN` model (`.dmn` file).
+  Dmn,
+  /// Any other document.
+  Other,
+}
+
+impl DocumentKind {
+  /// Returns the kind of the document with specified URI.
+  fn from_uri(uri: &str) -> Self {
+    if uri.ends_with(".feel") {
+      Self::Feel
+    } else if uri.ends_with(".ctx") {
+      Self::Context
+    } else if uri.ends_with(".dmn") {
+      Self::Dmn
+    } else {
+      Self::Other
+    }
+  }
+}
+
+/// Named element of the `DMN` model that may be a target of go to definition.
+struct ModelElement {
+  /// Name of the element.
+  name: String,
+  /// Name of the XML tag defining the element.
+  tag: &'static str,
+  /// Description displayed on hover.
+  description: String,
+}
+
+/// Results of the analysis of the document, reused until the document or its context file changes.
+struct Analysis {
+  /// Scope defined by the context file, or the reason why the context could not be evaluated.
+  scope: std::result::Result<Scope, String>,
+  /// Named elements of the `DMN` model.
+  model_elements: Vec<ModelElement>,
+  /// Type inferred for the `FEEL` expression.
+  expression_type: Option<FeelType>,
+  /// Diagnostics for the document.
+  diagnostics: Vec<JsonValue>,
+}
+
+/// Language server state.
+#[derive(Default)]
+struct LanguageServer {
+  /// Texts of open documents indexed by document URI.
+  documents: HashMap<String, String>,
+  /// Analyses of documents indexed by document URI, prepared on first use.
+  analyses: RefCell<HashMap<String, Rc<Analysis>>>,
+  /// Flag indicating that the client requested a shutdown.
+  shutdown_requested: bool,
+}
+
+/// Runs the language server, communicating over standard input and output.
+pub fn run_language_server() -> std::io::Result<()> {
+  let stdin = std::io::stdin();
+  let stdout = std::io::stdout();
+  serve(&mut stdin.lock(), &mut stdout.lock())
+}
+
+/// Serves messages read from `reader`, responses and notifications are written to `writer`.
+pub fn serve(reader: &mut impl BufRead, writer: &mut impl Write) -> std::io::Result<()> {
+  let mut server = LanguageServer::default();
+  while let Some(message) = read_message(reader)? {
+    let method = message.get("method").and_then(JsonValue::as_str).unwrap_or_default().to_string();
+    if method == "exit" {
+      break;
+    }
+    let params = message.get("params").cloned().unwrap_or(JsonValue::Null);
+    if let Some(id) = message.get("id").cloned() {
+      // request, a response is always sent back
+      let response = match server.handle_request(&method, &params) {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err(message) => json!({"jsonrpc": "2.0", "id": id, "error": {"code": METHOD_NOT_FOUND, "message": message}}),
+      };
+      write_message(writer, &response)?;
+    } else {
+      // notification, may result in publishing diagnostics
+      for notification in server.handle_notification(&method, &params) {
+        write_message(writer, &notification)?;
+      }
+    }
+  }
+  if server.shutdown_requested {
+    Ok(())
+  } else {
+    Err(std::io::Error::new(
+      std::io::ErrorKind::InvalidData,
+      "language server exited without shutdown request",
+    ))
+  }
+}
+
+/// Reads a single message, returns `None` when the input stream is closed.
+fn read_message(reader: &mut impl BufRead) -> std::io::Result<Option<JsonValue>> {
+  let mut content_length = 0_usize;
+  loop {
+    let mut header = String::new();
+    if reader.read_line(&mut header)? == 0 {
+      return Ok(None);
+    }
+    let header = header.trim();
+    if header.is_empty() {
+      break;
+    }
+    if let Some(value) = header.strip_prefix("Content-Length:") {
+      content_length = value
+        .trim()
+        .parse()
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid content length"))?;
+    }
+  }
+  let mut content = vec![0; content_length];
+  reader.read_exact(&mut content)?;
+  serde_json::from_slice(&content)
+    .map(Some)
+    .map_err(|reason| std::io::Error::new(std::io::ErrorKind::InvalidData, reason))
+}
+
+/// Writes a single message.
+fn write_message(writer: &mut impl Write, message: &JsonValue) -> std::io::Result<()> {
+  let content = message.to_string();
+  write!(writer, "Content-Length: {}\r\n\r\n{}", content.len(), content)?;
+  writer.flush()
+}
+
+impl LanguageServer {
+  /// Handles a request, returns the result or an error message.
+  fn handle_request(&mut self, method: &str, params: &JsonValue) -> std::result::Result<JsonValue, String> {
+    match method {
+      "initialize" => Ok(json!({
+        "capabilities": {
+          "textDocumentSync": 1,
+          "completionProvider": {},
+          "hoverProvider": true,
+          "definitionProvider": true
+        },
+        "serverInfo": {"name": "dmntk", "version": crate::DMNTK_VERSION}
+      })),
+      "shutdown" => {
+        self.shutdown_requested = true;
+        Ok(JsonValue::Null)
+      }
+      "textDocument/completion" => Ok(self.completion(&text_document_uri(params))),
+      "textDocument/hover" => Ok(self.hover(&text_document_uri(params), position(params))),
+      "textDocument/definition" => Ok(self.definition(&text_document_uri(params), position(params))),
+      other => Err(format!("unsupported method '{}'", other)),
+    }
+  }
+
+  /// Handles a notification, returns notifications to be sent back to the client.
+  fn handle_notification(&mut self, method: &str, params: &JsonValue) -> Vec<JsonValue> {
+    let uri = text_document_uri(params);
+    match method {
+      "textDocument/didOpen" => {
+        let text = params["textDocument"]["text"].as_str().unwrap_or_default().to_string();
+        self.documents.insert(uri.clone(), text);
+        self.invalidate(&uri);
+        self.publish_diagnostics(&uri)
+      }
+      "textDocument/didChange" => {
+        // only full document synchronization is supported
+        if let Some(text) = params["contentChanges"]
+          .as_array()
+          .and_then(|changes| changes.last())
+          .and_then(|change| change["text"].as_str())
+        {
+          self.documents.insert(uri.clone(), text.to_string());
+        }
+        self.invalidate(&uri);
+        self.publish_diagnostics(&uri)
+      }
+      "textDocument/didClose" => {
+        self.documents.remove(&uri);
+        self.invalidate(&uri);
+        vec![json!({"jsonrpc": "2.0", "method": "textDocument/publishDiagnostics", "params": {"uri": uri, "diagnostics": []}})]
+      }
+      _ => vec![],
+    }
+  }
+
+  /// Prepares diagnostics for the document and for all open documents depending on it.
+  fn publish_diagnostics(&self, uri: &str) -> Vec<JsonValue> {
+    let mut uris = vec![uri.to_string()];
+    if let Some(feel_uri) = feel_uri(uri) {
+      if self.documents.contains_key(&feel_uri) {
+        uris.push(feel_uri);
+      }
+    }
+    uris
+      .iter()
+      .map(|uri| json!({"jsonrpc": "2.0", "method": "textDocument/publishDiagnostics", "params": {"uri": uri, "diagnostics": self.analysis(uri).diagnostics}}))
+      .collect()
+  }
+
+  /// Discards the analyses of the changed document and of the documents depending on it.
+  fn invalidate(&mut self, uri: &str) {
+    let analyses = self.analyses.get_mut();
+    analyses.remove(uri);
+    if let Some(feel_uri) = feel_uri(uri) {
+      analyses.remove(&feel_uri);
+    }
+  }
+
+  /// Returns the analysis of the document, the document is analyzed only once until it changes.
+  fn analysis(&self, uri: &str) -> Rc<Analysis> {
+    let cached = self.analyses.borrow().get(uri).cloned();
+    if let Some(analysis) = cached {
+      return analysis;
+    }
+    let analysis = Rc::new(self.analyze(uri));
+    self.analyses.borrow_mut().insert(uri.to_string(), Rc::clone(&analysis));
+    analysis
+  }
+
+  /// Returns the analysis of the document defining the scope of the specified document.
+  ///
+  /// `FEEL` expressions are parsed in the scope of the context file with the same name,
+  /// names defined in context files are in scope of the context file itself.
+  fn scope_analysis(&self, uri: &str) -> Rc<Analysis> {
+    if DocumentKind::from_uri(uri) == DocumentKind::Feel {
+      self.analysis(&context_uri(uri))
+    } else {
+      self.analysis(uri)
+    }
+  }
+
+  /// Analyzes the document, type errors in expressions are reported at the location of failing sub-expression.
+  fn analyze(&self, uri: &str) -> Analysis {
+    let text = self.text(uri);
+    let whole = whole_range(&text);
+    let mut analysis = Analysis {
+      scope: Ok(FeelContext::default().into()),
+      model_elements: vec![],
+      expression_type: None,
+      diagnostics: vec![],
+    };
+    match DocumentKind::from_uri(uri) {
+      DocumentKind::Feel => match &self.scope_analysis(uri).scope {
+        Ok(scope) => match dmntk_feel_parser::parse_expression_with_spans(scope, &text, false) {
+          Ok((node, spans)) => {
+            let (feel_type, type_errors) = dmntk_evaluator::infer_type_with_spans(scope, &node, &spans);
+            for (type_error, span) in type_errors {
+              analysis
+                .diagnostics
+                .push(diagnostic(span_range(&text, span), SEVERITY_WARNING, type_error.to_string()));
+            }
+            analysis.expression_type = Some(feel_type);
+          }
+          Err(reason) => analysis.diagnostics.push(diagnostic(whole, SEVERITY_ERROR, reason.to_string())),
+        },
+        Err(reason) => analysis
+          .diagnostics
+          .push(diagnostic(whole, SEVERITY_ERROR, format!("evaluating context failed with reason: {}", reason))),
+      },
+      DocumentKind::Context => {
+        if !text.trim().is_empty() {
+          match dmntk_evaluator::evaluate_context(&Scope::default(), &text) {
+            Ok(ctx) => analysis.scope = Ok(ctx.into()),
+            Err(reason) => {
+              analysis.diagnostics.push(diagnostic(whole, SEVERITY_ERROR, reason.to_string()));
+              analysis.scope = Err(reason.to_string());
+            }
+          }
+        }
+      }
+      DocumentKind::Dmn => match dmntk_model::parse(&text) {
+        Ok(definitions) => match dmntk_evaluator::ModelEvaluator::new(&definitions) {
+          Ok(model_evaluator) => {
+            let decision_evaluator = model_evaluator.decision_evaluator().ok();
+            if let Some(decision_evaluator) = &decision_evaluator {
+              for decision in definitions.decisions() {
+                let decision_id = decision.id().clone().unwrap_or_default();
+                let range = element_range(&text, "decision", decision.name()).unwrap_or_else(|| whole.clone());
+                for type_error in decision_evaluator.get_type_errors(&decision_id).unwrap_or_default() {
+                  analysis.diagnostics.push(diagnostic(range.clone(), SEVERITY_WARNING, type_error.to_string()));
+                }
+              }
+            }
+            analysis.model_elements = model_elements(&definitions, |decision_id| {
+              decision_evaluator
+                .as_ref()
+                .and_then(|decision_evaluator| decision_evaluator.get_result_type(decision_id).cloned())
+            });
+          }
+          Err(reason) => {
+            analysis.diagnostics.push(diagnostic(whole, SEVERITY_ERROR, reason.to_string()));
+            analysis.model_elements = model_elements(&definitions, |_| None);
+          }
+        },
+        Err(reason) => analysis.diagnostics.push(diagnostic(whole, SEVERITY_ERROR, reason.to_string())),
+      },
+      DocumentKind::Other => {}
+    }
+    analysis
+  }
+
+  /// Returns completion items for the document.
+  fn completion(&self, uri: &str) -> JsonValue {
+    let mut items = vec![];
+    let mut names = self.names_in_scope(uri);
+    names.sort();
+    for name in names {
+      items.push(json!({"label": name, "kind": COMPLETION_KIND_VARIABLE}));
+    }
+    for signature in bif_signatures() {
+      items.push(json!({"label": signature.name, "kind": COMPLETION_KIND_FUNCTION, "detail": signature.to_string()}));
+    }
+    JsonValue::Array(items)
+  }
+
+  /// Returns hover information for the name at specified position.
+  fn hover(&self, uri: &str, position: (usize, usize)) -> JsonValue {
+    let text = self.text(uri);
+    let analysis = self.analysis(uri);
+    let mut candidates = self.names_in_scope(uri);
+    candidates.extend(bif_signatures().iter().map(|signature| signature.name.to_string()));
+    let contents = if let Some(name) = name_at(&text, position, &candidates) {
+      if let Some(element) = analysis.model_elements.iter().find(|element| element.name == name) {
+        element.description.clone()
+      } else if let Some(signature) = bif_signatures().iter().find(|signature| signature.name == name) {
+        format!("```\n{}\n```", signature)
+      } else if let Ok(scope) = &self.scope_analysis(uri).scope {
+        let feel_type = scope.get_entry(&name.as_str().into()).map(|value| value.type_of()).unwrap_or(FeelType::Any);
+        format!("`{}`: {}", name, feel_type)
+      } else {
+        return JsonValue::Null;
+      }
+    } else if let Some(feel_type) = &analysis.expression_type {
+      // not a name, display the type inferred for the whole expression
+      format!("expression: {}", feel_type)
+    } else {
+      return JsonValue::Null;
+    };
+    json!({"contents": {"kind": "markdown", "value": contents}})
+  }
+
+  /// Returns the location of the definition of the name at specified position.
+  fn definition(&self, uri: &str, position: (usize, usize)) -> JsonValue {
+    let text = self.text(uri);
+    let candidates = self.names_in_scope(uri);
+    if let Some(name) = name_at(&text, position, &candidates) {
+      match DocumentKind::from_uri(uri) {
+        DocumentKind::Dmn => {
+          for element in &self.analysis(uri).model_elements {
+            if element.name == name {
+              if let Some(range) = element_range(&text, element.tag, &name) {
+                return json!({"uri": uri, "range": range});
+              }
+            }
+          }
+        }
+        DocumentKind::Feel => {
+          let ctx_uri = context_uri(uri);
+          if let Some(range) = entry_range(&self.text(&ctx_uri), &name) {
+            return json!({"uri": ctx_uri, "range": range});
+          }
+        }
+        DocumentKind::Context => {
+          if let Some(range) = entry_range(&text, &name) {
+            return json!({"uri": uri, "range": range});
+          }
+        }
+        DocumentKind::Other => {}
+      }
+    }
+    JsonValue::Null
+  }
+
+  /// Returns the text of the document, when the document is not open, the text is loaded from file.
+  fn text(&self, uri: &str) -> String {
+    self
+      .documents
+      .get(uri)
+      .cloned()
+      .unwrap_or_else(|| std::fs::read_to_string(uri_to_path(uri)).unwrap_or_default())
+  }
+
+  /// Returns names available in the scope of the document.
+  fn names_in_scope(&self, uri: &str) -> Vec<String> {
+    if DocumentKind::from_uri(uri) == DocumentKind::Dmn {
+      self.analysis(uri).model_elements.iter().map(|element| element.name.clone()).collect()
+    } else if let Ok(scope) = &self.scope_analysis(uri).scope {
+      scope.flatten_keys().into_iter().filter(|key| !key.contains(" . ")).collect()
+    } else {
+      vec![]
+    }
+  }
+}
+
+/// Returns named elements of the `DMN` model, decisions are described with inferred result types.
+fn model_elements(definitions: &Definitions, result_type: impl Fn(&str) -> Option<FeelType>) -> Vec<ModelElement> {
+  let mut elements = vec![];
+  for decision in definitions.decisions() {
+    let result_type = result_type(decision.id().as_deref().unwrap_or_default()).unwrap_or(FeelType::Any);
+    elements.push(ModelElement {
+      name: decision.name().to_string(),
+      tag: "decision",
+      description: format!("decision `{}`: {}", decision.name(), result_type),
+    });
+  }
+  for input_data in definitions.input_data() {
+    elements.push(ModelElement {
+      name: input_data.name().to_string(),
+      tag: "inputData",
+      description: format!("input data `{}`: {}", input_data.name(), type_ref(input_data.variable().type_ref())),
+    });
+  }
+  for business_knowledge_model in definitions.business_knowledge_models() {
+    elements.push(ModelElement {
+      name: business_knowledge_model.name().to_string(),
+      tag: "businessKnowledgeModel",
+      description: format!(
+        "business knowledge model `{}`: {}",
+        business_knowledge_model.name(),
+        type_ref(business_knowledge_model.variable().type_ref())
+      ),
+    });
+  }
+  for decision_service in definitions.decision_services() {
+    elements.push(ModelElement {
+      name: decision_service.name().to_string(),
+      tag: "decisionService",
+      description: format!(
+        "decision service `{}`: {}",
+        decision_service.name(),
+        type_ref(decision_service.variable().type_ref())
+      ),
+    });
+  }
+  elements
+}
+
+/// Returns the type reference or `Any` when not specified.
+fn type_ref(type_ref: &Option<String>) -> &str {
+  type_ref.as_deref().unwrap_or("Any")
+}
+
+/// Returns the URI of the context file for `FEEL` expression document.
+fn context_uri(uri: &str) -> String {
+  format!("{}.ctx", uri.trim_end_matches(".feel"))
+}
+
+/// Returns the URI of `FEEL` expression document for the context file, expressions
+/// are parsed in the scope of context files with the same name.
+fn feel_uri(uri: &str) -> Option<String> {
+  uri.strip_suffix(".ctx").map(|uri| format!("{}.feel", uri))
+}
+
+/// Converts the `file` URI into file path.
+fn uri_to_path(uri: &str) -> String {
+  uri.trim_start_matches("file://").replace("%20", " ")
+}
+
+/// Returns the URI of the text document from request parameters.
+fn text_document_uri(params: &JsonValue) -> String {
+  params["textDocument"]["uri"].as_str().unwrap_or_default().to_string()
+}
+
+/// Returns the position (line, character) from request parameters.
+fn position(params: &JsonValue) -> (usize, usize) {
+  let line = params["position"]["line"].as_u64().unwrap_or_default() as usize;
+  let character = params["position"]["character"].as_u64().unwrap_or_default() as usize;
+  (line, character)
+}
+
+/// Creates a diagnostic.
+fn diagnostic(range: JsonValue, severity: u8, message: String) -> JsonValue {
+  json!({"range": range, "severity": severity, "source": "dmntk", "message": message})
+}
+
+/// Creates a range.
+fn range(line: usize, start: usize, end: usize) -> JsonValue {
+  json!({"start": {"line": line, "character": start}, "end": {"line": line, "character": end}})
+}
+
+/// Returns the range covering the whole text.
+fn whole_range(text: &str) -> JsonValue {
+  let lines: Vec<&str> = text.lines().collect();
+  let last_line = lines.len().saturating_sub(1);
+  let last_character = lines.last().map(|line| utf16_len(line)).unwrap_or_default();
+  json!({"start": {"line": 0, "character": 0}, "end": {"line": last_line, "character": last_character}})
+}
+
+/// Returns the range of the span, character offsets are converted into line and UTF-16 character positions.
+fn span_range(text: &str, span: Span) -> JsonValue {
+  json!({"start": text_position(text, span.start), "end": text_position(text, span.end)})
+}
+
+/// Returns the position of the character with specified offset.
+fn text_position(text: &str, offset: usize) -> JsonValue {
+  let (mut line, mut character) = (0, 0);
+  for ch in text.chars().take(offset) {
+    if ch == '\n' {
+      line += 1;
+      character = 0;
+    } else {
+      character += ch.len_utf16();
+    }
+  }
+  json!({"line": line, "character": character})
+}
+
+/// Returns the range of the `name` attribute of the XML element with specified tag and name.
+fn element_range(text: &str, tag: &str, name: &str) -> Option<JsonValue> {
+  let tag = format!("<{}", tag);
+  let attribute = format!("name=\"{}\"", name);
+  for (line_index, line) in text.lines().enumerate() {
+    if let Some(tag_offset) = line.find(&tag) {
+      if !line[tag_offset + tag.len()..].starts_with(|ch: char| ch.is_whitespace()) {
+        continue;
+      }
+      if let Some(offset) = line.find(&attribute) {
+        let start = utf16_len(&line[..offset]) + 6;
+        return Some(range(line_index, start, start + utf16_len(name)));
+      }
+    }
+  }
+  None
+}
+
+/// Returns the range of the context entry with specified name, defined in context file.
+fn entry_range(text: &str, name: &str) -> Option<JsonValue> {
+  for (line_index, line) in text.lines().enumerate() {
+    let mut search_offset = 0;
+    while let Some(offset) = line[search_offset..].find(name).map(|offset| offset + search_offset) {
+      let is_key = line[offset + name.len()..].trim_start().starts_with(':');
+      let is_start = line[..offset].trim_end().ends_with(['{', ',']) || line[..offset].trim().is_empty();
+      if is_key && is_start {
+        let start = utf16_len(&line[..offset]);
+        return Some(range(line_index, start, start + utf16_len(name)));
+      }
+      search_offset = offset + name.len();
+    }
+  }
+  None
+}
+
+/// Returns the longest candidate name found in the text at specified position.
+fn name_at(text: &str, position: (usize, usize), candidates: &[String]) -> Option<String> {
+  let (line_index, character) = position;
+  let line = text.lines().nth(line_index)?;
+  let mut found: Option<&String> = None;
+  for candidate in candidates {
+    if candidate.is_empty() || matches!(found, Some(found) if found.len() >= candidate.len()) {
+      continue;
+    }
+    for (offset, _) in line.match_indices(candidate.as_str()) {
+      let start = utf16_len(&line[..offset]);
+      let end = start + utf16_len(candidate);
+      let before = line[..offset].chars().last();
+      let after = line[offset + candidate.len()..].chars().next();
+      let is_whole_name = !matches!(before, Some(ch) if is_name_char(ch)) && !matches!(after, Some(ch) if is_name_char(ch));
+      if is_whole_name && start <= character && character <= end {
+        found = Some(candidate);
+        break;
+      }
+    }
+  }
+  found.cloned()
+}
+
+/// Returns `true` when the character may be a part of a name.
+fn is_name_char(ch: char) -> bool {
+  ch.is_alphanumeric() || ch == '_' || ch == '?'
+}
+
+/// Returns the length of the text in UTF-16 code units, as required by LSP positions.
+fn utf16_len(text: &str) -> usize {
+  text.chars().map(char::len_utf16).sum()
+}
@@ -40,13 +40,19 @@ extern crate dmntk_common;
 extern crate dmntk_evaluator;
 extern crate dmntk_feel;
 extern crate dmntk_feel_parser;
+extern crate dmntk_model;
 extern crate dmntk_recognizer;
 extern crate serde;
 extern crate serde_derive;
+extern crate serde_json;
 extern crate serde_yaml;
 
 mod actions;
 mod examples;
+mod lsp;
+mod repl;
+#[cfg(test)]
+mod tests;
 
 const DMNTK_VERSION: &str = env!("CARGO_PKG_VERSION");
 const DMNTK_DESCRIPTION: &str = env!("CARGO_PKG_DESCRIPTION");
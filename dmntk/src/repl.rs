@@ -0,0 +1,284 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Interactive `FEEL` shell (read-evaluate-print loop).
+
+use dmntk_evaluator::{EvaluationTrace, ModelEvaluator};
+use dmntk_feel::context::FeelContext;
+use dmntk_feel::values::Value;
+use dmntk_feel::{value_null, FeelType, FunctionBody, Name, Scope};
+use dmntk_model::model::{NamedElement, RequiredVariable};
+use std::io::{BufRead, Write};
+use std::sync::Arc;
+
+/// Prompt displayed before each input line.
+const PROMPT: &str = "feel> ";
+
+/// Name of the parameter of functions invoking decisions and decision services.
+const INPUT_DATA_PARAMETER: &str = "input data";
+
+/// Help displayed after `:help` command.
+const HELP: &str = r#"Type a FEEL expression to evaluate it, or one of the commands:
+  :let <name> = <expression>   evaluate the expression and bind the result to the name
+  :type <expression>           display the inferred and the actual type of the expression
+  :ast <expression>            display the abstract syntax tree of the expression
+  :trace                       switch on/off displaying values of all evaluated sub-expressions
+  :load <file>                 load context (.ctx) or DMN model (.dmn) file
+  :scope                       display names bound in scope
+  :help                        display this help
+  :quit                        exit the shell
+Decisions and decision services loaded from DMN model are invoked with input data context,
+e.g. Greeting Message({Full Name: "John Doe"}), business knowledge models are invoked with their parameters."#;
+
+/// State of the interactive shell.
+#[derive(Default)]
+pub struct Repl {
+  /// Persistent scope, names bound in this scope are visible in all evaluated expressions.
+  scope: Scope,
+  /// Trace of the evaluation, recorded when tracing is switched on.
+  trace: Option<EvaluationTrace>,
+}
+
+/// Runs the interactive shell, reading from standard input and printing to standard output.
+pub fn run_repl() -> std::io::Result<()> {
+  let stdin = std::io::stdin();
+  let mut reader = stdin.lock();
+  let stdout = std::io::stdout();
+  let mut writer = stdout.lock();
+  let mut repl = Repl::default();
+  writeln!(writer, "dmntk {}, type :help for help", crate::DMNTK_VERSION)?;
+  loop {
+    write!(writer, "{}", PROMPT)?;
+    writer.flush()?;
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+      writeln!(writer)?;
+      break;
+    }
+    let line = line.trim();
+    if line.is_empty() {
+      continue;
+    }
+    if line == ":quit" || line == ":q" {
+      break;
+    }
+    writeln!(writer, "{}", repl.execute(line))?;
+  }
+  Ok(())
+}
+
+impl Repl {
+  /// Executes a single input line, returns the text to be displayed.
+  pub fn execute(&mut self, line: &str) -> String {
+    let (command, argument) = if line.starts_with(':') {
+      line
+        .split_once(char::is_whitespace)
+        .map(|(command, argument)| (command, argument.trim()))
+        .unwrap_or((line, ""))
+    } else {
+      ("", line)
+    };
+    match command {
+      "" => match self.evaluate(argument) {
+        Ok(value) => self.traced(value.to_string()),
+        Err(reason) => reason,
+      },
+      ":let" => {
+        if let Some((name, expression)) = argument.split_once('=') {
+          let name = Name::from(name);
+          match self.evaluate(expression.trim()) {
+            Ok(value) => {
+              let text = format!("{} = {}", name, value);
+              self.scope.set_entry(&name, value);
+              self.traced(text)
+            }
+            Err(reason) => reason,
+          }
+        } else {
+          "expected :let <name> = <expression>".to_string()
+        }
+      }
+      ":type" => match dmntk_feel_parser::parse_expression(&self.scope, argument, false) {
+        Ok(node) => {
+          let (inferred_type, type_errors) = dmntk_evaluator::infer_type(&self.scope, &node);
+          let mut text = format!("inferred type: {}", inferred_type);
+          for type_error in type_errors {
+            text.push_str(&format!("\n  type error: {}", type_error));
+          }
+          if let Ok(value) = dmntk_evaluator::evaluate(&self.scope, &node) {
+            text.push_str(&format!("\n  actual type: {}", value.type_of()));
+          }
+          text
+        }
+        Err(reason) => format!("parsing expression failed with reason: {}", reason),
+      },
+      ":ast" => match dmntk_feel_parser::parse_expression(&self.scope, argument, false) {
+        Ok(node) => node.to_string().trim_end().to_string(),
+        Err(reason) => format!("parsing expression failed with reason: {}", reason),
+      },
+      ":trace" => {
+        self.trace = if self.trace.is_some() { None } else { Some(EvaluationTrace::default()) };
+        format!("tracing is {}", if self.trace.is_some() { "on" } else { "off" })
+      }
+      ":load" => self.load(argument),
+      ":scope" => {
+        let mut names = self
+          .scope
+          .peek()
+          .get_entries()
+          .iter()
+          .map(|(name, _)| name.to_string())
+          .collect::<Vec<String>>();
+        names.sort();
+        names.join("\n")
+      }
+      ":help" => HELP.to_string(),
+      other => format!("unknown command '{}', type :help for help", other),
+    }
+  }
+
+  /// Parses and evaluates the expression in the persistent scope,
+  /// `null` results report the location of the failing sub-expression.
+  fn evaluate(&self, expression: &str) -> Result<Value, String> {
+    let (node, spans) = dmntk_feel_parser::parse_expression_with_spans(&self.scope, expression, false)
+      .map_err(|reason| format!("parsing expression failed with reason: {}", reason))?;
+    let evaluator = match &self.trace {
+      Some(trace) => dmntk_evaluator::prepare_with_trace(&node, &spans, expression, trace),
+      None => dmntk_evaluator::prepare_with_spans(&node, &spans, expression),
+    }
+    .map_err(|reason| format!("evaluating expression failed with reason: {}", reason))?;
+    Ok(evaluator(&self.scope))
+  }
+
+  /// Prepends the trace of the last evaluation to the text, when tracing is switched on.
+  fn traced(&self, text: String) -> String {
+    if let Some(trace) = &self.trace {
+      let mut lines = trace.take().iter().map(|entry| entry.to_string()).collect::<Vec<String>>();
+      lines.push(text);
+      lines.join("\n")
+    } else {
+      text
+    }
+  }
+
+  /// Loads the content of the context or model file into the persistent scope.
+  fn load(&mut self, file_name: &str) -> String {
+    let content = match std::fs::read_to_string(file_name) {
+      Ok(content) => content,
+      Err(reason) => return format!("loading file `{}` failed with reason: {}", file_name, reason),
+    };
+    if file_name.ends_with(".dmn") {
+      match load_model(&content) {
+        Ok(ctx) => {
+          let count = ctx.len();
+          for (name, value) in ctx.get_entries() {
+            self.scope.set_entry(name, value.clone());
+          }
+          format!("loaded {} invocable(s) from `{}`", count, file_name)
+        }
+        Err(reason) => format!("loading model failed with reason: {}", reason),
+      }
+    } else {
+      match dmntk_evaluator::evaluate_context(&self.scope, &content) {
+        Ok(ctx) => {
+          let count = ctx.len();
+          for (name, value) in ctx.get_entries() {
+            self.scope.set_entry(name, value.clone());
+          }
+          format!("loaded {} name(s) from `{}`", count, file_name)
+        }
+        Err(reason) => format!("evaluating context failed with reason: {}", reason),
+      }
+    }
+  }
+}
+
+/// Builds the model and returns a context with functions invoking decisions,
+/// business knowledge models and decision services defined in this model.
+fn load_model(content: &str) -> dmntk_common::Result<FeelContext> {
+  let definitions = dmntk_model::parse(content)?;
+  let model_evaluator = ModelEvaluator::new(&definitions)?;
+  let mut ctx = FeelContext::default();
+  let input_data_parameter = vec![(Name::from(INPUT_DATA_PARAMETER), FeelType::Any)];
+  for decision in definitions.decisions() {
+    ctx.set_entry(
+      &Name::from(decision.name()),
+      invocable_function(&model_evaluator, decision.name(), input_data_parameter.clone()),
+    );
+  }
+  for decision_service in definitions.decision_services() {
+    ctx.set_entry(
+      &Name::from(decision_service.name()),
+      invocable_function(&model_evaluator, decision_service.name(), input_data_parameter.clone()),
+    );
+  }
+  for business_knowledge_model in definitions.business_knowledge_models() {
+    let mut parameters = vec![];
+    if let Some(function_definition) = business_knowledge_model.encapsulated_logic() {
+      for parameter in function_definition.formal_parameters() {
+        parameters.push((Name::from(parameter.name()), FeelType::Any));
+      }
+    }
+    if let Some(name) = business_knowledge_model.variable().feel_name() {
+      ctx.set_entry(name, invocable_function(&model_evaluator, business_knowledge_model.name(), parameters));
+    }
+  }
+  Ok(ctx)
+}
+
+/// Creates a function definition that evaluates an invocable with specified name.
+///
+/// When the function has a single parameter named `input data`, the argument is used as the input data,
+/// otherwise input data is the context of all arguments.
+fn invocable_function(model_evaluator: &Arc<ModelEvaluator>, invocable_name: &str, parameters: Vec<(Name, FeelType)>) -> Value {
+  let model_evaluator = Arc::clone(model_evaluator);
+  let invocable_name = invocable_name.to_string();
+  let parameter_names = parameters.iter().map(|(name, _)| name.clone()).collect::<Vec<Name>>();
+  let input_data_parameter = Name::from(INPUT_DATA_PARAMETER);
+  let body = FunctionBody::External(Arc::new(Box::new(move |scope: &Scope| {
+    let input_data = if parameter_names == [input_data_parameter.clone()] {
+      match scope.get_entry(&input_data_parameter) {
+        Some(Value::Context(ctx)) => ctx,
+        Some(Value::Null(_)) | None => FeelContext::default(),
+        Some(other) => return value_null!("expected input data context, actual value is {}", other),
+      }
+    } else {
+      let mut ctx = FeelContext::default();
+      for name in &parameter_names {
+        ctx.set_entry(name, scope.get_entry(name).unwrap_or_else(|| value_null!()));
+      }
+      ctx
+    };
+    model_evaluator.evaluate_invocable(&invocable_name, &input_data)
+  })));
+  Value::FunctionDefinition(parameters, body, FeelType::Any)
+}
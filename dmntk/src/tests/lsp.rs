@@ -0,0 +1,194 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::examples::E2_DMN;
+use crate::lsp::serve;
+use serde_json::{json, Value as JsonValue};
+use std::io::{Cursor, Write};
+
+const CTX_URI: &str = "file:///examples/e.ctx";
+const FEEL_URI: &str = "file:///examples/e.feel";
+const DMN_URI: &str = "file:///examples/e.dmn";
+
+/// Sends messages to the language server followed by shutdown and exit, returns all messages sent back.
+fn exchange(messages: &[JsonValue]) -> Vec<JsonValue> {
+  let mut input = vec![];
+  let shutdown = json!({"jsonrpc": "2.0", "id": 999, "method": "shutdown"});
+  let exit = json!({"jsonrpc": "2.0", "method": "exit"});
+  for message in messages.iter().chain([&shutdown, &exit]) {
+    let content = message.to_string();
+    write!(input, "Content-Length: {}\r\n\r\n{}", content.len(), content).unwrap();
+  }
+  let mut output = vec![];
+  serve(&mut Cursor::new(input), &mut output).unwrap();
+  let output = String::from_utf8(output).unwrap();
+  let mut responses = vec![];
+  let mut rest = output.as_str();
+  while let Some(header_end) = rest.find("\r\n\r\n") {
+    let content_length: usize = rest[..header_end].trim_start_matches("Content-Length:").trim().parse().unwrap();
+    let content_start = header_end + 4;
+    responses.push(serde_json::from_str(&rest[content_start..content_start + content_length]).unwrap());
+    rest = &rest[content_start + content_length..];
+  }
+  // the last response is the response to shutdown request
+  assert_eq!(Some(json!({"jsonrpc": "2.0", "id": 999, "result": null})), responses.pop());
+  responses
+}
+
+fn did_open(uri: &str, text: &str) -> JsonValue {
+  json!({"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {"textDocument": {"uri": uri, "languageId": "feel", "version": 1, "text": text}}})
+}
+
+fn did_change(uri: &str, text: &str) -> JsonValue {
+  json!({"jsonrpc": "2.0", "method": "textDocument/didChange", "params": {"textDocument": {"uri": uri, "version": 2}, "contentChanges": [{"text": text}]}})
+}
+
+fn request(id: u64, method: &str, uri: &str, line: usize, character: usize) -> JsonValue {
+  json!({"jsonrpc": "2.0", "id": id, "method": method, "params": {"textDocument": {"uri": uri}, "position": {"line": line, "character": character}}})
+}
+
+fn range(start_line: usize, start_character: usize, end_line: usize, end_character: usize) -> JsonValue {
+  json!({"start": {"line": start_line, "character": start_character}, "end": {"line": end_line, "character": end_character}})
+}
+
+fn diagnostics(uri: &str, diagnostics: JsonValue) -> JsonValue {
+  json!({"jsonrpc": "2.0", "method": "textDocument/publishDiagnostics", "params": {"uri": uri, "diagnostics": diagnostics}})
+}
+
+#[test]
+fn test_initialize() {
+  let responses = exchange(&[json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"capabilities": {}}})]);
+  assert_eq!(1, responses.len());
+  assert_eq!(1, responses[0]["id"]);
+  let capabilities = &responses[0]["result"]["capabilities"];
+  assert_eq!(1, capabilities["textDocumentSync"]);
+  assert_eq!(true, capabilities["hoverProvider"]);
+  assert_eq!(true, capabilities["definitionProvider"]);
+  assert!(capabilities["completionProvider"].is_object());
+  assert_eq!("dmntk", responses[0]["result"]["serverInfo"]["name"]);
+}
+
+#[test]
+fn test_unsupported_method() {
+  let responses = exchange(&[request(1, "textDocument/formatting", FEEL_URI, 0, 0)]);
+  assert_eq!(-32601, responses[0]["error"]["code"]);
+  assert_eq!("unsupported method 'textDocument/formatting'", responses[0]["error"]["message"]);
+}
+
+#[test]
+fn test_exit_without_shutdown() {
+  let content = json!({"jsonrpc": "2.0", "method": "exit"}).to_string();
+  let input = format!("Content-Length: {}\r\n\r\n{}", content.len(), content);
+  assert!(serve(&mut Cursor::new(input), &mut vec![]).is_err());
+}
+
+#[test]
+fn test_diagnostics() {
+  let responses = exchange(&[
+    did_open(CTX_URI, r#"{name: "John", birthday: date("2000-01-01")}"#),
+    did_open(FEEL_URI, "if birthday > date(\"1990-01-01\")\nthen name + birthday\nelse name"),
+  ]);
+  assert_eq!(
+    vec![
+      diagnostics(CTX_URI, json!([])),
+      diagnostics(
+        FEEL_URI,
+        json!([{"range": range(1, 5, 1, 20), "severity": 2, "source": "dmntk", "message": "incompatible types in '+': string and date"}])
+      ),
+    ],
+    responses
+  );
+}
+
+#[test]
+fn test_diagnostics_after_change() {
+  let responses = exchange(&[
+    did_open(CTX_URI, r#"{name: "John", birthday: date("2000-01-01")}"#),
+    did_open(FEEL_URI, "name + birthday"),
+    did_change(CTX_URI, r#"{name: "John", birthday: "2000-01-01"}"#),
+    did_change(FEEL_URI, "name +"),
+  ]);
+  assert_eq!(5, responses.len());
+  assert_eq!(range(0, 0, 0, 15), responses[1]["params"]["diagnostics"][0]["range"]);
+  // changing the context file publishes diagnostics of the expression parsed in its scope
+  assert_eq!(diagnostics(CTX_URI, json!([])), responses[2]);
+  assert_eq!(diagnostics(FEEL_URI, json!([])), responses[3]);
+  assert_eq!(1, responses[4]["params"]["diagnostics"][0]["severity"]);
+  assert_eq!(range(0, 0, 0, 6), responses[4]["params"]["diagnostics"][0]["range"]);
+}
+
+#[test]
+fn test_hover() {
+  let responses = exchange(&[
+    did_open(CTX_URI, r#"{name: "John", birthday: date("2000-01-01")}"#),
+    did_open(FEEL_URI, "string length(name) > 3"),
+    request(1, "textDocument/hover", FEEL_URI, 0, 16),
+    request(2, "textDocument/hover", FEEL_URI, 0, 3),
+    request(3, "textDocument/hover", FEEL_URI, 0, 21),
+    did_open(DMN_URI, E2_DMN),
+    request(4, "textDocument/hover", DMN_URI, 19, 32),
+    request(5, "textDocument/hover", DMN_URI, 13, 25),
+  ]);
+  let contents = |index: usize| responses[index]["result"]["contents"]["value"].as_str().unwrap_or_default().to_string();
+  assert_eq!("`name`: string", contents(2));
+  assert_eq!("```\nstring length(string: string) -> number\n```", contents(3));
+  assert_eq!("expression: boolean", contents(4));
+  assert_eq!(diagnostics(DMN_URI, json!([])), responses[5]);
+  assert_eq!("input data `Full Name`: string", contents(6));
+  assert_eq!("decision `Greeting Message`: string", contents(7));
+}
+
+#[test]
+fn test_completion() {
+  let responses = exchange(&[
+    did_open(CTX_URI, r#"{name: "John", birthday: date("2000-01-01")}"#),
+    did_open(FEEL_URI, ""),
+    request(1, "textDocument/completion", FEEL_URI, 0, 0),
+  ]);
+  let items = responses[2]["result"].as_array().unwrap();
+  assert_eq!(json!({"label": "birthday", "kind": 6}), items[0]);
+  assert_eq!(json!({"label": "name", "kind": 6}), items[1]);
+  assert!(items.contains(&json!({"label": "sum", "kind": 3, "detail": "sum(list: list<number>) / sum(n1...: number) -> number"})));
+}
+
+#[test]
+fn test_definition() {
+  let responses = exchange(&[
+    did_open(CTX_URI, "{\n  name: \"John\",\n  age: 49\n}"),
+    did_open(FEEL_URI, "age + 1"),
+    request(1, "textDocument/definition", FEEL_URI, 0, 1),
+    did_open(DMN_URI, E2_DMN),
+    request(2, "textDocument/definition", DMN_URI, 19, 32),
+  ]);
+  assert_eq!(json!({"uri": CTX_URI, "range": range(2, 2, 2, 5)}), responses[2]["result"]);
+  assert_eq!(json!({"uri": DMN_URI, "range": range(22, 21, 22, 30)}), responses[4]["result"]);
+}
@@ -0,0 +1,139 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::examples::E2_DMN;
+use crate::repl::Repl;
+
+/// Executes input lines in a new shell, returns the texts displayed after each line.
+fn execute(lines: &[&str]) -> Vec<String> {
+  let mut repl = Repl::default();
+  lines.iter().map(|line| repl.execute(line)).collect()
+}
+
+/// Writes the content to a file in temporary directory, returns the path of the file.
+fn temp_file(file_name: &str, content: &str) -> String {
+  let dir = std::env::temp_dir().join(format!("dmntk-repl-{}", std::process::id()));
+  std::fs::create_dir_all(&dir).unwrap();
+  let path = dir.join(file_name);
+  std::fs::write(&path, content).unwrap();
+  path.to_string_lossy().to_string()
+}
+
+#[test]
+fn test_evaluate() {
+  assert_eq!(vec!["3", "\"ab\"", "[1, 2]"], execute(&["1 + 2", r#""a" + "b""#, "[1, 2]"]));
+  let actual = execute(&["1 +"]);
+  assert!(actual[0].starts_with("parsing expression failed with reason: "), "{}", actual[0]);
+}
+
+#[test]
+fn test_let() {
+  // bound names are carried over between lines
+  assert_eq!(
+    vec!["x = 3", "y = 6", "9", "[6, 9]"],
+    execute(&[":let x = 1 + 2", ":let y = x * 2", "x + y", "for i in [x, y] return i + x"])
+  );
+  assert_eq!(vec!["x = 1", "x = 2", "2"], execute(&[":let x = 1", ":let x = x + 1", "x"]));
+  assert_eq!(vec!["expected :let <name> = <expression>"], execute(&[":let x"]));
+}
+
+#[test]
+fn test_null_location() {
+  assert_eq!(
+    vec!["a = {b: 1}", "null(`a.c` at 1:5: eval_path_expression: no entry c in context: {b: 1})"],
+    execute(&[":let a = {b: 1}", "1 + a.c"])
+  );
+}
+
+#[test]
+fn test_type() {
+  assert_eq!(
+    vec![
+      "x = 10",
+      "inferred type: number\n  actual type: number",
+      "inferred type: Null\n  type error: incompatible types in '+': number and string\n  actual type: Null",
+    ],
+    execute(&[":let x = 10", ":type x * 2", r#":type x + "a""#])
+  );
+}
+
+#[test]
+fn test_ast() {
+  let actual = execute(&[":ast 1 + 2"]);
+  assert!(actual[0].trim_start().starts_with("Add"), "{}", actual[0]);
+}
+
+#[test]
+fn test_trace() {
+  assert_eq!(
+    vec![
+      "x = 1",
+      "tracing is on",
+      "`x + 1` at 1:1 = 2\n  `x` at 1:1 = 1\n  `1` at 1:5 = 1\n2",
+      "`x * 3` at 1:1 = 3\n  `x` at 1:1 = 1\n  `3` at 1:5 = 3\ny = 3",
+      "tracing is off",
+      "2",
+    ],
+    execute(&[":let x = 1", ":trace", "x + 1", ":let y = x * 3", ":trace", "x + 1"])
+  );
+}
+
+#[test]
+fn test_load_context() {
+  let file_name = temp_file("e.ctx", r#"{name: "John", age: 49}"#);
+  assert_eq!(
+    vec![format!("loaded 2 name(s) from `{}`", file_name), "age\nname".to_string(), "50".to_string()],
+    execute(&[&format!(":load {}", file_name), ":scope", "age + 1"])
+  );
+  let actual = execute(&[":load /non/existing/file.ctx"]);
+  assert!(
+    actual[0].starts_with("loading file `/non/existing/file.ctx` failed with reason: "),
+    "{}",
+    actual[0]
+  );
+}
+
+#[test]
+fn test_load_model() {
+  let file_name = temp_file("e.dmn", E2_DMN);
+  assert_eq!(
+    vec![format!("loaded 1 invocable(s) from `{}`", file_name), r#""Hello John Doe""#.to_string()],
+    execute(&[&format!(":load {}", file_name), r#"Greeting Message({Full Name: "John Doe"})"#])
+  );
+}
+
+#[test]
+fn test_commands() {
+  let actual = execute(&[":help", ":unknown"]);
+  assert!(actual[0].contains(":trace"));
+  assert_eq!("unknown command ':unknown', type :help for help", actual[1]);
+}
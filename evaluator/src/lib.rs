@@ -38,6 +38,9 @@ extern crate dmntk_model_evaluator;
 
 mod test_files;
 
-pub use crate::dmntk_feel_evaluator::{evaluate, evaluate_context, evaluate_equals, evaluate_max, evaluate_min, evaluate_node_type, evaluate_sum};
-pub use crate::dmntk_model_evaluator::{build_decision_table_evaluator, ModelEvaluator};
+pub use crate::dmntk_feel_evaluator::{
+  evaluate, evaluate_context, evaluate_equals, evaluate_max, evaluate_min, evaluate_node_type, evaluate_sum, infer_type, infer_type_with_spans,
+  prepare_with_spans, prepare_with_trace, Backend, EvaluationTrace, TraceEntry, TypeError,
+};
+pub use crate::dmntk_model_evaluator::{build_decision_table_evaluator, query_decision_table, ModelEvaluator, RuleConditions};
 pub use test_files::evaluate_test_cases;
@@ -33,24 +33,97 @@
 use crate::bifs;
 use crate::errors::*;
 use crate::iterations::{EveryExpressionEvaluator, ForExpressionEvaluator, SomeExpressionEvaluator};
+use crate::limits;
+use crate::trace::EvaluationTrace;
 use dmntk_common::Result;
 use dmntk_feel::bif::Bif;
 use dmntk_feel::context::FeelContext;
 use dmntk_feel::values::{Value, Values, VALUE_FALSE, VALUE_TRUE};
 use dmntk_feel::{
-  subtract, value_null, AstNode, Evaluator, FeelDate, FeelDateTime, FeelDaysAndTimeDuration, FeelNumber, FeelTime, FeelType, FeelYearsAndMonthsDuration,
-  FunctionBody, Name, Scope,
+  subtract, value_null, AstNode, AstSpans, Evaluator, FeelDate, FeelDateTime, FeelDaysAndTimeDuration, FeelNumber, FeelTime, FeelType,
+  FeelYearsAndMonthsDuration, FunctionBody, Name, Scope,
 };
 use std::borrow::Borrow;
+use std::cell::RefCell;
 use std::cmp::Ordering;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryFrom;
 use std::ops::Deref;
 use std::str::FromStr;
 use std::sync::Arc;
 
+thread_local! {
+  /// Locations of the nodes in source text, set while building evaluators reporting source locations.
+  ///
+  /// Nodes are identified by their addresses, this is valid only while building evaluators,
+  /// because the node is borrowed and can be neither moved nor modified during building.
+  static SOURCE_LOCATIONS: RefCell<Option<HashMap<usize, String>>> = const { RefCell::new(None) };
+  /// Trace recorded by evaluators of the nodes having locations, set while building evaluators recording the trace.
+  static EVALUATION_TRACE: RefCell<Option<EvaluationTrace>> = const { RefCell::new(None) };
+}
+
+/// Builds an evaluator reporting the location of the sub-expression producing `null` in source text.
+pub fn build_evaluator_with_spans(node: &AstNode, spans: &AstSpans, source: &str) -> Result<Evaluator> {
+  build_evaluator_with_locations(node, spans, source, None)
+}
+
+/// Builds an evaluator reporting the location of the sub-expression producing `null` in source text,
+/// and recording the values of all sub-expressions in specified trace.
+pub fn build_evaluator_with_trace(node: &AstNode, spans: &AstSpans, source: &str, trace: &EvaluationTrace) -> Result<Evaluator> {
+  build_evaluator_with_locations(node, spans, source, Some(trace.clone()))
+}
+
+/// Builds an evaluator of the node, with the locations of all nodes in source text.
+fn build_evaluator_with_locations(node: &AstNode, spans: &AstSpans, source: &str, trace: Option<EvaluationTrace>) -> Result<Evaluator> {
+  let mut locations = HashMap::new();
+  spans.visit(node, &mut |node, span| {
+    let (line, column) = span.line_column(source);
+    locations.insert(node as *const AstNode as usize, format!("`{}` at {}:{}", span.text(source), line, column));
+  });
+  SOURCE_LOCATIONS.with(|source_locations| *source_locations.borrow_mut() = Some(locations));
+  EVALUATION_TRACE.with(|evaluation_trace| *evaluation_trace.borrow_mut() = trace);
+  let result = build_evaluator(node);
+  SOURCE_LOCATIONS.with(|source_locations| *source_locations.borrow_mut() = None);
+  EVALUATION_TRACE.with(|evaluation_trace| *evaluation_trace.borrow_mut() = None);
+  result
+}
+
 ///
 pub fn build_evaluator(node: &AstNode) -> Result<Evaluator> {
+  let evaluator = build_node_evaluator(node)?;
+  Ok(build_located(node, evaluator))
+}
+
+/// Wraps the evaluator of the node, so the reason of produced `null` starts with the location of the node.
+/// Reasons already having the location, propagated from nested nodes, are left unchanged.
+/// When the trace is recorded, the evaluator records the value of the node in the trace.
+fn build_located(node: &AstNode, evaluator: Evaluator) -> Evaluator {
+  let location = SOURCE_LOCATIONS.with(|source_locations| {
+    source_locations
+      .borrow()
+      .as_ref()
+      .and_then(|locations| locations.get(&(node as *const AstNode as usize)).cloned())
+  });
+  if let Some(location) = location {
+    let trace = EVALUATION_TRACE.with(|evaluation_trace| evaluation_trace.borrow().clone());
+    Box::new(move |scope: &Scope| {
+      let index = trace.as_ref().map(|trace| trace.enter(&location));
+      let value = match evaluator(scope) {
+        Value::Null(Some(reason)) if !reason.starts_with('`') => value_null!("{}: {}", location, reason),
+        value => value,
+      };
+      if let (Some(trace), Some(index)) = (&trace, index) {
+        trace.leave(index, &value);
+      }
+      value
+    })
+  } else {
+    evaluator
+  }
+}
+
+/// Builds an evaluator for specified node, without reporting its location.
+fn build_node_evaluator(node: &AstNode) -> Result<Evaluator> {
   match node {
     AstNode::Add(lhs, rhs) => build_add(lhs, rhs),
     AstNode::And(lhs, rhs) => build_and(lhs, rhs),
@@ -130,34 +203,7 @@ pub fn build_evaluator(node: &AstNode) -> Result<Evaluator> {
 fn build_add(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope) as Value;
-    let rhv = rhe(scope) as Value;
-    match lhv {
-      Value::Number(lh) => match rhv {
-        Value::Number(rh) => Value::Number(lh + rh),
-        value @ Value::Null(_) => value,
-        _ => value_null!("addition err 1"),
-      },
-      Value::String(mut lh) => {
-        if let Value::String(rh) = rhv {
-          lh.push_str(&rh);
-          Value::String(lh)
-        } else {
-          value_null!("addition err 2")
-        }
-      }
-      Value::DaysAndTimeDuration(lh) => {
-        if let Value::DaysAndTimeDuration(rh) = rhv {
-          Value::DaysAndTimeDuration(lh + rh)
-        } else {
-          value_null!("addition err 3")
-        }
-      }
-      value @ Value::Null(_) => value,
-      _ => value_null!("addition err"),
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_add(lhe(scope), rhe(scope))))
 }
 
 /// Builds evaluator of temporal expression after `@` (at) literal.
@@ -185,103 +231,7 @@ fn build_between(lhs: &AstNode, mhs: &AstNode, rhs: &AstNode) -> Result<Evaluato
   let lhe = build_evaluator(lhs)?;
   let mhe = build_evaluator(mhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope);
-    let mhv = mhe(scope);
-    let rhv = rhe(scope);
-    match lhv {
-      Value::Number(lh) => {
-        if let Value::Number(mh) = mhv {
-          if let Value::Number(rh) = rhv {
-            Value::Boolean(mh <= lh && lh <= rh)
-          } else {
-            value_null!("between err 1")
-          }
-        } else {
-          value_null!("between err 2")
-        }
-      }
-      Value::String(lh) => {
-        if let Value::String(mh) = mhv {
-          if let Value::String(rh) = rhv {
-            Value::Boolean(mh <= lh && lh <= rh)
-          } else {
-            value_null!("between err 3")
-          }
-        } else {
-          value_null!("between err 4")
-        }
-      }
-      Value::Date(lh) => {
-        if let Value::Date(mh) = mhv {
-          if let Value::Date(rh) = rhv {
-            if let Some(result) = lh.between(&mh, &rh, true, true) {
-              Value::Boolean(result)
-            } else {
-              value_null!("between err 5")
-            }
-          } else {
-            value_null!("between err 6")
-          }
-        } else {
-          value_null!("between err 7")
-        }
-      }
-      Value::Time(lh) => {
-        if let Value::Time(mh) = mhv {
-          if let Value::Time(rh) = rhv {
-            if let Some(result) = lh.between(&mh, &rh, true, true) {
-              Value::Boolean(result)
-            } else {
-              value_null!("between err 8")
-            }
-          } else {
-            value_null!("between err 9")
-          }
-        } else {
-          value_null!("between err 10")
-        }
-      }
-      Value::DateTime(lh) => {
-        if let Value::DateTime(mh) = mhv {
-          if let Value::DateTime(rh) = rhv {
-            if let Some(result) = lh.between(&mh, &rh, true, true) {
-              Value::Boolean(result)
-            } else {
-              value_null!("between err 11")
-            }
-          } else {
-            value_null!("between err 12")
-          }
-        } else {
-          value_null!("between err 13")
-        }
-      }
-      Value::YearsAndMonthsDuration(lh) => {
-        if let Value::YearsAndMonthsDuration(mh) = mhv {
-          if let Value::YearsAndMonthsDuration(rh) = rhv {
-            Value::Boolean(mh <= lh && lh <= rh)
-          } else {
-            value_null!("between err 14")
-          }
-        } else {
-          value_null!("between err 15")
-        }
-      }
-      Value::DaysAndTimeDuration(lh) => {
-        if let Value::DaysAndTimeDuration(mh) = mhv {
-          if let Value::DaysAndTimeDuration(rh) = rhv {
-            Value::Boolean(mh <= lh && lh <= rh)
-          } else {
-            value_null!("between err 16")
-          }
-        } else {
-          value_null!("between err 17")
-        }
-      }
-      _ => value_null!("between err"),
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_between(lhe(scope), mhe(scope), rhe(scope))))
 }
 
 ///
@@ -306,31 +256,10 @@ fn build_boolean(lhs: bool) -> Result<Evaluator> {
 fn build_and(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope);
-    let rhv = rhe(scope);
-    match lhv {
-      Value::Boolean(lh) => match rhv {
-        Value::Boolean(rh) => Value::Boolean(lh && rh),
-        _ => {
-          if lh {
-            value_null!()
-          } else {
-            Value::Boolean(false)
-          }
-        }
-      },
-      _ => match rhv {
-        Value::Boolean(rh) => {
-          if rh {
-            value_null!()
-          } else {
-            Value::Boolean(false)
-          }
-        }
-        _ => value_null!(),
-      },
-    }
+  Ok(Box::new(move |scope: &Scope| match lhe(scope) {
+    // when the left operand is false, the right operand does not change the result
+    Value::Boolean(false) => Value::Boolean(false),
+    lhv => eval_and(lhv, rhe(scope)),
   }))
 }
 
@@ -426,23 +355,7 @@ fn build_context_type_entry_key(lhs: &Name) -> Result<Evaluator> {
 fn build_div(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope) as Value;
-    let rhv = rhe(scope) as Value;
-    match lhv {
-      Value::Number(lh) => match rhv {
-        Value::Number(rh) => {
-          if rh.abs() == FeelNumber::zero() {
-            value_null!("[division] division by zero")
-          } else {
-            Value::Number(lh / rh)
-          }
-        }
-        _ => value_null!("[division] incompatible types: {} / {}", lhv, rhv),
-      },
-      _ => value_null!("[division] incompatible types: {} / {}", lhv, rhv),
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_div(lhe(scope), rhe(scope))))
 }
 
 ///
@@ -464,23 +377,7 @@ fn build_expression_list(lhs: &[AstNode]) -> Result<Evaluator> {
 fn build_exp(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope);
-    let rhv = rhe(scope);
-    if let Value::Number(lh) = lhv {
-      if let Value::Number(rh) = rhv {
-        if let Some(result) = lh.pow(&rh) {
-          Value::Number(result)
-        } else {
-          value_null!("exponentiation result is not a finite number")
-        }
-      } else {
-        value_null!("exponentiation exponent is not a number")
-      }
-    } else {
-      value_null!("exponentiation base is not a number")
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_exp(lhe(scope), rhe(scope))))
 }
 
 ///
@@ -642,7 +539,10 @@ fn build_for(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
         expression_evaluator.add_range(name.clone(), evaluator_range_start(scope), evaluator_range_end(scope));
       }
     }
-    Value::List(expression_evaluator.evaluate(scope, &rhe))
+    match expression_evaluator.evaluate(scope, &rhe) {
+      Ok(values) => Value::List(values),
+      Err(value) => value,
+    }
   }))
 }
 
@@ -725,15 +625,7 @@ fn build_function_definition(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator>
 fn build_eq(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope) as Value;
-    let rhv = rhe(scope) as Value;
-    if let Some(result) = eval_ternary_equality(&lhv, &rhv) {
-      Value::Boolean(result)
-    } else {
-      value_null!("equal err '{}' =?= '{}'", lhv, rhv)
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_eq(lhe(scope), rhe(scope))))
 }
 
 ///
@@ -852,50 +744,14 @@ fn build_function_type(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
 fn build_ge(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope);
-    let rhv = rhe(scope);
-    match lhv {
-      Value::Number(lh) => match rhv {
-        Value::Number(rh) => Value::Boolean(lh >= rh),
-        _ => value_null!("eval_less_or_equal_string"),
-      },
-      Value::String(lh) => match rhv {
-        Value::String(rh) => Value::Boolean(lh.cmp(&rh) != Ordering::Less),
-        _ => value_null!("eval_less_or_equal_string"),
-      },
-      Value::Date(lh) => match rhv {
-        Value::Date(rh) => Value::Boolean(lh >= rh),
-        _ => value_null!("eval_less_or_equal_date"),
-      },
-      _ => value_null!("eval_less_or_equal"),
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_ge(lhe(scope), rhe(scope))))
 }
 
 ///
 fn build_gt(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope);
-    let rhv = rhe(scope);
-    match lhv {
-      Value::Number(lh) => match rhv {
-        Value::Number(rh) => Value::Boolean(lh > rh),
-        _ => value_null!("eval_greater_then_number"),
-      },
-      Value::String(lh) => match rhv {
-        Value::String(rh) => Value::Boolean(lh.cmp(&rh) == Ordering::Greater),
-        _ => value_null!("eval_greater_then_string"),
-      },
-      Value::Date(lh) => match rhv {
-        Value::Date(rh) => Value::Boolean(lh > rh),
-        _ => value_null!("eval_greater_then_date"),
-      },
-      _ => value_null!("eval_greater_then"),
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_gt(lhe(scope), rhe(scope))))
 }
 
 ///
@@ -914,42 +770,7 @@ fn build_if(lhs: &AstNode, mhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
 fn build_in(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope);
-    let rhv = rhe(scope);
-    match rhv {
-      inner @ Value::Number(_)
-      | inner @ Value::String(_)
-      | inner @ Value::Boolean(_)
-      | inner @ Value::Date(_)
-      | inner @ Value::Time(_)
-      | inner @ Value::DateTime(_)
-      | inner @ Value::YearsAndMonthsDuration(_)
-      | inner @ Value::DaysAndTimeDuration(_)
-      | inner @ Value::Context(_) => eval_in_equal(&lhv, &inner),
-      Value::Range(_, _, _, _) => eval_in_range(&lhv, &rhv),
-      Value::List(inner) => {
-        if let Value::List(_) = lhv {
-          eval_in_list_in_list(&lhv, inner.as_vec())
-        } else {
-          eval_in_list(&lhv, inner.as_vec())
-        }
-      }
-      Value::ExpressionList(inner) => eval_in_list(&lhv, inner.as_vec()),
-      Value::NegatedCommaList(inner) => eval_in_negated_list(&lhv, inner.as_vec()),
-      Value::UnaryLess(inner) => eval_in_unary_less(&lhv, inner.borrow()),
-      Value::UnaryLessOrEqual(inner) => eval_in_unary_less_or_equal(&lhv, inner.borrow()),
-      Value::UnaryGreater(inner) => eval_in_unary_greater(&lhv, inner.borrow()),
-      Value::UnaryGreaterOrEqual(inner) => eval_in_unary_greater_or_equal(&lhv, inner.borrow()),
-      Value::Irrelevant => match lhv {
-        Value::Null(_) => VALUE_FALSE,
-        _ => VALUE_TRUE,
-      },
-      _ => {
-        value_null!("eval_in")
-      }
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_in(lhe(scope), rhe(scope))))
 }
 
 ///
@@ -1058,50 +879,14 @@ fn build_instance_of(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
 fn build_le(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope);
-    let rhv = rhe(scope);
-    match lhv {
-      Value::Number(lh) => match rhv {
-        Value::Number(rh) => Value::Boolean(lh <= rh),
-        _ => value_null!("eval_less_or_equal_number"),
-      },
-      Value::String(lh) => match rhv {
-        Value::String(rh) => Value::Boolean(lh.cmp(&rh) != Ordering::Greater),
-        _ => value_null!("eval_less_or_equal_string"),
-      },
-      Value::Date(lh) => match rhv {
-        Value::Date(rh) => Value::Boolean(lh <= rh),
-        _ => value_null!("eval_less_or_equal_date"),
-      },
-      _ => value_null!("eval_less_or_equal"),
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_le(lhe(scope), rhe(scope))))
 }
 
 ///
 fn build_lt(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope);
-    let rhv = rhe(scope);
-    match lhv {
-      Value::Number(lh) => match rhv {
-        Value::Number(rh) => Value::Boolean(lh < rh),
-        _ => value_null!("eval_less_then_number"),
-      },
-      Value::String(lh) => match rhv {
-        Value::String(rh) => Value::Boolean(lh.cmp(&rh) == Ordering::Less),
-        _ => value_null!("eval_less_then_string"),
-      },
-      Value::Date(lh) => match rhv {
-        Value::Date(rh) => Value::Boolean(lh < rh),
-        _ => value_null!("eval_less_then_date"),
-      },
-      _ => value_null!("eval_less_then"),
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_lt(lhe(scope), rhe(scope))))
 }
 
 ///
@@ -1136,31 +921,12 @@ fn build_list_type(lhs: &AstNode) -> Result<Evaluator> {
 fn build_mul(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope) as Value;
-    let rhv = rhe(scope) as Value;
-    match lhv {
-      Value::Number(lh) => match rhv {
-        Value::Number(rh) => Value::Number(lh * rh),
-        _ => value_null!("[multiplication] incompatible types: {} * {}", lhv, rhv),
-      },
-      value @ Value::Null(_) => value,
-      _ => value_null!("[multiplication] incompatible types: {} * {}", lhv, rhv),
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_mul(lhe(scope), rhe(scope))))
 }
 
 ///
 fn build_name(name: Name) -> Result<Evaluator> {
-  Ok(Box::new(move |scope: &Scope| {
-    if let Some(value) = scope.get_entry(&name) {
-      value
-    } else if let Ok(bif) = Bif::from_str(&name.to_string()) {
-      Value::BuiltInFunction(bif)
-    } else {
-      value_null!("context has no value for key '{}'", name)
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_name(scope, &name)))
 }
 
 ///
@@ -1200,14 +966,7 @@ fn build_named_parameters(lhs: &[AstNode]) -> Result<Evaluator> {
 ///
 fn build_neg(lhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope);
-    match lhv {
-      Value::Number(lh) => Value::Number(-lh),
-      Value::DaysAndTimeDuration(lh) => Value::DaysAndTimeDuration(-lh),
-      _ => value_null!("arithmetic negation err 1"),
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_neg(lhe(scope))))
 }
 
 ///
@@ -1244,15 +1003,7 @@ fn build_numeric(lhs: &str, rhs: &str) -> Result<Evaluator> {
 fn build_nq(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope);
-    let rhv = rhe(scope);
-    if let Some(result) = eval_ternary_equality(&lhv, &rhv) {
-      Value::Boolean(!result)
-    } else {
-      value_null!()
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_nq(lhe(scope), rhe(scope))))
 }
 
 /// Semantics of disjunction.
@@ -1272,31 +1023,10 @@ fn build_nq(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
 fn build_or(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope);
-    let rhv = rhe(scope);
-    match lhv {
-      Value::Boolean(lh) => match rhv {
-        Value::Boolean(rh) => Value::Boolean(lh || rh),
-        _ => {
-          if lh {
-            Value::Boolean(true)
-          } else {
-            value_null!()
-          }
-        }
-      },
-      _ => match rhv {
-        Value::Boolean(rh) => {
-          if rh {
-            Value::Boolean(true)
-          } else {
-            value_null!()
-          }
-        }
-        _ => value_null!(),
-      },
-    }
+  Ok(Box::new(move |scope: &Scope| match lhe(scope) {
+    // when the left operand is true, the right operand does not change the result
+    Value::Boolean(true) => Value::Boolean(true),
+    lhv => eval_or(lhv, rhe(scope)),
   }))
 }
 
@@ -1348,7 +1078,7 @@ fn build_qualified_name(lhs: &[AstNode]) -> Result<Evaluator> {
         names.push(name.clone());
       }
     }
-    scope.search_deep(&names).unwrap_or_else(|| value_null!("no value for qualified name"))
+    eval_qualified_name(scope, &names)
   }))
 }
 
@@ -1362,117 +1092,7 @@ fn build_qualified_name_segment(name: &Name) -> Result<Evaluator> {
 fn build_path(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   if let AstNode::Name(name) = rhs.clone() {
     let lhe = build_evaluator(lhs)?;
-    Ok(Box::new(move |scope: &Scope| {
-      let lhv = lhe(scope) as Value;
-      match lhv {
-        Value::Context(context) => {
-          if let Some(value) = context.get_entry(&name) {
-            value.clone()
-          } else {
-            value_null!("eval_path_expression: no entry {} in context: {}", name, context)
-          }
-        }
-        Value::List(items) => {
-          let mut result = vec![];
-          for item in items.as_vec() {
-            if let Value::Context(context) = item {
-              if let Some(value) = context.get_entry(&name) {
-                result.push(value.clone());
-              }
-            } else {
-              return value_null!("eval_path_expression: no context in list");
-            }
-          }
-          Value::List(Values::new(result))
-        }
-        Value::Date(date) => {
-          return match name.to_string().as_str() {
-            "year" => Value::Number(date.year().into()),
-            "month" => Value::Number(date.month().into()),
-            "day" => Value::Number(date.day().into()),
-            "weekday" => {
-              if let Some(day_num) = date.weekday() {
-                Value::Number(day_num.into())
-              } else {
-                value_null!("could not retrieve weekday for date")
-              }
-            }
-            _ => value_null!("no such property in date"),
-          }
-        }
-        Value::DateTime(date_time) => {
-          return match name.to_string().as_str() {
-            "year" => Value::Number(date_time.year().into()),
-            "month" => Value::Number(date_time.month().into()),
-            "day" => Value::Number(date_time.day().into()),
-            "weekday" => {
-              if let Some(day_num) = date_time.weekday() {
-                Value::Number(day_num.into())
-              } else {
-                value_null!("could not retrieve weekday for date and time")
-              }
-            }
-            "hour" => Value::Number(date_time.hour().into()),
-            "minute" => Value::Number(date_time.minute().into()),
-            "second" => Value::Number(date_time.second().into()),
-            "time offset" => {
-              if let Some(offset) = date_time.feel_time_offset() {
-                return Value::DaysAndTimeDuration(FeelDaysAndTimeDuration::default().second(offset as i64).build());
-              } else {
-                value_null!("aaaa")
-              }
-            }
-            "timezone" => {
-              if let Some(feel_time_zone) = date_time.feel_time_zone() {
-                return Value::String(feel_time_zone);
-              } else {
-                value_null!("bbb")
-              }
-            }
-            _ => value_null!("no such property in date and time"),
-          }
-        }
-        Value::Time(time) => {
-          return match name.to_string().as_str() {
-            "hour" => Value::Number(time.hour().into()),
-            "minute" => Value::Number(time.minute().into()),
-            "second" => Value::Number(time.second().into()),
-            "time offset" => {
-              if let Some(offset) = time.feel_time_offset() {
-                return Value::DaysAndTimeDuration(FeelDaysAndTimeDuration::default().second(offset as i64).build());
-              } else {
-                value_null!("ccc")
-              }
-            }
-            "timezone" => {
-              if let Some(feel_time_zone) = time.feel_time_zone() {
-                return Value::String(feel_time_zone);
-              } else {
-                value_null!("ddd")
-              }
-            }
-            _ => value_null!("no such property in date and time"),
-          }
-        }
-        Value::DaysAndTimeDuration(dt_duration) => {
-          return match name.to_string().as_str() {
-            "days" => Value::Number(dt_duration.get_days().into()),
-            "hours" => Value::Number(dt_duration.get_hours().into()),
-            "minutes" => Value::Number(dt_duration.get_minutes().into()),
-            "seconds" => Value::Number(dt_duration.get_seconds().into()),
-            _ => value_null!("no such property in days and time duration"),
-          }
-        }
-        Value::YearsAndMonthsDuration(ym_duration) => {
-          return match name.to_string().as_str() {
-            "years" => Value::Number(ym_duration.years().into()),
-            "months" => Value::Number(ym_duration.months().into()),
-            _ => value_null!("no such property in years and months duration"),
-          }
-        }
-        _ => value_null!("zzz lhv={}", lhv),
-      }
-    }))
+    Ok(Box::new(move |scope: &Scope| eval_path(lhe(scope), &name)))
   } else {
     Ok(Box::new(move |_: &Scope| {
       value_null!("no context (or list of contexts) on the left or no name on the right in path expression")
@@ -1484,19 +1104,7 @@ fn build_path(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
 fn build_range(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope);
-    let rhv = rhe(scope);
-    if let Value::IntervalStart(lhv, l_closed) = lhv {
-      if let Value::IntervalEnd(rhv, r_closed) = rhv {
-        Value::Range(lhv, l_closed, rhv, r_closed)
-      } else {
-        value_null!("expected interval end")
-      }
-    } else {
-      value_null!("expected interval start")
-    }
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_range(lhe(scope), rhe(scope))))
 }
 
 ///
@@ -1551,27 +1159,7 @@ fn build_string(lhs: &str) -> Result<Evaluator> {
 fn build_sub(lhs: &AstNode, rhs: &AstNode) -> Result<Evaluator> {
   let lhe = build_evaluator(lhs)?;
   let rhe = build_evaluator(rhs)?;
-  Ok(Box::new(move |scope: &Scope| {
-    let lhv = lhe(scope);
-    let rhv = rhe(scope);
-    match lhv {
-      Value::Number(ref lh) => {
-        if let Value::Number(ref rh) = rhv {
-          return Value::Number(*lh - *rh);
-        }
-      }
-      Value::DateTime(ref lh) => {
-        if let Value::DateTime(ref rh) = rhv {
-          if let Some(a) = subtract(lh, rh) {
-            return Value::DaysAndTimeDuration(FeelDaysAndTimeDuration::default().nano(a).build());
-          }
-        }
-      }
-      _ => {}
-    }
-    //TODO make a macro for incompatible types
-    value_null!("[subtraction] incompatible types: {} - {}", lhv as Value, rhv as Value)
-  }))
+  Ok(Box::new(move |scope: &Scope| eval_sub(lhe(scope), rhe(scope))))
 }
 
 ///
@@ -1610,6 +1198,562 @@ fn build_unary_lt(lhs: &AstNode) -> Result<Evaluator> {
   }))
 }
 
+/// Evaluates the sum of two values.
+pub(crate) fn eval_add(lhv: Value, rhv: Value) -> Value {
+  match lhv {
+    Value::Number(lh) => match rhv {
+      Value::Number(rh) => Value::Number(lh + rh),
+      value @ Value::Null(_) => value,
+      _ => value_null!("addition err 1"),
+    },
+    Value::String(mut lh) => {
+      if let Value::String(rh) = rhv {
+        lh.push_str(&rh);
+        Value::String(lh)
+      } else {
+        value_null!("addition err 2")
+      }
+    }
+    Value::DaysAndTimeDuration(lh) => match rhv {
+      Value::DaysAndTimeDuration(rh) => Value::DaysAndTimeDuration(lh + rh),
+      Value::DateTime(rh) => eval_add_date_time(&rh, &lh),
+      _ => value_null!("addition err 3"),
+    },
+    Value::DateTime(lh) => match rhv {
+      Value::DaysAndTimeDuration(rh) => eval_add_date_time(&lh, &rh),
+      value @ Value::Null(_) => value,
+      _ => value_null!("addition err 4"),
+    },
+    value @ Value::Null(_) => value,
+    _ => value_null!("addition err"),
+  }
+}
+
+/// Evaluates the sum of date and time and days and time duration.
+fn eval_add_date_time(date_time: &FeelDateTime, dt_duration: &FeelDaysAndTimeDuration) -> Value {
+  if let Some(result) = date_time + dt_duration {
+    Value::DateTime(result)
+  } else {
+    value_null!("[addition] date and time out of range: {} + {}", date_time, dt_duration)
+  }
+}
+
+/// Evaluates `between` expression.
+pub(crate) fn eval_between(lhv: Value, mhv: Value, rhv: Value) -> Value {
+  match lhv {
+    Value::Number(lh) => {
+      if let Value::Number(mh) = mhv {
+        if let Value::Number(rh) = rhv {
+          Value::Boolean(mh <= lh && lh <= rh)
+        } else {
+          value_null!("between err 1")
+        }
+      } else {
+        value_null!("between err 2")
+      }
+    }
+    Value::String(lh) => {
+      if let Value::String(mh) = mhv {
+        if let Value::String(rh) = rhv {
+          Value::Boolean(mh <= lh && lh <= rh)
+        } else {
+          value_null!("between err 3")
+        }
+      } else {
+        value_null!("between err 4")
+      }
+    }
+    Value::Date(lh) => {
+      if let Value::Date(mh) = mhv {
+        if let Value::Date(rh) = rhv {
+          if let Some(result) = lh.between(&mh, &rh, true, true) {
+            Value::Boolean(result)
+          } else {
+            value_null!("between err 5")
+          }
+        } else {
+          value_null!("between err 6")
+        }
+      } else {
+        value_null!("between err 7")
+      }
+    }
+    Value::Time(lh) => {
+      if let Value::Time(mh) = mhv {
+        if let Value::Time(rh) = rhv {
+          if let Some(result) = lh.between(&mh, &rh, true, true) {
+            Value::Boolean(result)
+          } else {
+            value_null!("between err 8")
+          }
+        } else {
+          value_null!("between err 9")
+        }
+      } else {
+        value_null!("between err 10")
+      }
+    }
+    Value::DateTime(lh) => {
+      if let Value::DateTime(mh) = mhv {
+        if let Value::DateTime(rh) = rhv {
+          if let Some(result) = lh.between(&mh, &rh, true, true) {
+            Value::Boolean(result)
+          } else {
+            value_null!("between err 11")
+          }
+        } else {
+          value_null!("between err 12")
+        }
+      } else {
+        value_null!("between err 13")
+      }
+    }
+    Value::YearsAndMonthsDuration(lh) => {
+      if let Value::YearsAndMonthsDuration(mh) = mhv {
+        if let Value::YearsAndMonthsDuration(rh) = rhv {
+          Value::Boolean(mh <= lh && lh <= rh)
+        } else {
+          value_null!("between err 14")
+        }
+      } else {
+        value_null!("between err 15")
+      }
+    }
+    Value::DaysAndTimeDuration(lh) => {
+      if let Value::DaysAndTimeDuration(mh) = mhv {
+        if let Value::DaysAndTimeDuration(rh) = rhv {
+          Value::Boolean(mh <= lh && lh <= rh)
+        } else {
+          value_null!("between err 16")
+        }
+      } else {
+        value_null!("between err 17")
+      }
+    }
+    _ => value_null!("between err"),
+  }
+}
+
+/// Evaluates the conjunction of two values.
+pub(crate) fn eval_and(lhv: Value, rhv: Value) -> Value {
+  match lhv {
+    Value::Boolean(lh) => match rhv {
+      Value::Boolean(rh) => Value::Boolean(lh && rh),
+      _ => {
+        if lh {
+          value_null!()
+        } else {
+          Value::Boolean(false)
+        }
+      }
+    },
+    _ => match rhv {
+      Value::Boolean(rh) => {
+        if rh {
+          value_null!()
+        } else {
+          Value::Boolean(false)
+        }
+      }
+      _ => value_null!(),
+    },
+  }
+}
+
+/// Evaluates the quotient of two values.
+pub(crate) fn eval_div(lhv: Value, rhv: Value) -> Value {
+  match lhv {
+    Value::Number(lh) => match rhv {
+      Value::Number(rh) => {
+        if rh.abs() == FeelNumber::zero() {
+          value_null!("[division] division by zero")
+        } else {
+          Value::Number(lh / rh)
+        }
+      }
+      _ => value_null!("[division] incompatible types: {} / {}", lhv, rhv),
+    },
+    _ => value_null!("[division] incompatible types: {} / {}", lhv, rhv),
+  }
+}
+
+/// Evaluates the exponentiation of two values.
+pub(crate) fn eval_exp(lhv: Value, rhv: Value) -> Value {
+  if let Value::Number(lh) = lhv {
+    if let Value::Number(rh) = rhv {
+      if let Some(result) = lh.pow(&rh) {
+        Value::Number(result)
+      } else {
+        value_null!("exponentiation result is not a finite number")
+      }
+    } else {
+      value_null!("exponentiation exponent is not a number")
+    }
+  } else {
+    value_null!("exponentiation base is not a number")
+  }
+}
+
+/// Evaluates the equality of two values.
+pub(crate) fn eval_eq(lhv: Value, rhv: Value) -> Value {
+  if let Some(result) = eval_ternary_equality(&lhv, &rhv) {
+    Value::Boolean(result)
+  } else {
+    value_null!("equal err '{}' =?= '{}'", lhv, rhv)
+  }
+}
+
+/// Evaluates whether the left value is greater than or equal to the right value.
+pub(crate) fn eval_ge(lhv: Value, rhv: Value) -> Value {
+  match lhv {
+    Value::Number(lh) => match rhv {
+      Value::Number(rh) => Value::Boolean(lh >= rh),
+      _ => value_null!("eval_less_or_equal_string"),
+    },
+    Value::String(lh) => match rhv {
+      Value::String(rh) => Value::Boolean(lh.cmp(&rh) != Ordering::Less),
+      _ => value_null!("eval_less_or_equal_string"),
+    },
+    Value::Date(lh) => match rhv {
+      Value::Date(rh) => Value::Boolean(lh >= rh),
+      _ => value_null!("eval_less_or_equal_date"),
+    },
+    Value::DateTime(lh) => match rhv {
+      Value::DateTime(rh) => lh
+        .after_or_equal(&rh)
+        .map_or_else(|| value_null!("eval_greater_or_equal_date_time"), Value::Boolean),
+      _ => value_null!("eval_greater_or_equal_date_time"),
+    },
+    _ => value_null!("eval_less_or_equal"),
+  }
+}
+
+/// Evaluates whether the left value is greater than the right value.
+pub(crate) fn eval_gt(lhv: Value, rhv: Value) -> Value {
+  match lhv {
+    Value::Number(lh) => match rhv {
+      Value::Number(rh) => Value::Boolean(lh > rh),
+      _ => value_null!("eval_greater_then_number"),
+    },
+    Value::String(lh) => match rhv {
+      Value::String(rh) => Value::Boolean(lh.cmp(&rh) == Ordering::Greater),
+      _ => value_null!("eval_greater_then_string"),
+    },
+    Value::Date(lh) => match rhv {
+      Value::Date(rh) => Value::Boolean(lh > rh),
+      _ => value_null!("eval_greater_then_date"),
+    },
+    Value::DateTime(lh) => match rhv {
+      Value::DateTime(rh) => lh.after(&rh).map_or_else(|| value_null!("eval_greater_then_date_time"), Value::Boolean),
+      _ => value_null!("eval_greater_then_date_time"),
+    },
+    _ => value_null!("eval_greater_then"),
+  }
+}
+
+/// Evaluates whether the left value is less than or equal to the right value.
+pub(crate) fn eval_le(lhv: Value, rhv: Value) -> Value {
+  match lhv {
+    Value::Number(lh) => match rhv {
+      Value::Number(rh) => Value::Boolean(lh <= rh),
+      _ => value_null!("eval_less_or_equal_number"),
+    },
+    Value::String(lh) => match rhv {
+      Value::String(rh) => Value::Boolean(lh.cmp(&rh) != Ordering::Greater),
+      _ => value_null!("eval_less_or_equal_string"),
+    },
+    Value::Date(lh) => match rhv {
+      Value::Date(rh) => Value::Boolean(lh <= rh),
+      _ => value_null!("eval_less_or_equal_date"),
+    },
+    Value::DateTime(lh) => match rhv {
+      Value::DateTime(rh) => lh
+        .before_or_equal(&rh)
+        .map_or_else(|| value_null!("eval_less_or_equal_date_time"), Value::Boolean),
+      _ => value_null!("eval_less_or_equal_date_time"),
+    },
+    _ => value_null!("eval_less_or_equal"),
+  }
+}
+
+/// Evaluates whether the left value is less than the right value.
+pub(crate) fn eval_lt(lhv: Value, rhv: Value) -> Value {
+  match lhv {
+    Value::Number(lh) => match rhv {
+      Value::Number(rh) => Value::Boolean(lh < rh),
+      _ => value_null!("eval_less_then_number"),
+    },
+    Value::String(lh) => match rhv {
+      Value::String(rh) => Value::Boolean(lh.cmp(&rh) == Ordering::Less),
+      _ => value_null!("eval_less_then_string"),
+    },
+    Value::Date(lh) => match rhv {
+      Value::Date(rh) => Value::Boolean(lh < rh),
+      _ => value_null!("eval_less_then_date"),
+    },
+    Value::DateTime(lh) => match rhv {
+      Value::DateTime(rh) => lh.before(&rh).map_or_else(|| value_null!("eval_less_then_date_time"), Value::Boolean),
+      _ => value_null!("eval_less_then_date_time"),
+    },
+    _ => value_null!("eval_less_then"),
+  }
+}
+
+/// Evaluates the product of two values.
+pub(crate) fn eval_mul(lhv: Value, rhv: Value) -> Value {
+  match lhv {
+    Value::Number(lh) => match rhv {
+      Value::Number(rh) => Value::Number(lh * rh),
+      _ => value_null!("[multiplication] incompatible types: {} * {}", lhv, rhv),
+    },
+    value @ Value::Null(_) => value,
+    _ => value_null!("[multiplication] incompatible types: {} * {}", lhv, rhv),
+  }
+}
+
+/// Evaluates the arithmetic negation of a value.
+pub(crate) fn eval_neg(lhv: Value) -> Value {
+  match lhv {
+    Value::Number(lh) => Value::Number(-lh),
+    Value::DaysAndTimeDuration(lh) => Value::DaysAndTimeDuration(-lh),
+    _ => value_null!("arithmetic negation err 1"),
+  }
+}
+
+/// Evaluates the inequality of two values.
+pub(crate) fn eval_nq(lhv: Value, rhv: Value) -> Value {
+  if let Some(result) = eval_ternary_equality(&lhv, &rhv) {
+    Value::Boolean(!result)
+  } else {
+    value_null!()
+  }
+}
+
+/// Evaluates the disjunction of two values.
+pub(crate) fn eval_or(lhv: Value, rhv: Value) -> Value {
+  match lhv {
+    Value::Boolean(lh) => match rhv {
+      Value::Boolean(rh) => Value::Boolean(lh || rh),
+      _ => {
+        if lh {
+          Value::Boolean(true)
+        } else {
+          value_null!()
+        }
+      }
+    },
+    _ => match rhv {
+      Value::Boolean(rh) => {
+        if rh {
+          Value::Boolean(true)
+        } else {
+          value_null!()
+        }
+      }
+      _ => value_null!(),
+    },
+  }
+}
+
+/// Evaluates the difference of two values.
+pub(crate) fn eval_sub(lhv: Value, rhv: Value) -> Value {
+  match lhv {
+    Value::Number(ref lh) => {
+      if let Value::Number(ref rh) = rhv {
+        return Value::Number(*lh - *rh);
+      }
+    }
+    Value::DateTime(ref lh) => match rhv {
+      Value::DateTime(ref rh) => {
+        if let Some(a) = subtract(lh, rh) {
+          return Value::DaysAndTimeDuration(FeelDaysAndTimeDuration::default().nano(a).build());
+        }
+      }
+      Value::DaysAndTimeDuration(ref rh) => {
+        if let Some(result) = lh - rh {
+          return Value::DateTime(result);
+        }
+      }
+      _ => {}
+    },
+    _ => {}
+  }
+  //TODO make a macro for incompatible types
+  value_null!("[subtraction] incompatible types: {} - {}", lhv, rhv)
+}
+
+/// Evaluates the `in` operator, checks if the value satisfies the test.
+pub(crate) fn eval_in(lhv: Value, rhv: Value) -> Value {
+  match rhv {
+    inner @ Value::Number(_)
+    | inner @ Value::String(_)
+    | inner @ Value::Boolean(_)
+    | inner @ Value::Date(_)
+    | inner @ Value::Time(_)
+    | inner @ Value::DateTime(_)
+    | inner @ Value::YearsAndMonthsDuration(_)
+    | inner @ Value::DaysAndTimeDuration(_)
+    | inner @ Value::Context(_) => eval_in_equal(&lhv, &inner),
+    Value::Range(_, _, _, _) => eval_in_range(&lhv, &rhv),
+    Value::List(inner) => {
+      if let Value::List(_) = lhv {
+        eval_in_list_in_list(&lhv, inner.as_vec())
+      } else {
+        eval_in_list(&lhv, inner.as_vec())
+      }
+    }
+    Value::ExpressionList(inner) => eval_in_list(&lhv, inner.as_vec()),
+    Value::NegatedCommaList(inner) => eval_in_negated_list(&lhv, inner.as_vec()),
+    Value::UnaryLess(inner) => eval_in_unary_less(&lhv, inner.borrow()),
+    Value::UnaryLessOrEqual(inner) => eval_in_unary_less_or_equal(&lhv, inner.borrow()),
+    Value::UnaryGreater(inner) => eval_in_unary_greater(&lhv, inner.borrow()),
+    Value::UnaryGreaterOrEqual(inner) => eval_in_unary_greater_or_equal(&lhv, inner.borrow()),
+    Value::Irrelevant => match lhv {
+      Value::Null(_) => VALUE_FALSE,
+      _ => VALUE_TRUE,
+    },
+    _ => {
+      value_null!("eval_in")
+    }
+  }
+}
+
+/// Evaluates the path expression, returns the value of the property with specified name.
+pub(crate) fn eval_path(lhv: Value, name: &Name) -> Value {
+  match lhv {
+    Value::Context(context) => {
+      if let Some(value) = context.get_entry(name) {
+        value.clone()
+      } else {
+        value_null!("eval_path_expression: no entry {} in context: {}", name, context)
+      }
+    }
+    Value::List(items) => {
+      let mut result = vec![];
+      for item in items.as_vec() {
+        if let Value::Context(context) = item {
+          if let Some(value) = context.get_entry(name) {
+            result.push(value.clone());
+          }
+        } else {
+          return value_null!("eval_path_expression: no context in list");
+        }
+      }
+      Value::List(Values::new(result))
+    }
+    Value::Date(date) => match name.to_string().as_str() {
+      "year" => Value::Number(date.year().into()),
+      "month" => Value::Number(date.month().into()),
+      "day" => Value::Number(date.day().into()),
+      "weekday" => {
+        if let Some(day_num) = date.weekday() {
+          Value::Number(day_num.into())
+        } else {
+          value_null!("could not retrieve weekday for date")
+        }
+      }
+      _ => value_null!("no such property in date"),
+    },
+    Value::DateTime(date_time) => match name.to_string().as_str() {
+      "year" => Value::Number(date_time.year().into()),
+      "month" => Value::Number(date_time.month().into()),
+      "day" => Value::Number(date_time.day().into()),
+      "weekday" => {
+        if let Some(day_num) = date_time.weekday() {
+          Value::Number(day_num.into())
+        } else {
+          value_null!("could not retrieve weekday for date and time")
+        }
+      }
+      "hour" => Value::Number(date_time.hour().into()),
+      "minute" => Value::Number(date_time.minute().into()),
+      "second" => Value::Number(date_time.second().into()),
+      "time offset" => {
+        if let Some(offset) = date_time.feel_time_offset() {
+          Value::DaysAndTimeDuration(FeelDaysAndTimeDuration::default().second(offset as i64).build())
+        } else {
+          value_null!("aaaa")
+        }
+      }
+      "timezone" => {
+        if let Some(feel_time_zone) = date_time.feel_time_zone() {
+          Value::String(feel_time_zone)
+        } else {
+          value_null!("bbb")
+        }
+      }
+      _ => value_null!("no such property in date and time"),
+    },
+    Value::Time(time) => match name.to_string().as_str() {
+      "hour" => Value::Number(time.hour().into()),
+      "minute" => Value::Number(time.minute().into()),
+      "second" => Value::Number(time.second().into()),
+      "time offset" => {
+        if let Some(offset) = time.feel_time_offset() {
+          Value::DaysAndTimeDuration(FeelDaysAndTimeDuration::default().second(offset as i64).build())
+        } else {
+          value_null!("ccc")
+        }
+      }
+      "timezone" => {
+        if let Some(feel_time_zone) = time.feel_time_zone() {
+          Value::String(feel_time_zone)
+        } else {
+          value_null!("ddd")
+        }
+      }
+      _ => value_null!("no such property in date and time"),
+    },
+    Value::DaysAndTimeDuration(dt_duration) => match name.to_string().as_str() {
+      "days" => Value::Number(dt_duration.get_days().into()),
+      "hours" => Value::Number(dt_duration.get_hours().into()),
+      "minutes" => Value::Number(dt_duration.get_minutes().into()),
+      "seconds" => Value::Number(dt_duration.get_seconds().into()),
+      _ => value_null!("no such property in days and time duration"),
+    },
+    Value::YearsAndMonthsDuration(ym_duration) => match name.to_string().as_str() {
+      "years" => Value::Number(ym_duration.years().into()),
+      "months" => Value::Number(ym_duration.months().into()),
+      _ => value_null!("no such property in years and months duration"),
+    },
+    _ => value_null!("zzz lhv={}", lhv),
+  }
+}
+
+/// Evaluates the value of the qualified name.
+pub(crate) fn eval_qualified_name(scope: &Scope, names: &[Name]) -> Value {
+  scope.search_deep(names).unwrap_or_else(|| value_null!("no value for qualified name"))
+}
+
+/// Evaluates the range from its start and end.
+pub(crate) fn eval_range(lhv: Value, rhv: Value) -> Value {
+  if let Value::IntervalStart(lhv, l_closed) = lhv {
+    if let Value::IntervalEnd(rhv, r_closed) = rhv {
+      Value::Range(lhv, l_closed, rhv, r_closed)
+    } else {
+      value_null!("expected interval end")
+    }
+  } else {
+    value_null!("expected interval start")
+  }
+}
+
+/// Evaluates the value of the name, falling back to built-in function with the same name
+/// and business calendar function with the same name, when the `calendar` feature is enabled.
+pub(crate) fn eval_name(scope: &Scope, name: &Name) -> Value {
+  if let Some(value) = scope.get_entry(name) {
+    value
+  } else if let Ok(bif) = Bif::from_str(&name.to_string()) {
+    Value::BuiltInFunction(bif)
+  } else {
+    #[cfg(feature = "calendar")]
+    if let Some(function) = crate::calendar::calendar_function(name) {
+      return function;
+    }
+    value_null!("context has no value for key '{}'", name)
+  }
+}
+
 /// Evaluates ternary equality between two values.
 pub fn eval_ternary_equality(lhs: &Value, rhs: &Value) -> Option<bool> {
   match lhs {
@@ -2164,8 +2308,11 @@ fn eval_function_named(scope: &Scope, arguments: &Value, parameters: &[(Name, Fe
 
 /// Evaluates function definition with arguments (actual parameters) passed in context.
 fn eval_function_definition(scope: &Scope, ctx: &FeelContext, body: &FunctionBody, result_type: FeelType) -> Value {
-  scope.push(ctx.clone());
-  let result = body.evaluate(scope);
-  scope.pop();
+  let result = limits::evaluate_function(|| {
+    scope.push(ctx.clone());
+    let result = body.evaluate(scope);
+    scope.pop();
+    result
+  });
   result_type.coerced(&result)
 }
@@ -0,0 +1,369 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Business calendar extension functions.
+//!
+//! When the `calendar` feature is enabled, the following functions are available in `FEEL` expressions:
+//! - `add business days(date, days, calendar)` - returns the date shifted by the number of business days,
+//! - `business days between(from, to, calendar)` - returns the number of business days from `from` (inclusive) to `to` (exclusive),
+//! - `is holiday(date, calendar)` - returns `true` when the date is a holiday listed in the calendar.
+//!
+//! The argument named `calendar` is the name of a [BusinessCalendar] from [BusinessCalendars]
+//! made available to the evaluation with [evaluate_with_calendars].
+//!
+//! ### Holiday calendar format
+//!
+//! Holiday calendars are plain text files, one entry per line:
+//! - `YYYY-MM-DD` - a holiday on a specific date,
+//! - `--MM-DD` - a holiday repeated every year,
+//! - `weekend: saturday sunday` - days of the week that are not business days (default: saturday and sunday).
+//!
+//! Any text following the date is treated as the name of the holiday and ignored.
+//! Empty lines and everything after `#` are ignored.
+
+use crate::errors::*;
+use dmntk_common::Result;
+use dmntk_feel::values::Value;
+use dmntk_feel::{last_day_of_month, value_null, FeelDate, FeelNumber, FeelType, FunctionBody, Name, Scope};
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// Name of the function that adds business days to a date.
+const ADD_BUSINESS_DAYS: &str = "add business days";
+/// Name of the function that counts business days between two dates.
+const BUSINESS_DAYS_BETWEEN: &str = "business days between";
+/// Name of the function that checks if a date is a holiday.
+const IS_HOLIDAY: &str = "is holiday";
+
+/// Maximum number of days scanned when adding business days or counting business days between dates (about 1000 years).
+pub const MAX_SCANNED_DAYS: usize = 366_000;
+
+thread_local! {
+  /// Business calendars available to the evaluation running in the current thread.
+  static CALENDARS: RefCell<Option<Arc<BusinessCalendars>>> = const { RefCell::new(None) };
+}
+
+/// Collection of business calendars, indexed by calendar name.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BusinessCalendars(HashMap<String, Arc<BusinessCalendar>>);
+
+impl BusinessCalendars {
+  /// Adds the business calendar, replacing a calendar with the same name.
+  pub fn add(&mut self, calendar: BusinessCalendar) {
+    self.0.insert(calendar.name.clone(), Arc::new(calendar));
+  }
+  /// Removes the business calendar with specified name.
+  pub fn remove(&mut self, name: &str) {
+    self.0.remove(name);
+  }
+  /// Removes all business calendars.
+  pub fn clear(&mut self) {
+    self.0.clear();
+  }
+  /// Returns the business calendar with specified name.
+  pub fn get(&self, name: &str) -> Option<&BusinessCalendar> {
+    self.0.get(name).map(|calendar| calendar.as_ref())
+  }
+}
+
+/// Restores business calendars available before the evaluation, also when the evaluation panics.
+struct CalendarsRestorer(Option<Arc<BusinessCalendars>>);
+
+impl Drop for CalendarsRestorer {
+  fn drop(&mut self) {
+    let previous = self.0.take();
+    CALENDARS.with(|cell| cell.replace(previous));
+  }
+}
+
+/// Runs the evaluation with specified business calendars available to business calendar functions.
+///
+/// Calendars are visible only in the current thread and only until the evaluation is finished.
+pub fn evaluate_with_calendars<R, F>(calendars: &Arc<BusinessCalendars>, f: F) -> R
+where
+  F: FnOnce() -> R,
+{
+  let _restorer = CalendarsRestorer(CALENDARS.with(|cell| cell.replace(Some(Arc::clone(calendars)))));
+  f()
+}
+
+/// Business calendar defining weekend days and holidays.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BusinessCalendar {
+  /// Name of the calendar.
+  name: String,
+  /// Days of the week that are not business days, `1` is Monday, `7` is Sunday.
+  weekend: BTreeSet<u32>,
+  /// Holidays on specific dates.
+  holidays: BTreeSet<(i32, u8, u8)>,
+  /// Holidays repeated every year.
+  annual_holidays: BTreeSet<(u8, u8)>,
+}
+
+impl BusinessCalendar {
+  /// Creates an empty calendar with Saturday and Sunday as weekend days.
+  pub fn new(name: &str) -> Self {
+    Self {
+      name: name.to_string(),
+      weekend: [6, 7].into_iter().collect(),
+      holidays: BTreeSet::new(),
+      annual_holidays: BTreeSet::new(),
+    }
+  }
+  /// Parses the calendar from text in holiday calendar format.
+  pub fn parse(name: &str, text: &str) -> Result<Self> {
+    let mut calendar = Self::new(name);
+    for (index, line) in text.lines().enumerate() {
+      let content = line.split('#').next().unwrap_or_default().trim();
+      if content.is_empty() {
+        continue;
+      }
+      if let Some(days) = content.strip_prefix("weekend:") {
+        calendar.weekend = days.split_whitespace().map(weekday_number).collect::<Result<BTreeSet<u32>>>()?;
+        continue;
+      }
+      let date = content.split_whitespace().next().unwrap_or_default();
+      if let Some(month_day) = date.strip_prefix("--") {
+        let (month, day) = parse_month_day(month_day).ok_or_else(|| err_invalid_calendar_entry(index + 1, line))?;
+        calendar.annual_holidays.insert((month, day));
+      } else {
+        let date = FeelDate::try_from(date).map_err(|_| err_invalid_calendar_entry(index + 1, line))?;
+        calendar.add_holiday(&date);
+      }
+    }
+    Ok(calendar)
+  }
+  /// Returns the name of the calendar.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+  /// Adds a holiday on specified date.
+  pub fn add_holiday(&mut self, date: &FeelDate) {
+    self.holidays.insert((date.year(), date.month(), date.day()));
+  }
+  /// Returns `true` when specified date is a holiday listed in the calendar.
+  pub fn is_holiday(&self, date: &FeelDate) -> bool {
+    self.holidays.contains(&(date.year(), date.month(), date.day())) || self.annual_holidays.contains(&(date.month(), date.day()))
+  }
+  /// Returns `true` when specified date is neither a weekend day nor a holiday.
+  pub fn is_business_day(&self, date: &FeelDate) -> Option<bool> {
+    Some(!self.weekend.contains(&date.weekday()?) && !self.is_holiday(date))
+  }
+  /// Returns the date shifted by specified number of business days,
+  /// negative number of days shifts the date backwards.
+  ///
+  /// Returns [None] when the date is out of range, or when more than [MAX_SCANNED_DAYS] days
+  /// would have to be scanned, or when the evaluation limits are exceeded.
+  pub fn add_business_days(&self, date: &FeelDate, days: isize) -> Option<FeelDate> {
+    if days != 0 && self.weekend.len() == 7 {
+      return None;
+    }
+    let mut result = date.clone();
+    let mut remaining = days.unsigned_abs();
+    let mut scanned = 0;
+    while remaining > 0 {
+      scanned += 1;
+      if scanned > MAX_SCANNED_DAYS || crate::limits::check_iteration().is_err() {
+        return None;
+      }
+      result = if days > 0 { result.next_day()? } else { result.previous_day()? };
+      if self.is_business_day(&result)? {
+        remaining -= 1;
+      }
+    }
+    Some(result)
+  }
+  /// Returns the number of business days from `from` (inclusive) to `to` (exclusive),
+  /// the number is negative when `to` is before `from`.
+  ///
+  /// Returns [None] when the date is out of range, or when more than [MAX_SCANNED_DAYS] days
+  /// would have to be scanned, or when the evaluation limits are exceeded.
+  pub fn business_days_between(&self, from: &FeelDate, to: &FeelDate) -> Option<isize> {
+    let (mut date, end, sign) = if from.before_or_equal(to)? {
+      (from.clone(), to, 1)
+    } else {
+      (to.clone(), from, -1)
+    };
+    let mut count = 0;
+    let mut scanned = 0;
+    while date.before(end)? {
+      scanned += 1;
+      if scanned > MAX_SCANNED_DAYS || crate::limits::check_iteration().is_err() {
+        return None;
+      }
+      if self.is_business_day(&date)? {
+        count += 1;
+      }
+      date = date.next_day()?;
+    }
+    Some(sign * count)
+  }
+}
+
+/// Returns the business calendar with specified name, available to the evaluation running in the current thread.
+fn available_calendar(name: &str) -> Option<Arc<BusinessCalendar>> {
+  CALENDARS.with(|cell| cell.borrow().as_ref().and_then(|calendars| calendars.0.get(name).cloned()))
+}
+
+/// Returns the definition of the business calendar function with specified name.
+pub(crate) fn calendar_function(name: &Name) -> Option<Value> {
+  match name.to_string().as_str() {
+    ADD_BUSINESS_DAYS => Some(function_definition(
+      &[("date", FeelType::Date), ("days", FeelType::Number), ("calendar", FeelType::String)],
+      FeelType::Date,
+      add_business_days,
+    )),
+    BUSINESS_DAYS_BETWEEN => Some(function_definition(
+      &[("from", FeelType::Date), ("to", FeelType::Date), ("calendar", FeelType::String)],
+      FeelType::Number,
+      business_days_between,
+    )),
+    IS_HOLIDAY => Some(function_definition(
+      &[("date", FeelType::Date), ("calendar", FeelType::String)],
+      FeelType::Boolean,
+      is_holiday,
+    )),
+    _ => None,
+  }
+}
+
+/// Builds the definition of a function implemented by specified evaluator.
+fn function_definition(parameters: &[(&str, FeelType)], result_type: FeelType, evaluator: fn(&Scope) -> Value) -> Value {
+  let parameters = parameters.iter().map(|(name, feel_type)| (Name::from(*name), feel_type.clone())).collect();
+  Value::FunctionDefinition(parameters, FunctionBody::External(Arc::new(Box::new(evaluator))), result_type)
+}
+
+/// Evaluates `add business days(date, days, calendar)`.
+fn add_business_days(scope: &Scope) -> Value {
+  let calendar = match calendar_argument(scope, ADD_BUSINESS_DAYS) {
+    Ok(calendar) => calendar,
+    Err(value) => return value,
+  };
+  let date = match date_argument(scope, ADD_BUSINESS_DAYS, "date") {
+    Ok(date) => date,
+    Err(value) => return value,
+  };
+  match scope.get_entry(&Name::from("days")) {
+    Some(Value::Number(days)) if days.is_integer() => match days.to_isize().and_then(|days| calendar.add_business_days(&date, days)) {
+      Some(result) => Value::Date(result),
+      None => value_null!("[{}] date out of range or more than {} days scanned", ADD_BUSINESS_DAYS, MAX_SCANNED_DAYS),
+    },
+    other => invalid_argument(ADD_BUSINESS_DAYS, "days", "integer number", other),
+  }
+}
+
+/// Evaluates `business days between(from, to, calendar)`.
+fn business_days_between(scope: &Scope) -> Value {
+  let calendar = match calendar_argument(scope, BUSINESS_DAYS_BETWEEN) {
+    Ok(calendar) => calendar,
+    Err(value) => return value,
+  };
+  let from = match date_argument(scope, BUSINESS_DAYS_BETWEEN, "from") {
+    Ok(date) => date,
+    Err(value) => return value,
+  };
+  let to = match date_argument(scope, BUSINESS_DAYS_BETWEEN, "to") {
+    Ok(date) => date,
+    Err(value) => return value,
+  };
+  match calendar.business_days_between(&from, &to) {
+    Some(count) => Value::Number(FeelNumber::from(count)),
+    None => value_null!("[{}] date out of range or more than {} days scanned", BUSINESS_DAYS_BETWEEN, MAX_SCANNED_DAYS),
+  }
+}
+
+/// Evaluates `is holiday(date, calendar)`.
+fn is_holiday(scope: &Scope) -> Value {
+  let calendar = match calendar_argument(scope, IS_HOLIDAY) {
+    Ok(calendar) => calendar,
+    Err(value) => return value,
+  };
+  match date_argument(scope, IS_HOLIDAY, "date") {
+    Ok(date) => Value::Boolean(calendar.is_holiday(&date)),
+    Err(value) => value,
+  }
+}
+
+/// Returns the available calendar named by the `calendar` argument.
+fn calendar_argument(scope: &Scope, function: &str) -> std::result::Result<Arc<BusinessCalendar>, Value> {
+  match scope.get_entry(&Name::from("calendar")) {
+    Some(Value::String(name)) => available_calendar(&name).ok_or_else(|| value_null!("[{}] business calendar '{}' is not available", function, name)),
+    other => Err(invalid_argument(function, "calendar", "string", other)),
+  }
+}
+
+/// Returns the value of the date argument with specified name.
+fn date_argument(scope: &Scope, function: &str, name: &str) -> std::result::Result<FeelDate, Value> {
+  match scope.get_entry(&Name::from(name)) {
+    Some(Value::Date(date)) => Ok(date),
+    other => Err(invalid_argument(function, name, "date", other)),
+  }
+}
+
+/// Builds null value with invalid argument message.
+fn invalid_argument(function: &str, argument: &str, expected: &str, actual: Option<Value>) -> Value {
+  value_null!(
+    "[{}] invalid argument '{}', expected {}, actual value is {}",
+    function,
+    argument,
+    expected,
+    actual.unwrap_or_else(|| value_null!())
+  )
+}
+
+/// Returns the number of the day of the week with specified name, `1` is Monday, `7` is Sunday.
+fn weekday_number(name: &str) -> Result<u32> {
+  match name.to_lowercase().as_str() {
+    "monday" => Ok(1),
+    "tuesday" => Ok(2),
+    "wednesday" => Ok(3),
+    "thursday" => Ok(4),
+    "friday" => Ok(5),
+    "saturday" => Ok(6),
+    "sunday" => Ok(7),
+    _ => Err(err_invalid_weekday_name(name)),
+  }
+}
+
+/// Parses month and day in format `MM-DD`.
+fn parse_month_day(text: &str) -> Option<(u8, u8)> {
+  let (month, day) = text.split_once('-')?;
+  let month = month.parse::<u8>().ok()?;
+  let day = day.parse::<u8>().ok()?;
+  // leap year is used, so 29th of February is a valid annual holiday
+  if day >= 1 && day <= last_day_of_month(2000, month)? {
+    Some((month, day))
+  } else {
+    None
+  }
+}
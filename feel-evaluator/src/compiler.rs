@@ -0,0 +1,247 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Compiler translating `FEEL` AST into instructions executed by the virtual machine.
+//!
+//! Literals are evaluated and subexpressions containing only constants are folded at compile time.
+//! Names and qualified names are resolved to variable slots at compile time, all references
+//! to the same name share the slot, so each variable is looked up in the scope at most once
+//! per execution.
+//!
+//! Nodes compiled into instructions of the virtual machine:
+//! - literals: `@` literals, booleans, `null`, numbers, strings and irrelevant entries (`-`),
+//! - names, qualified names and paths with a name as the right-hand side,
+//! - arithmetic operators (`+`, `-`, `*`, `/`, `**`) and arithmetic negation,
+//! - comparisons (`=`, `!=`, `<`, `<=`, `>`, `>=`, `between`) and logical operators (`and`, `or`),
+//! - lists, `if` expressions and `in` expressions,
+//! - unary tests: unary comparisons, expression lists, negated lists, intervals and ranges.
+//!
+//! All other nodes, like contexts, function invocations and definitions, `for`, `some` and `every`
+//! iterations, filters, `instance of` and paths not ending with a name, are compiled into
+//! a single instruction calling the evaluator built by the default builders,
+//! so the compiled program gives the same results, without the gain in speed for these nodes.
+
+use crate::builders::build_evaluator;
+use crate::vm::{Instruction, Program};
+use dmntk_common::Result;
+use dmntk_feel::values::Value;
+use dmntk_feel::{value_null, AstNode, Name, Scope};
+
+/// Compiles the AST node into program executed by the virtual machine.
+pub fn compile(node: &AstNode) -> Result<Program> {
+  let mut compiler = Compiler::default();
+  compiler.emit(node)?;
+  Ok(compiler.program)
+}
+
+/// Compiler state.
+#[derive(Default)]
+struct Compiler {
+  /// Program being compiled.
+  program: Program,
+}
+
+impl Compiler {
+  /// Emits instructions for specified node.
+  fn emit(&mut self, node: &AstNode) -> Result<()> {
+    match node {
+      AstNode::At(_) | AstNode::Boolean(_) | AstNode::Irrelevant | AstNode::Null | AstNode::Numeric(_, _) | AstNode::String(_) => {
+        let evaluator = build_evaluator(node)?;
+        self.emit_const(evaluator(&Scope::default()));
+        Ok(())
+      }
+      AstNode::Name(name) => {
+        self.emit_load(name);
+        Ok(())
+      }
+      AstNode::QualifiedName(segments) => {
+        let names = segments
+          .iter()
+          .filter_map(|segment| match segment {
+            AstNode::QualifiedNameSegment(name) => Some(name.clone()),
+            _ => None,
+          })
+          .collect::<Vec<Name>>();
+        let index = slot(&mut self.program.qualified_slots, &names);
+        self.program.code.push(Instruction::LoadQualified(index));
+        Ok(())
+      }
+      AstNode::Path(lhs, rhs) => match rhs.as_ref() {
+        AstNode::Name(name) => {
+          let index = slot(&mut self.program.names, name);
+          self.emit_operator(&[lhs], Instruction::Path(index))
+        }
+        _ => self.emit_eval(node),
+      },
+      AstNode::Add(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::Add),
+      AstNode::Sub(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::Sub),
+      AstNode::Mul(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::Mul),
+      AstNode::Div(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::Div),
+      AstNode::Exp(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::Exp),
+      AstNode::Neg(lhs) => self.emit_operator(&[lhs], Instruction::Neg),
+      AstNode::Eq(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::Eq),
+      AstNode::Nq(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::Nq),
+      AstNode::Lt(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::Lt),
+      AstNode::Le(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::Le),
+      AstNode::Gt(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::Gt),
+      AstNode::Ge(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::Ge),
+      AstNode::And(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::And),
+      AstNode::Or(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::Or),
+      AstNode::Between(lhs, mhs, rhs) => self.emit_operator(&[lhs, mhs, rhs], Instruction::Between),
+      AstNode::List(items) => {
+        let operands = items.iter().collect::<Vec<&AstNode>>();
+        self.emit_operator(&operands, Instruction::List(items.len()))
+      }
+      AstNode::If(lhs, mhs, rhs) => self.emit_if(lhs, mhs, rhs),
+      AstNode::In(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::In),
+      AstNode::UnaryLt(lhs) => self.emit_operator(&[lhs], Instruction::UnaryLt),
+      AstNode::UnaryLe(lhs) => self.emit_operator(&[lhs], Instruction::UnaryLe),
+      AstNode::UnaryGt(lhs) => self.emit_operator(&[lhs], Instruction::UnaryGt),
+      AstNode::UnaryGe(lhs) => self.emit_operator(&[lhs], Instruction::UnaryGe),
+      AstNode::ExpressionList(items) => {
+        let operands = items.iter().collect::<Vec<&AstNode>>();
+        self.emit_operator(&operands, Instruction::ExpressionList(items.len()))
+      }
+      AstNode::NegatedList(items) => {
+        let operands = items.iter().collect::<Vec<&AstNode>>();
+        self.emit_operator(&operands, Instruction::NegatedList(items.len()))
+      }
+      AstNode::IntervalStart(lhs, closed) => self.emit_operator(&[lhs], Instruction::IntervalStart(*closed)),
+      AstNode::IntervalEnd(lhs, closed) => self.emit_operator(&[lhs], Instruction::IntervalEnd(*closed)),
+      AstNode::Range(lhs, rhs) => self.emit_operator(&[lhs, rhs], Instruction::Range),
+      _ => self.emit_eval(node),
+    }
+  }
+
+  /// Emits the instruction evaluating the node with the evaluator built by the default builders.
+  fn emit_eval(&mut self, node: &AstNode) -> Result<()> {
+    let evaluator = build_evaluator(node)?;
+    self.program.evaluators.push(evaluator);
+    self.program.code.push(Instruction::Eval(self.program.evaluators.len() - 1));
+    Ok(())
+  }
+
+  /// Emits the instruction pushing a constant value.
+  fn emit_const(&mut self, value: Value) {
+    self.program.constants.push(value);
+    self.program.code.push(Instruction::Const(self.program.constants.len() - 1));
+  }
+
+  /// Emits the instruction loading the value of the variable, the slot is shared between all references to the same name.
+  fn emit_load(&mut self, name: &Name) {
+    let index = slot(&mut self.program.slots, name);
+    self.program.code.push(Instruction::Load(index));
+  }
+
+  /// Emits operands followed by the operator instruction,
+  /// when all operands are constants, the operator is evaluated at compile time.
+  fn emit_operator(&mut self, operands: &[&AstNode], instruction: Instruction) -> Result<()> {
+    let mut constants = vec![];
+    for operand in operands {
+      if let Some(index) = self.emit_operand(operand)? {
+        constants.push(index);
+      }
+    }
+    if constants.len() == operands.len() {
+      let values = self.take_constants(&constants);
+      let mut code: Vec<Instruction> = (0..values.len()).map(Instruction::Const).collect();
+      code.push(instruction);
+      let program = Program {
+        code,
+        constants: values,
+        names: self.program.names.clone(),
+        ..Default::default()
+      };
+      self.emit_const(program.execute(&Scope::default()));
+    } else {
+      self.program.code.push(instruction);
+    }
+    Ok(())
+  }
+
+  /// Emits the conditional expression, the branch is selected at compile time when the condition is constant.
+  fn emit_if(&mut self, lhs: &AstNode, mhs: &AstNode, rhs: &AstNode) -> Result<()> {
+    if let Some(index) = self.emit_operand(lhs)? {
+      return match self.take_constants(&[index]).remove(0) {
+        Value::Boolean(true) => self.emit(mhs),
+        Value::Boolean(false) | Value::Null(_) => self.emit(rhs),
+        _ => {
+          self.emit_const(value_null!("condition in 'if' expression is not a boolean value"));
+          Ok(())
+        }
+      };
+    }
+    let branch = self.program.code.len();
+    self.program.code.push(Instruction::Branch(0, 0));
+    self.emit(mhs)?;
+    let jump = self.program.code.len();
+    self.program.code.push(Instruction::Jump(0));
+    self.emit(rhs)?;
+    let end = self.program.code.len();
+    self.program.code[branch] = Instruction::Branch(jump + 1, end);
+    self.program.code[jump] = Instruction::Jump(end);
+    Ok(())
+  }
+
+  /// Emits an operand, returns the index of the constant when the operand was compiled into a single constant.
+  fn emit_operand(&mut self, node: &AstNode) -> Result<Option<usize>> {
+    let start = self.program.code.len();
+    self.emit(node)?;
+    if let [Instruction::Const(index)] = self.program.code[start..] {
+      Ok(Some(index))
+    } else {
+      Ok(None)
+    }
+  }
+
+  /// Removes trailing constant instructions referencing specified constants and returns the values of these constants.
+  fn take_constants(&mut self, indexes: &[usize]) -> Vec<Value> {
+    let values = indexes.iter().map(|index| self.program.constants[*index].clone()).collect();
+    self.program.code.truncate(self.program.code.len() - indexes.len());
+    if let Some(first) = indexes.first() {
+      if indexes.iter().enumerate().all(|(offset, index)| *index == first + offset) && first + indexes.len() == self.program.constants.len() {
+        self.program.constants.truncate(*first);
+      }
+    }
+    values
+  }
+}
+
+/// Returns the index of the slot holding specified item, the slot is added when not present.
+fn slot<T: PartialEq + Clone>(slots: &mut Vec<T>, item: &T) -> usize {
+  if let Some(index) = slots.iter().position(|slot| slot == item) {
+    index
+  } else {
+    slots.push(item.clone());
+    slots.len() - 1
+  }
+}
@@ -45,6 +45,14 @@ pub enum FeelEvaluatorError {
   ExpectedAstNode(String, String),
   #[error("unexpected AST node in evaluator builder {0}")]
   UnexpectedAstNode(String),
+  #[error("evaluation interrupted: {0}")]
+  EvaluationInterrupted(String),
+  #[cfg(feature = "calendar")]
+  #[error("invalid business calendar entry in line {0}: {1}")]
+  InvalidCalendarEntry(usize, String),
+  #[cfg(feature = "calendar")]
+  #[error("invalid name of the day of the week: {0}")]
+  InvalidWeekdayName(String),
 }
 
 impl From<FeelEvaluatorError> for DmntkError {
@@ -72,3 +80,17 @@ pub fn err_expected_ast_node(expected: &str, actual: &str) -> DmntkError {
 pub fn err_unexpected_ast_node(s: &str) -> DmntkError {
   FeelEvaluatorError::UnexpectedAstNode(s.to_string()).into()
 }
+
+pub fn err_evaluation_interrupted(reason: &str) -> DmntkError {
+  FeelEvaluatorError::EvaluationInterrupted(reason.to_string()).into()
+}
+
+#[cfg(feature = "calendar")]
+pub fn err_invalid_calendar_entry(line_number: usize, line: &str) -> DmntkError {
+  FeelEvaluatorError::InvalidCalendarEntry(line_number, line.to_string()).into()
+}
+
+#[cfg(feature = "calendar")]
+pub fn err_invalid_weekday_name(name: &str) -> DmntkError {
+  FeelEvaluatorError::InvalidWeekdayName(name.to_string()).into()
+}
@@ -31,10 +31,11 @@
  */
 
 use crate::errors::err_not_a_context;
+use crate::trace::EvaluationTrace;
 use dmntk_common::Result;
 use dmntk_feel::context::FeelContext;
 use dmntk_feel::values::Value;
-use dmntk_feel::{AstNode, Evaluator, FeelType, Scope};
+use dmntk_feel::{AstNode, AstSpans, Evaluator, FeelType, Scope};
 
 /// Evaluates a [Value] from given [AstNode].
 pub fn evaluate(scope: &Scope, node: &AstNode) -> Result<Value> {
@@ -47,6 +48,52 @@ pub fn prepare(node: &AstNode) -> Result<Evaluator> {
   crate::builders::build_evaluator(node)
 }
 
+/// Prepares an evaluator for given [AstNode] parsed from specified source text.
+///
+/// When the evaluator produces `null`, the reason starts with the sub-expression
+/// that produced it and its location in the source, like "`a.b` at 3:14: ...".
+pub fn prepare_with_spans(node: &AstNode, spans: &AstSpans, source: &str) -> Result<Evaluator> {
+  crate::builders::build_evaluator_with_spans(node, spans, source)
+}
+
+/// Prepares an evaluator for given [AstNode] parsed from specified source text,
+/// recording the values of all sub-expressions in specified trace.
+pub fn prepare_with_trace(node: &AstNode, spans: &AstSpans, source: &str, trace: &EvaluationTrace) -> Result<Evaluator> {
+  crate::builders::build_evaluator_with_trace(node, spans, source, trace)
+}
+
+/// Compiles given [AstNode] into an evaluator executing bytecode on the virtual machine.
+pub fn compile(node: &AstNode) -> Result<Evaluator> {
+  let program = crate::compiler::compile(node)?;
+  Ok(Box::new(move |scope: &Scope| program.execute(scope)))
+}
+
+/// Prepares an evaluator for given [AstNode] using specified backend.
+pub fn prepare_with(node: &AstNode, backend: Backend) -> Result<Evaluator> {
+  match backend {
+    Backend::Closures => prepare(node),
+    Backend::Bytecode => compile(node),
+  }
+}
+
+/// Backends available for evaluating `FEEL` expressions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Backend {
+  /// Expressions are evaluated by a tree of closures built from AST.
+  Closures,
+  /// Expressions are compiled into bytecode executed by the virtual machine,
+  /// nodes not supported by the virtual machine are evaluated by closures,
+  /// see the documentation of the compiler module for the list of supported nodes.
+  Bytecode,
+}
+
+impl Default for Backend {
+  /// Closures are the default backend.
+  fn default() -> Self {
+    Self::Closures
+  }
+}
+
 /// Evaluates the sum of specified values.
 pub fn evaluate_sum(values: Vec<Value>) -> Value {
   crate::bifs::core::sum(&values)
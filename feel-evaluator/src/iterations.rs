@@ -32,6 +32,7 @@
 
 //!
 
+use crate::limits::{check_iteration, check_list_size};
 use dmntk_feel::context::FeelContext;
 use dmntk_feel::values::{Value, Values};
 use dmntk_feel::{Evaluator, Name, Scope};
@@ -91,8 +92,8 @@ impl FeelIterator {
       values: Some(values),
     });
   }
-  ///
-  pub fn run<F>(&mut self, mut handler: F)
+  /// Runs the handler for each iteration, stops when the evaluation is interrupted.
+  pub fn run<F>(&mut self, mut handler: F) -> Result<(), Value>
   where
     F: FnMut(&FeelContext),
   {
@@ -120,6 +121,7 @@ impl FeelIterator {
           }
         }
         if !is_empty_iteration {
+          check_iteration()?;
           handler(&iteration_context);
         }
         let last_iteration_state_index = self.iteration_states.len() - 1;
@@ -161,6 +163,7 @@ impl FeelIterator {
         }
       }
     }
+    Ok(())
   }
 }
 
@@ -198,18 +201,20 @@ impl ForExpressionEvaluator {
       }
     }
   }
-  ///
-  pub fn evaluate(&mut self, scope: &Scope, evaluator: &Evaluator) -> Values {
+  /// Evaluates the list of results, returns `null` value when the evaluation is interrupted.
+  pub fn evaluate(&mut self, scope: &Scope, evaluator: &Evaluator) -> Result<Values, Value> {
     let mut results = vec![];
     self.feel_iterator.run(|ctx| {
-      let mut iteration_context = ctx.clone();
-      iteration_context.set_entry(&self.name_partial, Value::List(Values::new(results.clone())));
-      scope.push(iteration_context.clone());
-      let iteration_value = evaluator(scope);
-      scope.pop();
-      results.push(iteration_value);
-    });
-    Values::new(results)
+      if check_list_size(results.len() + 1).is_ok() {
+        let mut iteration_context = ctx.clone();
+        iteration_context.set_entry(&self.name_partial, Value::List(Values::new(results.clone())));
+        scope.push(iteration_context.clone());
+        let iteration_value = evaluator(scope);
+        scope.pop();
+        results.push(iteration_value);
+      }
+    })?;
+    Ok(Values::new(results))
   }
 }
 
@@ -236,14 +241,17 @@ impl SomeExpressionEvaluator {
   ///
   pub fn evaluate(&mut self, scope: &Scope, evaluator: &Evaluator) -> Value {
     let mut result = false;
-    self.feel_iterator.run(|ctx| {
+    let interrupted = self.feel_iterator.run(|ctx| {
       scope.push(ctx.clone());
       if let Value::Boolean(value) = evaluator(scope) {
         result = result || value;
       }
       scope.pop();
     });
-    Value::Boolean(result)
+    match interrupted {
+      Ok(()) => Value::Boolean(result),
+      Err(value) => value,
+    }
   }
 }
 
@@ -270,14 +278,17 @@ impl EveryExpressionEvaluator {
   ///
   pub fn evaluate(&mut self, scope: &Scope, evaluator: &Evaluator) -> Value {
     let mut result = true;
-    self.feel_iterator.run(|ctx| {
+    let interrupted = self.feel_iterator.run(|ctx| {
       scope.push(ctx.clone());
       if let Value::Boolean(value) = evaluator(scope) {
         result = result && value;
       }
       scope.pop();
     });
-    Value::Boolean(result)
+    match interrupted {
+      Ok(()) => Value::Boolean(result),
+      Err(value) => value,
+    }
   }
 }
 
@@ -293,7 +304,7 @@ mod tests {
     let mut iterator = FeelIterator::default();
     iterator.add_range("x".into(), 1, 3);
     let mut actual = vec![];
-    iterator.run(|ctx| actual.push(Value::Context(ctx.clone())));
+    iterator.run(|ctx| actual.push(Value::Context(ctx.clone()))).unwrap();
     assert_eq!(3, actual.len());
     assert_eq!(r#"[{x: 1}, {x: 2}, {x: 3}]"#, Values::new(actual).to_string());
   }
@@ -304,7 +315,7 @@ mod tests {
     iterator.add_range("x".into(), 1, 3);
     iterator.add_range("y".into(), 1, 5);
     let mut actual = vec![];
-    iterator.run(|ctx| actual.push(Value::Context(ctx.clone())));
+    iterator.run(|ctx| actual.push(Value::Context(ctx.clone()))).unwrap();
     assert_eq!(15, actual.len());
     assert_eq!(
       r#"[{x: 1, y: 1}, {x: 1, y: 2}, {x: 1, y: 3}, {x: 1, y: 4}, {x: 1, y: 5}, {x: 2, y: 1}, {x: 2, y: 2}, {x: 2, y: 3}, {x: 2, y: 4}, {x: 2, y: 5}, {x: 3, y: 1}, {x: 3, y: 2}, {x: 3, y: 3}, {x: 3, y: 4}, {x: 3, y: 5}]"#,
@@ -319,7 +330,7 @@ mod tests {
     iterator.add_range("y".into(), 1, 3);
     iterator.add_range("z".into(), 1, 4);
     let mut actual = vec![];
-    iterator.run(|ctx| actual.push(Value::Context(ctx.clone())));
+    iterator.run(|ctx| actual.push(Value::Context(ctx.clone()))).unwrap();
     assert_eq!(24, actual.len());
     assert_eq!(
       r#"[{x: 1, y: 1, z: 1}, {x: 1, y: 1, z: 2}, {x: 1, y: 1, z: 3}, {x: 1, y: 1, z: 4}, {x: 1, y: 2, z: 1}, {x: 1, y: 2, z: 2}, {x: 1, y: 2, z: 3}, {x: 1, y: 2, z: 4}, {x: 1, y: 3, z: 1}, {x: 1, y: 3, z: 2}, {x: 1, y: 3, z: 3}, {x: 1, y: 3, z: 4}, {x: 2, y: 1, z: 1}, {x: 2, y: 1, z: 2}, {x: 2, y: 1, z: 3}, {x: 2, y: 1, z: 4}, {x: 2, y: 2, z: 1}, {x: 2, y: 2, z: 2}, {x: 2, y: 2, z: 3}, {x: 2, y: 2, z: 4}, {x: 2, y: 3, z: 1}, {x: 2, y: 3, z: 2}, {x: 2, y: 3, z: 3}, {x: 2, y: 3, z: 4}]"#,
@@ -337,7 +348,7 @@ mod tests {
     ]);
     iterator.add_list("x".into(), list);
     let mut actual = vec![];
-    iterator.run(|ctx| actual.push(Value::Context(ctx.clone())));
+    iterator.run(|ctx| actual.push(Value::Context(ctx.clone()))).unwrap();
     assert_eq!(3, actual.len());
     assert_eq!(r#"[{x: "a"}, {x: "b"}, {x: "c"}]"#, Values::new(actual).to_string());
   }
@@ -354,7 +365,7 @@ mod tests {
     let list2 = Values::new(vec![value_number!(1, 0), value_number!(2, 0), value_number!(3, 0)]);
     iterator.add_list("y".into(), list2);
     let mut actual = vec![];
-    iterator.run(|ctx| actual.push(Value::Context(ctx.clone())));
+    iterator.run(|ctx| actual.push(Value::Context(ctx.clone()))).unwrap();
     assert_eq!(9, actual.len());
     assert_eq!(
       r#"[{x: "a", y: 1}, {x: "a", y: 2}, {x: "a", y: 3}, {x: "b", y: 1}, {x: "b", y: 2}, {x: "b", y: 3}, {x: "c", y: 1}, {x: "c", y: 2}, {x: "c", y: 3}]"#,
@@ -372,7 +383,7 @@ mod tests {
     iterator.add_list("y".into(), list_y);
     iterator.add_list("z".into(), list_z);
     let mut actual = vec![];
-    iterator.run(|ctx| actual.push(Value::Context(ctx.clone())));
+    iterator.run(|ctx| actual.push(Value::Context(ctx.clone()))).unwrap();
     assert_eq!(24, actual.len());
     assert_eq!(
       r#"[{x: "a", y: 1, z: 1}, {x: "a", y: 1, z: 2}, {x: "a", y: 1, z: 3}, {x: "a", y: 1, z: 4}, {x: "a", y: 2, z: 1}, {x: "a", y: 2, z: 2}, {x: "a", y: 2, z: 3}, {x: "a", y: 2, z: 4}, {x: "a", y: 3, z: 1}, {x: "a", y: 3, z: 2}, {x: "a", y: 3, z: 3}, {x: "a", y: 3, z: 4}, {x: "b", y: 1, z: 1}, {x: "b", y: 1, z: 2}, {x: "b", y: 1, z: 3}, {x: "b", y: 1, z: 4}, {x: "b", y: 2, z: 1}, {x: "b", y: 2, z: 2}, {x: "b", y: 2, z: 3}, {x: "b", y: 2, z: 4}, {x: "b", y: 3, z: 1}, {x: "b", y: 3, z: 2}, {x: "b", y: 3, z: 3}, {x: "b", y: 3, z: 4}]"#,
@@ -386,7 +397,7 @@ mod tests {
     iterator.add_range("x".into(), 1, 2);
     iterator.add_list("y".into(), Values::new(vec![value_number!(1, 0), value_number!(2, 0), value_number!(3, 0)]));
     let mut actual = vec![];
-    iterator.run(|ctx| actual.push(Value::Context(ctx.clone())));
+    iterator.run(|ctx| actual.push(Value::Context(ctx.clone()))).unwrap();
     assert_eq!(6, actual.len());
     assert_eq!(
       r#"[{x: 1, y: 1}, {x: 1, y: 2}, {x: 1, y: 3}, {x: 2, y: 1}, {x: 2, y: 2}, {x: 2, y: 3}]"#,
@@ -401,7 +412,7 @@ mod tests {
     let scope = &te_scope(r#"{x:null}"#);
     let node = dmntk_feel_parser::parse_expression(scope, "x+1", false).unwrap();
     let evaluator = crate::builders::build_evaluator(&node).unwrap();
-    let actual = iterator.evaluate(scope, &evaluator);
+    let actual = iterator.evaluate(scope, &evaluator).unwrap();
     assert_eq!(3, actual.len());
     assert_eq!(r#"[2, 3, 4]"#, actual.to_string());
   }
@@ -417,7 +428,7 @@ mod tests {
     let scope = &te_scope(r#"{x:null,y:null}"#);
     let node = dmntk_feel_parser::parse_expression(scope, "x+y", false).unwrap();
     let evaluator = crate::builders::build_evaluator(&node).unwrap();
-    let actual = iterator.evaluate(scope, &evaluator);
+    let actual = iterator.evaluate(scope, &evaluator).unwrap();
     assert_eq!(6, actual.len());
     assert_eq!(r#"[6, 7, 8, 7, 8, 9]"#, actual.to_string());
   }
@@ -429,7 +440,7 @@ mod tests {
     let scope = &te_scope(r#"{x:null}"#);
     let node = dmntk_feel_parser::parse_expression(scope, "x+1", false).unwrap();
     let evaluator = crate::builders::build_evaluator(&node).unwrap();
-    let actual = iterator.evaluate(scope, &evaluator);
+    let actual = iterator.evaluate(scope, &evaluator).unwrap();
     assert_eq!(0, actual.len());
     assert_eq!(r#"[]"#, actual.to_string());
   }
@@ -442,7 +453,7 @@ mod tests {
     let scope = &te_scope(r#"{x:null,y:null}"#);
     let node = dmntk_feel_parser::parse_expression(scope, "x+1", false).unwrap();
     let evaluator = crate::builders::build_evaluator(&node).unwrap();
-    let actual = iterator.evaluate(scope, &evaluator);
+    let actual = iterator.evaluate(scope, &evaluator).unwrap();
     assert_eq!(2, actual.len());
     assert_eq!(r#"[2, 3]"#, actual.to_string());
   }
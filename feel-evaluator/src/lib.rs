@@ -43,12 +43,25 @@ extern crate thiserror;
 
 mod bifs;
 mod builders;
+#[cfg(feature = "calendar")]
+mod calendar;
+mod compiler;
 mod errors;
 mod evaluators;
 mod iterations;
+mod limits;
 #[cfg(test)]
 mod tests;
+mod trace;
+mod type_checker;
+mod vm;
 
+#[cfg(feature = "calendar")]
+pub use crate::calendar::{evaluate_with_calendars, BusinessCalendar, BusinessCalendars, MAX_SCANNED_DAYS};
 pub use crate::evaluators::{
-  evaluate, evaluate_context, evaluate_context_node, evaluate_equals, evaluate_max, evaluate_min, evaluate_node_type, evaluate_sum, prepare,
+  compile, evaluate, evaluate_context, evaluate_context_node, evaluate_equals, evaluate_max, evaluate_min, evaluate_node_type, evaluate_sum, prepare,
+  prepare_with, prepare_with_spans, prepare_with_trace, Backend,
 };
+pub use crate::limits::{evaluate_with_limits, CancellationToken, EvaluationLimits, DEFAULT_MAX_RECURSION_DEPTH};
+pub use crate::trace::{EvaluationTrace, TraceEntry};
+pub use crate::type_checker::{infer_type, infer_type_with_spans, TypeError};
@@ -0,0 +1,279 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Limits and cancellation of the evaluation.
+//!
+//! Limits are enforced for evaluations run with [evaluate_with_limits].
+//! When any of the limits is exceeded or the evaluation is cancelled,
+//! all evaluators still running return `null` value with the reason of interruption,
+//! so the evaluation finishes as quickly as possible, and [evaluate_with_limits] reports an error.
+//!
+//! Evaluations run without limits are guarded by default limits from the outermost function invocation,
+//! so deep recursion ends with `null` value with the reason of interruption instead of overflowing the stack.
+
+use crate::errors::*;
+use dmntk_common::Result;
+use dmntk_feel::value_null;
+use dmntk_feel::values::Value;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default maximum depth of nested function invocations.
+pub const DEFAULT_MAX_RECURSION_DEPTH: usize = 256;
+
+thread_local! {
+  /// State of the evaluation run with limits in the current thread.
+  static GUARD: RefCell<Option<EvaluationGuard>> = const { RefCell::new(None) };
+}
+
+/// Limits applied to a single evaluation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvaluationLimits {
+  /// Maximum number of iterations in all `for`, `some` and `every` expressions.
+  pub max_iterations: Option<usize>,
+  /// Maximum depth of nested function invocations.
+  pub max_recursion_depth: Option<usize>,
+  /// Maximum number of elements in a list built by `for` expression.
+  pub max_list_size: Option<usize>,
+  /// Maximum duration of the evaluation.
+  pub timeout: Option<Duration>,
+}
+
+impl Default for EvaluationLimits {
+  /// Creates limits with default maximum recursion depth, all other limits are not set.
+  fn default() -> Self {
+    Self {
+      max_iterations: None,
+      max_recursion_depth: Some(DEFAULT_MAX_RECURSION_DEPTH),
+      max_list_size: None,
+      timeout: None,
+    }
+  }
+}
+
+/// Token for cancelling the evaluation from another thread.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+  /// Creates a new token, not cancelled yet.
+  pub fn new() -> Self {
+    Self::default()
+  }
+  /// Cancels all evaluations using this token.
+  pub fn cancel(&self) {
+    self.0.store(true, Ordering::Relaxed);
+  }
+  /// Returns `true` when this token was cancelled.
+  pub fn is_cancelled(&self) -> bool {
+    self.0.load(Ordering::Relaxed)
+  }
+}
+
+/// State of the evaluation run with limits.
+struct EvaluationGuard {
+  /// Limits applied to the evaluation.
+  limits: EvaluationLimits,
+  /// Token for cancelling the evaluation.
+  token: CancellationToken,
+  /// Moment in time when the evaluation times out.
+  deadline: Option<Instant>,
+  /// Number of iterations performed so far.
+  iterations: usize,
+  /// Current depth of nested function invocations.
+  depth: usize,
+  /// Reason of interrupting the evaluation.
+  interruption: Option<String>,
+}
+
+impl EvaluationGuard {
+  /// Creates the state of the evaluation with specified limits and cancellation token.
+  fn new(limits: &EvaluationLimits, token: &CancellationToken) -> Self {
+    Self {
+      limits: limits.clone(),
+      token: token.clone(),
+      deadline: limits.timeout.map(|timeout| Instant::now() + timeout),
+      iterations: 0,
+      depth: 0,
+      interruption: None,
+    }
+  }
+  /// Checks the timeout and cancellation.
+  fn check(&mut self) -> std::result::Result<(), Value> {
+    if self.interruption.is_none() {
+      if self.token.is_cancelled() {
+        self.interruption = Some("evaluation cancelled".to_string());
+      } else if matches!(self.deadline, Some(deadline) if Instant::now() > deadline) {
+        self.interruption = Some(format!("timeout of {} ms exceeded", self.limits.timeout.unwrap_or_default().as_millis()));
+      }
+    }
+    self.result()
+  }
+  /// Records the reason of interruption, unless the evaluation was already interrupted.
+  fn interrupt(&mut self, reason: String) -> std::result::Result<(), Value> {
+    self.interruption.get_or_insert(reason);
+    self.result()
+  }
+  /// Returns `null` value with the reason of interruption, when the evaluation was interrupted.
+  fn result(&self) -> std::result::Result<(), Value> {
+    match &self.interruption {
+      Some(reason) => Err(value_null!("{}", reason)),
+      None => Ok(()),
+    }
+  }
+}
+
+/// Runs the evaluation with specified limits and cancellation token.
+///
+/// Returns an error when any of the limits was exceeded or the evaluation was cancelled,
+/// the evaluation is not started at all when the token is already cancelled.
+pub fn evaluate_with_limits<R, F>(limits: &EvaluationLimits, token: &CancellationToken, f: F) -> Result<R>
+where
+  F: FnOnce() -> R,
+{
+  if token.is_cancelled() {
+    return Err(err_evaluation_interrupted("evaluation cancelled"));
+  }
+  let (result, interruption) = run_with_guard(EvaluationGuard::new(limits, token), f);
+  match interruption {
+    Some(reason) => Err(err_evaluation_interrupted(&reason)),
+    None => Ok(result),
+  }
+}
+
+/// Restores the state of the evaluation replaced by [run_with_guard], also when the evaluation panics.
+struct RestoreGuard(Option<Option<EvaluationGuard>>);
+
+impl RestoreGuard {
+  /// Restores the previous state of the evaluation and returns the replaced one.
+  fn restore(mut self) -> Option<EvaluationGuard> {
+    GUARD.with(|cell| cell.replace(self.0.take().flatten()))
+  }
+}
+
+impl Drop for RestoreGuard {
+  fn drop(&mut self) {
+    if let Some(previous) = self.0.take() {
+      GUARD.with(|cell| cell.replace(previous));
+    }
+  }
+}
+
+/// Runs the evaluation with specified state, returns the result and the reason of interruption.
+fn run_with_guard<R, F>(guard: EvaluationGuard, f: F) -> (R, Option<String>)
+where
+  F: FnOnce() -> R,
+{
+  let restore_guard = RestoreGuard(Some(GUARD.with(|cell| cell.replace(Some(guard)))));
+  let result = f();
+  let interruption = restore_guard.restore().and_then(|guard| guard.interruption);
+  (result, interruption)
+}
+
+/// Applies specified function to the state of the evaluation run with limits.
+fn with_guard<F>(f: F) -> std::result::Result<(), Value>
+where
+  F: FnOnce(&mut EvaluationGuard) -> std::result::Result<(), Value>,
+{
+  GUARD.with(|cell| cell.borrow_mut().as_mut().map_or(Ok(()), f))
+}
+
+/// Checks limits before the next iteration in `for`, `some` or `every` expression.
+pub(crate) fn check_iteration() -> std::result::Result<(), Value> {
+  with_guard(|guard| {
+    guard.iterations += 1;
+    match guard.limits.max_iterations {
+      Some(max_iterations) if guard.iterations > max_iterations => guard.interrupt(format!("maximum number of iterations {} exceeded", max_iterations)),
+      _ => guard.check(),
+    }
+  })
+}
+
+/// Checks if a list with specified number of elements does not exceed the maximum list size.
+pub(crate) fn check_list_size(size: usize) -> std::result::Result<(), Value> {
+  with_guard(|guard| match guard.limits.max_list_size {
+    Some(max_list_size) if size > max_list_size => guard.interrupt(format!("maximum list size {} exceeded", max_list_size)),
+    _ => guard.result(),
+  })
+}
+
+/// Evaluates the function body, checking limits before entering it.
+///
+/// When the evaluation was interrupted, `null` value with the reason of interruption is returned.
+/// Evaluations run without limits are guarded by default limits from the outermost function invocation.
+pub(crate) fn evaluate_function<F>(f: F) -> Value
+where
+  F: FnOnce() -> Value,
+{
+  if GUARD.with(|cell| cell.borrow().is_none()) {
+    let guard = EvaluationGuard::new(&EvaluationLimits::default(), &CancellationToken::new());
+    return run_with_guard(guard, || evaluate_function(f)).0;
+  }
+  if let Err(value) = enter_function() {
+    return value;
+  }
+  let function_exit = FunctionExit;
+  let value = f();
+  drop(function_exit);
+  match with_guard(|guard| guard.result()) {
+    Err(interruption) => interruption,
+    Ok(()) => value,
+  }
+}
+
+/// Checks limits before entering the function body.
+fn enter_function() -> std::result::Result<(), Value> {
+  with_guard(|guard| {
+    guard.check()?;
+    match guard.limits.max_recursion_depth {
+      Some(max_recursion_depth) if guard.depth >= max_recursion_depth => guard.interrupt(format!("maximum recursion depth {} exceeded", max_recursion_depth)),
+      _ => {
+        guard.depth += 1;
+        Ok(())
+      }
+    }
+  })
+}
+
+/// Records leaving the function body when dropped, also when the evaluation panics.
+struct FunctionExit;
+
+impl Drop for FunctionExit {
+  fn drop(&mut self) {
+    let _ = with_guard(|guard| {
+      guard.depth = guard.depth.saturating_sub(1);
+      Ok(())
+    });
+  }
+}
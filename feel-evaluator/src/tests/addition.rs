@@ -108,3 +108,78 @@ fn test_0013() {
     "Hello John Doe, you are EMPLOYED",
   );
 }
+
+#[test]
+fn test_0014() {
+  let scope = &te_scope(r#"{}"#);
+  te_date_time_local(
+    false,
+    scope,
+    r#"date and time("2021-12-31T23:30:00") + duration("PT1H")"#,
+    (2022, 1, 1),
+    (0, 30, 0, 0),
+  );
+  te_date_time_local(
+    false,
+    scope,
+    r#"duration("P1DT1H") + date and time("2021-12-31T23:30:00")"#,
+    (2022, 1, 2),
+    (0, 30, 0, 0),
+  );
+  te_date_time_offset(
+    false,
+    scope,
+    r#"date and time("2021-03-28T01:30:00+01:00") + duration("PT1H")"#,
+    (2021, 3, 28),
+    (2, 30, 0, 0),
+    3_600,
+  );
+  te_date_time_utc(
+    false,
+    scope,
+    r#"date and time("2021-03-28T01:30:00Z") + duration("-PT2H")"#,
+    (2021, 3, 27),
+    (23, 30, 0, 0),
+  );
+}
+
+#[test]
+fn test_0015() {
+  // adding duration across the start of daylight saving time (clocks are set forward)
+  let scope = &te_scope(r#"{}"#);
+  let expression = r#"date and time("2021-03-28T01:30:00@Europe/Warsaw") + duration("PT1H")"#;
+  te_string(false, scope, &format!("string({})", expression), "2021-03-28T03:30:00@Europe/Warsaw");
+  te_value(false, scope, &format!("({}).time offset", expression), r#"duration("PT2H")"#);
+  te_date_time_utc(false, scope, expression, (2021, 3, 28), (1, 30, 0, 0));
+  te_date_time_utc(
+    false,
+    scope,
+    r#"date and time("2021-03-27T12:00:00@Europe/Warsaw") + duration("P1D")"#,
+    (2021, 3, 28),
+    (11, 0, 0, 0),
+  );
+}
+
+#[test]
+fn test_0016() {
+  // adding duration across the end of daylight saving time (clocks are set back)
+  let scope = &te_scope(r#"{start: date and time("2021-10-31T01:30:00@Europe/Warsaw")}"#);
+  te_string(false, scope, r#"string(start + duration("PT1H"))"#, "2021-10-31T02:30:00@Europe/Warsaw");
+  te_value(false, scope, r#"(start + duration("PT1H")).time offset"#, r#"duration("PT2H")"#);
+  te_string(false, scope, r#"string(start + duration("PT2H"))"#, "2021-10-31T02:30:00@Europe/Warsaw");
+  te_value(false, scope, r#"(start + duration("PT2H")).time offset"#, r#"duration("PT1H")"#);
+  te_date_time_utc(false, scope, r#"start + duration("PT2H")"#, (2021, 10, 31), (1, 30, 0, 0));
+  te_string(
+    false,
+    scope,
+    r#"string(start + duration("PT2H") + duration("PT1H"))"#,
+    "2021-10-31T03:30:00@Europe/Warsaw",
+  );
+  te_value(
+    false,
+    scope,
+    r#"(start + duration("PT2H")) - (start + duration("PT1H"))"#,
+    r#"duration("PT1H")"#,
+  );
+  te_bool(false, scope, r#"start + duration("PT2H") > start + duration("PT1H")"#, true);
+}
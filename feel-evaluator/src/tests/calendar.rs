@@ -0,0 +1,242 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::calendar::{evaluate_with_calendars, BusinessCalendar, BusinessCalendars};
+use crate::{evaluate_with_limits, CancellationToken, EvaluationLimits};
+use dmntk_feel::last_day_of_month;
+use std::sync::Arc;
+
+const POLISH_HOLIDAYS: &str = r#"
+# Public holidays in Poland
+--01-01   New Year's Day
+--01-06   Epiphany
+2022-04-18  Easter Monday
+--05-01   Labour Day
+--05-03   Constitution Day
+--12-25   Christmas Day
+--12-26   Second Day of Christmas
+"#;
+
+const MIDDLE_EAST_HOLIDAYS: &str = r#"
+weekend: Friday Saturday
+2022-05-02  Eid al-Fitr # first day
+"#;
+
+fn calendars(name: &str, text: &str) -> Arc<BusinessCalendars> {
+  let mut calendars = BusinessCalendars::default();
+  calendars.add(BusinessCalendar::parse(name, text).unwrap());
+  Arc::new(calendars)
+}
+
+#[test]
+fn test_0001() {
+  evaluate_with_calendars(&calendars("PL-0001", POLISH_HOLIDAYS), || {
+    let scope = &te_scope("{}");
+    te_bool(false, scope, r#"is holiday(date("2022-12-25"), "PL-0001")"#, true);
+    te_bool(false, scope, r#"is holiday(date("2023-01-06"), "PL-0001")"#, true);
+    te_bool(false, scope, r#"is holiday(date("2022-04-18"), "PL-0001")"#, true);
+    te_bool(false, scope, r#"is holiday(date("2023-04-18"), "PL-0001")"#, false);
+    te_bool(false, scope, r#"is holiday(date("2022-04-16"), "PL-0001")"#, false);
+  });
+}
+
+#[test]
+fn test_0002() {
+  evaluate_with_calendars(&calendars("PL-0002", POLISH_HOLIDAYS), || {
+    let scope = &te_scope("{}");
+    te_date(false, scope, r#"add business days(date("2022-04-14"), 1, "PL-0002")"#, 2022, 4, 15);
+    te_date(false, scope, r#"add business days(date("2022-04-14"), 2, "PL-0002")"#, 2022, 4, 19);
+    te_date(false, scope, r#"add business days(date("2022-04-19"), -1, "PL-0002")"#, 2022, 4, 15);
+    te_date(false, scope, r#"add business days(date("2022-04-16"), 0, "PL-0002")"#, 2022, 4, 16);
+    te_date(false, scope, r#"add business days(date("2022-12-23"), 2, "PL-0002")"#, 2022, 12, 28);
+    te_date(false, scope, r#"add business days(date("2022-12-30"), 1, "PL-0002")"#, 2023, 1, 2);
+  });
+}
+
+#[test]
+fn test_0003() {
+  evaluate_with_calendars(&calendars("PL-0003", POLISH_HOLIDAYS), || {
+    let scope = &te_scope("{}");
+    te_number(
+      false,
+      scope,
+      r#"business days between(date("2022-04-11"), date("2022-04-25"), "PL-0003")"#,
+      9,
+      0,
+    );
+    te_number(
+      false,
+      scope,
+      r#"business days between(date("2022-04-25"), date("2022-04-11"), "PL-0003")"#,
+      -9,
+      0,
+    );
+    te_number(
+      false,
+      scope,
+      r#"business days between(date("2022-04-16"), date("2022-04-16"), "PL-0003")"#,
+      0,
+      0,
+    );
+    te_number(
+      false,
+      scope,
+      r#"business days between(from: date("2022-12-01"), to: date("2023-01-01"), calendar: "PL-0003")"#,
+      21,
+      0,
+    );
+  });
+}
+
+#[test]
+fn test_0004() {
+  evaluate_with_calendars(&calendars("AE-0004", MIDDLE_EAST_HOLIDAYS), || {
+    let scope = &te_scope("{}");
+    te_date(false, scope, r#"add business days(date("2022-04-28"), 1, "AE-0004")"#, 2022, 5, 1);
+    te_date(false, scope, r#"add business days(date("2022-04-28"), 2, "AE-0004")"#, 2022, 5, 3);
+    te_bool(false, scope, r#"is holiday(date("2022-04-29"), "AE-0004")"#, false);
+  });
+}
+
+#[test]
+fn test_0005() {
+  let scope = &te_scope(r#"{Start: date("2022-04-14"), Days: 2}"#);
+  evaluate_with_calendars(&calendars("PL-0005", POLISH_HOLIDAYS), || {
+    te_date(false, scope, r#"add business days(Start, Days, "PL-0005")"#, 2022, 4, 19);
+    te_null(
+      false,
+      scope,
+      r#"add business days(Start, Days, "XX-0005")"#,
+      "[add business days] business calendar 'XX-0005' is not available",
+    );
+    te_null(
+      false,
+      scope,
+      r#"add business days(Start, 1.5, "PL-0005")"#,
+      "[add business days] invalid argument 'days', expected integer number, actual value is 1.5",
+    );
+    te_null(false, scope, r#"is holiday(date("2022-04-14"))"#, "invalid number of arguments");
+  });
+}
+
+#[test]
+fn test_0006() {
+  assert_eq!(
+    r#"FeelEvaluatorError: invalid business calendar entry in line 2: 2022-02-30 Invalid"#,
+    BusinessCalendar::parse("0006", "# invalid date\n2022-02-30 Invalid").unwrap_err().to_string()
+  );
+  assert_eq!(
+    r#"FeelEvaluatorError: invalid business calendar entry in line 1: --02-30"#,
+    BusinessCalendar::parse("0006", "--02-30").unwrap_err().to_string()
+  );
+  assert_eq!(
+    r#"FeelEvaluatorError: invalid name of the day of the week: sat"#,
+    BusinessCalendar::parse("0006", "weekend: sat sun").unwrap_err().to_string()
+  );
+  let calendar = BusinessCalendar::parse("0006", "weekend:\n--02-29").unwrap();
+  assert_eq!("0006", calendar.name());
+  assert!(calendar.is_holiday(&FeelDate::new(2024, 2, 29)));
+  assert_eq!(Some(true), calendar.is_business_day(&FeelDate::new(2022, 4, 16)));
+  assert_eq!(Some(FeelDate::new(2024, 3, 1)), calendar.add_business_days(&FeelDate::new(2024, 2, 28), 1));
+}
+
+#[test]
+fn test_0007() {
+  // calendars are available only during the evaluation with calendars, also when the evaluation panics
+  let scope = &te_scope("{}");
+  let expression = r#"is holiday(date("2022-12-25"), "PL-0007")"#;
+  te_null(false, scope, expression, "[is holiday] business calendar 'PL-0007' is not available");
+  evaluate_with_calendars(&calendars("PL-0007", POLISH_HOLIDAYS), || {
+    te_bool(false, scope, expression, true);
+    evaluate_with_calendars(&calendars("AE-0007", MIDDLE_EAST_HOLIDAYS), || {
+      te_null(false, scope, expression, "[is holiday] business calendar 'PL-0007' is not available");
+    });
+    te_bool(false, scope, expression, true);
+  });
+  te_null(false, scope, expression, "[is holiday] business calendar 'PL-0007' is not available");
+  let result = std::panic::catch_unwind(|| evaluate_with_calendars(&calendars("PL-0007", POLISH_HOLIDAYS), || panic!("evaluation failed")));
+  assert!(result.is_err());
+  te_null(false, scope, expression, "[is holiday] business calendar 'PL-0007' is not available");
+}
+
+#[test]
+fn test_0008() {
+  // the number of scanned days is bounded, also when every day is a holiday
+  let mut text = "weekend:\n".to_string();
+  for month in 1..=12 {
+    for day in 1..=last_day_of_month(2000, month).unwrap() {
+      text.push_str(&format!("--{:02}-{:02}\n", month, day));
+    }
+  }
+  let scope = &te_scope("{}");
+  evaluate_with_calendars(&calendars("XX-0008", &text), || {
+    te_null(
+      false,
+      scope,
+      r#"add business days(date("2022-04-14"), 1, "XX-0008")"#,
+      "[add business days] date out of range or more than 366000 days scanned",
+    );
+  });
+  evaluate_with_calendars(&calendars("PL-0008", POLISH_HOLIDAYS), || {
+    te_null(
+      false,
+      scope,
+      r#"add business days(date("2022-04-14"), 1000000000, "PL-0008")"#,
+      "[add business days] date out of range or more than 366000 days scanned",
+    );
+    te_null(
+      false,
+      scope,
+      r#"business days between(date("2022-04-14"), date("9999-04-14"), "PL-0008")"#,
+      "[business days between] date out of range or more than 366000 days scanned",
+    );
+  });
+}
+
+#[test]
+fn test_0009() {
+  // scanned days are counted as iterations, so the evaluation limits are applied
+  let limits = EvaluationLimits {
+    max_iterations: Some(100),
+    ..Default::default()
+  };
+  let scope = &te_scope("{}");
+  let node = dmntk_feel_parser::parse_expression(scope, r#"add business days(date("2022-04-14"), 200, "PL-0009")"#, false).unwrap();
+  let result = evaluate_with_calendars(&calendars("PL-0009", POLISH_HOLIDAYS), || {
+    evaluate_with_limits(&limits, &CancellationToken::new(), || crate::evaluate(scope, &node))
+  });
+  assert_eq!(
+    "FeelEvaluatorError: evaluation interrupted: maximum number of iterations 100 exceeded",
+    result.unwrap_err().to_string()
+  );
+}
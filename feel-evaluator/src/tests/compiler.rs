@@ -0,0 +1,205 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::compiler::compile;
+use crate::tests::te_scope;
+use crate::vm::Instruction;
+use dmntk_feel::values::Value;
+use dmntk_feel::{value_number, AstNode, FeelNumber, Name, Scope};
+
+fn program(scope: &Scope, text: &str) -> crate::vm::Program {
+  compile(&dmntk_feel_parser::parse_expression(scope, text, false).unwrap()).unwrap()
+}
+
+#[test]
+fn _0001() {
+  let scope = &te_scope("{}");
+  let program = program(scope, "1 + 2 * 3");
+  assert_eq!(vec![Instruction::Const(0)], program.code);
+  assert_eq!(vec![value_number!(7)], program.constants);
+  assert_eq!(value_number!(7), program.execute(scope));
+}
+
+#[test]
+fn _0002() {
+  let scope = &te_scope("{a: 10, b: 2}");
+  let program = program(scope, "a * a - b / 2");
+  assert_eq!(
+    vec![
+      Instruction::Load(0),
+      Instruction::Load(0),
+      Instruction::Mul,
+      Instruction::Load(1),
+      Instruction::Const(0),
+      Instruction::Div,
+      Instruction::Sub
+    ],
+    program.code
+  );
+  assert_eq!(vec![Name::from("a"), Name::from("b")], program.slots);
+  assert_eq!(value_number!(99), program.execute(scope));
+}
+
+#[test]
+fn _0003() {
+  let scope = &te_scope("{a: 10}");
+  let program = program(scope, "if a > 5 then \"big\" else \"small\"");
+  assert_eq!(
+    vec![
+      Instruction::Load(0),
+      Instruction::Const(0),
+      Instruction::Gt,
+      Instruction::Branch(6, 7),
+      Instruction::Const(1),
+      Instruction::Jump(7),
+      Instruction::Const(2)
+    ],
+    program.code
+  );
+  assert_eq!(Value::String("big".to_string()), program.execute(scope));
+  assert_eq!(Value::String("small".to_string()), program.execute(&te_scope("{a: 1}")));
+}
+
+#[test]
+fn _0004() {
+  let scope = &te_scope("{a: 10}");
+  let program = program(scope, "if 1 < 2 then a else 0");
+  assert_eq!(vec![Instruction::Load(0)], program.code);
+  assert!(program.constants.is_empty());
+}
+
+#[test]
+fn _0005() {
+  let scope = &te_scope("{a: 10}");
+  let program = program(scope, "(if a > 5 then 1 else 2) + 3");
+  assert_eq!(Some(&Instruction::Add), program.code.last());
+  assert_eq!(value_number!(4), program.execute(scope));
+}
+
+#[test]
+fn _0006() {
+  let scope = &te_scope("{}");
+  let program = program(scope, "[1, 2 + 3, 4 * -1]");
+  assert_eq!(vec![Instruction::Const(0)], program.code);
+}
+
+#[test]
+fn _0007() {
+  let scope = &te_scope("{items: [1, 2, 3]}");
+  let program = program(scope, "count(items) + sum(for i in items return i * 2)");
+  assert_eq!(vec![Instruction::Eval(0), Instruction::Eval(1), Instruction::Add], program.code);
+  assert_eq!(value_number!(15), program.execute(scope));
+}
+
+#[test]
+fn _0008() {
+  let scope = &te_scope("{a: \"x\"}");
+  let program = program(scope, "if a then 1 else 2");
+  assert_eq!(
+    Value::Null(Some("condition in 'if' expression is not a boolean value".to_string())),
+    program.execute(scope)
+  );
+}
+
+#[test]
+fn _0009() {
+  // unary tests are compiled, no fallback evaluators are used, results are the same as built by default builders
+  let input = AstNode::Name(Name::from("a"));
+  for text in ["< 5, [10..20]", "> 10", "not(10, 11)", "-", "<= a", "(a..20]", "1, 2, 3", "[a, 15]"] {
+    let tests = dmntk_feel_parser::parse_unary_tests(&te_scope("{a: 1}"), text, false).unwrap();
+    let node = AstNode::In(Box::new(input.clone()), Box::new(tests));
+    let program = compile(&node).unwrap();
+    assert!(program.evaluators.is_empty(), "unexpected fallback evaluator in: {}", text);
+    assert_eq!(Some(&Instruction::In), program.code.last());
+    for scope in [te_scope("{a: 10}"), te_scope("{a: 15}"), te_scope("{a: [10, 15]}")] {
+      assert_eq!(crate::evaluate(&scope, &node).unwrap(), program.execute(&scope), "{}", text);
+    }
+  }
+}
+
+#[test]
+fn _0010() {
+  // unary tests with constant operands are folded
+  let scope = &te_scope("{}");
+  let tests = dmntk_feel_parser::parse_unary_tests(scope, "< 5, [10..20]", false).unwrap();
+  let program = compile(&AstNode::In(Box::new(AstNode::Name(Name::from("a"))), Box::new(tests))).unwrap();
+  assert_eq!(vec![Instruction::Load(0), Instruction::Const(0), Instruction::In], program.code);
+}
+
+#[test]
+fn _0011() {
+  // paths share the slot of the variable
+  let scope = &te_scope("{a: {b: 1, c: {d: 2}}}");
+  let program = program(scope, "a.b + a.c.d");
+  assert_eq!(
+    vec![
+      Instruction::Load(0),
+      Instruction::Path(0),
+      Instruction::Load(0),
+      Instruction::Path(2),
+      Instruction::Path(1),
+      Instruction::Add
+    ],
+    program.code
+  );
+  assert_eq!(vec![Name::from("a")], program.slots);
+  assert_eq!(vec![Name::from("b"), Name::from("d"), Name::from("c")], program.names);
+  assert_eq!(value_number!(3), program.execute(scope));
+  assert_eq!(
+    Value::Null(Some("eval_path_expression: no entry e in context: {b: 1, c: {d: 2}}".to_string())),
+    program_execute(scope, "a.e")
+  );
+  assert_eq!(value_number!(2021), program_execute(scope, "date(\"2021-05-12\").year"));
+}
+
+#[test]
+fn _0012() {
+  // qualified names are resolved to slots
+  let scope = &te_scope("{a: {b: 1}}");
+  let segments = |names: &[&str]| AstNode::QualifiedName(names.iter().map(|name| AstNode::QualifiedNameSegment(Name::from(*name))).collect());
+  let node = AstNode::Add(Box::new(segments(&["a", "b"])), Box::new(segments(&["a", "b"])));
+  let program = compile(&node).unwrap();
+  assert_eq!(
+    vec![Instruction::LoadQualified(0), Instruction::LoadQualified(0), Instruction::Add],
+    program.code
+  );
+  assert_eq!(vec![vec![Name::from("a"), Name::from("b")]], program.qualified_slots);
+  assert_eq!(value_number!(2), program.execute(scope));
+  assert_eq!(
+    Value::Null(Some("no value for qualified name".to_string())),
+    compile(&segments(&["a", "c"])).unwrap().execute(scope)
+  );
+}
+
+fn program_execute(scope: &Scope, text: &str) -> Value {
+  program(scope, text).execute(scope)
+}
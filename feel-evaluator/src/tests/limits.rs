@@ -0,0 +1,219 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use crate::{evaluate_with_limits, CancellationToken, EvaluationLimits};
+use dmntk_common::Result;
+use std::time::Duration;
+
+const RECURSIVE_FUNCTION: &str = "{f: function(n) if n = 0 then 0 else 1 + f(n - 1), r: f(N)}.r";
+
+fn eval_with_limits(limits: &EvaluationLimits, token: &CancellationToken, input: &str, expression: &str) -> Result<Value> {
+  let scope = &te_scope(input);
+  let node = dmntk_feel_parser::parse_expression(scope, expression, false).unwrap();
+  let evaluator = crate::builders::build_evaluator(&node).unwrap();
+  evaluate_with_limits(limits, token, || evaluator(scope))
+}
+
+fn assert_interrupted(reason: &str, result: Result<Value>) {
+  assert_eq!(
+    format!("FeelEvaluatorError: evaluation interrupted: {}", reason),
+    result.unwrap_err().to_string()
+  );
+}
+
+#[test]
+fn test_0001() {
+  let limits = EvaluationLimits {
+    max_iterations: Some(100),
+    ..Default::default()
+  };
+  let token = CancellationToken::new();
+  assert_eq!(
+    "100",
+    eval_with_limits(&limits, &token, "{}", "count(for i in 1..100 return i)").unwrap().to_string()
+  );
+  assert_interrupted(
+    "maximum number of iterations 100 exceeded",
+    eval_with_limits(&limits, &token, "{}", "count(for i in 1..1000000000 return i)"),
+  );
+  assert_interrupted(
+    "maximum number of iterations 100 exceeded",
+    eval_with_limits(&limits, &token, "{L: for j in 1..20 return j}", "some i in L, k in L satisfies i > k + 100"),
+  );
+  assert_interrupted(
+    "maximum number of iterations 100 exceeded",
+    eval_with_limits(&limits, &token, "{L: for j in 1..101 return j}", "every i in L satisfies i > 0"),
+  );
+}
+
+#[test]
+fn test_0002() {
+  let limits = EvaluationLimits {
+    max_recursion_depth: Some(50),
+    ..Default::default()
+  };
+  let token = CancellationToken::new();
+  assert_eq!("49", eval_with_limits(&limits, &token, "{N: 49}", RECURSIVE_FUNCTION).unwrap().to_string());
+  assert_interrupted(
+    "maximum recursion depth 50 exceeded",
+    eval_with_limits(&limits, &token, "{N: 50}", RECURSIVE_FUNCTION),
+  );
+}
+
+#[test]
+fn test_0003() {
+  let limits = EvaluationLimits::default();
+  let token = CancellationToken::new();
+  assert_interrupted(
+    "maximum recursion depth 256 exceeded",
+    eval_with_limits(&limits, &token, "{N: 1000000}", RECURSIVE_FUNCTION),
+  );
+}
+
+#[test]
+fn test_0004() {
+  let limits = EvaluationLimits {
+    max_list_size: Some(10),
+    ..Default::default()
+  };
+  let token = CancellationToken::new();
+  assert_eq!(
+    "[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]",
+    eval_with_limits(&limits, &token, "{}", "for i in 1..10 return i").unwrap().to_string()
+  );
+  assert_interrupted(
+    "maximum list size 10 exceeded",
+    eval_with_limits(&limits, &token, "{}", "for i in 1..11 return i"),
+  );
+}
+
+#[test]
+fn test_0005() {
+  let limits = EvaluationLimits {
+    timeout: Some(Duration::from_millis(10)),
+    ..Default::default()
+  };
+  let token = CancellationToken::new();
+  assert_interrupted(
+    "timeout of 10 ms exceeded",
+    eval_with_limits(&limits, &token, "{}", "count(for i in 1..1000000000 return i)"),
+  );
+}
+
+#[test]
+fn test_0006() {
+  let limits = EvaluationLimits::default();
+  let token = CancellationToken::new();
+  let cancellation = token.clone();
+  std::thread::spawn(move || {
+    std::thread::sleep(Duration::from_millis(10));
+    cancellation.cancel();
+  });
+  assert_interrupted(
+    "evaluation cancelled",
+    eval_with_limits(&limits, &token, "{}", "count(for i in 1..1000000000 return i)"),
+  );
+  assert!(token.is_cancelled());
+}
+
+#[test]
+fn test_0007() {
+  let limits = EvaluationLimits {
+    max_iterations: Some(5),
+    ..Default::default()
+  };
+  let token = CancellationToken::new();
+  let scope = &te_scope("{}");
+  let node = dmntk_feel_parser::parse_expression(scope, "for i in 1..10 return i", false).unwrap();
+  let evaluator = crate::builders::build_evaluator(&node).unwrap();
+  let mut value = Value::Null(None);
+  assert!(evaluate_with_limits(&limits, &token, || value = evaluator(scope)).is_err());
+  assert_eq!("null(maximum number of iterations 5 exceeded)", value.to_string());
+  // limits are not applied outside of evaluation with limits
+  assert_eq!("[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]", evaluator(scope).to_string());
+}
+
+#[test]
+fn test_0008() {
+  let limits = EvaluationLimits {
+    max_iterations: Some(5),
+    ..Default::default()
+  };
+  let token = CancellationToken::new();
+  // right operands are not evaluated when the left operand determines the result
+  assert_eq!(
+    "false",
+    eval_with_limits(&limits, &token, "{}", "false and count(for i in 1..10 return i) > 0")
+      .unwrap()
+      .to_string()
+  );
+  assert_eq!(
+    "true",
+    eval_with_limits(&limits, &token, "{}", "true or count(for i in 1..10 return i) > 0")
+      .unwrap()
+      .to_string()
+  );
+  assert_interrupted(
+    "maximum number of iterations 5 exceeded",
+    eval_with_limits(&limits, &token, "{}", "true and count(for i in 1..10 return i) > 0"),
+  );
+}
+
+#[test]
+fn test_0009() {
+  // evaluations run without limits are guarded by default limits
+  let scope = &te_scope("{N: 1000000}");
+  let node = dmntk_feel_parser::parse_expression(scope, RECURSIVE_FUNCTION, false).unwrap();
+  let evaluator = crate::builders::build_evaluator(&node).unwrap();
+  assert_eq!("null(maximum recursion depth 256 exceeded)", evaluator(scope).to_string());
+  let scope = &te_scope("{N: 255}");
+  assert_eq!("255", evaluator(scope).to_string());
+}
+
+#[test]
+fn test_0010() {
+  let limits = EvaluationLimits {
+    max_iterations: Some(5),
+    ..Default::default()
+  };
+  let token = CancellationToken::new();
+  let scope = &te_scope("{}");
+  let node = dmntk_feel_parser::parse_expression(scope, "for i in 1..10 return i", false).unwrap();
+  let evaluator = crate::builders::build_evaluator(&node).unwrap();
+  let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+    let _ = evaluate_with_limits(&limits, &token, || -> Value { panic!("evaluation failed") });
+  }));
+  assert!(result.is_err());
+  // limits are not applied after the evaluation with limits panicked
+  assert_eq!("[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]", evaluator(scope).to_string());
+}
@@ -37,6 +37,8 @@ use crate::builders::build_evaluator;
 
 mod addition;
 mod bifs;
+#[cfg(feature = "calendar")]
+mod calendar;
 mod comments;
 mod comparison_between;
 mod comparison_eq;
@@ -46,6 +48,7 @@ mod comparison_in;
 mod comparison_le;
 mod comparison_lt;
 mod comparison_ne;
+mod compiler;
 mod conjunction;
 mod disjunction;
 mod division;
@@ -58,6 +61,7 @@ mod function_invocation;
 mod if_expression;
 mod instance_of;
 mod join;
+mod limits;
 mod list;
 mod literal_at;
 mod literal_boolean;
@@ -69,7 +73,9 @@ mod parentheses;
 mod properties;
 mod range;
 mod some_expression;
+mod spans;
 mod subtraction;
+mod type_checker;
 mod unary_tests;
 
 const SECONDS_IN_DAY: i64 = 86_400;
@@ -251,10 +257,17 @@ pub fn boxed_expression(trace: bool, scope: &Scope, text: &str, expected: Value)
 /// Utility function that takes a text parameter, evaluates the textual expression
 /// represented by this text and compares the result with provided expected value.
 /// The result must be equal to expected value, otherwise an error is reported.
+/// The same expression compiled to bytecode must give the same result.
 fn textual_expression(trace: bool, scope: &Scope, text: &str, expected: Value) {
   match dmntk_feel_parser::parse_textual_expression(scope, text, trace) {
     Ok(node) => match build_evaluator(&node) {
-      Ok(evaluator) => assert_eq!(evaluator(scope), expected),
+      Ok(evaluator) => {
+        assert_eq!(evaluator(scope), expected);
+        match crate::compile(&node) {
+          Ok(evaluator) => assert_eq!(evaluator(scope), expected, "compiled expression gives different result"),
+          Err(reason) => panic!("compiling textual expression failed with reason: {}", reason),
+        }
+      }
       Err(reason) => {
         panic!("building evaluator for textual expression failed with reason: {}", reason);
       }
@@ -0,0 +1,104 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::tests::te_scope;
+use crate::{prepare_with_spans, prepare_with_trace, EvaluationTrace};
+use dmntk_feel::values::Value;
+use dmntk_feel::Scope;
+
+fn located_null(scope: &Scope, text: &str) -> String {
+  let (node, spans) = dmntk_feel_parser::parse_expression_with_spans(scope, text, false).unwrap();
+  let evaluator = prepare_with_spans(&node, &spans, text).unwrap();
+  match evaluator(scope) {
+    Value::Null(Some(reason)) => reason,
+    other => panic!("expected null with reason, actual value is {}", other),
+  }
+}
+
+#[test]
+fn _0001() {
+  let scope = &te_scope(r#"{a: {b: "x"}, c: 10}"#);
+  assert_eq!(
+    "`a.d` at 1:5: eval_path_expression: no entry d in context: {b: \"x\"}",
+    located_null(scope, "c + a.d")
+  );
+}
+
+#[test]
+fn _0002() {
+  let scope = &te_scope(r#"{a: "x", c: 10}"#);
+  let reason = located_null(scope, "if c > 0\nthen\n  a + c\nelse\n  0");
+  assert!(reason.starts_with("`a + c` at 3:3: "), "{}", reason);
+}
+
+#[test]
+fn _0003() {
+  let scope = &te_scope(r#"{a: "x", c: 10}"#);
+  let node = dmntk_feel_parser::parse_expression(scope, "a + c", false).unwrap();
+  let evaluator = crate::prepare(&node).unwrap();
+  assert!(matches!(evaluator(scope), Value::Null(Some(reason)) if !reason.starts_with('`')));
+}
+
+#[test]
+fn _0004() {
+  // equal sub-expressions are located by their own spans
+  let scope = &te_scope(r#"{a: {b: "x"}, c: 10}"#);
+  assert_eq!(
+    "`a.d` at 1:31: eval_path_expression: no entry d in context: {b: \"x\"}",
+    located_null(scope, "[{a: {d: 1}, z: c + a.d}, c + a.d][2]")
+  );
+}
+
+#[test]
+fn _0005() {
+  // values of all sub-expressions are recorded in the trace
+  let scope = &te_scope(r#"{a: 1}"#);
+  let text = "if a > 0 then a + 1 else 0";
+  let (node, spans) = dmntk_feel_parser::parse_expression_with_spans(scope, text, false).unwrap();
+  let trace = EvaluationTrace::default();
+  let evaluator = prepare_with_trace(&node, &spans, text, &trace).unwrap();
+  assert_eq!(Value::Number(2.into()), evaluator(scope));
+  let entries = trace.take().iter().map(|entry| entry.to_string()).collect::<Vec<String>>();
+  assert_eq!(
+    vec![
+      "`if a > 0 then a + 1 else 0` at 1:1 = 2",
+      "  `a > 0` at 1:4 = true",
+      "    `a` at 1:4 = 1",
+      "    `0` at 1:8 = 0",
+      "  `a + 1` at 1:15 = 2",
+      "    `a` at 1:15 = 1",
+      "    `1` at 1:19 = 1",
+    ],
+    entries
+  );
+  assert!(trace.take().is_empty());
+}
@@ -85,3 +85,48 @@ fn test_0009() {
   let scope = &te_scope("{ a: 11.2, b: 0.2, c: 5.351 }");
   te_number(false, scope, " a  \n -  b \n  -  c", 5649, 3);
 }
+
+#[test]
+fn test_0010() {
+  let scope = &te_scope(r#"{}"#);
+  te_date_time_local(
+    false,
+    scope,
+    r#"date and time("2022-01-01T00:30:00") - duration("PT1H")"#,
+    (2021, 12, 31),
+    (23, 30, 0, 0),
+  );
+  te_date_time_offset(
+    false,
+    scope,
+    r#"date and time("2021-03-28T03:30:00-05:00") - duration("P1DT1H")"#,
+    (2021, 3, 27),
+    (2, 30, 0, 0),
+    -18_000,
+  );
+}
+
+#[test]
+fn test_0011() {
+  // subtracting duration across daylight saving time transitions
+  let scope = &te_scope(r#"{}"#);
+  te_string(
+    false,
+    scope,
+    r#"string(date and time("2021-03-28T03:30:00@Europe/Warsaw") - duration("PT1H"))"#,
+    "2021-03-28T01:30:00@Europe/Warsaw",
+  );
+  te_value(
+    false,
+    scope,
+    r#"date and time("2021-10-31T03:30:00@Europe/Warsaw") - date and time("2021-10-31T01:30:00@Europe/Warsaw")"#,
+    r#"duration("PT3H")"#,
+  );
+  te_date_time_utc(
+    false,
+    scope,
+    r#"date and time("2021-10-31T03:30:00@Europe/Warsaw") - duration("PT1H")"#,
+    (2021, 10, 31),
+    (1, 30, 0, 0),
+  );
+}
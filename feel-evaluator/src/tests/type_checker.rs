@@ -0,0 +1,234 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::tests::te_scope;
+use crate::type_checker::{infer_type, infer_type_with_spans, TypeError};
+use dmntk_feel::{FeelType, Name, Scope, Span};
+use std::collections::BTreeMap;
+
+fn infer(scope: &Scope, text: &str) -> (FeelType, Vec<TypeError>) {
+  infer_type(scope, &dmntk_feel_parser::parse_expression(scope, text, false).unwrap())
+}
+
+#[test]
+fn _0001() {
+  let scope = &te_scope("{a: 10, b: 2}");
+  assert_eq!((FeelType::Number, vec![]), infer(scope, "a * a - b / 2"));
+  assert_eq!((FeelType::Boolean, vec![]), infer(scope, "a > b and b between 1 and 3"));
+}
+
+#[test]
+fn _0002() {
+  let scope = &te_scope(r#"{name: "John", birthday: date("2000-01-01")}"#);
+  assert_eq!(
+    (
+      FeelType::Null,
+      vec![TypeError::IncompatibleOperands("+".to_string(), FeelType::String, FeelType::Date)]
+    ),
+    infer(scope, "name + birthday")
+  );
+  assert_eq!("incompatible types in '+': string and date", infer(scope, "name + birthday").1[0].to_string());
+}
+
+#[test]
+fn _0003() {
+  let scope = &te_scope("{}");
+  assert_eq!(
+    (FeelType::Null, vec![TypeError::UnknownFunction(Name::from("lenght"))]),
+    infer(scope, r#"lenght("abc")"#)
+  );
+  assert_eq!((FeelType::Number, vec![]), infer(scope, r#"string length("abc")"#));
+}
+
+#[test]
+fn _0004() {
+  let scope = &te_scope("{customer: {name: \"John\", age: 49}}");
+  assert_eq!((FeelType::Number, vec![]), infer(scope, "customer.age"));
+  let (feel_type, errors) = infer(scope, "customer.income");
+  assert_eq!(FeelType::Null, feel_type);
+  assert!(matches!(&errors[..], [TypeError::UnknownContextEntry(name, FeelType::Context(_))] if name == &Name::from("income")));
+}
+
+#[test]
+fn _0005() {
+  let scope = &te_scope("{a: 10}");
+  let mut entries = BTreeMap::new();
+  entries.insert(Name::from("x"), FeelType::Number);
+  entries.insert(Name::from("y"), FeelType::String);
+  assert_eq!(
+    (FeelType::Context(entries), vec![]),
+    infer(scope, r#"{x: a + 1, y: if x > 5 then "big" else null}"#)
+  );
+}
+
+#[test]
+fn _0006() {
+  let scope = &te_scope("{items: [1, 2, 3]}");
+  assert_eq!(
+    (FeelType::List(Box::new(FeelType::Number)), vec![]),
+    infer(scope, "for i in items return i * 2")
+  );
+  assert_eq!((FeelType::List(Box::new(FeelType::Number)), vec![]), infer(scope, "items[item > 1]"));
+  assert_eq!((FeelType::Number, vec![]), infer(scope, "items[1]"));
+  assert_eq!((FeelType::Boolean, vec![]), infer(scope, "some i in items satisfies i > 2"));
+}
+
+#[test]
+fn _0007() {
+  let scope = &te_scope("{}");
+  assert_eq!(
+    (FeelType::Number, vec![]),
+    infer(scope, "{f: function(a: number, b: number) a * b, r: f(2, 3)}.r")
+  );
+  assert_eq!(
+    (FeelType::Null, vec![TypeError::NonBooleanCondition(FeelType::Number)]),
+    infer(scope, "if 1 then null else null")
+  );
+}
+
+#[test]
+fn _0008() {
+  let scope = &te_scope("{x: null}");
+  assert_eq!((FeelType::Any, vec![]), infer(scope, "x + 1"));
+  let scope = &te_scope(r#"{d: date("2021-05-12")}"#);
+  assert_eq!((FeelType::Number, vec![]), infer(scope, "d.year"));
+  assert_eq!(
+    (FeelType::Null, vec![TypeError::UnknownProperty(Name::from("hour"), FeelType::Date)]),
+    infer(scope, "d.hour")
+  );
+}
+
+#[test]
+fn _0009() {
+  let scope = &te_scope(r#"{a: date and time("2021-05-12T10:00:00"), b: date and time("2021-05-13T12:00:00"), c: duration("PT2H")}"#);
+  assert_eq!((FeelType::Boolean, vec![]), infer(scope, "a < b"));
+  assert_eq!((FeelType::Boolean, vec![]), infer(scope, "a >= b"));
+  assert_eq!((FeelType::DaysAndTimeDuration, vec![]), infer(scope, "b - a"));
+  assert_eq!((FeelType::DateTime, vec![]), infer(scope, "a + c"));
+  assert_eq!((FeelType::DateTime, vec![]), infer(scope, "c + a"));
+  assert_eq!((FeelType::DateTime, vec![]), infer(scope, "a - c"));
+  assert_eq!(
+    (
+      FeelType::Null,
+      vec![TypeError::IncompatibleOperands(
+        "-".to_string(),
+        FeelType::DaysAndTimeDuration,
+        FeelType::DateTime
+      )]
+    ),
+    infer(scope, "c - a")
+  );
+  assert_eq!(
+    (
+      FeelType::Null,
+      vec![TypeError::IncompatibleOperands(
+        "<".to_string(),
+        FeelType::DateTime,
+        FeelType::DaysAndTimeDuration
+      )]
+    ),
+    infer(scope, "a < c")
+  );
+}
+
+#[test]
+fn _0010() {
+  // the type checker reports an error exactly when the evaluator produces null,
+  // for all binary operators and all pairs of simple operand types
+  let scope = &te_scope(
+    r#"{
+      n: 1,
+      s: "a",
+      b: true,
+      d: date("2021-05-12"),
+      t: time("10:00:00"),
+      dt: date and time("2021-05-12T10:00:00"),
+      ym: duration("P1Y"),
+      dd: duration("P1D")
+    }"#,
+  );
+  let names = ["n", "s", "b", "d", "t", "dt", "ym", "dd"];
+  for operator in ["+", "-", "*", "/", "**", "<", "<=", ">", ">="] {
+    for lhs in names {
+      for rhs in names {
+        let text = format!("{} {} {}", lhs, operator, rhs);
+        let node = dmntk_feel_parser::parse_expression(scope, &text, false).unwrap();
+        let value = crate::evaluate(scope, &node).unwrap();
+        let (feel_type, errors) = infer_type(scope, &node);
+        if value.is_null() {
+          assert_eq!((FeelType::Null, 1), (feel_type, errors.len()), "expected type error in: {}", text);
+        } else {
+          assert_eq!((value.type_of(), 0), (feel_type, errors.len()), "unexpected type in: {}", text);
+        }
+      }
+    }
+  }
+}
+
+#[test]
+fn _0011() {
+  // errors are located in the nodes they were detected in
+  let scope = &te_scope(r#"{name: "John", birthday: date("2000-01-01"), n: 1}"#);
+  let text = "n + 1 > 0 and (name + birthday) = lenght(name)";
+  let (node, spans) = dmntk_feel_parser::parse_expression_with_spans(scope, text, false).unwrap();
+  let (_, errors) = infer_type_with_spans(scope, &node, &spans);
+  assert_eq!(
+    vec![
+      (
+        TypeError::IncompatibleOperands("+".to_string(), FeelType::String, FeelType::Date),
+        Span::new(14, 31)
+      ),
+      (TypeError::UnknownFunction(Name::from("lenght")), Span::new(34, 46)),
+    ],
+    errors
+  );
+}
+
+#[test]
+fn _0012() {
+  // errors nested in contexts, iterations and invocation parameters are located in the nodes they were detected in
+  let scope = &te_scope("{}");
+  let text = r#"{a: for x in [1, 2] return x + "s", b: sum(1, date("2000-01-01").foo)}"#;
+  let (node, spans) = dmntk_feel_parser::parse_expression_with_spans(scope, text, false).unwrap();
+  let (_, errors) = infer_type_with_spans(scope, &node, &spans);
+  let located = errors
+    .iter()
+    .map(|(error, span)| (error.to_string(), span.text(text)))
+    .collect::<Vec<(String, String)>>();
+  assert_eq!(
+    vec![
+      ("incompatible types in '+': number and string".to_string(), r#"x + "s""#.to_string()),
+      ("no property 'foo' in value of type date".to_string(), r#"date("2000-01-01").foo"#.to_string()),
+    ],
+    located
+  );
+}
@@ -0,0 +1,101 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Trace of the evaluation of `FEEL` expressions.
+//!
+//! Evaluators prepared with [prepare_with_trace](crate::prepare_with_trace) record the value
+//! of every sub-expression of the source text, in the order the sub-expressions are entered.
+
+use dmntk_feel::values::Value;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Single entry of the evaluation trace.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEntry {
+  /// Nesting depth of the sub-expression.
+  pub depth: usize,
+  /// Sub-expression and its location in source text, like "`a + b` at 1:1".
+  pub location: String,
+  /// Value of the sub-expression, `None` when its evaluation did not finish.
+  pub value: Option<Value>,
+}
+
+impl fmt::Display for TraceEntry {
+  /// Formats the entry indented with its nesting depth.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{:1$}{2}", "", self.depth * 2, self.location)?;
+    if let Some(value) = &self.value {
+      write!(f, " = {}", value)?;
+    }
+    Ok(())
+  }
+}
+
+/// Trace of the evaluation, shared by all evaluators recording it.
+#[derive(Debug, Default, Clone)]
+pub struct EvaluationTrace {
+  /// Recorded entries and the nesting depth of currently evaluated sub-expression.
+  state: Arc<Mutex<(Vec<TraceEntry>, usize)>>,
+}
+
+impl EvaluationTrace {
+  /// Records entering the evaluation of the sub-expression, returns the index of its entry.
+  pub(crate) fn enter(&self, location: &str) -> usize {
+    let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let (entries, depth) = &mut *state;
+    entries.push(TraceEntry {
+      depth: *depth,
+      location: location.to_string(),
+      value: None,
+    });
+    *depth += 1;
+    entries.len() - 1
+  }
+
+  /// Records the value of the sub-expression entered with [EvaluationTrace::enter].
+  pub(crate) fn leave(&self, index: usize, value: &Value) {
+    let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let (entries, depth) = &mut *state;
+    *depth = depth.saturating_sub(1);
+    if let Some(entry) = entries.get_mut(index) {
+      entry.value = Some(value.clone());
+    }
+  }
+
+  /// Removes and returns all recorded entries.
+  pub fn take(&self) -> Vec<TraceEntry> {
+    let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    state.1 = 0;
+    std::mem::take(&mut state.0)
+  }
+}
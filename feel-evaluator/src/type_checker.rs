@@ -0,0 +1,539 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Static type checker for `FEEL` expressions.
+//!
+//! The checker infers the type of the expression without evaluating it,
+//! using the types of the values (or types stored as values) available in the scope.
+//! The inference follows the semantics of the evaluator, so the reported errors
+//! point to the places where the evaluator would produce `null` at runtime.
+//! When a type can not be inferred, [FeelType::Any] is assumed and no errors are reported.
+
+use crate::builders::build_evaluator;
+use dmntk_feel::bif::Bif;
+use dmntk_feel::{AstNode, AstSpans, FeelType, Name, Scope, Span};
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Errors reported by the type checker.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TypeError {
+  #[error("incompatible types in '{0}': {1} and {2}")]
+  IncompatibleOperands(String, FeelType, FeelType),
+  #[error("incompatible type in '{0}': {1}")]
+  IncompatibleOperand(String, FeelType),
+  #[error("unknown function '{0}'")]
+  UnknownFunction(Name),
+  #[error("no entry '{0}' in context of type {1}")]
+  UnknownContextEntry(Name, FeelType),
+  #[error("no property '{0}' in value of type {1}")]
+  UnknownProperty(Name, FeelType),
+  #[error("condition in 'if' expression is not a boolean, actual type is {0}")]
+  NonBooleanCondition(FeelType),
+}
+
+/// Infers the type of the expression represented by AST node,
+/// returns inferred type and all detected type errors.
+pub fn infer_type(scope: &Scope, node: &AstNode) -> (FeelType, Vec<TypeError>) {
+  let (feel_type, errors) = check_types(scope, node, None);
+  (feel_type, errors.into_iter().map(|(error, _)| error).collect())
+}
+
+/// Infers the type of the expression represented by AST node, returns inferred type
+/// and all detected type errors, together with the spans of the nodes they were detected in.
+pub fn infer_type_with_spans(scope: &Scope, node: &AstNode, spans: &AstSpans) -> (FeelType, Vec<(TypeError, Span)>) {
+  let (feel_type, errors) = check_types(scope, node, Some(spans));
+  let errors = errors.into_iter().map(|(error, span)| (error, span.unwrap_or_else(|| spans.span()))).collect();
+  (feel_type, errors)
+}
+
+/// Infers the type of the node, returns detected errors with the spans of the nodes
+/// they were detected in, when the spans of the node are specified.
+fn check_types(scope: &Scope, node: &AstNode, spans: Option<&AstSpans>) -> (FeelType, Vec<(TypeError, Option<Span>)>) {
+  let mut type_checker = TypeChecker {
+    scope,
+    frames: vec![],
+    spans: vec![],
+    errors: vec![],
+  };
+  let feel_type = type_checker.infer(node, spans);
+  (feel_type, type_checker.errors)
+}
+
+/// Returns the spans of the child node at specified index, in the order returned by [AstNode::children].
+fn child(spans: Option<&AstSpans>, index: usize) -> Option<&AstSpans> {
+  spans.and_then(|spans| spans.children().get(index))
+}
+
+/// Type checker state.
+struct TypeChecker<'a> {
+  /// Scope with values (or types) of the names used in checked expression.
+  scope: &'a Scope,
+  /// Types of names introduced by the checked expression (context entries, iteration variables, function parameters).
+  frames: Vec<BTreeMap<Name, FeelType>>,
+  /// Spans of the nodes being inferred, the innermost node is on top.
+  spans: Vec<Option<Span>>,
+  /// Detected type errors with the spans of the nodes they were detected in.
+  errors: Vec<(TypeError, Option<Span>)>,
+}
+
+impl<'a> TypeChecker<'a> {
+  /// Reports an error detected in the node being inferred.
+  fn error(&mut self, error: TypeError) {
+    let span = self.spans.last().cloned().flatten();
+    self.errors.push((error, span));
+  }
+
+  /// Infers the type of the node having specified spans.
+  fn infer(&mut self, node: &AstNode, spans: Option<&AstSpans>) -> FeelType {
+    self.spans.push(spans.map(|spans| spans.span()));
+    let feel_type = self.infer_node(node, spans);
+    self.spans.pop();
+    feel_type
+  }
+
+  /// Infers the type of the node, being on top of the stack of inferred nodes.
+  fn infer_node(&mut self, node: &AstNode, spans: Option<&AstSpans>) -> FeelType {
+    match node {
+      AstNode::At(_) | AstNode::Boolean(_) | AstNode::Null | AstNode::Numeric(_, _) | AstNode::String(_) => {
+        if let Ok(evaluator) = build_evaluator(node) {
+          evaluator(&Scope::default()).type_of()
+        } else {
+          FeelType::Any
+        }
+      }
+      AstNode::Name(name) => self.lookup(name).unwrap_or(FeelType::Any),
+      AstNode::QualifiedName(segments) => {
+        let mut names = segments.iter().filter_map(|segment| match segment {
+          AstNode::Name(name) | AstNode::QualifiedNameSegment(name) => Some(name),
+          _ => None,
+        });
+        let mut feel_type = names.next().and_then(|name| self.lookup(name)).unwrap_or(FeelType::Any);
+        for name in names {
+          feel_type = self.property(&feel_type, name);
+        }
+        feel_type
+      }
+      AstNode::Add(lhs, rhs) => self.binary("+", lhs, rhs, spans, addition),
+      AstNode::Sub(lhs, rhs) => self.binary("-", lhs, rhs, spans, subtraction),
+      AstNode::Mul(lhs, rhs) => self.binary("*", lhs, rhs, spans, numeric),
+      AstNode::Div(lhs, rhs) => self.binary("/", lhs, rhs, spans, numeric),
+      AstNode::Exp(lhs, rhs) => self.binary("**", lhs, rhs, spans, numeric),
+      AstNode::Neg(lhs) => {
+        let lht = self.infer(lhs, child(spans, 0));
+        match lht {
+          FeelType::Number | FeelType::DaysAndTimeDuration => lht,
+          FeelType::Any | FeelType::Null => FeelType::Any,
+          other => {
+            self.error(TypeError::IncompatibleOperand("-".to_string(), other));
+            FeelType::Null
+          }
+        }
+      }
+      AstNode::Lt(lhs, rhs) => self.binary("<", lhs, rhs, spans, comparable),
+      AstNode::Le(lhs, rhs) => self.binary("<=", lhs, rhs, spans, comparable),
+      AstNode::Gt(lhs, rhs) => self.binary(">", lhs, rhs, spans, comparable),
+      AstNode::Ge(lhs, rhs) => self.binary(">=", lhs, rhs, spans, comparable),
+      AstNode::Between(lhs, mhs, rhs) => {
+        let lht = self.infer(lhs, child(spans, 0));
+        let mht = self.infer(mhs, child(spans, 1));
+        let rht = self.infer(rhs, child(spans, 2));
+        if is_known(&lht) && is_known(&mht) && is_known(&rht) {
+          let supported = matches!(
+            lht,
+            FeelType::Number
+              | FeelType::String
+              | FeelType::Date
+              | FeelType::Time
+              | FeelType::DateTime
+              | FeelType::YearsAndMonthsDuration
+              | FeelType::DaysAndTimeDuration
+          );
+          if !supported || lht != mht || lht != rht {
+            self.error(TypeError::IncompatibleOperands("between".to_string(), mht, rht));
+            return FeelType::Null;
+          }
+        }
+        FeelType::Boolean
+      }
+      AstNode::Eq(lhs, rhs) | AstNode::Nq(lhs, rhs) | AstNode::And(lhs, rhs) | AstNode::Or(lhs, rhs) => {
+        self.check(lhs, child(spans, 0));
+        self.check(rhs, child(spans, 1));
+        FeelType::Boolean
+      }
+      AstNode::In(lhs, rhs) | AstNode::InstanceOf(lhs, rhs) => {
+        self.check(lhs, child(spans, 0));
+        self.check(rhs, child(spans, 1));
+        FeelType::Boolean
+      }
+      AstNode::If(lhs, mhs, rhs) => {
+        let lht = self.infer(lhs, child(spans, 0));
+        if !matches!(lht, FeelType::Boolean | FeelType::Null | FeelType::Any) {
+          self.error(TypeError::NonBooleanCondition(lht));
+        }
+        let mht = self.infer(mhs, child(spans, 1));
+        let rht = self.infer(rhs, child(spans, 2));
+        join(&mht, &rht)
+      }
+      AstNode::List(items) => {
+        let mut item_type: Option<FeelType> = None;
+        for (index, item) in items.iter().enumerate() {
+          let feel_type = self.infer(item, child(spans, index));
+          item_type = Some(if let Some(item_type) = item_type {
+            join(&item_type, &feel_type)
+          } else {
+            feel_type
+          });
+        }
+        FeelType::List(Box::new(item_type.unwrap_or(FeelType::Any)))
+      }
+      AstNode::Range(lhs, rhs) => {
+        let lht = self.infer(lhs, child(spans, 0));
+        let rht = self.infer(rhs, child(spans, 1));
+        FeelType::Range(Box::new(join(&lht, &rht)))
+      }
+      AstNode::IntervalStart(lhs, _) | AstNode::IntervalEnd(lhs, _) => self.infer(lhs, child(spans, 0)),
+      AstNode::Context(entries) => {
+        self.frames.push(BTreeMap::new());
+        for (index, entry) in entries.iter().enumerate() {
+          if let AstNode::ContextEntry(key, value) = entry {
+            if let AstNode::ContextEntryKey(name) = key.borrow() {
+              let feel_type = self.infer(value, child(child(spans, index), 1));
+              if let Some(frame) = self.frames.last_mut() {
+                frame.insert(name.clone(), feel_type);
+              }
+            }
+          }
+        }
+        FeelType::Context(self.frames.pop().unwrap_or_default())
+      }
+      AstNode::Path(lhs, rhs) => {
+        let lht = self.infer(lhs, child(spans, 0));
+        if let AstNode::Name(name) = rhs.borrow() {
+          self.property(&lht, name)
+        } else {
+          FeelType::Any
+        }
+      }
+      AstNode::Filter(lhs, rhs) => {
+        let lht = self.infer(lhs, child(spans, 0));
+        let item_type = if let FeelType::List(item_type) = &lht {
+          *item_type.clone()
+        } else {
+          FeelType::Any
+        };
+        let mut frame = BTreeMap::new();
+        frame.insert("item".into(), item_type.clone());
+        // entries of the context item take precedence over the `item` name
+        if let FeelType::Context(entries) = &item_type {
+          frame.extend(entries.clone());
+        }
+        self.frames.push(frame);
+        let rht = self.infer(rhs, child(spans, 1));
+        self.frames.pop();
+        match rht {
+          FeelType::Number => item_type,
+          FeelType::Boolean => FeelType::List(Box::new(item_type)),
+          _ => FeelType::Any,
+        }
+      }
+      AstNode::For(lhs, rhs) => {
+        let mut frame = BTreeMap::new();
+        if let AstNode::IterationContexts(items) = lhs.borrow() {
+          for (index, item) in items.iter().enumerate() {
+            let item_spans = child(child(spans, 0), index);
+            match item {
+              AstNode::IterationContextSingle(variable, node) => {
+                let feel_type = self.infer(node, child(item_spans, 1));
+                if let AstNode::Name(name) = variable.borrow() {
+                  frame.insert(name.clone(), element_type(&feel_type));
+                }
+              }
+              AstNode::IterationContextRange(variable, start, end) => {
+                self.check(start, child(item_spans, 1));
+                self.check(end, child(item_spans, 2));
+                if let AstNode::Name(name) = variable.borrow() {
+                  frame.insert(name.clone(), FeelType::Number);
+                }
+              }
+              _ => {}
+            }
+          }
+        }
+        frame.insert("partial".into(), FeelType::List(Box::new(FeelType::Any)));
+        self.frames.push(frame);
+        let rht = self.infer(rhs, child(spans, 1));
+        self.frames.pop();
+        FeelType::List(Box::new(rht))
+      }
+      AstNode::Some(lhs, rhs) | AstNode::Every(lhs, rhs) => {
+        let mut frame = BTreeMap::new();
+        if let AstNode::QuantifiedContexts(items) = lhs.borrow() {
+          for (index, item) in items.iter().enumerate() {
+            if let AstNode::QuantifiedContext(variable, node) = item {
+              let feel_type = self.infer(node, child(child(child(spans, 0), index), 1));
+              if let AstNode::Name(name) = variable.borrow() {
+                frame.insert(name.clone(), element_type(&feel_type));
+              }
+            }
+          }
+        }
+        self.frames.push(frame);
+        self.check(rhs, child(spans, 1));
+        self.frames.pop();
+        FeelType::Boolean
+      }
+      AstNode::Satisfies(lhs) | AstNode::EvaluatedExpression(lhs) => self.infer(lhs, child(spans, 0)),
+      AstNode::FunctionDefinition(lhs, rhs) => {
+        let mut parameters = vec![];
+        let mut frame = BTreeMap::new();
+        if let AstNode::FormalParameters(items) = lhs.borrow() {
+          for item in items {
+            if let AstNode::FormalParameter(name, feel_type) = item {
+              let feel_type = if let AstNode::FeelType(feel_type) = feel_type.borrow() {
+                feel_type.clone()
+              } else {
+                FeelType::Any
+              };
+              if let AstNode::ParameterName(name) = name.borrow() {
+                frame.insert(name.clone(), feel_type.clone());
+              }
+              parameters.push(feel_type);
+            }
+          }
+        }
+        self.frames.push(frame);
+        let result_type = match rhs.borrow() {
+          AstNode::FunctionBody(body, false) => self.infer(body, child(child(spans, 1), 0)),
+          _ => FeelType::Any,
+        };
+        self.frames.pop();
+        FeelType::Function(parameters, Box::new(result_type))
+      }
+      AstNode::FunctionInvocation(lhs, rhs) => {
+        let parameters_spans = child(spans, 1);
+        match rhs.borrow() {
+          AstNode::PositionalParameters(items) => items.iter().enumerate().for_each(|(index, item)| {
+            self.check(item, child(parameters_spans, index));
+          }),
+          AstNode::NamedParameters(items) => items.iter().enumerate().for_each(|(index, item)| {
+            if let AstNode::NamedParameter(_, value) = item {
+              self.check(value, child(child(parameters_spans, index), 1));
+            }
+          }),
+          _ => {}
+        }
+        if let AstNode::Name(name) = lhs.borrow() {
+          match self.lookup(name) {
+            Some(FeelType::Function(_, result_type)) => *result_type,
+            Some(_) => FeelType::Any,
+            None => {
+              if let Ok(bif) = Bif::from_str(&name.to_string()) {
+                bif.result_type()
+              } else {
+                self.error(TypeError::UnknownFunction(name.clone()));
+                FeelType::Null
+              }
+            }
+          }
+        } else {
+          self.check(lhs, child(spans, 0));
+          FeelType::Any
+        }
+      }
+      AstNode::FeelType(_)
+      | AstNode::ContextType(_)
+      | AstNode::ListType(_)
+      | AstNode::RangeType(_)
+      | AstNode::FunctionType(_, _)
+      | AstNode::ContextTypeEntry(_, _)
+      | AstNode::ContextTypeEntryKey(_) => node.type_of(self.scope),
+      _ => FeelType::Any,
+    }
+  }
+
+  /// Checks the node, when the inferred type is not needed.
+  fn check(&mut self, node: &AstNode, spans: Option<&AstSpans>) {
+    let _ = self.infer(node, spans);
+  }
+
+  /// Infers the type of binary operation, reports an error when both operand types are known,
+  /// but are not accepted by the operator.
+  fn binary(
+    &mut self,
+    operator: &str,
+    lhs: &AstNode,
+    rhs: &AstNode,
+    spans: Option<&AstSpans>,
+    result: fn((&FeelType, &FeelType)) -> Option<FeelType>,
+  ) -> FeelType {
+    let lht = self.infer(lhs, child(spans, 0));
+    let rht = self.infer(rhs, child(spans, 1));
+    if let Some(feel_type) = result((&lht, &rht)) {
+      feel_type
+    } else if matches!(lht, FeelType::Null) || matches!(rht, FeelType::Null) {
+      FeelType::Null
+    } else if is_known(&lht) && is_known(&rht) {
+      self.error(TypeError::IncompatibleOperands(operator.to_string(), lht, rht));
+      FeelType::Null
+    } else {
+      FeelType::Any
+    }
+  }
+
+  /// Returns the type of the property (or context entry) with specified name.
+  fn property(&mut self, feel_type: &FeelType, name: &Name) -> FeelType {
+    let property_type = match feel_type {
+      FeelType::Any | FeelType::Null => return FeelType::Any,
+      FeelType::Context(entries) => {
+        return if let Some(entry_type) = entries.get(name) {
+          entry_type.clone()
+        } else {
+          self.error(TypeError::UnknownContextEntry(name.clone(), feel_type.clone()));
+          FeelType::Null
+        };
+      }
+      FeelType::List(item_type) => {
+        return match item_type.borrow() {
+          FeelType::Context(entries) => FeelType::List(Box::new(entries.get(name).cloned().unwrap_or(FeelType::Any))),
+          _ => FeelType::Any,
+        };
+      }
+      FeelType::Date => match name.to_string().as_str() {
+        "year" | "month" | "day" | "weekday" => Some(FeelType::Number),
+        _ => None,
+      },
+      FeelType::DateTime => match name.to_string().as_str() {
+        "year" | "month" | "day" | "weekday" | "hour" | "minute" | "second" => Some(FeelType::Number),
+        "time offset" => Some(FeelType::DaysAndTimeDuration),
+        "timezone" => Some(FeelType::String),
+        _ => None,
+      },
+      FeelType::Time => match name.to_string().as_str() {
+        "hour" | "minute" | "second" => Some(FeelType::Number),
+        "time offset" => Some(FeelType::DaysAndTimeDuration),
+        "timezone" => Some(FeelType::String),
+        _ => None,
+      },
+      FeelType::DaysAndTimeDuration => match name.to_string().as_str() {
+        "days" | "hours" | "minutes" | "seconds" => Some(FeelType::Number),
+        _ => None,
+      },
+      FeelType::YearsAndMonthsDuration => match name.to_string().as_str() {
+        "years" | "months" => Some(FeelType::Number),
+        _ => None,
+      },
+      _ => None,
+    };
+    property_type.unwrap_or_else(|| {
+      self.error(TypeError::UnknownProperty(name.clone(), feel_type.clone()));
+      FeelType::Null
+    })
+  }
+
+  /// Returns the type of the name, searching names introduced by checked expression first.
+  fn lookup(&self, name: &Name) -> Option<FeelType> {
+    for frame in self.frames.iter().rev() {
+      if let Some(feel_type) = frame.get(name) {
+        return Some(feel_type.clone());
+      }
+    }
+    self.scope.get_entry(name).map(|value| match value.type_of() {
+      // `null` in scope usually stands for a value not known until evaluation
+      FeelType::Null => FeelType::Any,
+      other => other,
+    })
+  }
+}
+
+/// Returns `true` when the type is known well enough to report type errors.
+fn is_known(feel_type: &FeelType) -> bool {
+  !matches!(feel_type, FeelType::Any | FeelType::Null)
+}
+
+/// Returns the common type of two types.
+fn join(lht: &FeelType, rht: &FeelType) -> FeelType {
+  match (lht, rht) {
+    (FeelType::Null, other) | (other, FeelType::Null) => other.clone(),
+    _ => lht.zip(rht),
+  }
+}
+
+/// Returns the type of list elements, or the type itself when it is not a list type.
+fn element_type(feel_type: &FeelType) -> FeelType {
+  match feel_type {
+    FeelType::List(item_type) => *item_type.clone(),
+    FeelType::Range(item_type) => *item_type.clone(),
+    _ => FeelType::Any,
+  }
+}
+
+/// Result type of addition, follows the operand types accepted by the evaluator.
+fn addition(types: (&FeelType, &FeelType)) -> Option<FeelType> {
+  match types {
+    (FeelType::Number, FeelType::Number) => Some(FeelType::Number),
+    (FeelType::String, FeelType::String) => Some(FeelType::String),
+    (FeelType::DaysAndTimeDuration, FeelType::DaysAndTimeDuration) => Some(FeelType::DaysAndTimeDuration),
+    (FeelType::DateTime, FeelType::DaysAndTimeDuration) | (FeelType::DaysAndTimeDuration, FeelType::DateTime) => Some(FeelType::DateTime),
+    _ => None,
+  }
+}
+
+/// Result type of subtraction, follows the operand types accepted by the evaluator.
+fn subtraction(types: (&FeelType, &FeelType)) -> Option<FeelType> {
+  match types {
+    (FeelType::Number, FeelType::Number) => Some(FeelType::Number),
+    (FeelType::DateTime, FeelType::DateTime) => Some(FeelType::DaysAndTimeDuration),
+    (FeelType::DateTime, FeelType::DaysAndTimeDuration) => Some(FeelType::DateTime),
+    _ => None,
+  }
+}
+
+/// Result type of arithmetic operators accepting only numbers.
+fn numeric(types: (&FeelType, &FeelType)) -> Option<FeelType> {
+  match types {
+    (FeelType::Number, FeelType::Number) => Some(FeelType::Number),
+    _ => None,
+  }
+}
+
+/// Result type of comparison operators, follows the operand types accepted by the evaluator.
+fn comparable(types: (&FeelType, &FeelType)) -> Option<FeelType> {
+  match types {
+    (FeelType::Number, FeelType::Number)
+    | (FeelType::String, FeelType::String)
+    | (FeelType::Date, FeelType::Date)
+    | (FeelType::DateTime, FeelType::DateTime) => Some(FeelType::Boolean),
+    _ => None,
+  }
+}
@@ -0,0 +1,228 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Stack based virtual machine executing compiled `FEEL` expressions.
+
+use crate::builders::{
+  eval_add, eval_and, eval_between, eval_div, eval_eq, eval_exp, eval_ge, eval_gt, eval_in, eval_le, eval_lt, eval_mul, eval_name, eval_neg, eval_nq, eval_or,
+  eval_path, eval_qualified_name, eval_range, eval_sub,
+};
+use dmntk_feel::values::{Value, Values};
+use dmntk_feel::{value_null, Evaluator, Name, Scope};
+
+/// Instructions executed by the virtual machine.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Instruction {
+  /// Pushes the constant with specified index onto the stack.
+  Const(usize),
+  /// Pushes the value of the variable resolved in slot with specified index onto the stack.
+  Load(usize),
+  /// Pushes the value of the qualified name resolved in slot with specified index onto the stack.
+  LoadQualified(usize),
+  /// Pops a value and pushes the value of its property with the name having specified index.
+  Path(usize),
+  /// Pushes the result of the fallback evaluator with specified index onto the stack.
+  Eval(usize),
+  /// Pops two values and pushes their sum.
+  Add,
+  /// Pops two values and pushes their difference.
+  Sub,
+  /// Pops two values and pushes their product.
+  Mul,
+  /// Pops two values and pushes their quotient.
+  Div,
+  /// Pops two values and pushes the exponentiation result.
+  Exp,
+  /// Pops a value and pushes its arithmetic negation.
+  Neg,
+  /// Pops two values and pushes the result of equality comparison.
+  Eq,
+  /// Pops two values and pushes the result of inequality comparison.
+  Nq,
+  /// Pops two values and pushes the result of `<` comparison.
+  Lt,
+  /// Pops two values and pushes the result of `<=` comparison.
+  Le,
+  /// Pops two values and pushes the result of `>` comparison.
+  Gt,
+  /// Pops two values and pushes the result of `>=` comparison.
+  Ge,
+  /// Pops two values and pushes their conjunction.
+  And,
+  /// Pops two values and pushes their disjunction.
+  Or,
+  /// Pops three values and pushes the result of `between` comparison.
+  Between,
+  /// Pops specified number of values and pushes a list containing them.
+  List(usize),
+  /// Pops the test and the value, pushes the result of checking if the value satisfies the test.
+  In,
+  /// Pops a value and pushes the unary test `< value`.
+  UnaryLt,
+  /// Pops a value and pushes the unary test `<= value`.
+  UnaryLe,
+  /// Pops a value and pushes the unary test `> value`.
+  UnaryGt,
+  /// Pops a value and pushes the unary test `>= value`.
+  UnaryGe,
+  /// Pops specified number of values and pushes a list of unary tests containing them.
+  ExpressionList(usize),
+  /// Pops specified number of values and pushes a negated list of unary tests containing them.
+  NegatedList(usize),
+  /// Pops a value and pushes the start of the range, closed when the flag is `true`.
+  IntervalStart(bool),
+  /// Pops a value and pushes the end of the range, closed when the flag is `true`.
+  IntervalEnd(bool),
+  /// Pops the end and the start of the range and pushes the range.
+  Range,
+  /// Pops the condition; continues when it is `true`, jumps to the first target
+  /// when it is `false` or `null`, otherwise pushes `null` and jumps to the second target.
+  Branch(usize, usize),
+  /// Jumps unconditionally to specified target.
+  Jump(usize),
+}
+
+/// Compiled `FEEL` expression.
+#[derive(Default)]
+pub struct Program {
+  /// Instruction stream.
+  pub(crate) code: Vec<Instruction>,
+  /// Constants referenced by [Instruction::Const].
+  pub(crate) constants: Vec<Value>,
+  /// Names of the variables referenced by [Instruction::Load].
+  pub(crate) slots: Vec<Name>,
+  /// Qualified names referenced by [Instruction::LoadQualified].
+  pub(crate) qualified_slots: Vec<Vec<Name>>,
+  /// Names of the properties referenced by [Instruction::Path].
+  pub(crate) names: Vec<Name>,
+  /// Evaluators of subexpressions not handled by the virtual machine, referenced by [Instruction::Eval].
+  pub(crate) evaluators: Vec<Evaluator>,
+}
+
+impl Program {
+  /// Executes this program in specified scope.
+  ///
+  /// Each variable slot is resolved in the scope at most once per execution.
+  pub fn execute(&self, scope: &Scope) -> Value {
+    let mut stack: Vec<Value> = Vec::with_capacity(8);
+    let mut slots: Vec<Option<Value>> = vec![None; self.slots.len()];
+    let mut qualified_slots: Vec<Option<Value>> = vec![None; self.qualified_slots.len()];
+    let mut pc = 0;
+    while let Some(instruction) = self.code.get(pc) {
+      pc += 1;
+      match *instruction {
+        Instruction::Const(index) => stack.push(self.constants[index].clone()),
+        Instruction::Load(index) => {
+          let value = slots[index].get_or_insert_with(|| eval_name(scope, &self.slots[index]));
+          stack.push(value.clone());
+        }
+        Instruction::LoadQualified(index) => {
+          let value = qualified_slots[index].get_or_insert_with(|| eval_qualified_name(scope, &self.qualified_slots[index]));
+          stack.push(value.clone());
+        }
+        Instruction::Path(index) => {
+          let value = pop(&mut stack);
+          stack.push(eval_path(value, &self.names[index]));
+        }
+        Instruction::Eval(index) => stack.push((self.evaluators[index])(scope)),
+        Instruction::Add => binary(&mut stack, eval_add),
+        Instruction::Sub => binary(&mut stack, eval_sub),
+        Instruction::Mul => binary(&mut stack, eval_mul),
+        Instruction::Div => binary(&mut stack, eval_div),
+        Instruction::Exp => binary(&mut stack, eval_exp),
+        Instruction::Neg => unary(&mut stack, eval_neg),
+        Instruction::Eq => binary(&mut stack, eval_eq),
+        Instruction::Nq => binary(&mut stack, eval_nq),
+        Instruction::Lt => binary(&mut stack, eval_lt),
+        Instruction::Le => binary(&mut stack, eval_le),
+        Instruction::Gt => binary(&mut stack, eval_gt),
+        Instruction::Ge => binary(&mut stack, eval_ge),
+        Instruction::And => binary(&mut stack, eval_and),
+        Instruction::Or => binary(&mut stack, eval_or),
+        Instruction::Between => {
+          let rhv = pop(&mut stack);
+          let mhv = pop(&mut stack);
+          let lhv = pop(&mut stack);
+          stack.push(eval_between(lhv, mhv, rhv));
+        }
+        Instruction::List(count) => {
+          let items = stack.split_off(stack.len().saturating_sub(count));
+          stack.push(Value::List(Values::new(items)));
+        }
+        Instruction::In => binary(&mut stack, eval_in),
+        Instruction::UnaryLt => unary(&mut stack, |value| Value::UnaryLess(Box::new(value))),
+        Instruction::UnaryLe => unary(&mut stack, |value| Value::UnaryLessOrEqual(Box::new(value))),
+        Instruction::UnaryGt => unary(&mut stack, |value| Value::UnaryGreater(Box::new(value))),
+        Instruction::UnaryGe => unary(&mut stack, |value| Value::UnaryGreaterOrEqual(Box::new(value))),
+        Instruction::ExpressionList(count) => {
+          let items = stack.split_off(stack.len().saturating_sub(count));
+          stack.push(Value::ExpressionList(Values::new(items)));
+        }
+        Instruction::NegatedList(count) => {
+          let items = stack.split_off(stack.len().saturating_sub(count));
+          stack.push(Value::NegatedCommaList(Values::new(items)));
+        }
+        Instruction::IntervalStart(closed) => unary(&mut stack, |value| Value::IntervalStart(Box::new(value), closed)),
+        Instruction::IntervalEnd(closed) => unary(&mut stack, |value| Value::IntervalEnd(Box::new(value), closed)),
+        Instruction::Range => binary(&mut stack, eval_range),
+        Instruction::Branch(otherwise, end) => match pop(&mut stack) {
+          Value::Boolean(true) => {}
+          Value::Boolean(false) | Value::Null(_) => pc = otherwise,
+          _ => {
+            stack.push(value_null!("condition in 'if' expression is not a boolean value"));
+            pc = end;
+          }
+        },
+        Instruction::Jump(target) => pc = target,
+      }
+    }
+    pop(&mut stack)
+  }
+}
+
+/// Pops the value from the top of the stack.
+fn pop(stack: &mut Vec<Value>) -> Value {
+  stack.pop().unwrap_or_else(|| value_null!("virtual machine stack is empty"))
+}
+
+/// Pops an operand, applies unary operator and pushes the result.
+fn unary(stack: &mut Vec<Value>, operator: impl Fn(Value) -> Value) {
+  let value = pop(stack);
+  stack.push(operator(value));
+}
+
+/// Pops two operands, applies binary operator and pushes the result.
+fn binary(stack: &mut Vec<Value>, operator: fn(Value, Value) -> Value) {
+  let rhv = pop(stack);
+  let lhv = pop(stack);
+  stack.push(operator(lhv, rhv));
+}
@@ -31,6 +31,10 @@
  */
 
 fn main() {
+  // the pure-Rust backend, always used for WebAssembly, does not need the decNumber library
+  if std::env::var("CARGO_FEATURE_PURE_RUST").is_ok() || std::env::var("CARGO_CFG_TARGET_ARCH").as_deref() == Ok("wasm32") {
+    return;
+  }
   let output_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
   let target_endian = if cfg!(target_endian = "little") { "1" } else { "0" };
   cc::Build::new()
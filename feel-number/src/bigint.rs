@@ -0,0 +1,401 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Unsigned integers of arbitrary size used by the pure-Rust decimal arithmetic.
+
+use std::cmp::Ordering;
+
+/// Base of the limbs.
+const BASE: u64 = 1_000_000_000;
+/// Number of decimal digits in a single limb.
+const LIMB_DIGITS: usize = 9;
+/// Powers of ten fitting in a single limb.
+const POWERS: [u32; 10] = [1, 10, 100, 1_000, 10_000, 100_000, 1_000_000, 10_000_000, 100_000_000, 1_000_000_000];
+
+/// Unsigned integer stored as little-endian limbs in base `10^9`,
+/// so multiplying and dividing by powers of ten is cheap.
+/// The most significant limb is never zero, zero has no limbs.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BigUint(Vec<u32>);
+
+impl BigUint {
+  /// Returns zero.
+  pub fn zero() -> Self {
+    Self(vec![])
+  }
+  /// Returns ten raised to the power `k`.
+  pub fn pow10(k: usize) -> Self {
+    let mut limbs = vec![0; k / LIMB_DIGITS];
+    limbs.push(POWERS[k % LIMB_DIGITS]);
+    Self(limbs)
+  }
+  /// Creates an integer from [u128] value.
+  pub fn from_u128(mut n: u128) -> Self {
+    let mut limbs = vec![];
+    while n > 0 {
+      limbs.push((n % BASE as u128) as u32);
+      n /= BASE as u128;
+    }
+    Self(limbs)
+  }
+  /// Creates an integer from decimal digits, returns `None` when the string contains other characters.
+  pub fn from_digits(s: &str) -> Option<Self> {
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+      return None;
+    }
+    let mut limbs = vec![];
+    let mut end = s.len();
+    while end > 0 {
+      let start = end.saturating_sub(LIMB_DIGITS);
+      limbs.push(s[start..end].parse().ok()?);
+      end = start;
+    }
+    Some(Self(limbs).normalized())
+  }
+  /// Converts this integer into [u128] value, returns `None` when it does not fit.
+  pub fn to_u128(&self) -> Option<u128> {
+    let mut n = 0_u128;
+    for limb in self.0.iter().rev() {
+      n = n.checked_mul(BASE as u128)?.checked_add(*limb as u128)?;
+    }
+    Some(n)
+  }
+  /// Returns `true` when this integer is zero.
+  pub fn is_zero(&self) -> bool {
+    self.0.is_empty()
+  }
+  /// Returns `true` when this integer is odd.
+  pub fn is_odd(&self) -> bool {
+    matches!(self.0.first(), Some(limb) if limb % 2 == 1)
+  }
+  /// Returns the number of decimal digits, zero has one digit.
+  pub fn digits(&self) -> usize {
+    match self.0.last() {
+      Some(top) => (self.0.len() - 1) * LIMB_DIGITS + POWERS.iter().take_while(|power| **power <= *top).count(),
+      None => 1,
+    }
+  }
+  /// Returns the number of trailing decimal zeros, zero has no trailing zeros.
+  pub fn trailing_zeros(&self) -> usize {
+    let mut count = 0;
+    for limb in &self.0 {
+      if *limb == 0 {
+        count += LIMB_DIGITS;
+      } else {
+        return count + POWERS[1..].iter().take_while(|power| *limb % **power == 0).count();
+      }
+    }
+    0
+  }
+  /// Returns this integer multiplied by `10^k`.
+  pub fn mul_pow10(&self, k: usize) -> Self {
+    if self.is_zero() {
+      return Self::zero();
+    }
+    let mut limbs = vec![0; k / LIMB_DIGITS];
+    limbs.extend_from_slice(&self.0);
+    let result = Self(limbs);
+    match k % LIMB_DIGITS {
+      0 => result,
+      digits => result.mul_small(POWERS[digits]),
+    }
+  }
+  /// Returns the quotient and the remainder of the division by `10^k`.
+  pub fn div_rem_pow10(&self, k: usize) -> (Self, Self) {
+    let (limbs, digits) = (k / LIMB_DIGITS, k % LIMB_DIGITS);
+    if limbs >= self.0.len() {
+      return (Self::zero(), self.clone());
+    }
+    let mut remainder = self.0[..limbs].to_vec();
+    let high = &self.0[limbs..];
+    if digits == 0 {
+      return (Self(high.to_vec()), Self(remainder).normalized());
+    }
+    let divisor = POWERS[digits] as u64;
+    let multiplier = POWERS[LIMB_DIGITS - digits] as u64;
+    let quotient = (0..high.len())
+      .map(|i| {
+        let low = high[i] as u64 / divisor;
+        let carried = high.get(i + 1).map_or(0, |next| (*next as u64 % divisor) * multiplier);
+        (low + carried) as u32
+      })
+      .collect();
+    remainder.push((high[0] as u64 % divisor) as u32);
+    (Self(quotient).normalized(), Self(remainder).normalized())
+  }
+  /// Returns the sum of this and other integer.
+  pub fn add(&self, other: &Self) -> Self {
+    let (longer, shorter) = if self.0.len() >= other.0.len() { (self, other) } else { (other, self) };
+    let mut limbs = Vec::with_capacity(longer.0.len() + 1);
+    let mut carry = 0_u64;
+    for (i, limb) in longer.0.iter().enumerate() {
+      let sum = *limb as u64 + shorter.0.get(i).map_or(0, |l| *l as u64) + carry;
+      limbs.push((sum % BASE) as u32);
+      carry = sum / BASE;
+    }
+    if carry > 0 {
+      limbs.push(carry as u32);
+    }
+    Self(limbs)
+  }
+  /// Returns the difference of this and other integer, other integer must not be greater than this one.
+  pub fn sub(&self, other: &Self) -> Self {
+    debug_assert!(*self >= *other);
+    let mut limbs = Vec::with_capacity(self.0.len());
+    let mut borrow = 0_i64;
+    for (i, limb) in self.0.iter().enumerate() {
+      let mut difference = *limb as i64 - other.0.get(i).map_or(0, |l| *l as i64) - borrow;
+      borrow = 0;
+      if difference < 0 {
+        difference += BASE as i64;
+        borrow = 1;
+      }
+      limbs.push(difference as u32);
+    }
+    Self(limbs).normalized()
+  }
+  /// Returns the product of this and other integer.
+  pub fn mul(&self, other: &Self) -> Self {
+    if self.is_zero() || other.is_zero() {
+      return Self::zero();
+    }
+    let mut limbs = vec![0_u64; self.0.len() + other.0.len()];
+    for (i, a) in self.0.iter().enumerate() {
+      let mut carry = 0_u64;
+      for (j, b) in other.0.iter().enumerate() {
+        let product = limbs[i + j] + *a as u64 * *b as u64 + carry;
+        limbs[i + j] = product % BASE;
+        carry = product / BASE;
+      }
+      limbs[i + other.0.len()] += carry;
+    }
+    Self(limbs.into_iter().map(|limb| limb as u32).collect()).normalized()
+  }
+  /// Returns this integer multiplied by a small value.
+  pub fn mul_small(&self, multiplier: u32) -> Self {
+    let mut limbs = Vec::with_capacity(self.0.len() + 1);
+    let mut carry = 0_u64;
+    for limb in &self.0 {
+      let product = *limb as u64 * multiplier as u64 + carry;
+      limbs.push((product % BASE) as u32);
+      carry = product / BASE;
+    }
+    if carry > 0 {
+      limbs.push(carry as u32);
+    }
+    Self(limbs).normalized()
+  }
+  /// Returns the quotient and the remainder of the division by a small non-zero value.
+  pub fn div_rem_small(&self, divisor: u32) -> (Self, u32) {
+    let mut limbs = vec![0; self.0.len()];
+    let mut remainder = 0_u64;
+    for (i, limb) in self.0.iter().enumerate().rev() {
+      let current = remainder * BASE + *limb as u64;
+      limbs[i] = (current / divisor as u64) as u32;
+      remainder = current % divisor as u64;
+    }
+    (Self(limbs).normalized(), remainder as u32)
+  }
+  /// Returns the quotient and the remainder of the division by non-zero integer.
+  pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+    if *self < *divisor {
+      return (Self::zero(), self.clone());
+    }
+    if divisor.0.len() == 1 {
+      let (quotient, remainder) = self.div_rem_small(divisor.0[0]);
+      return (quotient, Self::from_u128(remainder as u128));
+    }
+    // long division, as described by Knuth in The Art of Computer Programming, vol. 2, 4.3.1, algorithm D
+    let n = divisor.0.len();
+    let m = self.0.len() - n;
+    let factor = (BASE / (divisor.0[n - 1] as u64 + 1)) as u32;
+    let v = divisor.mul_small(factor).0;
+    let mut u = self.mul_small(factor).0;
+    u.resize(self.0.len() + 1, 0);
+    let mut quotient = vec![0_u32; m + 1];
+    for j in (0..=m).rev() {
+      let numerator = u[j + n] as u64 * BASE + u[j + n - 1] as u64;
+      let mut q = numerator / v[n - 1] as u64;
+      let mut r = numerator % v[n - 1] as u64;
+      while q >= BASE || q * v[n - 2] as u64 > r * BASE + u[j + n - 2] as u64 {
+        q -= 1;
+        r += v[n - 1] as u64;
+        if r >= BASE {
+          break;
+        }
+      }
+      let mut borrow = 0_i64;
+      let mut carry = 0_u64;
+      for i in 0..n {
+        let product = q * v[i] as u64 + carry;
+        carry = product / BASE;
+        let mut difference = u[i + j] as i64 - (product % BASE) as i64 - borrow;
+        borrow = 0;
+        if difference < 0 {
+          difference += BASE as i64;
+          borrow = 1;
+        }
+        u[i + j] = difference as u32;
+      }
+      let top = u[j + n] as i64 - carry as i64 - borrow;
+      if top < 0 {
+        // the estimated quotient digit was one too large, add the divisor back
+        q -= 1;
+        let mut carry = 0_u64;
+        for i in 0..n {
+          let sum = u[i + j] as u64 + v[i] as u64 + carry;
+          u[i + j] = (sum % BASE) as u32;
+          carry = sum / BASE;
+        }
+        u[j + n] = (top + carry as i64) as u32;
+      } else {
+        u[j + n] = top as u32;
+      }
+      quotient[j] = q as u32;
+    }
+    u.truncate(n);
+    let (remainder, _) = Self(u).normalized().div_rem_small(factor);
+    (Self(quotient).normalized(), remainder)
+  }
+  /// Returns the integer square root, the greatest integer whose square is not greater than this integer.
+  pub fn isqrt(&self) -> Self {
+    if self.is_zero() {
+      return Self::zero();
+    }
+    // initial estimate is not less than the square root
+    let mut x = Self::pow10(self.digits() / 2 + 1);
+    loop {
+      let (quotient, _) = self.div_rem(&x);
+      let (y, _) = x.add(&quotient).div_rem_small(2);
+      if y >= x {
+        return x;
+      }
+      x = y;
+    }
+  }
+  /// Removes the most significant zero limbs.
+  fn normalized(mut self) -> Self {
+    while let Some(0) = self.0.last() {
+      self.0.pop();
+    }
+    self
+  }
+}
+
+impl PartialOrd for BigUint {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for BigUint {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.0.len().cmp(&other.0.len()).then_with(|| self.0.iter().rev().cmp(other.0.iter().rev()))
+  }
+}
+
+impl std::fmt::Display for BigUint {
+  /// Writes decimal digits of the integer.
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self.0.split_last() {
+      Some((top, rest)) => {
+        write!(f, "{}", top)?;
+        for limb in rest.iter().rev() {
+          write!(f, "{:09}", limb)?;
+        }
+        Ok(())
+      }
+      None => write!(f, "0"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn big(s: &str) -> BigUint {
+    s.chars().fold(BigUint::zero(), |n, ch| {
+      n.mul_small(10).add(&BigUint::from_u128(ch.to_digit(10).unwrap() as u128))
+    })
+  }
+
+  #[test]
+  fn test_digits() {
+    assert_eq!(1, BigUint::zero().digits());
+    assert_eq!(1, big("9").digits());
+    assert_eq!(9, big("999999999").digits());
+    assert_eq!(10, big("1000000000").digits());
+    assert_eq!(35, BigUint::pow10(34).digits());
+    assert_eq!(3, big("1234000").trailing_zeros());
+    assert_eq!(18, BigUint::pow10(18).trailing_zeros());
+  }
+
+  #[test]
+  fn test_pow10() {
+    assert_eq!("123000000000000", big("123").mul_pow10(12).to_string());
+    let (quotient, remainder) = big("123456789012345678901234567890").div_rem_pow10(13);
+    assert_eq!("12345678901234567", quotient.to_string());
+    assert_eq!("8901234567890", remainder.to_string());
+    let (quotient, remainder) = big("12345").div_rem_pow10(20);
+    assert_eq!("0", quotient.to_string());
+    assert_eq!("12345", remainder.to_string());
+  }
+
+  #[test]
+  fn test_arithmetic() {
+    let a = big("98765432109876543210987654321098765432109876543210");
+    let b = big("1234567890123456789012345678901");
+    assert_eq!("98765432109876543212222222211222222221122222222111", a.add(&b).to_string());
+    assert_eq!("98765432109876543209753086430975308643097530864309", a.sub(&b).to_string());
+    let product = a.mul(&b);
+    assert_eq!(
+      "121932631137021795226185032733843621395484362139547216887643473403443222511812210",
+      product.to_string()
+    );
+    let (quotient, remainder) = product.add(&big("12345")).div_rem(&b);
+    assert_eq!(a, quotient);
+    assert_eq!("12345", remainder.to_string());
+    let (quotient, remainder) = a.div_rem(&big("1000000007"));
+    assert_eq!(a, quotient.mul(&big("1000000007")).add(&remainder));
+    assert_eq!(Some(u128::MAX), BigUint::from_u128(u128::MAX).to_u128());
+    assert_eq!(None, BigUint::from_u128(u128::MAX).add(&big("1")).to_u128());
+  }
+
+  #[test]
+  fn test_isqrt() {
+    assert_eq!("0", BigUint::zero().isqrt().to_string());
+    assert_eq!("3", big("15").isqrt().to_string());
+    assert_eq!("4", big("16").isqrt().to_string());
+    assert_eq!("14142135623730950488016887242096", BigUint::pow10(62).mul_small(2).isqrt().to_string());
+  }
+}
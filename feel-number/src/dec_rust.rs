@@ -0,0 +1,916 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Pure-Rust implementation of 128-bit decimal arithmetic.
+//!
+//! This module provides the same API as the bindings to the decNumber library
+//! and gives identical results, so it can be used on targets where building
+//! the C library is not possible.
+
+use crate::bigint::BigUint;
+use crate::decimal::*;
+use lazy_static::lazy_static;
+
+/// Maximum number of digits in the payload of NaN.
+const MAX_PAYLOAD_DIGITS: usize = 33;
+
+lazy_static! {
+  pub static ref DEC_ZERO: DecQuad = dec_zero();
+  pub static ref DEC_ONE: DecQuad = dec_from_string("1");
+  pub static ref DEC_TWO: DecQuad = dec_from_string("2");
+  pub static ref DEC_NANO: DecQuad = dec_from_string("1000000000");
+}
+
+/// 128-bit decimal value.
+#[derive(Debug, Copy, Clone)]
+pub struct DecQuad {
+  negative: bool,
+  kind: Kind,
+  coefficient: u128,
+  exponent: i32,
+}
+
+impl Default for DecQuad {
+  /// Returns zero with the minimum exponent, like all-zero bits of decimal128.
+  fn default() -> Self {
+    Self {
+      negative: false,
+      kind: Kind::Finite,
+      coefficient: 0,
+      exponent: Context::QUAD.etiny() as i32,
+    }
+  }
+}
+
+impl From<&DecQuad> for Decimal {
+  fn from(q: &DecQuad) -> Self {
+    Decimal {
+      negative: q.negative,
+      kind: q.kind,
+      coefficient: BigUint::from_u128(q.coefficient),
+      exponent: q.exponent as i64,
+    }
+  }
+}
+
+impl From<Decimal> for DecQuad {
+  /// Converts a decimal already rounded to the quad context.
+  fn from(d: Decimal) -> Self {
+    let mut coefficient = d.coefficient.to_u128().unwrap_or_default();
+    if matches!(d.kind, Kind::NaN | Kind::SignalingNaN) && d.digits() > MAX_PAYLOAD_DIGITS {
+      coefficient = 0;
+    }
+    Self {
+      negative: d.negative,
+      kind: d.kind,
+      coefficient,
+      exponent: d.exponent as i32,
+    }
+  }
+}
+
+/// Applies unary operation in quad context.
+fn unary(q: &DecQuad, f: fn(&Decimal, &Context, &mut u32) -> Decimal) -> DecQuad {
+  f(&Decimal::from(q), &Context::QUAD, &mut 0).into()
+}
+
+/// Applies binary operation in quad context.
+fn binary(q1: &DecQuad, q2: &DecQuad, f: fn(&Decimal, &Decimal, &Context, &mut u32) -> Decimal) -> DecQuad {
+  f(&Decimal::from(q1), &Decimal::from(q2), &Context::QUAD, &mut 0).into()
+}
+
+/// Rounds the value to integral value using specified rounding.
+fn integral(q: &DecQuad, rounding: Rounding) -> DecQuad {
+  to_integral(&Decimal::from(q), &Context::QUAD, rounding).into()
+}
+
+/// Parses the digits of NaN payload.
+fn parse_payload(s: &str) -> Option<BigUint> {
+  let payload = BigUint::from_digits(s)?;
+  if payload.digits() > MAX_PAYLOAD_DIGITS {
+    return None;
+  }
+  Some(payload)
+}
+
+/// Parses the string into decimal, returns `None` when the string is not a valid number.
+fn parse(s: &str) -> Option<Decimal> {
+  let (negative, s) = match s.as_bytes().first() {
+    Some(b'-') => (true, &s[1..]),
+    Some(b'+') => (false, &s[1..]),
+    _ => (false, s),
+  };
+  let lower = s.to_ascii_lowercase();
+  if lower == "inf" || lower == "infinity" {
+    return Some(Decimal::infinity(negative));
+  }
+  if let Some(payload) = lower.strip_prefix("nan") {
+    return Some(Decimal::nan(negative, parse_payload(payload)?));
+  }
+  if let Some(payload) = lower.strip_prefix("snan") {
+    return Some(Decimal {
+      kind: Kind::SignalingNaN,
+      ..Decimal::nan(negative, parse_payload(payload)?)
+    });
+  }
+  let (mantissa, exponent) = match s.find(['e', 'E']) {
+    Some(position) => (&s[..position], Some(&s[position + 1..])),
+    None => (s, None),
+  };
+  let (integer, fraction) = match mantissa.find('.') {
+    Some(position) => (&mantissa[..position], &mantissa[position + 1..]),
+    None => (mantissa, ""),
+  };
+  if integer.len() + fraction.len() == 0 {
+    return None;
+  }
+  let coefficient = BigUint::from_digits(&format!("{}{}", integer, fraction))?;
+  let mut exponent = match exponent {
+    Some(e) => {
+      let (negative, digits) = match e.as_bytes().first() {
+        Some(b'-') => (true, &e[1..]),
+        Some(b'+') => (false, &e[1..]),
+        _ => (false, e),
+      };
+      if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+      }
+      let digits = digits.trim_start_matches('0');
+      // exponents with too many digits overflow or underflow anyway
+      let value = if digits.len() > 4 { 12288 } else { digits.parse::<i64>().unwrap_or_default() };
+      if negative {
+        -value
+      } else {
+        value
+      }
+    }
+    None => 0,
+  };
+  exponent -= fraction.len() as i64;
+  Some(finish(&Context::QUAD, negative, coefficient, exponent, false, &mut 0))
+}
+
+/// Converts a string into decimal.
+pub fn dec_from_string(s: &str) -> DecQuad {
+  parse(s).unwrap_or_else(|| Decimal::nan(false, BigUint::zero())).into()
+}
+
+/// Converts a BCD into decimal.
+pub fn dec_from_bcd(bcd: &[u8]) -> DecQuad {
+  let coefficient = bcd.iter().fold(0_u128, |acc, digit| acc * 10 + *digit as u128);
+  DecQuad {
+    coefficient,
+    exponent: 0,
+    ..DecQuad::default()
+  }
+}
+
+/// Converts [i32] into decimal.
+pub fn dec_from_i32(n: i32) -> DecQuad {
+  Decimal::from_i64(n as i64).into()
+}
+
+/// Converts [u32] into decimal.
+pub fn dec_from_u32(n: u32) -> DecQuad {
+  Decimal::from_i64(n as i64).into()
+}
+
+/// Converts [DecQuad] into [String].
+pub fn dec_to_string(q: &DecQuad) -> String {
+  let sign = if q.negative { "-" } else { "" };
+  match q.kind {
+    Kind::Infinite => return format!("{}Infinity", sign),
+    Kind::NaN | Kind::SignalingNaN => {
+      let prefix = if q.kind == Kind::NaN { "NaN" } else { "sNaN" };
+      let payload = if q.coefficient == 0 { "".to_string() } else { q.coefficient.to_string() };
+      return format!("{}{}{}", sign, prefix, payload);
+    }
+    Kind::Finite => {}
+  }
+  let digits = q.coefficient.to_string();
+  let exponent = q.exponent as i64;
+  let adjusted = exponent + digits.len() as i64 - 1;
+  if exponent <= 0 && adjusted >= -6 {
+    if exponent == 0 {
+      return format!("{}{}", sign, digits);
+    }
+    let scale = (-exponent) as usize;
+    return if digits.len() > scale {
+      let (integer, fraction) = digits.split_at(digits.len() - scale);
+      format!("{}{}.{}", sign, integer, fraction)
+    } else {
+      format!("{}0.{}{}", sign, "0".repeat(scale - digits.len()), digits)
+    };
+  }
+  let (first, rest) = digits.split_at(1);
+  let point = if rest.is_empty() { "" } else { "." };
+  format!("{}{}{}{}E{:+}", sign, first, point, rest, adjusted)
+}
+
+/// Calculates the square root.
+pub fn dec_square_root(q: &DecQuad) -> DecQuad {
+  unary(q, square_root)
+}
+
+/// Calculates the natural logarithm.
+pub fn dec_ln(q: &DecQuad) -> DecQuad {
+  unary(q, ln)
+}
+
+/// Calculates the exponent.
+pub fn dec_exp(q: &DecQuad) -> DecQuad {
+  unary(q, exp)
+}
+
+/// Raises the number to the power.
+pub fn dec_power(q1: &DecQuad, q2: &DecQuad) -> DecQuad {
+  binary(q1, q2, power)
+}
+
+/// Returns absolute value of the number.
+pub fn dec_abs(q: &DecQuad) -> DecQuad {
+  match q.kind {
+    Kind::NaN | Kind::SignalingNaN => unary(q, |x, ctx, _| nans(x, None, ctx)),
+    _ => DecQuad { negative: false, ..*q },
+  }
+}
+
+/// Rounds the number towards negative infinity.
+pub fn dec_floor(q: &DecQuad) -> DecQuad {
+  integral(q, Rounding::Floor)
+}
+
+/// Rounds the number towards positive infinity.
+pub fn dec_ceiling(q: &DecQuad) -> DecQuad {
+  let qr = integral(q, Rounding::Ceiling);
+  if dec_is_negative(q) && dec_is_zero(&qr) {
+    // if the rounding comes from negative value near zero, then we convert -0 to +0
+    return dec_zero();
+  }
+  qr
+}
+
+/// Rounds the number towards zero.
+pub fn dec_trunc(q: &DecQuad) -> DecQuad {
+  integral(q, Rounding::Down)
+}
+
+/// Returns the fractional part of the number.
+pub fn dec_fract(q: &DecQuad) -> DecQuad {
+  dec_subtract(q, &dec_trunc(q))
+}
+
+/// Compares two numbers, returns -1, 0 or 1.
+pub fn dec_compare(q1: &DecQuad, q2: &DecQuad) -> DecQuad {
+  binary(q1, q2, |x, y, ctx, _| compare_op(x, y, ctx))
+}
+
+/// Adds two numbers.
+pub fn dec_add(q1: &DecQuad, q2: &DecQuad) -> DecQuad {
+  binary(q1, q2, |x, y, ctx, status| add(x, y, false, ctx, status))
+}
+
+/// Subtracts two numbers.
+pub fn dec_subtract(q1: &DecQuad, q2: &DecQuad) -> DecQuad {
+  binary(q1, q2, |x, y, ctx, status| add(x, y, true, ctx, status))
+}
+
+/// Multiplies two numbers.
+pub fn dec_multiply(q1: &DecQuad, q2: &DecQuad) -> DecQuad {
+  binary(q1, q2, multiply)
+}
+
+/// Divides two numbers.
+pub fn dec_divide(q1: &DecQuad, q2: &DecQuad) -> DecQuad {
+  binary(q1, q2, divide)
+}
+
+/// Negates the number.
+pub fn dec_minus(q: &DecQuad) -> DecQuad {
+  match q.kind {
+    Kind::NaN | Kind::SignalingNaN => unary(q, |x, ctx, _| nans(x, None, ctx)),
+    _ => DecQuad {
+      negative: !q.negative && !dec_is_zero(q),
+      ..*q
+    },
+  }
+}
+
+/// Calculates the remainder of the truncating division.
+pub fn dec_remainder(q1: &DecQuad, q2: &DecQuad) -> DecQuad {
+  binary(q1, q2, remainder)
+}
+
+/// Sets the exponent of the number, rounding when needed.
+pub fn dec_rescale(q1: &DecQuad, q2: &DecQuad) -> DecQuad {
+  binary(q1, q2, rescale)
+}
+
+/// Multiplies the number by the integral power of ten.
+pub fn dec_scale_b(q1: &DecQuad, q2: &DecQuad) -> DecQuad {
+  binary(q1, q2, scale_b)
+}
+
+/// Removes trailing zeros from the number.
+pub fn dec_reduce(q: &DecQuad) -> DecQuad {
+  unary(q, reduce)
+}
+
+/// Returns zero.
+pub fn dec_zero() -> DecQuad {
+  DecQuad {
+    exponent: 0,
+    ..DecQuad::default()
+  }
+}
+
+/// Tests whether a number is finite (not an `infinity` or a `NaN`).
+pub fn dec_is_finite(q: &DecQuad) -> bool {
+  q.kind == Kind::Finite
+}
+
+/// Tests whether a number is an integer with zero exponent.
+pub fn dec_is_integer(q: &DecQuad) -> bool {
+  q.kind == Kind::Finite && q.exponent == 0
+}
+
+/// Tests whether a number is less than zero.
+pub fn dec_is_negative(q: &DecQuad) -> bool {
+  q.negative && !dec_is_zero(q) && matches!(q.kind, Kind::Finite | Kind::Infinite)
+}
+
+/// Tests whether a number is greater than zero.
+pub fn dec_is_positive(q: &DecQuad) -> bool {
+  !q.negative && !dec_is_zero(q) && matches!(q.kind, Kind::Finite | Kind::Infinite)
+}
+
+/// Tests whether a number is zero.
+pub fn dec_is_zero(q: &DecQuad) -> bool {
+  q.kind == Kind::Finite && q.coefficient == 0
+}
+
+/// Rounds the finite number to integer, returns `None` when the number is special
+/// or the result would not fit into 34 digits.
+fn dec_to_integer(q: &DecQuad) -> Option<(bool, u128)> {
+  if q.kind != Kind::Finite {
+    return None;
+  }
+  let x = Decimal::from(q);
+  let value = if q.exponent == 0 {
+    x.coefficient
+  } else {
+    let result = rescale(&x, &Decimal::zero(), &Context::QUAD, &mut 0);
+    if result.kind != Kind::Finite {
+      return None;
+    }
+    result.coefficient
+  };
+  Some((q.negative, value.to_u128()?))
+}
+
+/// Converts the number into [i32], returns zero when out of range.
+pub fn dec_to_i32(q: &DecQuad) -> i32 {
+  match dec_to_integer(q) {
+    Some((false, value)) if value <= i32::MAX as u128 => value as i32,
+    Some((true, value)) if value <= i32::MAX as u128 + 1 => (value as i64).wrapping_neg() as i32,
+    _ => 0,
+  }
+}
+
+/// Converts the number into [u32], returns zero when out of range.
+pub fn dec_to_u32(q: &DecQuad) -> u32 {
+  match dec_to_integer(q) {
+    Some((false, value)) if value <= u32::MAX as u128 => value as u32,
+    _ => 0,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_dec_abs() {
+    assert_eq!("0", dec_to_string(&dec_abs(&dec_from_string("0"))));
+    assert_eq!("0", dec_to_string(&dec_abs(&dec_from_string("-0"))));
+    assert_eq!("1", dec_to_string(&dec_abs(&dec_from_string("1"))));
+    assert_eq!("1", dec_to_string(&dec_abs(&dec_from_string("-1"))));
+    assert_eq!("12.29308753409583475", dec_to_string(&dec_abs(&dec_from_string("12.29308753409583475"))));
+    assert_eq!("12.29308753409583475", dec_to_string(&dec_abs(&dec_from_string("-12.29308753409583475"))));
+  }
+
+  #[test]
+  fn test_dec_add() {
+    assert_eq!("0", dec_to_string(&dec_add(&dec_from_string("0"), &dec_from_string("0"))));
+    assert_eq!("1", dec_to_string(&dec_add(&dec_from_string("0"), &dec_from_string("1"))));
+    assert_eq!("1", dec_to_string(&dec_add(&dec_from_string("1"), &dec_from_string("0"))));
+    assert_eq!("2", dec_to_string(&dec_add(&dec_from_string("1"), &dec_from_string("1"))));
+    assert_eq!("0.3", dec_to_string(&dec_add(&dec_from_string("0.1"), &dec_from_string("0.2"))));
+  }
+
+  #[test]
+  fn test_dec_ceiling() {
+    assert_eq!("2", dec_to_string(&dec_ceiling(&dec_from_string("1.5"))));
+    assert_eq!("-1", dec_to_string(&dec_ceiling(&dec_from_string("-1.5"))));
+  }
+
+  #[test]
+  fn test_dec_compare() {
+    assert_eq!("0", dec_to_string(&dec_compare(&dec_from_string("0"), &dec_from_string("0"))));
+    assert_eq!("0", dec_to_string(&dec_compare(&dec_from_string("0"), &dec_from_string("-0"))));
+    assert_eq!("-1", dec_to_string(&dec_compare(&dec_from_string("0"), &dec_from_string("1"))));
+    assert_eq!("1", dec_to_string(&dec_compare(&dec_from_string("1"), &dec_from_string("0"))));
+  }
+
+  #[test]
+  fn test_dec_divide() {
+    assert_eq!("NaN", dec_to_string(&dec_divide(&dec_from_string("0"), &dec_from_string("0"))));
+    assert_eq!("3", dec_to_string(&dec_divide(&dec_from_string("6"), &dec_from_string("2"))));
+    assert_eq!(
+      "0.3333333333333333333333333333333333",
+      dec_to_string(&dec_divide(&dec_from_string("1"), &dec_from_string("3")))
+    );
+  }
+
+  #[test]
+  fn test_dec_exp() {
+    assert_eq!("1", dec_to_string(&dec_exp(&dec_from_string("0"))));
+    assert_eq!("2.718281828459045235360287471352662", dec_to_string(&dec_exp(&dec_from_string("1"))));
+    assert_eq!("54.59815003314423907811026120286088", dec_to_string(&dec_exp(&dec_from_string("4"))));
+    assert_eq!("148.4131591025766034211155800405523", dec_to_string(&dec_exp(&dec_from_string("5"))));
+    assert_eq!("162754.7914190039208080052048984868", dec_to_string(&dec_exp(&dec_from_string("12"))));
+  }
+
+  #[test]
+  fn test_dec_from_i32() {
+    assert_eq!("-1", dec_to_string(&dec_from_i32(-1)));
+    assert_eq!("0", dec_to_string(&dec_from_i32(-0)));
+    assert_eq!("0", dec_to_string(&dec_from_i32(0)));
+    assert_eq!("1", dec_to_string(&dec_from_i32(1)));
+  }
+
+  #[test]
+  fn test_dec_from_u32() {
+    assert_eq!("0", dec_to_string(&dec_from_u32(0)));
+    assert_eq!("1", dec_to_string(&dec_from_u32(1)));
+  }
+
+  #[test]
+  fn test_dec_floor() {
+    assert_eq!("1", dec_to_string(&dec_floor(&dec_from_string("1.5"))));
+    assert_eq!("-2", dec_to_string(&dec_floor(&dec_from_string("-1.5"))));
+  }
+
+  #[test]
+  fn test_dec_from_string() {
+    let value = dec_from_string("0");
+    assert_eq!("0", dec_to_string(&value));
+    let value = dec_from_string("1");
+    assert_eq!("1", dec_to_string(&value));
+    let value = dec_from_string("1234a");
+    assert_eq!("NaN", dec_to_string(&value));
+  }
+
+  #[test]
+  fn test_dec_from_bcd() {
+    let value = dec_from_bcd(&[
+      0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3,
+    ]);
+    assert_eq!("123", dec_to_string(&value));
+    let value = dec_from_bcd(&[
+      8, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9, 9,
+    ]);
+    assert_eq!("8999999999999999999999999999999999", dec_to_string(&value));
+  }
+
+  #[test]
+  fn test_dec_is_finite() {
+    // 0**0 is NaN
+    assert!(!dec_is_finite(&dec_power(&dec_from_string("0"), &dec_from_string("0"))));
+    // ln(-1) is NaN
+    assert!(!dec_is_finite(&dec_ln(&dec_from_string("-1"))));
+    // ln(0) is -Infinity
+    assert!(!dec_is_finite(&dec_ln(&dec_from_string("0"))));
+    // 1 + 1 is finite
+    assert!(dec_is_finite(&dec_add(&dec_from_string("1"), &dec_from_string("1"))));
+  }
+
+  #[test]
+  fn test_dec_is_integer() {
+    assert!(dec_is_integer(&dec_from_string("0")));
+    assert!(dec_is_integer(&dec_from_string("-0")));
+    assert!(dec_is_integer(&dec_from_string("1")));
+    assert!(dec_is_integer(&dec_from_string("-1")));
+    assert!(dec_is_integer(&dec_from_string("2")));
+    assert!(dec_is_integer(&dec_from_string("-2")));
+    assert!(!dec_is_integer(&dec_from_string("0.5")));
+    assert!(!dec_is_integer(&dec_from_string("-0.5")));
+  }
+
+  #[test]
+  fn test_dec_is_negative() {
+    assert!(dec_is_negative(&dec_from_string("-1.23")));
+    assert!(dec_is_negative(&dec_from_string("-1")));
+    assert!(!dec_is_negative(&dec_from_string("-0")));
+    assert!(!dec_is_negative(&dec_from_string("0")));
+    assert!(!dec_is_negative(&dec_from_string("1")));
+    assert!(!dec_is_negative(&dec_from_string("1.23")));
+  }
+
+  #[test]
+  fn test_dec_is_positive() {
+    assert!(!dec_is_positive(&dec_from_string("-1.23")));
+    assert!(!dec_is_positive(&dec_from_string("-1")));
+    assert!(!dec_is_positive(&dec_from_string("-0")));
+    assert!(!dec_is_positive(&dec_from_string("0")));
+    assert!(dec_is_positive(&dec_from_string("1")));
+    assert!(dec_is_positive(&dec_from_string("1.23")));
+  }
+
+  #[test]
+  fn test_dec_is_zero() {
+    assert!(dec_is_zero(&dec_from_string("0")));
+    assert!(dec_is_zero(&dec_from_string("-0")));
+    assert!(!dec_is_zero(&dec_from_string("1")));
+    assert!(!dec_is_zero(&dec_from_string("-1")));
+    assert!(!dec_is_zero(&dec_from_string("0.000000000123456789")));
+    assert!(!dec_is_zero(&dec_from_string("-0.000000000123456789")));
+  }
+
+  #[test]
+  fn test_dec_ln() {
+    assert_eq!("NaN", dec_to_string(&dec_ln(&dec_from_string("-1"))));
+    assert_eq!("-Infinity", dec_to_string(&dec_ln(&dec_from_string("0"))));
+    assert_eq!("0", dec_to_string(&dec_ln(&dec_from_string("1"))));
+    assert_eq!("1.386294361119890618834464242916353", dec_to_string(&dec_ln(&dec_from_string("4"))));
+    assert_eq!("2.302585092994045684017991454684364", dec_to_string(&dec_ln(&dec_from_string("10"))));
+  }
+
+  #[test]
+  fn test_dec_multiply() {
+    assert_eq!("0", dec_to_string(&dec_multiply(&dec_from_string("0"), &dec_from_string("0"))));
+    assert_eq!("0", dec_to_string(&dec_multiply(&dec_from_string("1"), &dec_from_string("0"))));
+    assert_eq!("2", dec_to_string(&dec_multiply(&dec_from_string("1"), &dec_from_string("2"))));
+    assert_eq!("3.0135", dec_to_string(&dec_multiply(&dec_from_string("1.23"), &dec_from_string("2.45"))));
+  }
+
+  #[test]
+  fn test_dec_neg() {
+    assert_eq!("-1.23", dec_to_string(&dec_minus(&dec_from_string("1.23"))));
+    assert_eq!("1.23", dec_to_string(&dec_minus(&dec_from_string("-1.23"))));
+  }
+
+  #[test]
+  fn test_dec_power() {
+    assert_eq!("NaN", dec_to_string(&dec_power(&dec_from_string("0"), &dec_from_string("0"))));
+    assert_eq!("1", dec_to_string(&dec_power(&dec_from_string("1"), &dec_from_string("0"))));
+    assert_eq!("8", dec_to_string(&dec_power(&dec_from_string("2"), &dec_from_string("3"))));
+    assert_eq!(
+      "41959.85737359436186095331070746801",
+      dec_to_string(&dec_power(&dec_from_string("12.2384283"), &dec_from_string("4.25")))
+    );
+    assert_eq!("0.001953125", dec_to_string(&dec_power(&dec_from_string("8"), &dec_from_string("-3"))));
+  }
+
+  #[test]
+  fn test_dec_reduce() {
+    assert_eq!("1.23", dec_to_string(&dec_reduce(&dec_from_string("1.23000"))));
+  }
+
+  #[test]
+  fn test_dec_remainder() {
+    assert_eq!("1", dec_to_string(&dec_remainder(&dec_from_string("3"), &dec_from_string("2"))));
+    assert_eq!("0", dec_to_string(&dec_remainder(&dec_from_string("4"), &dec_from_string("2"))));
+    assert_eq!("1.5", dec_to_string(&dec_remainder(&dec_from_string("7.5"), &dec_from_string("2"))));
+  }
+
+  #[test]
+  fn test_dec_rescale() {
+    assert_eq!("123.46", dec_to_string(&dec_rescale(&dec_from_string("123.4567"), &dec_from_string("-2"))));
+    assert_eq!("123.45", dec_to_string(&dec_rescale(&dec_from_string("123.4547"), &dec_from_string("-2"))));
+    assert_eq!("1E+2", dec_to_string(&dec_rescale(&dec_from_string("123.4567"), &dec_from_string("2"))));
+    assert_eq!("2E+2", dec_to_string(&dec_rescale(&dec_from_string("163.4567"), &dec_from_string("2"))));
+  }
+
+  #[test]
+  fn test_dec_scale_b() {
+    assert_eq!("1.23", dec_to_string(&dec_scale_b(&dec_from_string("123"), &dec_from_string("-2"))));
+    assert_eq!("1.23E+4", dec_to_string(&dec_scale_b(&dec_from_string("123"), &dec_from_string("2"))));
+  }
+
+  #[test]
+  fn test_dec_square_root() {
+    assert_eq!("NaN", dec_to_string(&dec_square_root(&dec_from_string("-1"))));
+    assert_eq!("0", dec_to_string(&dec_square_root(&dec_from_string("0"))));
+    assert_eq!("1", dec_to_string(&dec_square_root(&dec_from_string("1"))));
+    assert_eq!("1.414213562373095048801688724209698", dec_to_string(&dec_square_root(&dec_from_string("2"))));
+    assert_eq!("2", dec_to_string(&dec_square_root(&dec_from_string("4"))));
+    assert_eq!("4", dec_to_string(&dec_square_root(&dec_from_string("16"))));
+  }
+
+  #[test]
+  fn test_dec_subtract() {
+    assert_eq!("0", dec_to_string(&dec_subtract(&dec_from_string("0"), &dec_from_string("0"))));
+    assert_eq!("-1", dec_to_string(&dec_subtract(&dec_from_string("0"), &dec_from_string("1"))));
+    assert_eq!("1", dec_to_string(&dec_subtract(&dec_from_string("1"), &dec_from_string("0"))));
+    assert_eq!("0", dec_to_string(&dec_subtract(&dec_from_string("1"), &dec_from_string("1"))));
+    assert_eq!("-0.1", dec_to_string(&dec_subtract(&dec_from_string("0.1"), &dec_from_string("0.2"))));
+  }
+
+  #[test]
+  fn test_dec_to_i32() {
+    assert_eq!(-1, dec_to_i32(&dec_from_string("-1")));
+    assert_eq!(1, dec_to_i32(&dec_from_string("1")));
+  }
+
+  #[test]
+  fn test_dec_to_u32() {
+    assert_eq!(0, dec_to_u32(&dec_from_string("-0")));
+    assert_eq!(0, dec_to_i32(&dec_from_string("0")));
+    assert_eq!(1, dec_to_i32(&dec_from_string("1")));
+  }
+
+  #[test]
+  fn test_dec_to_string() {
+    assert_eq!("1", dec_to_string(&dec_from_string("1")));
+    assert_eq!("0.000123", dec_to_string(&dec_from_string("0.000123")));
+    assert_eq!("1000000000.01", dec_to_string(&dec_from_string("1000000000.01")));
+    assert_eq!("1E-23", dec_to_string(&dec_from_string("0.00000000000000000000001")));
+    assert_eq!("1.234567E-17", dec_to_string(&dec_from_string("0.00000000000000001234567")));
+  }
+
+  #[test]
+  fn test_dec_trunc() {
+    assert_eq!("-1", dec_to_string(&dec_trunc(&dec_from_string("-1.9"))));
+    assert_eq!("-1", dec_to_string(&dec_trunc(&dec_from_string("-1.8"))));
+    assert_eq!("-1", dec_to_string(&dec_trunc(&dec_from_string("-1.7"))));
+    assert_eq!("-1", dec_to_string(&dec_trunc(&dec_from_string("-1.6"))));
+    assert_eq!("-1", dec_to_string(&dec_trunc(&dec_from_string("-1.5"))));
+    assert_eq!("-1", dec_to_string(&dec_trunc(&dec_from_string("-1.4"))));
+    assert_eq!("-1", dec_to_string(&dec_trunc(&dec_from_string("-1.3"))));
+    assert_eq!("-1", dec_to_string(&dec_trunc(&dec_from_string("-1.2"))));
+    assert_eq!("-1", dec_to_string(&dec_trunc(&dec_from_string("-1.1"))));
+    assert_eq!("-1", dec_to_string(&dec_trunc(&dec_from_string("-1"))));
+    assert_eq!("-0", dec_to_string(&dec_trunc(&dec_from_string("-0"))));
+    assert_eq!("0", dec_to_string(&dec_trunc(&dec_from_string("0"))));
+    assert_eq!("1", dec_to_string(&dec_trunc(&dec_from_string("1"))));
+    assert_eq!("1", dec_to_string(&dec_trunc(&dec_from_string("1.1"))));
+    assert_eq!("1", dec_to_string(&dec_trunc(&dec_from_string("1.2"))));
+    assert_eq!("1", dec_to_string(&dec_trunc(&dec_from_string("1.3"))));
+    assert_eq!("1", dec_to_string(&dec_trunc(&dec_from_string("1.4"))));
+    assert_eq!("1", dec_to_string(&dec_trunc(&dec_from_string("1.5"))));
+    assert_eq!("1", dec_to_string(&dec_trunc(&dec_from_string("1.6"))));
+    assert_eq!("1", dec_to_string(&dec_trunc(&dec_from_string("1.7"))));
+    assert_eq!("1", dec_to_string(&dec_trunc(&dec_from_string("1.8"))));
+    assert_eq!("1", dec_to_string(&dec_trunc(&dec_from_string("1.9"))));
+  }
+
+  #[test]
+  fn test_dec_fract() {
+    assert_eq!("-0.9", dec_to_string(&dec_fract(&dec_from_string("-1.9"))));
+    assert_eq!("-0.8", dec_to_string(&dec_fract(&dec_from_string("-1.8"))));
+    assert_eq!("-0.7", dec_to_string(&dec_fract(&dec_from_string("-1.7"))));
+    assert_eq!("-0.6", dec_to_string(&dec_fract(&dec_from_string("-1.6"))));
+    assert_eq!("-0.5", dec_to_string(&dec_fract(&dec_from_string("-1.5"))));
+    assert_eq!("-0.4", dec_to_string(&dec_fract(&dec_from_string("-1.4"))));
+    assert_eq!("-0.3", dec_to_string(&dec_fract(&dec_from_string("-1.3"))));
+    assert_eq!("-0.2", dec_to_string(&dec_fract(&dec_from_string("-1.2"))));
+    assert_eq!("-0.1", dec_to_string(&dec_fract(&dec_from_string("-1.1"))));
+    assert_eq!("0", dec_to_string(&dec_fract(&dec_from_string("-1"))));
+    assert_eq!("0", dec_to_string(&dec_fract(&dec_from_string("-0"))));
+    assert_eq!("0", dec_to_string(&dec_fract(&dec_from_string("0"))));
+    assert_eq!("0", dec_to_string(&dec_fract(&dec_from_string("1"))));
+    assert_eq!("0.1", dec_to_string(&dec_fract(&dec_from_string("1.1"))));
+    assert_eq!("0.2", dec_to_string(&dec_fract(&dec_from_string("1.2"))));
+    assert_eq!("0.3", dec_to_string(&dec_fract(&dec_from_string("1.3"))));
+    assert_eq!("0.4", dec_to_string(&dec_fract(&dec_from_string("1.4"))));
+    assert_eq!("0.5", dec_to_string(&dec_fract(&dec_from_string("1.5"))));
+    assert_eq!("0.6", dec_to_string(&dec_fract(&dec_from_string("1.6"))));
+    assert_eq!("0.7", dec_to_string(&dec_fract(&dec_from_string("1.7"))));
+    assert_eq!("0.8", dec_to_string(&dec_fract(&dec_from_string("1.8"))));
+    assert_eq!("0.9", dec_to_string(&dec_fract(&dec_from_string("1.9"))));
+  }
+
+  #[test]
+  #[allow(clippy::clone_on_copy)]
+  fn test_dec_quad_clone() {
+    let dq_src = dec_from_string("123");
+    let dq_dst = dq_src.clone();
+    assert_eq!("123", dec_to_string(&dq_dst));
+  }
+}
+
+#[cfg(all(test, not(any(feature = "pure-rust", target_arch = "wasm32"))))]
+mod compatibility {
+  use crate::dec as c;
+
+  const VALUES: [&str; 64] = [
+    "0",
+    "-0",
+    "0.00",
+    "-0.000",
+    "1",
+    "-1",
+    "2",
+    "3",
+    "4",
+    "7.5",
+    "10",
+    "16",
+    "0.1",
+    "0.2",
+    "-0.5",
+    "1.5",
+    "-1.5",
+    "2.5",
+    "0.5",
+    "1.23000",
+    "123.4567",
+    "-123.4547",
+    "12.2384283",
+    "4.25",
+    "-3",
+    "100",
+    "1E+2",
+    "1.00E-3",
+    "0.000123",
+    "1000000000.01",
+    "12.29308753409583475",
+    "-12.29308753409583475",
+    "0.3333333333333333333333333333333333",
+    "9999999999999999999999999999999999",
+    "99999999999999999999999999999999999",
+    "1234567890123456789012345678901234567890",
+    "0.00000000000000000000001",
+    "1E-6176",
+    "1E-6200",
+    "9.999999999999999999999999999999999E+6144",
+    "1E+6144",
+    "1E+6145",
+    "1E+6111",
+    "-1E-6143",
+    "2147483647",
+    "2147483648",
+    "-2147483648",
+    "-2147483649",
+    "4294967295",
+    "4294967296",
+    "0.99999",
+    "1.000001",
+    "0.000001",
+    "-0.0000001",
+    "123456789",
+    "3.14159265358979323846264338327950288",
+    "2.718281828459045235360287471352662",
+    "1000000",
+    "Infinity",
+    "-Infinity",
+    "NaN",
+    "-NaN123",
+    "sNaN45",
+    "1234a",
+  ];
+
+  fn values() -> Vec<(String, c::DecQuad, super::DecQuad)> {
+    VALUES
+      .iter()
+      .map(|s| (s.to_string(), c::dec_from_string(s), super::dec_from_string(s)))
+      .collect()
+  }
+
+  #[test]
+  fn test_from_to_string() {
+    for s in VALUES {
+      assert_eq!(
+        c::dec_to_string(&c::dec_from_string(s)),
+        super::dec_to_string(&super::dec_from_string(s)),
+        "{}",
+        s
+      );
+    }
+    for n in [0, 1, -1, i32::MIN, i32::MAX] {
+      assert_eq!(c::dec_to_string(&c::dec_from_i32(n)), super::dec_to_string(&super::dec_from_i32(n)));
+    }
+    for n in [0, 1, u32::MAX] {
+      assert_eq!(c::dec_to_string(&c::dec_from_u32(n)), super::dec_to_string(&super::dec_from_u32(n)));
+    }
+  }
+
+  #[test]
+  fn test_unary_operations() {
+    let c_ops: [fn(&c::DecQuad) -> c::DecQuad; 11] = [
+      c::dec_square_root,
+      c::dec_ln,
+      c::dec_exp,
+      c::dec_abs,
+      c::dec_floor,
+      c::dec_ceiling,
+      c::dec_trunc,
+      c::dec_fract,
+      c::dec_minus,
+      c::dec_reduce,
+      |q| *q,
+    ];
+    let rust_ops: [fn(&super::DecQuad) -> super::DecQuad; 11] = [
+      super::dec_square_root,
+      super::dec_ln,
+      super::dec_exp,
+      super::dec_abs,
+      super::dec_floor,
+      super::dec_ceiling,
+      super::dec_trunc,
+      super::dec_fract,
+      super::dec_minus,
+      super::dec_reduce,
+      |q| *q,
+    ];
+    for (s, c_value, rust_value) in values() {
+      for (i, (c_op, rust_op)) in c_ops.iter().zip(rust_ops.iter()).enumerate() {
+        assert_eq!(
+          c::dec_to_string(&c_op(&c_value)),
+          super::dec_to_string(&rust_op(&rust_value)),
+          "operation {} on {}",
+          i,
+          s
+        );
+      }
+      assert_eq!(c::dec_is_finite(&c_value), super::dec_is_finite(&rust_value), "{}", s);
+      assert_eq!(c::dec_is_integer(&c_value), super::dec_is_integer(&rust_value), "{}", s);
+      assert_eq!(c::dec_is_negative(&c_value), super::dec_is_negative(&rust_value), "{}", s);
+      assert_eq!(c::dec_is_positive(&c_value), super::dec_is_positive(&rust_value), "{}", s);
+      assert_eq!(c::dec_is_zero(&c_value), super::dec_is_zero(&rust_value), "{}", s);
+      assert_eq!(c::dec_to_i32(&c_value), super::dec_to_i32(&rust_value), "{}", s);
+      assert_eq!(c::dec_to_u32(&c_value), super::dec_to_u32(&rust_value), "{}", s);
+    }
+  }
+
+  #[test]
+  fn test_binary_operations() {
+    let c_ops: [fn(&c::DecQuad, &c::DecQuad) -> c::DecQuad; 9] = [
+      c::dec_add,
+      c::dec_subtract,
+      c::dec_multiply,
+      c::dec_divide,
+      c::dec_remainder,
+      c::dec_compare,
+      c::dec_power,
+      c::dec_rescale,
+      c::dec_scale_b,
+    ];
+    let rust_ops: [fn(&super::DecQuad, &super::DecQuad) -> super::DecQuad; 9] = [
+      super::dec_add,
+      super::dec_subtract,
+      super::dec_multiply,
+      super::dec_divide,
+      super::dec_remainder,
+      super::dec_compare,
+      super::dec_power,
+      super::dec_rescale,
+      super::dec_scale_b,
+    ];
+    let values = values();
+    for (s1, c1, rust1) in &values {
+      for (s2, c2, rust2) in &values {
+        for (i, (c_op, rust_op)) in c_ops.iter().zip(rust_ops.iter()).enumerate() {
+          assert_eq!(
+            c::dec_to_string(&c_op(c1, c2)),
+            super::dec_to_string(&rust_op(rust1, rust2)),
+            "operation {} on {} and {}",
+            i,
+            s1,
+            s2
+          );
+        }
+      }
+    }
+  }
+}
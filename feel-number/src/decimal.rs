@@ -0,0 +1,961 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Decimal floating-point arithmetic with arbitrary precision.
+//!
+//! Operations follow the General Decimal Arithmetic specification and,
+//! where the specification leaves the details to the implementation
+//! (exponent, logarithm and power), the algorithms of the decNumber library,
+//! so results are identical to the results of the C implementation.
+
+use crate::bigint::BigUint;
+use std::cmp::{max, min, Ordering};
+
+/// Exponent overflow flag.
+pub const OVERFLOW: u32 = 0x01;
+/// Exponent underflow flag.
+pub const UNDERFLOW: u32 = 0x02;
+/// Flag set when the result was rounded and digits were lost.
+pub const INEXACT: u32 = 0x04;
+/// Invalid operation flag.
+pub const INVALID: u32 = 0x08;
+
+/// Maximum value of operands of mathematical functions.
+const MAX_MATH: i64 = 999_999;
+/// Minimum value of the exponent.
+const MIN_EMIN: i64 = -999_999_999;
+/// Natural logarithm of 10 with 40 digits.
+const LN10: (&str, i64) = ("2302585092994045684017991454684364207601", -39);
+/// Natural logarithm of 2 with 40 digits.
+const LN2: (&str, i64) = ("6931471805599453094172321214581765680755", -40);
+/// Initial estimates of natural logarithms for values from 0.10 to 0.99, the same as in decNumber.
+const LN_NN: [u32; 90] = [
+  9016, 8652, 8316, 8008, 7724, 7456, 7208, 6972, 6748, 6540, 6340, 6148, 5968, 5792, 5628, 5464, 5312, 5164, 5020, 4884, 4748, 4620, 4496, 4376, 4256, 4144,
+  4032, 39233, 38181, 37157, 36157, 35181, 34229, 33297, 32389, 31501, 30629, 29777, 28945, 28129, 27329, 26545, 25777, 25021, 24281, 23553, 22837, 22137,
+  21445, 20769, 20101, 19445, 18801, 18165, 17541, 16925, 16321, 15721, 15133, 14553, 13985, 13421, 12865, 12317, 11777, 11241, 10717, 10197, 9685, 9177, 8677,
+  8185, 7697, 7213, 6737, 6269, 5801, 5341, 4889, 4437, 39930, 35534, 31186, 26886, 22630, 18418, 14254, 10130, 6046, 20055,
+];
+
+/// Kind of the decimal value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Kind {
+  Finite,
+  Infinite,
+  NaN,
+  SignalingNaN,
+}
+
+/// Rounding modes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Rounding {
+  Ceiling,
+  Down,
+  Floor,
+  HalfEven,
+}
+
+/// Context of decimal operations.
+#[derive(Debug, Copy, Clone)]
+pub struct Context {
+  /// Precision, the maximum number of digits in coefficient.
+  pub digits: usize,
+  /// Maximum adjusted exponent.
+  pub emax: i64,
+  /// Minimum adjusted exponent of normal numbers.
+  pub emin: i64,
+  /// Flag indicating if exponents are clamped like in concrete formats.
+  pub clamp: bool,
+  /// Rounding mode.
+  pub rounding: Rounding,
+}
+
+impl Context {
+  /// Context of 128-bit decimals.
+  pub const QUAD: Context = Context {
+    digits: 34,
+    emax: 6144,
+    emin: -6143,
+    clamp: true,
+    rounding: Rounding::HalfEven,
+  };
+  /// Context of 64-bit decimals, used as a base for working contexts.
+  const DOUBLE: Context = Context {
+    digits: 16,
+    emax: 384,
+    emin: -383,
+    clamp: true,
+    rounding: Rounding::HalfEven,
+  };
+  /// Returns the minimum exponent of subnormal numbers.
+  pub fn etiny(&self) -> i64 {
+    self.emin - self.digits as i64 + 1
+  }
+}
+
+/// Decimal value, `(-1)^negative * coefficient * 10^exponent` when finite.
+/// The coefficient of NaNs holds the payload.
+#[derive(Debug, Clone)]
+pub struct Decimal {
+  pub negative: bool,
+  pub kind: Kind,
+  pub coefficient: BigUint,
+  pub exponent: i64,
+}
+
+impl Decimal {
+  /// Creates a finite decimal.
+  pub fn finite(negative: bool, coefficient: BigUint, exponent: i64) -> Self {
+    Self {
+      negative,
+      kind: Kind::Finite,
+      coefficient,
+      exponent,
+    }
+  }
+  /// Creates a finite decimal from integer value.
+  pub fn from_i64(n: i64) -> Self {
+    Self::finite(n < 0, BigUint::from_u128(n.unsigned_abs() as u128), 0)
+  }
+  /// Creates a finite decimal from small coefficient and exponent.
+  fn small(negative: bool, coefficient: u128, exponent: i64) -> Self {
+    Self::finite(negative, BigUint::from_u128(coefficient), exponent)
+  }
+  /// Returns positive zero.
+  pub fn zero() -> Self {
+    Self::finite(false, BigUint::zero(), 0)
+  }
+  /// Returns one.
+  pub fn one() -> Self {
+    Self::from_i64(1)
+  }
+  /// Returns one padded with zeros to the precision of the context.
+  fn padded_one(ctx: &Context) -> Self {
+    Self::finite(false, BigUint::pow10(ctx.digits - 1), 1 - ctx.digits as i64)
+  }
+  /// Returns infinity.
+  pub fn infinity(negative: bool) -> Self {
+    Self {
+      negative,
+      kind: Kind::Infinite,
+      coefficient: BigUint::zero(),
+      exponent: 0,
+    }
+  }
+  /// Returns quiet NaN with payload.
+  pub fn nan(negative: bool, payload: BigUint) -> Self {
+    Self {
+      negative,
+      kind: Kind::NaN,
+      coefficient: payload,
+      exponent: 0,
+    }
+  }
+  /// Returns `true` when this decimal is a quiet or signaling NaN.
+  pub fn is_nan(&self) -> bool {
+    matches!(self.kind, Kind::NaN | Kind::SignalingNaN)
+  }
+  /// Returns `true` when this decimal is an infinity.
+  pub fn is_infinite(&self) -> bool {
+    self.kind == Kind::Infinite
+  }
+  /// Returns `true` when this decimal is finite zero.
+  pub fn is_zero(&self) -> bool {
+    self.kind == Kind::Finite && self.coefficient.is_zero()
+  }
+  /// Returns the number of digits in coefficient.
+  pub fn digits(&self) -> usize {
+    self.coefficient.digits()
+  }
+  /// Returns the exponent of the most significant digit.
+  pub fn adjusted(&self) -> i64 {
+    self.exponent + self.digits() as i64 - 1
+  }
+  /// Returns this decimal with the sign inverted.
+  fn negated(&self) -> Self {
+    Self {
+      negative: !self.negative,
+      ..self.clone()
+    }
+  }
+}
+
+/// Result of checking whether a decimal is an integer, like `decGetInt` in decNumber.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Integer {
+  /// Not an integer.
+  Bad,
+  /// An integer too big to be represented, with its parity.
+  Big(bool),
+  /// An integer.
+  Int(i64),
+}
+
+/// Classification of the discarded digits, relative to the half of the unit in the last place.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Fraction {
+  Zero,
+  Below,
+  Half,
+  Above,
+}
+
+/// Drops `drop` least significant digits of the coefficient and rounds the result.
+/// The flag `sticky` indicates that the exact value is slightly greater than the coefficient.
+/// Returns the rounded coefficient and a flag indicating that the result is inexact.
+pub fn shorten(coefficient: &BigUint, drop: usize, sticky: bool, negative: bool, rounding: Rounding) -> (BigUint, bool) {
+  let (quotient, mut fraction) = if drop == 0 {
+    (coefficient.clone(), Fraction::Zero)
+  } else if drop > coefficient.digits() + 1 {
+    (BigUint::zero(), if coefficient.is_zero() { Fraction::Zero } else { Fraction::Below })
+  } else {
+    let (quotient, remainder) = coefficient.div_rem_pow10(drop);
+    let fraction = if remainder.is_zero() {
+      Fraction::Zero
+    } else {
+      match remainder.cmp(&BigUint::pow10(drop - 1).mul_small(5)) {
+        Ordering::Less => Fraction::Below,
+        Ordering::Equal => Fraction::Half,
+        Ordering::Greater => Fraction::Above,
+      }
+    };
+    (quotient, fraction)
+  };
+  if sticky {
+    fraction = match fraction {
+      Fraction::Zero => Fraction::Below,
+      Fraction::Half => Fraction::Above,
+      other => other,
+    };
+  }
+  let increment = match rounding {
+    Rounding::HalfEven => fraction == Fraction::Above || (fraction == Fraction::Half && quotient.is_odd()),
+    Rounding::Down => false,
+    Rounding::Ceiling => fraction != Fraction::Zero && !negative,
+    Rounding::Floor => fraction != Fraction::Zero && negative,
+  };
+  let rounded = if increment { quotient.add(&BigUint::from_u128(1)) } else { quotient };
+  (rounded, fraction != Fraction::Zero)
+}
+
+/// Rounds the exact value to the precision of the context and checks the exponent range,
+/// handling subnormal values, overflow and clamping like `decFinalize` in decNumber.
+pub fn finish(ctx: &Context, negative: bool, coefficient: BigUint, exponent: i64, sticky: bool, status: &mut u32) -> Decimal {
+  let etiny = ctx.etiny();
+  if coefficient.is_zero() && !sticky {
+    let top = if ctx.clamp { ctx.emax - ctx.digits as i64 + 1 } else { ctx.emax };
+    return Decimal::finite(negative, coefficient, exponent.clamp(etiny, max(top, etiny)));
+  }
+  let drop = max(coefficient.digits() as i64 - ctx.digits as i64, etiny - exponent).max(0);
+  let (mut coefficient, inexact) = shorten(&coefficient, drop as usize, sticky, negative, ctx.rounding);
+  let mut exponent = exponent + drop;
+  if coefficient.digits() > ctx.digits {
+    coefficient = coefficient.div_rem_small(10).0;
+    exponent += 1;
+  }
+  if inexact {
+    *status |= INEXACT;
+    if coefficient.is_zero() || exponent + (coefficient.digits() as i64) - 1 < ctx.emin {
+      *status |= UNDERFLOW;
+    }
+  }
+  if coefficient.is_zero() {
+    return Decimal::finite(negative, coefficient, exponent);
+  }
+  if exponent + coefficient.digits() as i64 - 1 > ctx.emax {
+    *status |= OVERFLOW | INEXACT;
+    let to_infinity = match ctx.rounding {
+      Rounding::HalfEven => true,
+      Rounding::Down => false,
+      Rounding::Ceiling => !negative,
+      Rounding::Floor => negative,
+    };
+    return if to_infinity {
+      Decimal::infinity(negative)
+    } else {
+      Decimal::finite(
+        negative,
+        BigUint::pow10(ctx.digits).sub(&BigUint::from_u128(1)),
+        ctx.emax - ctx.digits as i64 + 1,
+      )
+    };
+  }
+  let top = ctx.emax - ctx.digits as i64 + 1;
+  if ctx.clamp && exponent > top {
+    coefficient = coefficient.mul_pow10((exponent - top) as usize);
+    exponent = top;
+  }
+  Decimal::finite(negative, coefficient, exponent)
+}
+
+/// Rounds the finite value to the precision of the context.
+fn fit(x: &Decimal, ctx: &Context, sticky: bool, status: &mut u32) -> Decimal {
+  match x.kind {
+    Kind::Finite => finish(ctx, x.negative, x.coefficient.clone(), x.exponent, sticky, status),
+    _ => x.clone(),
+  }
+}
+
+/// Returns NaN resulting from operation on NaN operands, propagating the payload.
+pub fn nans(lhs: &Decimal, rhs: Option<&Decimal>, ctx: &Context) -> Decimal {
+  let source = match rhs {
+    _ if lhs.kind == Kind::SignalingNaN => lhs,
+    Some(rhs) if rhs.kind == Kind::SignalingNaN => rhs,
+    _ if lhs.kind == Kind::NaN => lhs,
+    Some(rhs) => rhs,
+    None => lhs,
+  };
+  let payload = if source.digits() > ctx.digits {
+    source.coefficient.div_rem_pow10(ctx.digits).1
+  } else {
+    source.coefficient.clone()
+  };
+  Decimal::nan(source.negative, payload)
+}
+
+/// Returns NaN resulting from invalid operation.
+fn invalid(status: &mut u32) -> Decimal {
+  *status |= INVALID;
+  Decimal::nan(false, BigUint::zero())
+}
+
+/// Compares magnitudes of two finite decimals.
+fn compare_magnitude(lhs: &Decimal, rhs: &Decimal) -> Ordering {
+  match (lhs.coefficient.is_zero(), rhs.coefficient.is_zero()) {
+    (true, true) => return Ordering::Equal,
+    (true, false) => return Ordering::Less,
+    (false, true) => return Ordering::Greater,
+    _ => {}
+  }
+  lhs.adjusted().cmp(&rhs.adjusted()).then_with(|| {
+    let exponent = min(lhs.exponent, rhs.exponent);
+    let left = lhs.coefficient.mul_pow10((lhs.exponent - exponent) as usize);
+    let right = rhs.coefficient.mul_pow10((rhs.exponent - exponent) as usize);
+    left.cmp(&right)
+  })
+}
+
+/// Compares two decimals, returns `None` when any of them is NaN.
+pub fn compare(lhs: &Decimal, rhs: &Decimal) -> Option<Ordering> {
+  if lhs.is_nan() || rhs.is_nan() {
+    return None;
+  }
+  let sign = |x: &Decimal| {
+    if x.is_zero() {
+      0
+    } else if x.negative {
+      -1
+    } else {
+      1
+    }
+  };
+  let (left_sign, right_sign) = (sign(lhs), sign(rhs));
+  if left_sign != right_sign {
+    return Some(left_sign.cmp(&right_sign));
+  }
+  let magnitude = match (lhs.is_infinite(), rhs.is_infinite()) {
+    (true, true) => Ordering::Equal,
+    (true, false) => Ordering::Greater,
+    (false, true) => Ordering::Less,
+    (false, false) => compare_magnitude(lhs, rhs),
+  };
+  Some(if left_sign < 0 { magnitude.reverse() } else { magnitude })
+}
+
+/// Compares two decimals, returns the signum of the comparison as a decimal.
+pub fn compare_op(lhs: &Decimal, rhs: &Decimal, ctx: &Context) -> Decimal {
+  match compare(lhs, rhs) {
+    Some(ordering) => Decimal::from_i64(ordering as i64),
+    None => nans(lhs, Some(rhs), ctx),
+  }
+}
+
+/// Adds two decimals, or subtracts them when `negate` is `true`.
+pub fn add(lhs: &Decimal, rhs: &Decimal, negate: bool, ctx: &Context, status: &mut u32) -> Decimal {
+  if lhs.is_nan() || rhs.is_nan() {
+    return nans(lhs, Some(rhs), ctx);
+  }
+  let rhs_negative = rhs.negative ^ negate;
+  if lhs.is_infinite() || rhs.is_infinite() {
+    if lhs.is_infinite() && rhs.is_infinite() && lhs.negative != rhs_negative {
+      return invalid(status);
+    }
+    return Decimal::infinity(if lhs.is_infinite() { lhs.negative } else { rhs_negative });
+  }
+  let mut left = (lhs.negative, lhs.coefficient.clone(), lhs.exponent);
+  let mut right = (rhs_negative, rhs.coefficient.clone(), rhs.exponent);
+  {
+    let (high, low) = if left.2 >= right.2 { (&left, &mut right) } else { (&right, &mut left) };
+    if !high.1.is_zero() {
+      // the operand with much lower exponent can only influence the rounding,
+      // so it is replaced with a small value at a reasonable distance
+      let high_adjusted = high.2 + high.1.digits() as i64 - 1;
+      let limit = min(high.2, high_adjusted - ctx.digits as i64) - 2;
+      if low.1.is_zero() {
+        low.2 = max(low.2, limit);
+      } else if low.2 + (low.1.digits() as i64) - 1 < limit {
+        low.1 = BigUint::from_u128(1);
+        low.2 = limit - 1;
+      }
+    }
+  }
+  let exponent = min(left.2, right.2);
+  let left_coefficient = left.1.mul_pow10((left.2 - exponent) as usize);
+  let right_coefficient = right.1.mul_pow10((right.2 - exponent) as usize);
+  let (negative, coefficient) = if left.0 == right.0 {
+    (left.0, left_coefficient.add(&right_coefficient))
+  } else {
+    match left_coefficient.cmp(&right_coefficient) {
+      Ordering::Greater => (left.0, left_coefficient.sub(&right_coefficient)),
+      Ordering::Less => (right.0, right_coefficient.sub(&left_coefficient)),
+      Ordering::Equal => (ctx.rounding == Rounding::Floor, BigUint::zero()),
+    }
+  };
+  finish(ctx, negative, coefficient, exponent, false, status)
+}
+
+/// Multiplies two decimals.
+pub fn multiply(lhs: &Decimal, rhs: &Decimal, ctx: &Context, status: &mut u32) -> Decimal {
+  if lhs.is_nan() || rhs.is_nan() {
+    return nans(lhs, Some(rhs), ctx);
+  }
+  let negative = lhs.negative ^ rhs.negative;
+  if lhs.is_infinite() || rhs.is_infinite() {
+    if lhs.is_zero() || rhs.is_zero() {
+      return invalid(status);
+    }
+    return Decimal::infinity(negative);
+  }
+  finish(ctx, negative, lhs.coefficient.mul(&rhs.coefficient), lhs.exponent + rhs.exponent, false, status)
+}
+
+/// Divides two decimals.
+pub fn divide(lhs: &Decimal, rhs: &Decimal, ctx: &Context, status: &mut u32) -> Decimal {
+  if lhs.is_nan() || rhs.is_nan() {
+    return nans(lhs, Some(rhs), ctx);
+  }
+  let negative = lhs.negative ^ rhs.negative;
+  if lhs.is_infinite() {
+    if rhs.is_infinite() {
+      return invalid(status);
+    }
+    return Decimal::infinity(negative);
+  }
+  if rhs.is_infinite() {
+    return Decimal::finite(negative, BigUint::zero(), ctx.etiny());
+  }
+  if rhs.is_zero() {
+    if lhs.is_zero() {
+      return invalid(status);
+    }
+    return Decimal::infinity(negative);
+  }
+  let ideal = lhs.exponent - rhs.exponent;
+  if lhs.is_zero() {
+    return finish(ctx, negative, BigUint::zero(), ideal, false, status);
+  }
+  let shift = (ctx.digits + 1 + rhs.digits()).saturating_sub(lhs.digits());
+  let (mut quotient, remainder) = lhs.coefficient.mul_pow10(shift).div_rem(&rhs.coefficient);
+  let mut exponent = ideal - shift as i64;
+  if remainder.is_zero() {
+    // exact result gets the exponent closest to the ideal one
+    let zeros = min(quotient.trailing_zeros(), shift);
+    quotient = quotient.div_rem_pow10(zeros).0;
+    exponent += zeros as i64;
+  }
+  finish(ctx, negative, quotient, exponent, !remainder.is_zero(), status)
+}
+
+/// Calculates the remainder of the truncating division, the sign of the result is the sign of the dividend.
+pub fn remainder(lhs: &Decimal, rhs: &Decimal, ctx: &Context, status: &mut u32) -> Decimal {
+  if lhs.is_nan() || rhs.is_nan() {
+    return nans(lhs, Some(rhs), ctx);
+  }
+  if lhs.is_infinite() || rhs.is_zero() {
+    return invalid(status);
+  }
+  if rhs.is_infinite() {
+    return fit(lhs, ctx, false, status);
+  }
+  let exponent = min(lhs.exponent, rhs.exponent);
+  if lhs.is_zero() {
+    return Decimal::finite(lhs.negative, BigUint::zero(), exponent);
+  }
+  if lhs.adjusted() < rhs.adjusted() {
+    let coefficient = lhs.coefficient.mul_pow10((lhs.exponent - exponent) as usize);
+    return finish(ctx, lhs.negative, coefficient, exponent, false, status);
+  }
+  if lhs.adjusted() - rhs.adjusted() > ctx.digits as i64 + 1 {
+    return invalid(status);
+  }
+  let dividend = lhs.coefficient.mul_pow10((lhs.exponent - exponent) as usize);
+  let divisor = rhs.coefficient.mul_pow10((rhs.exponent - exponent) as usize);
+  let (quotient, remainder) = dividend.div_rem(&divisor);
+  if quotient.digits() > ctx.digits {
+    return invalid(status);
+  }
+  finish(ctx, lhs.negative, remainder, exponent, false, status)
+}
+
+/// Rounds the decimal to an integral value using specified rounding mode.
+pub fn to_integral(x: &Decimal, ctx: &Context, rounding: Rounding) -> Decimal {
+  match x.kind {
+    Kind::NaN | Kind::SignalingNaN => nans(x, None, ctx),
+    Kind::Infinite => x.clone(),
+    Kind::Finite if x.exponent >= 0 => x.clone(),
+    Kind::Finite => {
+      let (coefficient, _) = shorten(&x.coefficient, (-x.exponent) as usize, false, x.negative, rounding);
+      Decimal::finite(x.negative, coefficient, 0)
+    }
+  }
+}
+
+/// Checks whether the decimal is an integer, like `decGetInt` in decNumber.
+pub fn get_int(x: &Decimal) -> Integer {
+  if x.kind != Kind::Finite {
+    return Integer::Bad;
+  }
+  if x.coefficient.is_zero() {
+    return Integer::Int(0);
+  }
+  let integral = if x.exponent < 0 {
+    if x.coefficient.trailing_zeros() < (-x.exponent) as usize {
+      return Integer::Bad;
+    }
+    x.coefficient.div_rem_pow10((-x.exponent) as usize).0
+  } else {
+    x.coefficient.clone()
+  };
+  let odd = x.exponent <= 0 && integral.is_odd();
+  if x.digits() as i64 + x.exponent > 10 {
+    return Integer::Big(odd);
+  }
+  let value = integral.mul_pow10(max(x.exponent, 0) as usize).to_u128().unwrap_or(u128::MAX);
+  if (x.negative && value > 1_999_999_997) || (!x.negative && value > 999_999_999) {
+    return Integer::Big(odd);
+  }
+  Integer::Int(if x.negative { -(value as i64) } else { value as i64 })
+}
+
+/// Sets the exponent of the decimal to specified value, rounding when needed.
+pub fn rescale(x: &Decimal, rhs: &Decimal, ctx: &Context, status: &mut u32) -> Decimal {
+  if x.is_nan() || rhs.is_nan() {
+    return nans(x, Some(rhs), ctx);
+  }
+  if x.is_infinite() || rhs.is_infinite() {
+    return if x.is_infinite() && rhs.is_infinite() { x.clone() } else { invalid(status) };
+  }
+  let exponent = match get_int(rhs) {
+    Integer::Int(exponent) if exponent >= ctx.etiny() && exponent <= ctx.emax => exponent,
+    _ => return invalid(status),
+  };
+  let result = if x.is_zero() {
+    Decimal::finite(x.negative, BigUint::zero(), exponent)
+  } else {
+    let adjust = exponent - x.exponent;
+    if x.digits() as i64 - adjust > ctx.digits as i64 {
+      return invalid(status);
+    }
+    let coefficient = if adjust > 0 {
+      let (coefficient, _) = shorten(&x.coefficient, adjust as usize, false, x.negative, ctx.rounding);
+      if coefficient.digits() > ctx.digits {
+        return invalid(status);
+      }
+      coefficient
+    } else {
+      x.coefficient.mul_pow10((-adjust) as usize)
+    };
+    Decimal::finite(x.negative, coefficient, exponent)
+  };
+  if result.exponent > ctx.emax - result.digits() as i64 + 1 {
+    return invalid(status);
+  }
+  fit(&result, ctx, false, &mut 0)
+}
+
+/// Multiplies the decimal by the integral power of ten.
+pub fn scale_b(x: &Decimal, rhs: &Decimal, ctx: &Context, status: &mut u32) -> Decimal {
+  if x.is_nan() || rhs.is_nan() {
+    return nans(x, Some(rhs), ctx);
+  }
+  if rhs.is_infinite() || rhs.exponent != 0 {
+    return invalid(status);
+  }
+  match get_int(rhs) {
+    Integer::Int(n) if (n.abs() + 1) / 2 <= ctx.digits as i64 + ctx.emax => {
+      if x.is_infinite() {
+        x.clone()
+      } else {
+        finish(ctx, x.negative, x.coefficient.clone(), x.exponent + n, false, status)
+      }
+    }
+    _ => invalid(status),
+  }
+}
+
+/// Removes trailing zeros from the coefficient.
+pub fn reduce(x: &Decimal, ctx: &Context, status: &mut u32) -> Decimal {
+  if x.is_nan() {
+    return nans(x, None, ctx);
+  }
+  let mut result = fit(x, ctx, false, status);
+  if result.kind != Kind::Finite {
+    return result;
+  }
+  if result.coefficient.is_zero() {
+    result.exponent = 0;
+    return result;
+  }
+  let mut zeros = result.coefficient.trailing_zeros() as i64;
+  if ctx.clamp {
+    zeros = min(zeros, ctx.emax - ctx.digits as i64 + 1 - result.exponent);
+  }
+  if zeros > 0 {
+    result.coefficient = result.coefficient.div_rem_pow10(zeros as usize).0;
+    result.exponent += zeros;
+  }
+  result
+}
+
+/// Calculates the square root, the result is correctly rounded.
+pub fn square_root(x: &Decimal, ctx: &Context, status: &mut u32) -> Decimal {
+  match x.kind {
+    Kind::NaN | Kind::SignalingNaN => return nans(x, None, ctx),
+    Kind::Infinite => return if x.negative { invalid(status) } else { x.clone() },
+    Kind::Finite => {}
+  }
+  let ideal = x.exponent.div_euclid(2);
+  if x.coefficient.is_zero() {
+    return finish(ctx, x.negative, BigUint::zero(), ideal, false, status);
+  }
+  if x.negative {
+    return invalid(status);
+  }
+  // scale the coefficient to get at least one more digit than needed in the integer square root
+  let mut shift = (2 * ctx.digits + 1).saturating_sub(x.digits()) as i64;
+  if (x.exponent - shift) % 2 != 0 {
+    shift += 1;
+  }
+  let scaled = x.coefficient.mul_pow10(shift as usize);
+  let root = scaled.isqrt();
+  let exact = root.mul(&root) == scaled;
+  let mut rounding_status = 0;
+  let mut result = finish(ctx, false, root, (x.exponent - shift) / 2, !exact, &mut rounding_status);
+  *status |= rounding_status;
+  if exact && rounding_status & INEXACT == 0 && result.kind == Kind::Finite {
+    let mut zeros = min(ideal - result.exponent, result.coefficient.trailing_zeros() as i64);
+    if ctx.clamp {
+      zeros = min(zeros, ctx.emax - ctx.digits as i64 + 1 - result.exponent);
+    }
+    if zeros > 0 {
+      result.coefficient = result.coefficient.div_rem_pow10(zeros as usize).0;
+      result.exponent += zeros;
+    }
+  }
+  result
+}
+
+/// Raises the decimal to a positive integer power using binary method, like decNumber does.
+fn raise(x: &Decimal, mut n: i32, ctx: &Context, status: &mut u32) -> Decimal {
+  let mut acc = Decimal::one();
+  let mut seen_bit = false;
+  for i in 1..=31 {
+    if *status & OVERFLOW != 0 || (*status & UNDERFLOW != 0 && acc.is_zero()) {
+      break;
+    }
+    n = n.wrapping_shl(1);
+    if n < 0 {
+      seen_bit = true;
+      acc = multiply(&acc, x, ctx, status);
+    }
+    if i == 31 {
+      break;
+    }
+    if seen_bit {
+      acc = multiply(&acc, &acc, ctx, status);
+    }
+  }
+  acc
+}
+
+/// Calculates the exponential function, port of `decExpOp` from decNumber.
+pub fn exp(x: &Decimal, ctx: &Context, status: &mut u32) -> Decimal {
+  match x.kind {
+    Kind::NaN | Kind::SignalingNaN => return nans(x, None, ctx),
+    Kind::Infinite => return if x.negative { Decimal::zero() } else { x.clone() },
+    Kind::Finite => {}
+  }
+  if x.coefficient.is_zero() {
+    return Decimal::one();
+  }
+  // tiny values give one, padded to full precision
+  let tiny = Decimal::small(false, 4, -(ctx.digits as i64) - if x.negative { 1 } else { 0 });
+  if compare_magnitude(&tiny, x) != Ordering::Less {
+    *status |= INEXACT;
+    return Decimal::padded_one(ctx);
+  }
+  let mut aset = Context {
+    emax: ctx.emax,
+    emin: ctx.emin,
+    clamp: false,
+    ..Context::DOUBLE
+  };
+  let mut h = x.exponent + x.digits() as i64;
+  let p;
+  let mut a;
+  if h > 8 {
+    // the result overflows or underflows, calculated like 2^(10^8)
+    a = Decimal::small(false, 2, if x.negative { -2 } else { 0 });
+    h = 8;
+    p = 9;
+  } else {
+    // calculate exp(x/10^h) using Taylor series
+    let lever = min(8 - h, if x.digits() > 8 { 1 } else { 0 });
+    let mut exponent = -(x.digits() as i64) - lever;
+    h += lever;
+    if h < 0 {
+      exponent += h;
+      h = 0;
+    }
+    let x = Decimal { exponent, ..x.clone() };
+    p = max(x.digits(), ctx.digits) + h as usize + 2;
+    let one = Decimal::one();
+    let mut t = x.clone();
+    let mut d = Decimal::from_i64(2);
+    let mut tset = Context::DOUBLE;
+    let dset = tset;
+    aset.digits = p * 2;
+    tset.digits = p;
+    tset.emin = MIN_EMIN;
+    let mut ignore = 0;
+    a = Decimal::one();
+    loop {
+      a = add(&a, &t, false, &aset, status);
+      t = multiply(&t, &x, &tset, &mut ignore);
+      t = divide(&t, &d, &tset, &mut ignore);
+      if a.digits() as i64 + a.exponent > t.digits() as i64 + t.exponent + p as i64 && a.digits() >= p {
+        break;
+      }
+      d = add(&d, &one, false, &dset, &mut ignore);
+    }
+  }
+  if h > 0 {
+    aset.digits = p + 2;
+    a = raise(&a, 10_i32.pow(h as u32), &aset, status);
+  }
+  let sticky = !a.is_zero();
+  fit(&a, ctx, sticky, status)
+}
+
+/// Calculates the natural logarithm, port of `decLnOp` from decNumber.
+pub fn ln(x: &Decimal, ctx: &Context, status: &mut u32) -> Decimal {
+  match x.kind {
+    Kind::NaN | Kind::SignalingNaN => return nans(x, None, ctx),
+    Kind::Infinite => return if x.negative { invalid(status) } else { x.clone() },
+    Kind::Finite => {}
+  }
+  if x.coefficient.is_zero() {
+    return Decimal::infinity(true);
+  }
+  if x.negative {
+    return invalid(status);
+  }
+  if x.exponent == 0 && ctx.digits <= 40 {
+    let rounding_ctx = Context {
+      rounding: Rounding::HalfEven,
+      ..*ctx
+    };
+    match x.coefficient.to_u128() {
+      Some(10) => return finish(&rounding_ctx, false, BigUint::from_digits(LN10.0).unwrap_or_default(), LN10.1, false, status),
+      Some(2) => return finish(&rounding_ctx, false, BigUint::from_digits(LN2.0).unwrap_or_default(), LN2.1, false, status),
+      _ => {}
+    }
+  }
+  let p = max(x.digits(), max(ctx.digits, 7)) + 2;
+  let mut ignore = 0;
+  // initial estimate
+  let mut aset = Context::DOUBLE;
+  let mut a = multiply(
+    &Decimal::from_i64(x.exponent + x.digits() as i64),
+    &Decimal::small(false, 2302585, -6),
+    &aset,
+    &mut ignore,
+  );
+  let leading = if x.digits() > 2 {
+    x.coefficient.div_rem_pow10(x.digits() - 2).0
+  } else {
+    x.coefficient.clone()
+  };
+  let mut leading = leading.to_u128().unwrap_or_default() as usize;
+  if leading < 10 {
+    leading *= 10;
+  }
+  let entry = LN_NN[leading - 10];
+  let b = Decimal::small(true, (entry >> 2) as u128, -((entry & 3) as i64) - 3);
+  a = add(&a, &b, false, &aset, &mut ignore);
+  // Newton's iteration, a' = a + x * exp(-a) - 1
+  let one = Decimal::one();
+  aset.emax = ctx.emax;
+  aset.emin = ctx.emin;
+  aset.clamp = false;
+  let mut bset = aset;
+  bset.emax = MAX_MATH * 2;
+  bset.emin = -MAX_MATH * 2;
+  let mut pp = 9;
+  aset.digits = pp;
+  bset.digits = pp + x.digits();
+  loop {
+    let mut b = exp(&a.negated(), &bset, &mut ignore);
+    b = multiply(&b, x, &bset, &mut ignore);
+    b = add(&b, &one, true, &bset, &mut ignore);
+    if b.is_zero() || a.digits() as i64 + a.exponent > b.digits() as i64 + b.exponent + ctx.digits as i64 {
+      if a.digits() == p {
+        break;
+      }
+      if a.is_zero() {
+        if compare(x, &one) == Some(Ordering::Equal) {
+          a.exponent = 0;
+        } else {
+          *status |= INEXACT;
+        }
+        break;
+      }
+      if b.is_zero() {
+        b.exponent = a.exponent - p as i64;
+      }
+    }
+    a = add(&a, &b, false, &aset, &mut ignore);
+    if pp == p {
+      continue;
+    }
+    pp = min(pp * 2, p);
+    aset.digits = pp;
+    bset.digits = pp + x.digits();
+  }
+  let sticky = !a.is_zero();
+  fit(&a, ctx, sticky, status)
+}
+
+/// Raises the decimal to the power, port of `decNumberPower` from decNumber.
+pub fn power(lhs: &Decimal, rhs: &Decimal, ctx: &Context, status: &mut u32) -> Decimal {
+  if lhs.is_nan() || rhs.is_nan() {
+    return nans(lhs, Some(rhs), ctx);
+  }
+  let one = Decimal::one();
+  if rhs.is_infinite() {
+    if lhs.negative && !lhs.is_zero() {
+      return invalid(status);
+    }
+    return match compare(lhs, &one) {
+      Some(Ordering::Less) if rhs.negative => Decimal::infinity(false),
+      Some(Ordering::Equal) => {
+        *status |= INEXACT;
+        Decimal::padded_one(ctx)
+      }
+      Some(Ordering::Greater) if !rhs.negative => Decimal::infinity(false),
+      _ => Decimal::zero(),
+    };
+  }
+  let n = get_int(rhs);
+  let rhs_int = n != Integer::Bad;
+  let odd = match n {
+    Integer::Int(n) => n % 2 != 0,
+    Integer::Big(odd) => odd,
+    Integer::Bad => false,
+  };
+  let negative = lhs.negative && odd;
+  if lhs.is_infinite() {
+    if n == Integer::Int(0) {
+      return one;
+    }
+    if !rhs_int && lhs.negative {
+      return invalid(status);
+    }
+    return if rhs.negative {
+      Decimal::finite(negative, BigUint::zero(), 0)
+    } else {
+      Decimal::infinity(negative)
+    };
+  }
+  if lhs.is_zero() {
+    if n == Integer::Int(0) {
+      return invalid(status);
+    }
+    return if rhs.negative {
+      Decimal::infinity(negative)
+    } else {
+      Decimal::finite(negative, BigUint::zero(), 0)
+    };
+  }
+  let n = match n {
+    Integer::Int(n) => n,
+    _ => {
+      // non-integer power, calculated as exp(ln(x) * y)
+      if lhs.negative {
+        return invalid(status);
+      }
+      let aset = Context {
+        digits: max(lhs.digits(), ctx.digits) + 10,
+        emax: MAX_MATH,
+        emin: -MAX_MATH,
+        clamp: false,
+        rounding: Rounding::HalfEven,
+      };
+      let mut acc = ln(lhs, &aset, status);
+      if acc.is_zero() {
+        acc = if rhs_int {
+          Decimal::finite(acc.negative, BigUint::from_u128(1), acc.exponent)
+        } else {
+          *status |= INEXACT;
+          Decimal::padded_one(ctx)
+        };
+      } else {
+        acc = multiply(&acc, rhs, &aset, status);
+        acc = exp(&acc, &aset, status);
+      }
+      return fit(&acc, ctx, false, status);
+    }
+  };
+  if n == 0 {
+    return one;
+  }
+  let aset = Context {
+    digits: (ctx.digits as i64 + rhs.digits() as i64 + rhs.exponent + 2) as usize,
+    rounding: Rounding::HalfEven,
+    ..*ctx
+  };
+  let base = if rhs.negative { divide(&one, lhs, &aset, status) } else { lhs.clone() };
+  let mut acc = raise(&base, n.unsigned_abs() as i32, &aset, status);
+  if *status & (OVERFLOW | UNDERFLOW) != 0 {
+    acc.negative = negative;
+  }
+  fit(&acc, ctx, false, status)
+}
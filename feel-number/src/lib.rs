@@ -31,9 +31,20 @@
  */
 
 extern crate lazy_static;
+#[cfg(not(any(feature = "pure-rust", target_arch = "wasm32")))]
 extern crate libc;
 
+#[cfg(any(test, feature = "pure-rust", target_arch = "wasm32"))]
+mod bigint;
+#[cfg(not(any(feature = "pure-rust", target_arch = "wasm32")))]
 pub mod dec;
+#[cfg(any(feature = "pure-rust", target_arch = "wasm32"))]
+#[path = "dec_rust.rs"]
+pub mod dec;
+#[cfg(all(test, not(any(feature = "pure-rust", target_arch = "wasm32"))))]
+mod dec_rust;
+#[cfg(any(test, feature = "pure-rust", target_arch = "wasm32"))]
+mod decimal;
 mod number;
 
 pub use number::FeelNumber;
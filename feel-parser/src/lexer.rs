@@ -37,7 +37,7 @@ use crate::lalr::TokenType;
 use dmntk_common::Result;
 use dmntk_feel::context::FeelContext;
 use dmntk_feel::values::Value;
-use dmntk_feel::{value_null, Name, Scope};
+use dmntk_feel::{value_null, Name, Scope, Span};
 
 /// Definition of a single space character.
 const WS: char = ' ';
@@ -124,6 +124,8 @@ pub struct Lexer<'lexer> {
   input: Vec<char>,
   /// Current cursor position in input vector.
   position: usize,
+  /// Position in input vector where the last read token starts.
+  token_start: usize,
   /// Flag indicating if the unary tests rule is the starting point.
   /// This flag is used to identify `not` keyword,
   /// which otherwise would be recognized as a name.
@@ -151,6 +153,7 @@ impl<'lexer> Lexer<'lexer> {
       start_token_type: Some(start_token_type),
       input: input.chars().collect(),
       position: 0,
+      token_start: 0,
       unary_tests: false,
       between: false,
       type_name: false,
@@ -205,6 +208,15 @@ impl<'lexer> Lexer<'lexer> {
     result
   }
 
+  /// Returns the span of the last token read from input, trailing whitespace is not included.
+  pub fn token_span(&self) -> Span {
+    let mut end = self.position.min(self.input.len());
+    while end > self.token_start && is_whitespace(self.input[end - 1]) {
+      end -= 1;
+    }
+    Span::new(self.token_start, end.max(self.token_start))
+  }
+
   /// Reads the next token starting from current position.
   fn read_next_token(&mut self) -> Result<(TokenType, TokenValue)> {
     let chars = self.read_input();
@@ -424,6 +436,7 @@ impl<'lexer> Lexer<'lexer> {
     self.consume_whitespace();
     self.consume_comment();
     self.consume_whitespace();
+    self.token_start = self.position;
     let mut buffer: [char; BUF_SIZE] = [WS; BUF_SIZE];
     for (offset, value) in buffer.iter_mut().enumerate() {
       if let Some(ch) = self.char_at(offset) {
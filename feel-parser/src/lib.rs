@@ -42,6 +42,6 @@ mod parser;
 mod tests;
 
 pub use parser::{
-  parse_boxed_expression, parse_context, parse_expression, parse_longest_name, parse_name, parse_textual_expression, parse_textual_expressions,
-  parse_unary_tests,
+  parse_boxed_expression, parse_context, parse_expression, parse_expression_with_spans, parse_longest_name, parse_name, parse_textual_expression,
+  parse_textual_expressions, parse_unary_tests,
 };
@@ -38,13 +38,18 @@ use crate::lexer::{Lexer, TokenValue};
 use dmntk_common::Result;
 use dmntk_feel::context::FeelContext;
 use dmntk_feel::values::Value;
-use dmntk_feel::{value_null, AstNode, FeelType, Name, Scope};
+use dmntk_feel::{value_null, AstNode, AstSpans, FeelType, Name, Scope, Span};
 
 /// Parses an `expression` as defined in grammar rule `1`.
 pub fn parse_expression(scope: &Scope, input: &str, trace: bool) -> Result<AstNode> {
   Parser::new(scope, TokenType::StartExpression, input, trace).parse()
 }
 
+/// Parses an `expression` as defined in grammar rule `1`, and returns the spans of all nodes of the parsed AST.
+pub fn parse_expression_with_spans(scope: &Scope, input: &str, trace: bool) -> Result<(AstNode, AstSpans)> {
+  Parser::new(scope, TokenType::StartExpression, input, trace).parse_with_spans()
+}
+
 /// Parses a `textual expression` as defined in grammar rule `2`.
 pub fn parse_textual_expression(scope: &Scope, input: &str, trace: bool) -> Result<AstNode> {
   Parser::new(scope, TokenType::StartTextualExpression, input, trace).parse()
@@ -114,6 +119,195 @@ macro_rules! trace_action {
   };
 }
 
+/// Part of the layout describing the spans of the child nodes of a node pushed by reduce action.
+#[derive(Debug, Copy, Clone)]
+enum Part {
+  /// Spans of the node popped during the reduction, popped nodes are numbered in source order.
+  Popped(usize),
+  /// Spans of the items of the list node popped during the reduction, numbered like in [Part::Popped].
+  Items(usize),
+  /// Span of the symbol of the reduced rule, numbered from the top of the stack, starting from 1.
+  Symbol(usize),
+  /// Span of the first symbol of the reduced rule.
+  FirstSymbol,
+  /// Node wrapping the node popped during the reduction, numbered like in [Part::Popped].
+  Wrapped(usize),
+  /// Node without counterpart in the source text, like the implicit type of a parameter,
+  /// spanning the whole rule.
+  Implicit,
+}
+
+/// Layout of the spans of the node pushed by reduce action.
+#[derive(Debug, Copy, Clone)]
+enum Layout {
+  /// Child nodes are the popped nodes, in source order.
+  Popped,
+  /// Child nodes are described by parts.
+  Parts(&'static [Part]),
+  /// Leaf node built from the symbol of the reduced rule, numbered like in [Part::Symbol].
+  Symbol(usize),
+}
+
+/// Stack of AST nodes built by reduce actions.
+///
+/// When spans are tracked, the stack keeps the spans of every node. Reduce actions pop nodes
+/// and push new nodes, built from popped nodes, together with the layout describing where
+/// the spans of popped nodes go in the new node. The spans of new nodes are built from
+/// these layouts after each reduction, so nodes are never compared nor cloned.
+#[derive(Default)]
+struct NodeStack {
+  /// Flag indicating whether spans are tracked.
+  tracked: bool,
+  /// AST nodes.
+  nodes: Vec<AstNode>,
+  /// Spans of the nodes, when spans are tracked.
+  spans: Vec<AstSpans>,
+  /// Spans of the nodes popped during the current reduction, in pop order.
+  popped: Vec<AstSpans>,
+  /// Layouts of the nodes pushed during the current reduction.
+  pushed: Vec<Layout>,
+}
+
+impl std::fmt::Debug for NodeStack {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{:?}", self.nodes)
+  }
+}
+
+impl NodeStack {
+  /// Pushes a node built from all nodes popped during the reduction, in source order.
+  fn push(&mut self, node: AstNode) {
+    self.push_with(node, Layout::Popped);
+  }
+
+  /// Pushes a node, spans of its child nodes are described by specified parts.
+  fn push_parts(&mut self, node: AstNode, parts: &'static [Part]) {
+    self.push_with(node, Layout::Parts(parts));
+  }
+
+  /// Pushes a leaf node built from the symbol of the reduced rule, numbered from the top of the stack.
+  fn push_symbol(&mut self, node: AstNode, symbol: usize) {
+    self.push_with(node, Layout::Symbol(symbol));
+  }
+
+  /// Pushes a node with specified layout of spans.
+  fn push_with(&mut self, node: AstNode, layout: Layout) {
+    self.nodes.push(node);
+    if self.tracked {
+      self.pushed.push(layout);
+    }
+  }
+
+  /// Pops a node from the top of the stack.
+  fn pop(&mut self) -> Option<AstNode> {
+    let node = self.nodes.pop()?;
+    if let Some(spans) = self.spans.pop() {
+      self.popped.push(spans);
+    }
+    Some(node)
+  }
+
+  /// Pops a node from the top of the stack together with its spans.
+  fn pop_with_spans(&mut self) -> Option<(AstNode, Option<AstSpans>)> {
+    let spans = self.spans.pop();
+    self.nodes.pop().map(|node| (node, spans))
+  }
+
+  /// Returns `true` when the stack is empty.
+  fn is_empty(&self) -> bool {
+    self.nodes.is_empty()
+  }
+
+  /// Builds spans of the nodes pushed during the reduction of the rule with specified span,
+  /// `symbols` are the spans of the symbols on the stack, `rule_len` is the number of symbols of the rule.
+  fn end_reduce(&mut self, rule_span: Span, symbols: &[Span], rule_len: usize) {
+    if self.pushed.is_empty() {
+      if self.popped.is_empty() {
+        // the rule has no action building nodes, but it may enclose the node
+        // on the top of the stack in delimiters, like braces or parentheses
+        if let Some(spans) = self.spans.pop() {
+          let span = enclosing(spans.span(), rule_span);
+          self.spans.push(spans.with_span(span));
+        }
+      }
+      self.popped.clear();
+      return;
+    }
+    let symbol = |index: usize| {
+      symbols
+        .len()
+        .checked_sub(index)
+        .and_then(|index| symbols.get(index))
+        .cloned()
+        .unwrap_or(rule_span)
+    };
+    // popped nodes are numbered in source order
+    let mut popped: Vec<Option<AstSpans>> = self.popped.drain(..).rev().map(Some).collect();
+    for layout in std::mem::take(&mut self.pushed) {
+      let spans = match layout {
+        Layout::Popped => covering(rule_span, popped.iter_mut().filter_map(Option::take).collect()),
+        Layout::Parts(parts) => {
+          let mut children = vec![];
+          for part in parts {
+            match *part {
+              Part::Popped(index) => children.push(take(&mut popped, index)),
+              Part::Items(index) => children.append(&mut take(&mut popped, index).into_children()),
+              Part::Symbol(index) => children.push(AstSpans::new(symbol(index), vec![])),
+              Part::FirstSymbol => children.push(AstSpans::new(symbol(rule_len), vec![])),
+              Part::Wrapped(index) => {
+                let wrapped = take(&mut popped, index);
+                children.push(AstSpans::new(wrapped.span(), vec![wrapped]));
+              }
+              Part::Implicit => children.push(AstSpans::new(rule_span, vec![])),
+            }
+          }
+          covering(rule_span, children)
+        }
+        Layout::Symbol(index) => AstSpans::new(symbol(index), vec![]),
+      };
+      self.spans.push(spans);
+    }
+  }
+}
+
+/// Takes the spans of the popped node with specified number.
+fn take(popped: &mut [Option<AstSpans>], index: usize) -> AstSpans {
+  popped.get_mut(index).and_then(Option::take).unwrap_or_default()
+}
+
+/// Returns the spans of the node having specified spans of child nodes.
+///
+/// Nodes are not always built by the rule covering all their symbols, like lists built
+/// from items reduced by previous rules, so the span of the node covers the span
+/// of the rule and the spans of its child nodes.
+fn covering(rule_span: Span, children: Vec<AstSpans>) -> AstSpans {
+  let span = children
+    .iter()
+    .map(AstSpans::span)
+    .filter(|span| span.start < span.end)
+    .fold(
+      rule_span,
+      |span, child_span| if span.start < span.end { hull(span, child_span) } else { child_span },
+    );
+  AstSpans::new(span, children)
+}
+
+/// Returns the span of the rule when it encloses the span of the node, starting before it,
+/// otherwise the span of the node is returned unchanged. Closing delimiters are often reduced
+/// together with the last child, so only the opening delimiter decides about enclosing.
+fn enclosing(node_span: Span, rule_span: Span) -> Span {
+  if rule_span.start < node_span.start && node_span.end <= rule_span.end {
+    rule_span
+  } else {
+    node_span
+  }
+}
+
+/// Returns the span covering both spans.
+fn hull(a: Span, b: Span) -> Span {
+  Span::new(a.start.min(b.start), a.end.max(b.end))
+}
+
 /// Parser.
 pub struct Parser<'parser> {
   /// Parsing scope.
@@ -136,12 +330,16 @@ pub struct Parser<'parser> {
   yy_n: i16,
   /// The number of symbols on the RHS of the reduced rule, keep to zero when no symbol should be popped.
   yy_len: i16,
+  /// The span of the lookahead token.
+  yy_location: Span,
   /// State stack.
   yy_state_stack: Vec<usize>,
   /// Semantic value stack.
   yy_value_stack: Vec<TokenValue>,
+  /// Location stack, spans of the symbols in semantic value stack.
+  yy_location_stack: Vec<Span>,
   /// AST node stack.
-  yy_node_stack: Vec<AstNode>,
+  yy_node_stack: NodeStack,
 }
 
 impl<'parser> Parser<'parser> {
@@ -159,14 +357,43 @@ impl<'parser> Parser<'parser> {
       yy_state: 0,
       yy_n: 0,
       yy_len: 0,
+      yy_location: Span::default(),
       yy_state_stack: vec![0],
       yy_value_stack: vec![TokenValue::YyEmpty],
-      yy_node_stack: vec![],
+      yy_location_stack: vec![Span::default()],
+      yy_node_stack: NodeStack::default(),
     }
   }
 
   /// Parses the input.
   pub fn parse(&mut self) -> Result<AstNode> {
+    self.parse_node().map(|(node, _)| node)
+  }
+
+  /// Parses the input and returns the spans of all nodes of the parsed AST.
+  pub fn parse_with_spans(&mut self) -> Result<(AstNode, AstSpans)> {
+    self.yy_node_stack.tracked = true;
+    let (node, spans) = self.parse_node()?;
+    Ok((node, spans.unwrap_or_default()))
+  }
+
+  /// Returns the span of the rule being reduced, covering all symbols on the right-hand side.
+  fn rule_span(&self) -> Span {
+    let len = self.yy_location_stack.len();
+    let count = (self.yy_len as usize).min(len);
+    let symbols = &self.yy_location_stack[len - count..];
+    let end = symbols
+      .last()
+      .or_else(|| self.yy_location_stack.last())
+      .map(|span| span.end)
+      .unwrap_or_default();
+    // symbols reduced from empty rules have no width, so they do not move the start of the span
+    let start = symbols.iter().find(|span| span.start < span.end).map(|span| span.start).unwrap_or(end);
+    Span::new(start, end)
+  }
+
+  /// Parses the input, returns the root node and its spans when spans are tracked.
+  fn parse_node(&mut self) -> Result<(AstNode, Option<AstSpans>)> {
     let mut action = Action::NewState;
     loop {
       match action {
@@ -190,6 +417,7 @@ impl<'parser> Parser<'parser> {
             self.yy_char = token_type as i16;
             self.yy_token = SymbolKind::YyEmpty as i16;
             self.yy_value = opt_token_value;
+            self.yy_location = self.yy_lexer.token_span();
             trace!(self, "  lexer: yy_char={}", self.yy_char);
             trace!(self, "  lexer: yy_value={:?}", self.yy_value);
           }
@@ -244,6 +472,7 @@ impl<'parser> Parser<'parser> {
           self.yy_state = self.yy_n as usize;
           self.yy_state_stack.push(self.yy_state);
           self.yy_value_stack.push(self.yy_value.clone());
+          self.yy_location_stack.push(self.yy_location);
           trace!(self, "  state_stack={:?}", self.yy_state_stack);
           trace!(self, "  value_stack={:?}", self.yy_value_stack);
           trace!(self, "  node_stack={:?}", self.yy_node_stack);
@@ -259,12 +488,17 @@ impl<'parser> Parser<'parser> {
           // yy_n is the number of a rule to reduce with
           trace!(self, "  --------------------------------------------");
           trace!(self, "  reducing_using_rule = {}", self.yy_n);
+          let rule_span = self.rule_span();
           crate::lalr::reduce(self, self.yy_n)?;
+          if self.yy_node_stack.tracked {
+            self.yy_node_stack.end_reduce(rule_span, &self.yy_location_stack, self.yy_len as usize);
+          }
           trace!(self, "  --------------------------------------------");
-          // pop the state stack and semantic value stack
+          // pop the state stack, semantic value stack and location stack
           for _ in 0..self.yy_len {
             self.yy_state_stack.pop();
             self.yy_value_stack.pop();
+            self.yy_location_stack.pop();
           }
           // keep yy_len = 0
           self.yy_len = 0;
@@ -281,6 +515,7 @@ impl<'parser> Parser<'parser> {
           // push the new state on the stack
           self.yy_state_stack.push(self.yy_state);
           self.yy_value_stack.push(TokenValue::YyState(self.yy_state));
+          self.yy_location_stack.push(rule_span);
           trace!(self, "  state_stack={:?}", self.yy_state_stack);
           trace!(self, "  value_stack={:?}", self.yy_value_stack);
           trace!(self, "  node_stack={:?}", self.yy_node_stack);
@@ -300,12 +535,12 @@ impl<'parser> Parser<'parser> {
           trace!(self, "* ACCEPT *");
           trace!(self, "**********\n");
           self.yy_token = SymbolKind::YyAccept as i16;
-          if let Some(node) = self.yy_node_stack.pop() {
+          if let Some((node, spans)) = self.yy_node_stack.pop_with_spans() {
             if self.yy_node_stack.is_empty() {
               if self.yy_trace {
                 node.trace();
               }
-              return Ok(node);
+              return Ok((node, spans));
             }
           }
           return Err(invalid_parse_result());
@@ -488,7 +723,7 @@ impl<'parser> ReduceActions for Parser<'parser> {
     if let AstNode::Context(mut items) = node {
       let item = self.yy_node_stack.pop().ok_or_else(err_pop)?;
       items.insert(0, item);
-      self.yy_node_stack.push(AstNode::Context(items));
+      self.yy_node_stack.push_parts(AstNode::Context(items), &[Part::Popped(0), Part::Items(1)]);
       return Ok(());
     }
     self.yy_node_stack.push(AstNode::Context(vec![node]));
@@ -502,7 +737,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
     if let TokenValue::Name(name) = &self.yy_value_stack[self.yy_value_stack.len() - self.yy_len as usize] {
       let lhs = Box::new(AstNode::ContextTypeEntryKey(name.clone()));
       let rhs = Box::new(type_node);
-      self.yy_node_stack.push(AstNode::ContextTypeEntry(lhs, rhs));
+      self
+        .yy_node_stack
+        .push_parts(AstNode::ContextTypeEntry(lhs, rhs), &[Part::FirstSymbol, Part::Popped(0)]);
     }
     Ok(())
   }
@@ -514,7 +751,7 @@ impl<'parser> ReduceActions for Parser<'parser> {
     if let AstNode::ContextType(mut items) = node {
       let item = self.yy_node_stack.pop().ok_or_else(err_pop)?;
       items.insert(0, item);
-      self.yy_node_stack.push(AstNode::ContextType(items));
+      self.yy_node_stack.push_parts(AstNode::ContextType(items), &[Part::Popped(0), Part::Items(1)]);
       return Ok(());
     }
     self.yy_node_stack.push(AstNode::ContextType(vec![node]));
@@ -554,7 +791,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
     let rhs = self.yy_node_stack.pop().ok_or_else(err_pop)?;
     let lhs = self.yy_node_stack.pop().ok_or_else(err_pop)?;
     let satisfies = Box::new(AstNode::Satisfies(Box::new(rhs)));
-    self.yy_node_stack.push(AstNode::Every(Box::new(lhs), satisfies));
+    self
+      .yy_node_stack
+      .push_parts(AstNode::Every(Box::new(lhs), satisfies), &[Part::Popped(0), Part::Wrapped(1)]);
     Ok(())
   }
 
@@ -584,7 +823,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
     if let AstNode::ExpressionList(mut items) = node {
       let item = self.yy_node_stack.pop().ok_or_else(err_pop)?;
       items.insert(0, item);
-      self.yy_node_stack.push(AstNode::ExpressionList(items));
+      self
+        .yy_node_stack
+        .push_parts(AstNode::ExpressionList(items), &[Part::Popped(0), Part::Items(1)]);
       return Ok(());
     }
     self.yy_node_stack.push(AstNode::ExpressionList(vec![node]));
@@ -610,7 +851,10 @@ impl<'parser> ReduceActions for Parser<'parser> {
     let rhs = self.yy_node_stack.pop().ok_or_else(err_pop)?;
     let lhs = self.yy_node_stack.pop().ok_or_else(err_pop)?;
     let evaluated_expression = AstNode::EvaluatedExpression(Box::new(rhs));
-    self.yy_node_stack.push(AstNode::For(Box::new(lhs), Box::new(evaluated_expression)));
+    self.yy_node_stack.push_parts(
+      AstNode::For(Box::new(lhs), Box::new(evaluated_expression)),
+      &[Part::Popped(0), Part::Wrapped(1)],
+    );
     Ok(())
   }
 
@@ -637,7 +881,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
       // push the new formal parameter on top of node stack
       let parameter_name = Box::new(AstNode::ParameterName(name.clone()));
       let parameter_type = Box::new(rhs);
-      self.yy_node_stack.push(AstNode::FormalParameter(parameter_name, parameter_type));
+      self
+        .yy_node_stack
+        .push_parts(AstNode::FormalParameter(parameter_name, parameter_type), &[Part::FirstSymbol, Part::Popped(0)]);
       // set the name of the parameter to local context on the top of the scope stack
       // this name will be properly interpreted as a name while parsing the function body
       self.scope.set_entry(name, value_null!());
@@ -653,7 +899,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
       // push the new formal parameter on top of node stack
       let parameter_name = Box::new(AstNode::ParameterName(name.clone()));
       let parameter_type = Box::new(AstNode::FeelType(FeelType::Any));
-      self.yy_node_stack.push(AstNode::FormalParameter(parameter_name, parameter_type));
+      self
+        .yy_node_stack
+        .push_parts(AstNode::FormalParameter(parameter_name, parameter_type), &[Part::FirstSymbol, Part::Implicit]);
       // set the name of the parameter to local context on the top of the scope stack
       // this name will be properly interpreted as a name while parsing the function body
       self.scope.set_entry(name, value_null!());
@@ -694,7 +942,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
     // the collection of formal parameters is now on top of the node stack
     if let Some(AstNode::FormalParameters(mut items)) = self.yy_node_stack.pop() {
       items.push(rhs);
-      self.yy_node_stack.push(AstNode::FormalParameters(items));
+      self
+        .yy_node_stack
+        .push_parts(AstNode::FormalParameters(items), &[Part::Items(0), Part::Popped(1)]);
     }
     Ok(())
   }
@@ -754,7 +1004,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
     trace_action!(self, "function_invocation_no_parameters");
     if let Some(lhs) = self.yy_node_stack.pop() {
       let rhs = AstNode::PositionalParameters(vec![]);
-      self.yy_node_stack.push(AstNode::FunctionInvocation(Box::new(lhs), Box::new(rhs)));
+      self
+        .yy_node_stack
+        .push_parts(AstNode::FunctionInvocation(Box::new(lhs), Box::new(rhs)), &[Part::Popped(0), Part::Implicit]);
     }
     Ok(())
   }
@@ -782,7 +1034,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
     if let AstNode::ParameterTypes(mut items) = node {
       let item = self.yy_node_stack.pop().ok_or_else(err_pop)?;
       items.insert(0, item);
-      self.yy_node_stack.push(AstNode::ParameterTypes(items));
+      self
+        .yy_node_stack
+        .push_parts(AstNode::ParameterTypes(items), &[Part::Popped(0), Part::Items(1)]);
       return Ok(());
     }
     self.yy_node_stack.push(AstNode::ParameterTypes(vec![node]));
@@ -871,7 +1125,7 @@ impl<'parser> ReduceActions for Parser<'parser> {
   fn action_iteration_context_variable_name(&mut self) -> Result<()> {
     trace_action!(self, "iteration_context_variable_name");
     if let TokenValue::Name(name) = &self.yy_value_stack[self.yy_value_stack.len() - 1] {
-      self.yy_node_stack.push(AstNode::Name(name.clone()));
+      self.yy_node_stack.push_symbol(AstNode::Name(name.clone()), 1);
       // add this variable name to the temporary context present on top of the scope
       self.yy_lexer.add_name_to_scope(name);
     }
@@ -892,7 +1146,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
     if let AstNode::IterationContexts(mut items) = node {
       let item = self.yy_node_stack.pop().ok_or_else(err_pop)?;
       items.insert(0, item);
-      self.yy_node_stack.push(AstNode::IterationContexts(items));
+      self
+        .yy_node_stack
+        .push_parts(AstNode::IterationContexts(items), &[Part::Popped(0), Part::Items(1)]);
       return Ok(());
     }
     self.yy_node_stack.push(AstNode::IterationContexts(vec![node]));
@@ -921,7 +1177,7 @@ impl<'parser> ReduceActions for Parser<'parser> {
   fn action_list(&mut self) -> Result<()> {
     trace_action!(self, "list");
     if let Some(AstNode::CommaList(items)) = self.yy_node_stack.pop() {
-      self.yy_node_stack.push(AstNode::List(items));
+      self.yy_node_stack.push_parts(AstNode::List(items), &[Part::Items(0)]);
     }
     Ok(())
   }
@@ -940,7 +1196,7 @@ impl<'parser> ReduceActions for Parser<'parser> {
     if let AstNode::CommaList(mut items) = node {
       let item = self.yy_node_stack.pop().ok_or_else(err_pop)?;
       items.insert(0, item);
-      self.yy_node_stack.push(AstNode::CommaList(items));
+      self.yy_node_stack.push_parts(AstNode::CommaList(items), &[Part::Popped(0), Part::Items(1)]);
       return Ok(());
     }
     self.yy_node_stack.push(AstNode::CommaList(vec![node]));
@@ -977,7 +1233,7 @@ impl<'parser> ReduceActions for Parser<'parser> {
   fn action_literal_date_time(&mut self) -> Result<()> {
     trace_action!(self, "literal_date_time");
     if let TokenValue::NameDateTime(name) = &self.yy_value_stack[self.yy_value_stack.len() - 2] {
-      self.yy_node_stack.push(AstNode::Name(name.clone()));
+      self.yy_node_stack.push_symbol(AstNode::Name(name.clone()), 2);
     }
     Ok(())
   }
@@ -1035,7 +1291,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
       let rhs = self.yy_node_stack.pop().ok_or_else(err_pop)?;
       let parameter_name = Box::new(AstNode::ParameterName(name.clone()));
       let parameter_value = Box::new(rhs);
-      self.yy_node_stack.push(AstNode::NamedParameter(parameter_name, parameter_value));
+      self
+        .yy_node_stack
+        .push_parts(AstNode::NamedParameter(parameter_name, parameter_value), &[Part::Symbol(3), Part::Popped(0)]);
     }
     Ok(())
   }
@@ -1047,7 +1305,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
     if let AstNode::NamedParameters(mut items) = node {
       let item = self.yy_node_stack.pop().ok_or_else(err_pop)?;
       items.insert(0, item);
-      self.yy_node_stack.push(AstNode::NamedParameters(items));
+      self
+        .yy_node_stack
+        .push_parts(AstNode::NamedParameters(items), &[Part::Popped(0), Part::Items(1)]);
       return Ok(());
     }
     self.yy_node_stack.push(AstNode::NamedParameters(vec![node]));
@@ -1069,7 +1329,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
     let lhs = self.yy_node_stack.pop().ok_or_else(err_pop)?;
     if let Some(TokenValue::Name(name)) = &self.yy_value_stack.last() {
       let rhs = AstNode::Name(name.clone());
-      self.yy_node_stack.push(AstNode::Path(Box::new(lhs), Box::new(rhs)));
+      self
+        .yy_node_stack
+        .push_parts(AstNode::Path(Box::new(lhs), Box::new(rhs)), &[Part::Popped(0), Part::Symbol(1)]);
     }
     Ok(())
   }
@@ -1081,7 +1343,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
       if let Some(TokenValue::Name(rhs_name)) = &self.yy_value_stack.last() {
         let lhs = AstNode::Name(lhs_name.clone());
         let rhs = AstNode::Name(rhs_name.clone());
-        self.yy_node_stack.push(AstNode::Path(Box::new(lhs), Box::new(rhs)));
+        self
+          .yy_node_stack
+          .push_parts(AstNode::Path(Box::new(lhs), Box::new(rhs)), &[Part::Symbol(3), Part::Symbol(1)]);
       }
     }
     Ok(())
@@ -1094,7 +1358,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
     if let AstNode::PositionalParameters(mut items) = node {
       let item = self.yy_node_stack.pop().ok_or_else(err_pop)?;
       items.insert(0, item);
-      self.yy_node_stack.push(AstNode::PositionalParameters(items));
+      self
+        .yy_node_stack
+        .push_parts(AstNode::PositionalParameters(items), &[Part::Popped(0), Part::Items(1)]);
       return Ok(());
     }
     self.yy_node_stack.push(AstNode::PositionalParameters(vec![node]));
@@ -1107,7 +1373,7 @@ impl<'parser> ReduceActions for Parser<'parser> {
     if let Some(TokenValue::Name(name)) = &self.yy_value_stack.last() {
       self
         .yy_node_stack
-        .push(AstNode::QualifiedName(vec![AstNode::QualifiedNameSegment(name.clone())]));
+        .push_parts(AstNode::QualifiedName(vec![AstNode::QualifiedNameSegment(name.clone())]), &[Part::Symbol(1)]);
     }
     Ok(())
   }
@@ -1118,7 +1384,7 @@ impl<'parser> ReduceActions for Parser<'parser> {
     if let TokenValue::Name(name) = &self.yy_value_stack[self.yy_value_stack.len() - 3] {
       if let Some(AstNode::QualifiedName(mut parts)) = self.yy_node_stack.pop() {
         parts.insert(0, AstNode::QualifiedNameSegment(name.clone()));
-        self.yy_node_stack.push(AstNode::QualifiedName(parts));
+        self.yy_node_stack.push_parts(AstNode::QualifiedName(parts), &[Part::Symbol(3), Part::Items(0)]);
       }
     }
     Ok(())
@@ -1140,7 +1406,7 @@ impl<'parser> ReduceActions for Parser<'parser> {
   fn action_quantified_expression_variable_name(&mut self) -> Result<()> {
     trace_action!(self, "quantified_expression_variable_name");
     if let TokenValue::Name(name) = &self.yy_value_stack[self.yy_value_stack.len() - 1] {
-      self.yy_node_stack.push(AstNode::Name(name.clone()));
+      self.yy_node_stack.push_symbol(AstNode::Name(name.clone()), 1);
       // add this variable name to the temporary context present on top of the scope
       self.yy_lexer.add_name_to_scope(name);
     }
@@ -1161,7 +1427,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
     if let AstNode::QuantifiedContexts(mut items) = node {
       let item = self.yy_node_stack.pop().ok_or_else(err_pop)?;
       items.insert(0, item);
-      self.yy_node_stack.push(AstNode::QuantifiedContexts(items));
+      self
+        .yy_node_stack
+        .push_parts(AstNode::QuantifiedContexts(items), &[Part::Popped(0), Part::Items(1)]);
       return Ok(());
     }
     self.yy_node_stack.push(AstNode::QuantifiedContexts(vec![node]));
@@ -1184,7 +1452,9 @@ impl<'parser> ReduceActions for Parser<'parser> {
     let rhs = self.yy_node_stack.pop().ok_or_else(err_pop)?;
     let lhs = self.yy_node_stack.pop().ok_or_else(err_pop)?;
     let satisfies = Box::new(AstNode::Satisfies(Box::new(rhs)));
-    self.yy_node_stack.push(AstNode::Some(Box::new(lhs), satisfies));
+    self
+      .yy_node_stack
+      .push_parts(AstNode::Some(Box::new(lhs), satisfies), &[Part::Popped(0), Part::Wrapped(1)]);
     Ok(())
   }
 
@@ -1232,7 +1502,7 @@ impl<'parser> ReduceActions for Parser<'parser> {
   fn action_unary_tests_negated(&mut self) -> Result<()> {
     trace_action!(self, "unary_tests_negated");
     if let Some(AstNode::ExpressionList(items)) = self.yy_node_stack.pop() {
-      self.yy_node_stack.push(AstNode::NegatedList(items));
+      self.yy_node_stack.push_parts(AstNode::NegatedList(items), &[Part::Items(0)]);
     }
     Ok(())
   }
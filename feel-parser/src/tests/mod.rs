@@ -68,6 +68,7 @@ mod path;
 mod range;
 mod simple_positive_unary_test;
 mod some_expression;
+mod spans;
 mod subtraction;
 mod temporal_date;
 mod temporal_date_time;
@@ -0,0 +1,143 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::parse_expression_with_spans;
+use dmntk_feel::context::FeelContext;
+use dmntk_feel::values::Value;
+use dmntk_feel::{scope, AstNode, Scope, Span};
+
+/// Returns the source text covered by each node, in pre-order.
+fn texts(input: &str, scope: &Scope) -> Vec<String> {
+  let (node, spans) = parse_expression_with_spans(scope, input, false).unwrap();
+  let mut texts = vec![];
+  spans.visit(&node, &mut |_, span| texts.push(span.text(input)));
+  texts
+}
+
+/// Returns the span of each node, in pre-order.
+fn spans(input: &str, scope: &Scope) -> Vec<(usize, usize)> {
+  let (node, spans) = parse_expression_with_spans(scope, input, false).unwrap();
+  let mut result = vec![];
+  spans.visit(&node, &mut |_, span: Span| result.push((span.start, span.end)));
+  result
+}
+
+#[test]
+fn _0001() {
+  let scope = scope!();
+  scope.set_entry(&"a".into(), Value::Null(None));
+  scope.set_entry(&"b".into(), Value::Null(None));
+  assert_eq!(vec!["a + b * 2", "a", "b * 2", "b", "2"], texts("a + b * 2", &scope));
+}
+
+#[test]
+fn _0002() {
+  let scope = scope!();
+  assert_eq!(vec!["( 1 )"], texts("  ( 1 )  ", &scope));
+}
+
+#[test]
+fn _0003() {
+  let scope = scope!();
+  assert_eq!(
+    vec!["{x: 1,\n y: [x, \"s\"]}", "x: 1", "x", "1", "y: [x, \"s\"]", "y", "[x, \"s\"]", "x", "\"s\""],
+    texts("{x: 1,\n y: [x, \"s\"]}", &scope)
+  );
+}
+
+#[test]
+fn _0004() {
+  let scope = scope!();
+  let actual = texts("for i in [1,2] return sum(i, 2)", &scope);
+  assert_eq!("for i in [1,2] return sum(i, 2)", actual[0]);
+  assert_eq!("i in [1,2]", actual[2]);
+  assert_eq!("i", actual[3]);
+  assert_eq!("[1,2]", actual[4]);
+  assert_eq!("sum(i, 2)", actual[8]);
+  assert_eq!("sum", actual[9]);
+}
+
+#[test]
+fn _0005() {
+  let scope = scope!();
+  assert_eq!(
+    vec!["function(x) x + 1", "x", "x", "x", "x", "x + 1", "x + 1", "x", "1"],
+    texts("function(x) x + 1", &scope)
+  );
+}
+
+#[test]
+fn _0006() {
+  let scope = scope!();
+  scope.set_entry(&"a".into(), Value::Null(None));
+  let input = "if a > 0\nthen a\nelse not(a)";
+  let (node, spans) = parse_expression_with_spans(&scope, input, false).unwrap();
+  if let AstNode::If(_, _, _) = node {
+    let span = spans.children()[2].span();
+    assert_eq!("not(a)", span.text(input));
+    assert_eq!((3, 6), span.line_column(input));
+  } else {
+    panic!("expected if expression");
+  }
+}
+
+#[test]
+fn _0007() {
+  // equal sub-expressions have their own spans
+  let scope = scope!();
+  scope.set_entry(&"x".into(), Value::Null(None));
+  assert_eq!(vec![(0, 5), (0, 1), (4, 5)], spans("x + x", &scope));
+  assert_eq!(
+    vec![(0, 15), (0, 5), (0, 1), (4, 5), (8, 15), (9, 10), (13, 14)],
+    spans("x + x = (x + x)", &scope)
+  );
+  assert_eq!(
+    vec![(0, 13), (0, 5), (1, 2), (3, 4), (8, 13), (9, 10), (11, 12)],
+    spans("[x,x] = [x,x]", &scope)[..7].to_vec()
+  );
+}
+
+#[test]
+fn _0008() {
+  // equal names in paths, parameters and iteration contexts have their own spans
+  let scope = scope!();
+  let mut ctx = FeelContext::default();
+  ctx.set_entry(&"a".into(), Value::Null(None));
+  scope.set_entry(&"a".into(), Value::Context(ctx));
+  assert_eq!(vec!["a.a", "a", "a"], texts("a.a", &scope));
+  assert_eq!(vec![(0, 3), (0, 1), (2, 3)], spans("a.a", &scope));
+  let actual = spans("sum(1, 1)", &scope);
+  assert_eq!((0, 9), actual[0]);
+  assert_eq!(vec![(4, 5), (7, 8)], actual[3..].to_vec());
+  let actual = texts("for i in [i] return i", &scope);
+  assert_eq!(vec!["for i in [i] return i", "i in [i]", "i in [i]", "i", "[i]", "i", "i", "i"], actual);
+}
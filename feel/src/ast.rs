@@ -461,4 +461,79 @@ impl AstNode {
   pub fn trace(&self) {
     println!("      AST:{}", self.to_string());
   }
+
+  /// Returns direct child nodes of this node, in the order they appear in source text.
+  pub fn children(&self) -> Vec<&AstNode> {
+    match self {
+      AstNode::Add(lhs, rhs)
+      | AstNode::And(lhs, rhs)
+      | AstNode::ContextEntry(lhs, rhs)
+      | AstNode::ContextTypeEntry(lhs, rhs)
+      | AstNode::Div(lhs, rhs)
+      | AstNode::Eq(lhs, rhs)
+      | AstNode::Every(lhs, rhs)
+      | AstNode::Exp(lhs, rhs)
+      | AstNode::Filter(lhs, rhs)
+      | AstNode::For(lhs, rhs)
+      | AstNode::FormalParameter(lhs, rhs)
+      | AstNode::FunctionDefinition(lhs, rhs)
+      | AstNode::FunctionInvocation(lhs, rhs)
+      | AstNode::FunctionType(lhs, rhs)
+      | AstNode::Ge(lhs, rhs)
+      | AstNode::Gt(lhs, rhs)
+      | AstNode::In(lhs, rhs)
+      | AstNode::InstanceOf(lhs, rhs)
+      | AstNode::IterationContextSingle(lhs, rhs)
+      | AstNode::Le(lhs, rhs)
+      | AstNode::Lt(lhs, rhs)
+      | AstNode::Mul(lhs, rhs)
+      | AstNode::NamedParameter(lhs, rhs)
+      | AstNode::Nq(lhs, rhs)
+      | AstNode::Or(lhs, rhs)
+      | AstNode::Out(lhs, rhs)
+      | AstNode::Path(lhs, rhs)
+      | AstNode::QuantifiedContext(lhs, rhs)
+      | AstNode::Range(lhs, rhs)
+      | AstNode::Some(lhs, rhs)
+      | AstNode::Sub(lhs, rhs) => vec![lhs, rhs],
+      AstNode::Between(lhs, mhs, rhs) | AstNode::If(lhs, mhs, rhs) | AstNode::IterationContextRange(lhs, mhs, rhs) => vec![lhs, mhs, rhs],
+      AstNode::EvaluatedExpression(lhs)
+      | AstNode::FunctionBody(lhs, _)
+      | AstNode::IntervalEnd(lhs, _)
+      | AstNode::IntervalStart(lhs, _)
+      | AstNode::ListType(lhs)
+      | AstNode::Neg(lhs)
+      | AstNode::RangeType(lhs)
+      | AstNode::Satisfies(lhs)
+      | AstNode::UnaryGe(lhs)
+      | AstNode::UnaryGt(lhs)
+      | AstNode::UnaryLe(lhs)
+      | AstNode::UnaryLt(lhs) => vec![lhs],
+      AstNode::CommaList(items)
+      | AstNode::Context(items)
+      | AstNode::ContextType(items)
+      | AstNode::ExpressionList(items)
+      | AstNode::FormalParameters(items)
+      | AstNode::IterationContexts(items)
+      | AstNode::List(items)
+      | AstNode::NamedParameters(items)
+      | AstNode::NegatedList(items)
+      | AstNode::ParameterTypes(items)
+      | AstNode::PositionalParameters(items)
+      | AstNode::QualifiedName(items)
+      | AstNode::QuantifiedContexts(items) => items.iter().collect(),
+      AstNode::At(_)
+      | AstNode::Boolean(_)
+      | AstNode::ContextEntryKey(_)
+      | AstNode::ContextTypeEntryKey(_)
+      | AstNode::FeelType(_)
+      | AstNode::Irrelevant
+      | AstNode::Name(_)
+      | AstNode::Null
+      | AstNode::Numeric(_, _)
+      | AstNode::ParameterName(_)
+      | AstNode::QualifiedNameSegment(_)
+      | AstNode::String(_) => vec![],
+    }
+  }
 }
@@ -32,6 +32,7 @@
 
 //! Definitions of built-in functions.
 
+use crate::types::FeelType;
 use dmntk_common::DmntkError;
 use std::str::FromStr;
 
@@ -214,6 +215,129 @@ pub fn is_built_in_date_time_function_name(name: &str) -> bool {
   }
 }
 
+/// Signature of a built-in function.
+#[derive(Debug, Clone)]
+pub struct BifSignature {
+  /// Name of the built-in function.
+  pub name: &'static str,
+  /// Built-in function.
+  pub bif: Bif,
+  /// Invocation forms of the built-in function, with names and types of parameters.
+  pub forms: &'static str,
+  /// Type of the value returned by the built-in function.
+  pub result_type: FeelType,
+}
+
+impl BifSignature {
+  /// Creates a new signature of a built-in function.
+  fn new(name: &'static str, bif: Bif, forms: &'static str, result_type: FeelType) -> Self {
+    Self { name, bif, forms, result_type }
+  }
+}
+
+impl std::fmt::Display for BifSignature {
+  /// Implements [Display](std::fmt::Display) trait for [BifSignature].
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{} -> {}", self.forms, self.result_type)
+  }
+}
+
+lazy_static! {
+  /// Signatures of all built-in functions, ordered by name.
+  static ref BIF_SIGNATURES: Vec<BifSignature> = vec![
+    BifSignature::new("abs", Bif::Abs, "abs(n: number)", FeelType::Number),
+    BifSignature::new("after", Bif::After, "after(point1/range1: Any, point2/range2: Any)", FeelType::Boolean),
+    BifSignature::new("all", Bif::All, "all(list: list<boolean>)", FeelType::Boolean),
+    BifSignature::new("any", Bif::Any, "any(list: list<boolean>)", FeelType::Boolean),
+    BifSignature::new("append", Bif::Append, "append(list: list<Any>, item...: Any)", FeelType::List(Box::new(FeelType::Any))),
+    BifSignature::new("before", Bif::Before, "before(point1/range1: Any, point2/range2: Any)", FeelType::Boolean),
+    BifSignature::new("ceiling", Bif::Ceiling, "ceiling(n: number)", FeelType::Number),
+    BifSignature::new("coincides", Bif::Coincides, "coincides(point1/range1: Any, point2/range2: Any)", FeelType::Boolean),
+    BifSignature::new("concatenate", Bif::Concatenate, "concatenate(list...: list<Any>)", FeelType::List(Box::new(FeelType::Any))),
+    BifSignature::new("contains", Bif::Contains, "contains(string: string, match: string)", FeelType::Boolean),
+    BifSignature::new("count", Bif::Count, "count(list: list<Any>)", FeelType::Number),
+    BifSignature::new("date", Bif::Date, "date(from: string) / date(year: number, month: number, day: number)", FeelType::Date),
+    BifSignature::new("date and time", Bif::DateAndTime, "date and time(from: string) / date and time(date: date, time: time)", FeelType::DateTime),
+    BifSignature::new("day of week", Bif::DayOfWeek, "day of week(date: date)", FeelType::String),
+    BifSignature::new("day of year", Bif::DayOfYear, "day of year(date: date)", FeelType::Number),
+    BifSignature::new("decimal", Bif::Decimal, "decimal(n: number, scale: number)", FeelType::Number),
+    BifSignature::new("distinct values", Bif::DistinctValues, "distinct values(list: list<Any>)", FeelType::List(Box::new(FeelType::Any))),
+    BifSignature::new("duration", Bif::Duration, "duration(from: string)", FeelType::Any),
+    BifSignature::new("during", Bif::During, "during(point/range1: Any, range/range2: range<Any>)", FeelType::Boolean),
+    BifSignature::new("ends with", Bif::EndsWith, "ends with(string: string, match: string)", FeelType::Boolean),
+    BifSignature::new("even", Bif::Even, "even(number: number)", FeelType::Boolean),
+    BifSignature::new("exp", Bif::Exp, "exp(number: number)", FeelType::Number),
+    BifSignature::new("finished by", Bif::FinishedBy, "finished by(range: range<Any>, point/range2: Any)", FeelType::Boolean),
+    BifSignature::new("finishes", Bif::Finishes, "finishes(point/range1: Any, range/range2: range<Any>)", FeelType::Boolean),
+    BifSignature::new("flatten", Bif::Flatten, "flatten(list: list<Any>)", FeelType::List(Box::new(FeelType::Any))),
+    BifSignature::new("floor", Bif::Floor, "floor(n: number)", FeelType::Number),
+    BifSignature::new("get entries", Bif::GetEntries, "get entries(m: context<>)", FeelType::List(Box::new(FeelType::Context(vec![("key".into(), FeelType::String), ("value".into(), FeelType::Any)].into_iter().collect())))),
+    BifSignature::new("get value", Bif::GetValue, "get value(m: context<>, key: string)", FeelType::Any),
+    BifSignature::new("includes", Bif::Includes, "includes(range: range<Any>, point/range2: Any)", FeelType::Boolean),
+    BifSignature::new("index of", Bif::IndexOf, "index of(list: list<Any>, match: Any)", FeelType::List(Box::new(FeelType::Number))),
+    BifSignature::new("insert before", Bif::InsertBefore, "insert before(list: list<Any>, position: number, newItem: Any)", FeelType::List(Box::new(FeelType::Any))),
+    BifSignature::new("is", Bif::Is, "is(value1: Any, value2: Any)", FeelType::Boolean),
+    BifSignature::new("list contains", Bif::ListContains, "list contains(list: list<Any>, element: Any)", FeelType::Boolean),
+    BifSignature::new("log", Bif::Log, "log(number: number)", FeelType::Number),
+    BifSignature::new("lower case", Bif::LoweCase, "lower case(string: string)", FeelType::String),
+    BifSignature::new("matches", Bif::Matches, "matches(input: string, pattern: string, flags?: string)", FeelType::Boolean),
+    BifSignature::new("max", Bif::Max, "max(list: list<Any>) / max(c1...: Any)", FeelType::Any),
+    BifSignature::new("mean", Bif::Mean, "mean(list: list<number>) / mean(n1...: number)", FeelType::Number),
+    BifSignature::new("median", Bif::Median, "median(list: list<number>) / median(n1...: number)", FeelType::Number),
+    BifSignature::new("meets", Bif::Meets, "meets(range1: range<Any>, range2: range<Any>)", FeelType::Boolean),
+    BifSignature::new("met by", Bif::MetBy, "met by(range1: range<Any>, range2: range<Any>)", FeelType::Boolean),
+    BifSignature::new("min", Bif::Min, "min(list: list<Any>) / min(c1...: Any)", FeelType::Any),
+    BifSignature::new("mode", Bif::Mode, "mode(list: list<number>) / mode(n1...: number)", FeelType::List(Box::new(FeelType::Number))),
+    BifSignature::new("modulo", Bif::Modulo, "modulo(dividend: number, divisor: number)", FeelType::Number),
+    BifSignature::new("month of year", Bif::MonthOfYear, "month of year(date: date)", FeelType::String),
+    BifSignature::new("not", Bif::Not, "not(negand: boolean)", FeelType::Boolean),
+    BifSignature::new("number", Bif::Number, "number(from: string, grouping separator: string, decimal separator: string)", FeelType::Number),
+    BifSignature::new("odd", Bif::Odd, "odd(number: number)", FeelType::Boolean),
+    BifSignature::new("overlaps after", Bif::OverlapsAfter, "overlaps after(range1: range<Any>, range2: range<Any>)", FeelType::Boolean),
+    BifSignature::new("overlaps before", Bif::OverlapsBefore, "overlaps before(range1: range<Any>, range2: range<Any>)", FeelType::Boolean),
+    BifSignature::new("product", Bif::Product, "product(list: list<number>) / product(n1...: number)", FeelType::Number),
+    BifSignature::new("remove", Bif::Remove, "remove(list: list<Any>, position: number)", FeelType::List(Box::new(FeelType::Any))),
+    BifSignature::new("replace", Bif::Replace, "replace(input: string, pattern: string, replacement: string, flags?: string)", FeelType::String),
+    BifSignature::new("reverse", Bif::Reverse, "reverse(list: list<Any>)", FeelType::List(Box::new(FeelType::Any))),
+    BifSignature::new("sort", Bif::Sort, "sort(list: list<Any>, precedes: function<Any, Any> -> boolean)", FeelType::List(Box::new(FeelType::Any))),
+    BifSignature::new("split", Bif::Split, "split(string: string, delimiter: string)", FeelType::List(Box::new(FeelType::String))),
+    BifSignature::new("sqrt", Bif::Sqrt, "sqrt(number: number)", FeelType::Number),
+    BifSignature::new("started by", Bif::StartedBy, "started by(range: range<Any>, point/range2: Any)", FeelType::Boolean),
+    BifSignature::new("starts", Bif::Starts, "starts(point/range1: Any, range/range2: range<Any>)", FeelType::Boolean),
+    BifSignature::new("starts with", Bif::StartsWith, "starts with(string: string, match: string)", FeelType::Boolean),
+    BifSignature::new("stddev", Bif::Stddev, "stddev(list: list<number>) / stddev(n1...: number)", FeelType::Number),
+    BifSignature::new("string", Bif::String, "string(from: Any)", FeelType::String),
+    BifSignature::new("string length", Bif::StringLength, "string length(string: string)", FeelType::Number),
+    BifSignature::new("sublist", Bif::Sublist, "sublist(list: list<Any>, start position: number, length?: number)", FeelType::List(Box::new(FeelType::Any))),
+    BifSignature::new("substring", Bif::Substring, "substring(string: string, start position: number, length?: number)", FeelType::String),
+    BifSignature::new("substring after", Bif::SubstringAfter, "substring after(string: string, match: string)", FeelType::String),
+    BifSignature::new("substring before", Bif::SubstringBefore, "substring before(string: string, match: string)", FeelType::String),
+    BifSignature::new("sum", Bif::Sum, "sum(list: list<number>) / sum(n1...: number)", FeelType::Number),
+    BifSignature::new("time", Bif::Time, "time(from: string) / time(hour: number, minute: number, second: number, offset?: days and time duration)", FeelType::Time),
+    BifSignature::new("union", Bif::Union, "union(list...: list<Any>)", FeelType::List(Box::new(FeelType::Any))),
+    BifSignature::new("upper case", Bif::UpperCase, "upper case(string: string)", FeelType::String),
+    BifSignature::new("week of year", Bif::WeekOfYear, "week of year(date: date)", FeelType::Number),
+    BifSignature::new("years and months duration", Bif::YearsAndMonthsDuration, "years and months duration(from: date, to: date)", FeelType::YearsAndMonthsDuration),
+  ];
+}
+
+/// Returns signatures of all built-in functions, ordered by name.
+pub fn bif_signatures() -> &'static [BifSignature] {
+  &BIF_SIGNATURES
+}
+
+impl Bif {
+  /// Returns the signature of the built-in function.
+  pub fn signature(&self) -> Option<&'static BifSignature> {
+    BIF_SIGNATURES.iter().find(|signature| signature.bif == *self)
+  }
+
+  /// Returns the type of the value returned by the built-in function.
+  pub fn result_type(&self) -> FeelType {
+    self.signature().map(|signature| signature.result_type.clone()).unwrap_or(FeelType::Any)
+  }
+}
+
 /// Definitions of errors raised in built-in functions module.
 pub mod errors {
   use dmntk_common::DmntkError;
@@ -249,9 +373,25 @@ pub mod errors {
 
 #[cfg(test)]
 mod tests {
-  use super::{is_built_in_date_time_function_name, is_built_in_function_name, Bif};
+  use super::{bif_signatures, is_built_in_date_time_function_name, is_built_in_function_name, Bif};
+  use crate::types::FeelType;
   use std::str::FromStr;
 
+  #[test]
+  fn test_signatures() {
+    assert_eq!(73, bif_signatures().len());
+    for signature in bif_signatures() {
+      assert_eq!(signature.bif, Bif::from_str(signature.name).unwrap());
+      assert_eq!(Some(signature.name), signature.bif.signature().map(|signature| signature.name));
+    }
+    assert_eq!("string length(string: string) -> number", Bif::StringLength.signature().unwrap().to_string());
+    assert_eq!(
+      "get entries(m: context<>) -> list<context<key: string, value: Any>>",
+      Bif::GetEntries.signature().unwrap().to_string()
+    );
+    assert_eq!(FeelType::List(Box::new(FeelType::String)), Bif::Split.result_type());
+  }
+
   #[test]
   fn test_built_in_function_from_string() {
     assert_eq!(Bif::Abs, Bif::from_str("abs").unwrap());
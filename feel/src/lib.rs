@@ -42,6 +42,9 @@ extern crate dmntk_feel_number;
 #[macro_use]
 extern crate lazy_static;
 extern crate regex;
+extern crate serde;
+extern crate serde_derive;
+extern crate serde_json;
 #[macro_use]
 extern crate thiserror;
 
@@ -52,8 +55,10 @@ pub use function::FunctionBody;
 pub use names::Name;
 pub use qualified_names::QualifiedName;
 pub use scope::Scope;
+pub use serialization::Plain;
+pub use span::{AstSpans, Span};
 pub use strings::ToFeelString;
-pub use temporal::date::FeelDate;
+pub use temporal::date::{last_day_of_month, FeelDate};
 pub use temporal::dt_duration::FeelDaysAndTimeDuration;
 pub use temporal::ym_duration::FeelYearsAndMonthsDuration;
 pub use temporal::{subtract, FeelDateTime, FeelTime};
@@ -68,6 +73,8 @@ mod function;
 mod names;
 mod qualified_names;
 mod scope;
+mod serialization;
+mod span;
 mod strings;
 mod temporal;
 mod types;
@@ -34,10 +34,11 @@
 
 use crate::context::FeelContext;
 use crate::values::Value;
-use crate::Name;
+use crate::{Evaluator, Name};
 use dmntk_common::Jsonify;
 use std::cell::RefCell;
 use std::collections::HashSet;
+use std::sync::Arc;
 
 /// Creates a scope.
 #[macro_export]
@@ -47,11 +48,26 @@ macro_rules! scope {
   }};
 }
 
+/// Entry in [Scope], which value is evaluated when accessed for the first time.
+#[derive(Derivative)]
+#[derivative(Debug)]
+struct LazyEntry {
+  /// Index of the context in scope stack, this entry belongs to.
+  level: usize,
+  /// Name of the entry.
+  name: Name,
+  /// Evaluator of the entry's value.
+  #[derivative(Debug = "ignore")]
+  evaluator: Arc<Evaluator>,
+}
+
 /// The `FEEL` scope.
 #[derive(Debug)]
 pub struct Scope {
   /// The stack of contexts.
   contexts: RefCell<Vec<FeelContext>>,
+  /// Entries not evaluated yet.
+  lazy_entries: RefCell<Vec<LazyEntry>>,
 }
 
 impl Default for Scope {
@@ -59,6 +75,7 @@ impl Default for Scope {
   fn default() -> Self {
     Self {
       contexts: RefCell::new(vec![FeelContext::default()]),
+      lazy_entries: RefCell::new(vec![]),
     }
   }
 }
@@ -68,6 +85,7 @@ impl From<FeelContext> for Scope {
   fn from(context: FeelContext) -> Self {
     Self {
       contexts: RefCell::new(vec![context]),
+      lazy_entries: RefCell::new(vec![]),
     }
   }
 }
@@ -98,6 +116,7 @@ impl Scope {
   pub fn new() -> Self {
     Self {
       contexts: RefCell::new(vec![]),
+      lazy_entries: RefCell::new(vec![]),
     }
   }
   /// Pushes a context on the top of the scope stack.
@@ -105,8 +124,12 @@ impl Scope {
     self.contexts.borrow_mut().push(ctx)
   }
   /// Takes and returns a context from the top of the stack.
+  /// Lazy entries belonging to this context are discarded.
   pub fn pop(&self) -> Option<FeelContext> {
-    self.contexts.borrow_mut().pop()
+    let context = self.contexts.borrow_mut().pop();
+    let level = self.contexts.borrow().len();
+    self.lazy_entries.borrow_mut().retain(|entry| entry.level < level);
+    context
   }
   /// Peeks a to context from the top of the stack.
   /// If the stack is empty, the default context is returned.
@@ -116,33 +139,87 @@ impl Scope {
   }
   /// Returns a vector of flattened keys in all contexts in scope.
   pub fn flatten_keys(&self) -> HashSet<String> {
-    self
+    let mut keys = self
       .contexts
       .borrow_mut()
       .iter()
       .flat_map(|ctx| ctx.flatten_keys())
-      .collect::<HashSet<String>>()
+      .collect::<HashSet<String>>();
+    keys.extend(self.lazy_entries.borrow().iter().map(|entry| entry.name.to_string()));
+    keys
   }
   /// Returns a value for an entry specified by name.
   /// Entries are searched from the last to the first context
   /// (from top to bottom of scope stack).
   pub fn get_entry(&self, name: &Name) -> Option<Value> {
-    for context in self.contexts.borrow_mut().iter().rev() {
-      if let Some(value) = context.get_entry(name) {
+    let level_count = self.contexts.borrow().len();
+    for level in (0..level_count).rev() {
+      if let Some(value) = self.contexts.borrow()[level].get_entry(name) {
         return Some(value.clone());
       }
+      if let Some(value) = self.evaluate_lazy_entry(level, name) {
+        return Some(value);
+      }
     }
     None
   }
   ///
   pub fn search_deep(&self, names: &[Name]) -> Option<Value> {
-    for context in self.contexts.borrow_mut().iter().rev() {
-      if let Some(value) = context.search_deep(names) {
+    let level_count = self.contexts.borrow().len();
+    for level in (0..level_count).rev() {
+      if let Some(value) = self.contexts.borrow()[level].search_deep(names) {
         return Some(value.clone());
       }
+      if let Some((name, tail)) = names.split_first() {
+        if let Some(value) = self.evaluate_lazy_entry(level, name) {
+          if tail.is_empty() {
+            return Some(value);
+          }
+          if let Value::Context(ctx) = value {
+            return ctx.search_deep(tail).cloned();
+          }
+        }
+      }
     }
     None
   }
+  /// Sets an entry in [FeelContext] placed on the top of the scope stack (last context),
+  /// the value of this entry is evaluated by specified evaluator when accessed for the first time.
+  ///
+  /// The evaluator takes the scope in state from the moment of setting this entry,
+  /// all contexts pushed later are not visible for the evaluator.
+  /// While evaluating, the entry is not visible for the evaluator,
+  /// after evaluation the value of the entry is stored in the context this entry belongs to.
+  pub fn set_lazy_entry(&self, name: &Name, evaluator: Arc<Evaluator>) {
+    if let Some(level) = self.contexts.borrow().len().checked_sub(1) {
+      self.lazy_entries.borrow_mut().push(LazyEntry {
+        level,
+        name: name.clone(),
+        evaluator,
+      });
+    }
+  }
+  /// Evaluates the lazy entry with specified name, belonging to the context at specified level.
+  fn evaluate_lazy_entry(&self, level: usize, name: &Name) -> Option<Value> {
+    let entry = {
+      let mut lazy_entries = self.lazy_entries.borrow_mut();
+      let index = lazy_entries.iter().position(|entry| entry.level == level && &entry.name == name)?;
+      lazy_entries.remove(index)
+    };
+    // hide all contexts and lazy entries above the level of evaluated entry
+    let upper_contexts = self.contexts.borrow_mut().split_off(level + 1);
+    let (upper_lazy_entries, lazy_entries) = self.lazy_entries.take().into_iter().partition(|entry| entry.level > level);
+    self.lazy_entries.replace(lazy_entries);
+    let value = (entry.evaluator)(self);
+    // restore hidden contexts and lazy entries
+    self.contexts.borrow_mut().extend(upper_contexts);
+    self.lazy_entries.borrow_mut().extend(upper_lazy_entries);
+    // store the evaluated value in the context the entry belongs to
+    if let Some(context) = self.contexts.borrow_mut().get_mut(level) {
+      context.set_entry(name, value.clone());
+    }
+    Some(value)
+  }
   /// Sets a specified value for entry name in [FeelContext] placed on the top of the scope stack (last context).
   pub fn set_entry(&self, name: &Name, value: Value) {
     if let Some(context) = self.contexts.borrow_mut().last_mut() {
@@ -162,6 +239,8 @@ mod tests {
   use crate::context::FeelContext;
   use crate::values::Value;
   use crate::{scope, value_number, FeelNumber, Name, Scope};
+  use std::sync::atomic::{AtomicUsize, Ordering};
+  use std::sync::Arc;
 
   #[test]
   fn test_scope_default() {
@@ -225,4 +304,58 @@ mod tests {
     scope.pop();
     assert_eq!("[]", scope.to_string());
   }
+
+  #[test]
+  fn test_scope_lazy_entry() {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let evaluations = Arc::clone(&counter);
+    let scope = Scope::default();
+    let name_a = Name::from("a");
+    let name_b = Name::from("b");
+    scope.set_entry(&name_a, value_number!(10));
+    scope.set_lazy_entry(
+      &name_b,
+      Arc::new(Box::new(move |scope: &Scope| {
+        evaluations.fetch_add(1, Ordering::Relaxed);
+        match scope.get_entry(&Name::from("a")) {
+          Some(Value::Number(n)) => Value::Number(n + FeelNumber::from(1)),
+          _ => Value::Null(None),
+        }
+      })),
+    );
+    assert_eq!(0, counter.load(Ordering::Relaxed));
+    assert!(scope.flatten_keys().contains("b"));
+    // contexts pushed after setting the lazy entry are not visible while evaluating it
+    scope.push(FeelContext::default());
+    scope.set_entry(&name_a, value_number!(20));
+    assert_eq!("11", scope.get_entry(&name_b).unwrap().to_string());
+    assert_eq!("11", scope.get_entry(&name_b).unwrap().to_string());
+    assert_eq!(1, counter.load(Ordering::Relaxed));
+    assert_eq!("[{a: 10, b: 11}, {a: 20}]", scope.to_string());
+  }
+
+  #[test]
+  fn test_scope_lazy_entry_self_reference() {
+    let scope = Scope::default();
+    let name_a = Name::from("a");
+    scope.set_lazy_entry(
+      &name_a,
+      Arc::new(Box::new(move |scope: &Scope| match scope.get_entry(&Name::from("a")) {
+        Some(value) => value,
+        None => Value::Null(Some("no value".to_string())),
+      })),
+    );
+    assert_eq!("null(no value)", scope.get_entry(&name_a).unwrap().to_string());
+  }
+
+  #[test]
+  fn test_scope_lazy_entry_discarded() {
+    let scope = Scope::default();
+    let name_a = Name::from("a");
+    scope.push(FeelContext::default());
+    scope.set_lazy_entry(&name_a, Arc::new(Box::new(move |_: &Scope| Value::Boolean(true))));
+    scope.pop();
+    assert!(scope.get_entry(&name_a).is_none());
+    assert!(!scope.flatten_keys().contains("a"));
+  }
 }
@@ -0,0 +1,645 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Serialization and deserialization of `FEEL` values and contexts.
+//!
+//! [Value] and [FeelContext] are serialized in a lossless tagged form,
+//! where every value is an object with the name of its type and its content:
+//!
+//! ```json
+//! {"type": "context", "value": {
+//!   "Name": {"type": "string", "value": "John"},
+//!   "Age": {"type": "number", "value": "42"},
+//!   "Birthday": {"type": "date", "value": "1980-03-14"},
+//!   "Grades": {"type": "range", "value": {"start": {"type": "number", "value": "1"}, "closedStart": true, "end": {"type": "number", "value": "5"}, "closedEnd": false}}
+//! }}
+//! ```
+//!
+//! Numbers are stored as text to keep all significant digits, temporal values
+//! and durations are stored as their `FEEL` literals. The later occurrence of an ambiguous
+//! local time in named time zone is stored with explicit offset, like `2021-10-31T02:30:00+01:00@Europe/Warsaw`. Values wrapped in [Plain]
+//! are serialized in a plain `JSON` form, convenient for exchanging data
+//! with other tools, but not preserving the types of values.
+//! Plain `JSON` text with numbers having all significant digits is read and written by [Plain::from_json] and [Plain::to_json].
+
+use self::errors::*;
+use crate::context::FeelContext;
+use crate::names::Name;
+use crate::values::{Value, Values};
+use crate::{FeelDate, FeelDateTime, FeelDaysAndTimeDuration, FeelNumber, FeelTime, FeelYearsAndMonthsDuration};
+use dmntk_common::Result as DmntkResult;
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, SerializeMap, SerializeSeq, Serializer};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// Tagged representation of a [Value], used as an intermediary form during (de)serialization.
+#[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+enum TaggedValue {
+  Null(Option<String>),
+  Boolean(bool),
+  Number(String),
+  String(String),
+  Date(String),
+  DateAndTime(String),
+  Time(String),
+  DaysAndTimeDuration(String),
+  YearsAndMonthsDuration(String),
+  List(Vec<TaggedValue>),
+  Context(BTreeMap<String, TaggedValue>),
+  #[serde(rename_all = "camelCase")]
+  Range {
+    start: Box<TaggedValue>,
+    closed_start: bool,
+    end: Box<TaggedValue>,
+    closed_end: bool,
+  },
+}
+
+impl TryFrom<&Value> for TaggedValue {
+  type Error = String;
+  /// Tries to convert a [Value] into its tagged representation.
+  fn try_from(value: &Value) -> Result<Self, Self::Error> {
+    Ok(match value {
+      Value::Null(trace) => TaggedValue::Null(trace.clone()),
+      Value::Boolean(b) => TaggedValue::Boolean(*b),
+      Value::Number(n) => TaggedValue::Number(n.to_string()),
+      Value::String(s) => TaggedValue::String(s.clone()),
+      Value::Date(date) => TaggedValue::Date(date.to_string()),
+      Value::DateTime(date_time) => TaggedValue::DateAndTime(date_time.to_extended_string()),
+      Value::Time(time) => TaggedValue::Time(time.to_extended_string()),
+      Value::DaysAndTimeDuration(dt_duration) => TaggedValue::DaysAndTimeDuration(dt_duration.to_string()),
+      Value::YearsAndMonthsDuration(ym_duration) => TaggedValue::YearsAndMonthsDuration(ym_duration.to_string()),
+      Value::List(items) => TaggedValue::List(items.as_vec().iter().map(TaggedValue::try_from).collect::<Result<_, _>>()?),
+      Value::Context(ctx) => TaggedValue::Context(tagged_entries(ctx)?),
+      Value::Range(start, closed_start, end, closed_end) => TaggedValue::Range {
+        start: Box::new(TaggedValue::try_from(start.as_ref())?),
+        closed_start: *closed_start,
+        end: Box::new(TaggedValue::try_from(end.as_ref())?),
+        closed_end: *closed_end,
+      },
+      other => return Err(format!("value of type '{}' can not be serialized: {}", other.type_of(), other)),
+    })
+  }
+}
+
+impl TryFrom<TaggedValue> for Value {
+  type Error = String;
+  /// Tries to convert a tagged representation into [Value].
+  fn try_from(value: TaggedValue) -> Result<Self, Self::Error> {
+    Ok(match value {
+      TaggedValue::Null(trace) => Value::Null(trace),
+      TaggedValue::Boolean(b) => Value::Boolean(b),
+      TaggedValue::Number(text) => Value::Number(FeelNumber::from_str(&text).map_err(|_| format!("invalid number: {}", text))?),
+      TaggedValue::String(s) => Value::String(s),
+      TaggedValue::Date(text) => Value::Date(FeelDate::parse_extended(&text).map_err(|e| e.to_string())?),
+      TaggedValue::DateAndTime(text) => Value::DateTime(FeelDateTime::parse_extended(&text).map_err(|e| e.to_string())?),
+      TaggedValue::Time(text) => Value::Time(FeelTime::parse_extended(&text).map_err(|e| e.to_string())?),
+      TaggedValue::DaysAndTimeDuration(text) => Value::DaysAndTimeDuration(FeelDaysAndTimeDuration::try_from(text.as_str()).map_err(|e| e.to_string())?),
+      TaggedValue::YearsAndMonthsDuration(text) => {
+        Value::YearsAndMonthsDuration(FeelYearsAndMonthsDuration::try_from(text.as_str()).map_err(|e| e.to_string())?)
+      }
+      TaggedValue::List(items) => Value::List(Values::new(items.into_iter().map(Value::try_from).collect::<Result<_, _>>()?)),
+      TaggedValue::Context(entries) => Value::Context(untagged_entries(entries)?),
+      TaggedValue::Range {
+        start,
+        closed_start,
+        end,
+        closed_end,
+      } => Value::Range(Box::new(Value::try_from(*start)?), closed_start, Box::new(Value::try_from(*end)?), closed_end),
+    })
+  }
+}
+
+/// Converts entries of a [FeelContext] into tagged representation.
+fn tagged_entries(ctx: &FeelContext) -> Result<BTreeMap<String, TaggedValue>, String> {
+  ctx.iter().map(|(name, value)| Ok((name.to_string(), TaggedValue::try_from(value)?))).collect()
+}
+
+/// Converts tagged entries into [FeelContext].
+fn untagged_entries(entries: BTreeMap<String, TaggedValue>) -> Result<FeelContext, String> {
+  let mut ctx = FeelContext::default();
+  for (name, value) in entries {
+    ctx.set_entry(&Name::from(name), Value::try_from(value)?);
+  }
+  Ok(ctx)
+}
+
+impl Serialize for Value {
+  /// Serializes [Value] in lossless tagged form.
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    TaggedValue::try_from(self).map_err(ser::Error::custom)?.serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for Value {
+  /// Deserializes [Value] from lossless tagged form.
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    Value::try_from(TaggedValue::deserialize(deserializer)?).map_err(de::Error::custom)
+  }
+}
+
+impl Serialize for FeelContext {
+  /// Serializes [FeelContext] as a map of entry names and values in lossless tagged form.
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    tagged_entries(self).map_err(ser::Error::custom)?.serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for FeelContext {
+  /// Deserializes [FeelContext] from a map of entry names and values in lossless tagged form.
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    untagged_entries(BTreeMap::deserialize(deserializer)?).map_err(de::Error::custom)
+  }
+}
+
+/// Wrapper for serializing values and contexts in plain `JSON` form.
+///
+/// Nulls, booleans, strings, lists and contexts are serialized as their `JSON` counterparts,
+/// all other values, except numbers, are serialized as strings containing their `FEEL` representation.
+/// Deserialized strings always become `FEEL` strings.
+///
+/// Serialization is independent of the data format, so numbers are serialized as integers when they fit
+/// in `i64`, otherwise as the nearest `f64`. To read and write numbers with all significant digits
+/// in `JSON` text, use [Plain::from_json] and [Plain::to_json].
+///
+/// ```
+/// use dmntk_feel::values::Value;
+/// use dmntk_feel::Plain;
+///
+/// let Plain(value): Plain<Value> = serde_json::from_str(r#"{"a": [1, 2.5, "x"]}"#).unwrap();
+/// assert_eq!(r#"{a: [1, 2.5, "x"]}"#, value.to_string());
+/// assert_eq!(r#"{"a":[1,2.5,"x"]}"#, serde_json::to_string(&Plain(&value)).unwrap());
+///
+/// let Plain(value) = Plain::<Value>::from_json(r#"{"a": 0.1234567890123456789}"#).unwrap();
+/// assert_eq!(r#"{a: 0.1234567890123456789}"#, value.to_string());
+/// assert_eq!(r#"{"a":0.1234567890123456789}"#, Plain(&value).to_json());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plain<T>(pub T);
+
+impl Plain<Value> {
+  /// Parses a value from plain `JSON` text, numbers are parsed with all significant digits.
+  pub fn from_json(json: &str) -> DmntkResult<Self> {
+    let raw = serde_json::from_str::<&RawValue>(json).map_err(|reason| err_invalid_json(&reason.to_string()))?;
+    value_from_raw(raw).map(Plain)
+  }
+}
+
+impl Plain<FeelContext> {
+  /// Parses a context from plain `JSON` text, numbers are parsed with all significant digits.
+  pub fn from_json(json: &str) -> DmntkResult<Self> {
+    match Plain::<Value>::from_json(json)? {
+      Plain(Value::Context(ctx)) => Ok(Plain(ctx)),
+      Plain(other) => Err(err_invalid_json(&format!("expected context, actual value is {}", other))),
+    }
+  }
+}
+
+impl Plain<&Value> {
+  /// Writes the value as plain `JSON` text, numbers are written with all significant digits.
+  pub fn to_json(&self) -> String {
+    let mut json = String::new();
+    write_json(self.0, &mut json);
+    json
+  }
+}
+
+impl Plain<&FeelContext> {
+  /// Writes the context as plain `JSON` text, numbers are written with all significant digits.
+  pub fn to_json(&self) -> String {
+    let mut json = String::new();
+    write_json_context(self.0, &mut json);
+    json
+  }
+}
+
+/// Builds a [Value] from raw `JSON` value, numbers are taken from the text of raw value.
+fn value_from_raw(raw: &RawValue) -> DmntkResult<Value> {
+  let text = raw.get();
+  let invalid_json = |reason: serde_json::Error| err_invalid_json(&reason.to_string());
+  match text.chars().next() {
+    Some('{') => {
+      let mut ctx = FeelContext::default();
+      for (name, raw_value) in serde_json::from_str::<BTreeMap<String, &RawValue>>(text).map_err(invalid_json)? {
+        ctx.set_entry(&Name::from(name), value_from_raw(raw_value)?);
+      }
+      Ok(Value::Context(ctx))
+    }
+    Some('[') => {
+      let items = serde_json::from_str::<Vec<&RawValue>>(text).map_err(invalid_json)?;
+      Ok(Value::List(Values::new(items.into_iter().map(value_from_raw).collect::<DmntkResult<_>>()?)))
+    }
+    Some('"') => Ok(Value::String(serde_json::from_str::<String>(text).map_err(invalid_json)?)),
+    Some('t') | Some('f') => Ok(Value::Boolean(serde_json::from_str::<bool>(text).map_err(invalid_json)?)),
+    Some('n') => Ok(Value::Null(None)),
+    _ => FeelNumber::from_str(text)
+      .map(Value::Number)
+      .map_err(|_| err_invalid_json(&format!("invalid number: {}", text))),
+  }
+}
+
+/// Writes a [Value] as plain `JSON` text.
+fn write_json(value: &Value, json: &mut String) {
+  match value {
+    Value::Null(_) => json.push_str("null"),
+    Value::Boolean(b) => json.push_str(if *b { "true" } else { "false" }),
+    Value::Number(n) => json.push_str(&n.to_string()),
+    Value::String(s) => write_json_string(s, json),
+    Value::List(items) => {
+      json.push('[');
+      for (index, item) in items.as_vec().iter().enumerate() {
+        if index > 0 {
+          json.push(',');
+        }
+        write_json(item, json);
+      }
+      json.push(']');
+    }
+    Value::Context(ctx) => write_json_context(ctx, json),
+    other => write_json_string(&other.to_string(), json),
+  }
+}
+
+/// Writes a [FeelContext] as plain `JSON` object.
+fn write_json_context(ctx: &FeelContext, json: &mut String) {
+  json.push('{');
+  for (index, (name, value)) in ctx.iter().enumerate() {
+    if index > 0 {
+      json.push(',');
+    }
+    write_json_string(&name.to_string(), json);
+    json.push(':');
+    write_json(value, json);
+  }
+  json.push('}');
+}
+
+/// Writes a string as `JSON` string literal.
+fn write_json_string(s: &str, json: &mut String) {
+  // serializing a string into JSON never fails
+  json.push_str(&serde_json::to_string(s).unwrap_or_default());
+}
+
+impl Serialize for Plain<&Value> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    match self.0 {
+      Value::Null(_) => serializer.serialize_unit(),
+      Value::Boolean(b) => serializer.serialize_bool(*b),
+      Value::Number(n) => {
+        let text = n.to_string();
+        if let Ok(i) = text.parse::<i64>() {
+          serializer.serialize_i64(i)
+        } else {
+          serializer.serialize_f64(text.parse::<f64>().map_err(ser::Error::custom)?)
+        }
+      }
+      Value::String(s) => serializer.serialize_str(s),
+      Value::List(items) => {
+        let mut seq = serializer.serialize_seq(Some(items.len()))?;
+        for item in items.as_vec() {
+          seq.serialize_element(&Plain(item))?;
+        }
+        seq.end()
+      }
+      Value::Context(ctx) => Plain(ctx).serialize(serializer),
+      other => serializer.serialize_str(&other.to_string()),
+    }
+  }
+}
+
+impl Serialize for Plain<Value> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    Plain(&self.0).serialize(serializer)
+  }
+}
+
+impl Serialize for Plain<&FeelContext> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut map = serializer.serialize_map(Some(self.0.len()))?;
+    for (name, value) in self.0.iter() {
+      map.serialize_entry(&name.to_string(), &Plain(value))?;
+    }
+    map.end()
+  }
+}
+
+impl Serialize for Plain<FeelContext> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    Plain(&self.0).serialize(serializer)
+  }
+}
+
+impl<'de> Deserialize<'de> for Plain<Value> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_any(PlainValueVisitor).map(Plain)
+  }
+}
+
+impl<'de> Deserialize<'de> for Plain<FeelContext> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    match deserializer.deserialize_any(PlainValueVisitor)? {
+      Value::Context(ctx) => Ok(Plain(ctx)),
+      other => Err(de::Error::custom(format!("expected context, actual value is {}", other))),
+    }
+  }
+}
+
+/// Visitor building [Value] from plain `JSON` form.
+struct PlainValueVisitor;
+
+impl<'de> Visitor<'de> for PlainValueVisitor {
+  type Value = Value;
+
+  fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    formatter.write_str("null, boolean, number, string, array or object")
+  }
+
+  fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+    Ok(Value::Null(None))
+  }
+
+  fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+    Ok(Value::Null(None))
+  }
+
+  fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+    deserializer.deserialize_any(self)
+  }
+
+  fn visit_bool<E: de::Error>(self, b: bool) -> Result<Value, E> {
+    Ok(Value::Boolean(b))
+  }
+
+  fn visit_i64<E: de::Error>(self, n: i64) -> Result<Value, E> {
+    Ok(Value::Number(n.into()))
+  }
+
+  fn visit_u64<E: de::Error>(self, n: u64) -> Result<Value, E> {
+    Ok(Value::Number(FeelNumber::from_i128(n as i128)))
+  }
+
+  fn visit_f64<E: de::Error>(self, n: f64) -> Result<Value, E> {
+    if n.is_finite() {
+      Ok(Value::Number(FeelNumber::from_string(&n.to_string())))
+    } else {
+      Ok(Value::Null(None))
+    }
+  }
+
+  fn visit_str<E: de::Error>(self, s: &str) -> Result<Value, E> {
+    Ok(Value::String(s.to_string()))
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+    let mut items = vec![];
+    while let Some(Plain(item)) = seq.next_element::<Plain<Value>>()? {
+      items.push(item);
+    }
+    Ok(Value::List(Values::new(items)))
+  }
+
+  fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+    let mut ctx = FeelContext::default();
+    while let Some(name) = map.next_key::<String>()? {
+      let Plain(value) = map.next_value::<Plain<Value>>()?;
+      ctx.set_entry(&Name::from(name), value);
+    }
+    Ok(Value::Context(ctx))
+  }
+}
+
+/// Definitions of serialization errors.
+pub mod errors {
+  use dmntk_common::DmntkError;
+
+  /// Serialization errors.
+  #[derive(Debug, PartialEq)]
+  enum SerializationError {
+    /// Used when parsed text is not a valid plain `JSON`.
+    InvalidJson(String),
+  }
+
+  impl From<SerializationError> for DmntkError {
+    fn from(e: SerializationError) -> Self {
+      DmntkError::new("SerializationError", &format!("{}", e))
+    }
+  }
+
+  impl std::fmt::Display for SerializationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+      match self {
+        SerializationError::InvalidJson(reason) => {
+          write!(f, "invalid JSON: {}", reason)
+        }
+      }
+    }
+  }
+
+  pub fn err_invalid_json(reason: &str) -> DmntkError {
+    SerializationError::InvalidJson(reason.to_string()).into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::context::FeelContext;
+  use crate::values::{Value, Values};
+  use crate::{value_number, FeelDate, FeelDateTime, FeelDaysAndTimeDuration, FeelNumber, Name, Plain};
+
+  /// Serializes value in tagged form, deserializes it back and checks if both values are equal.
+  fn round_trip(value: &Value) -> String {
+    let json = serde_json::to_string(value).unwrap();
+    let actual: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value.to_string(), actual.to_string());
+    assert_eq!(value.type_of(), actual.type_of());
+    json
+  }
+
+  /// Converts a temporal literal into value.
+  fn feel(text: &str) -> Value {
+    Value::try_from_xsd_date_time(text)
+      .or_else(|_| Value::try_from_xsd_date(text))
+      .or_else(|_| Value::try_from_xsd_time(text))
+      .or_else(|_| Value::try_from_xsd_duration(text))
+      .unwrap()
+  }
+
+  #[test]
+  fn test_tagged_simple_values() {
+    assert_eq!(r#"{"type":"null","value":null}"#, round_trip(&Value::Null(None)));
+    assert_eq!(r#"{"type":"null","value":"trace"}"#, round_trip(&Value::Null(Some("trace".to_string()))));
+    assert!(serde_json::from_str::<Value>(r#"{"type":"null"}"#).unwrap().is_null());
+    assert_eq!(r#"{"type":"boolean","value":true}"#, round_trip(&Value::Boolean(true)));
+    assert_eq!(
+      r#"{"type":"number","value":"12.3456789012345678901234567890123"}"#,
+      round_trip(&Value::Number(FeelNumber::from_string("12.3456789012345678901234567890123")))
+    );
+    assert_eq!(r#"{"type":"string","value":"a \"b\""}"#, round_trip(&Value::String(r#"a "b""#.to_string())));
+  }
+
+  #[test]
+  fn test_tagged_temporal_values() {
+    assert_eq!(r#"{"type":"date","value":"2022-03-14"}"#, round_trip(&feel("2022-03-14")));
+    assert_eq!(
+      r#"{"type":"dateAndTime","value":"2022-03-14T10:30:15.25@Europe/Warsaw"}"#,
+      round_trip(&feel("2022-03-14T10:30:15.25@Europe/Warsaw"))
+    );
+    assert_eq!(
+      r#"{"type":"dateAndTime","value":"2022-03-14T10:30:15-05:00"}"#,
+      round_trip(&feel("2022-03-14T10:30:15-05:00"))
+    );
+    assert_eq!(r#"{"type":"time","value":"10:30:15Z"}"#, round_trip(&feel("10:30:15Z")));
+    assert_eq!(r#"{"type":"daysAndTimeDuration","value":"-P1DT2H"}"#, round_trip(&feel("-P1DT2H")));
+    assert_eq!(r#"{"type":"yearsAndMonthsDuration","value":"P1Y2M"}"#, round_trip(&feel("P1Y2M")));
+  }
+
+  #[test]
+  fn test_tagged_ambiguous_date_time() {
+    // both occurrences of the local time repeated when clocks are set back are different instants
+    let hour = FeelDaysAndTimeDuration::default().second(3_600).build();
+    let start = FeelDateTime::try_from("2021-10-31T01:30:00@Europe/Warsaw").unwrap();
+    let first = Value::DateTime((&start + &hour).unwrap());
+    let second = Value::DateTime((&(&start + &hour).unwrap() + &hour).unwrap());
+    assert_eq!(r#"{"type":"dateAndTime","value":"2021-10-31T02:30:00@Europe/Warsaw"}"#, round_trip(&first));
+    assert_eq!(
+      r#"{"type":"dateAndTime","value":"2021-10-31T02:30:00+01:00@Europe/Warsaw"}"#,
+      round_trip(&second)
+    );
+    let first_parsed: Value = serde_json::from_str(&serde_json::to_string(&first).unwrap()).unwrap();
+    let second_parsed: Value = serde_json::from_str(&serde_json::to_string(&second).unwrap()).unwrap();
+    assert_eq!(first, first_parsed);
+    assert_eq!(second, second_parsed);
+    assert_ne!(first_parsed, second_parsed);
+  }
+
+  #[test]
+  fn test_tagged_composite_values() {
+    let range = Value::Range(Box::new(value_number!(1)), true, Box::new(value_number!(5)), false);
+    assert_eq!(
+      r#"{"type":"range","value":{"start":{"type":"number","value":"1"},"closedStart":true,"end":{"type":"number","value":"5"},"closedEnd":false}}"#,
+      round_trip(&range)
+    );
+    let mut ctx = FeelContext::default();
+    ctx.set_entry(&Name::from("Full Name"), Value::String("John".to_string()));
+    ctx.set_entry(&Name::from("Grades"), range);
+    ctx.set_entry(&Name::from("Items"), Value::List(Values::new(vec![feel("P1D"), Value::Null(None)])));
+    round_trip(&Value::Context(ctx.clone()));
+    let json = serde_json::to_string(&ctx).unwrap();
+    assert!(json.starts_with(r#"{"Full Name":{"type":"string","value":"John"},"#));
+    assert_eq!(ctx, serde_json::from_str::<FeelContext>(&json).unwrap());
+  }
+
+  #[test]
+  fn test_tagged_errors() {
+    assert!(serde_json::to_string(&Value::Irrelevant).is_err());
+    assert!(serde_json::from_str::<Value>(r#"{"type":"number","value":"abc"}"#).is_err());
+    assert!(serde_json::from_str::<Value>(r#"{"type":"date","value":"2022-02-30"}"#).is_err());
+    assert!(serde_json::from_str::<Value>(r#"{"type":"unknown","value":1}"#).is_err());
+  }
+
+  #[test]
+  fn test_plain() {
+    let Plain(value): Plain<Value> = serde_json::from_str(r#"{"a": [1, -2.5, "x", null, true], "b": {"c": 18446744073709551615}}"#).unwrap();
+    assert_eq!(r#"{a: [1, -2.5, "x", null, true], b: {c: 18446744073709551615}}"#, value.to_string());
+    let mut ctx = FeelContext::try_from(value).unwrap();
+    ctx.set_entry(&Name::from("d"), feel("2022-03-14"));
+    ctx.set_entry(&Name::from("e"), Value::Number(FeelNumber::from_string("0.1")));
+    // numbers not fitting in i64 are serialized as f64
+    assert_eq!(
+      r#"{"a":[1,-2.5,"x",null,true],"b":{"c":1.8446744073709552e+19},"d":"2022-03-14","e":0.1}"#,
+      serde_json::to_string(&Plain(&ctx)).unwrap()
+    );
+    assert!(serde_json::from_str::<Plain<FeelContext>>("[1]").is_err());
+  }
+
+  #[test]
+  fn test_plain_other_formats() {
+    // serialization of plain values does not depend on JSON
+    let value = Value::List(Values::new(vec![value_number!(1), Value::Number(FeelNumber::from_string("0.1"))]));
+    assert_eq!(
+      vec![serde_json::json!(1), serde_json::json!(0.1)],
+      serde_json::to_value(Plain(&value)).unwrap().as_array().unwrap().clone()
+    );
+  }
+
+  #[test]
+  fn test_plain_exact_numbers() {
+    let json = r#" [12.3456789012345678901234567890123, -98765432109876543210.5, 1e-30, {"a b": "x\"y", "c": [true, null]}] "#;
+    let Plain(value) = Plain::<Value>::from_json(json).unwrap();
+    assert_eq!(
+      r#"[12.3456789012345678901234567890123, -98765432109876543210.5, 0.000000000000000000000000000001, {a b: "x"y", c: [true, null]}]"#,
+      value.to_string()
+    );
+    assert_eq!(
+      r#"[12.3456789012345678901234567890123,-98765432109876543210.5,0.000000000000000000000000000001,{"a b":"x\"y","c":[true,null]}]"#,
+      Plain(&value).to_json()
+    );
+    let Plain(ctx) = Plain::<FeelContext>::from_json(r#"{"d": 0.1}"#).unwrap();
+    assert_eq!(r#"{"d":0.1,"e":"2022-03-14"}"#, {
+      let mut ctx = ctx;
+      ctx.set_entry(&Name::from("e"), feel("2022-03-14"));
+      Plain(&ctx).to_json()
+    });
+    assert_eq!(
+      "SerializationError: invalid JSON: expected context, actual value is [1]",
+      Plain::<FeelContext>::from_json("[1]").unwrap_err().to_string()
+    );
+    assert!(Plain::<Value>::from_json("[1,").is_err());
+  }
+
+  #[test]
+  fn test_tagged_exact_temporal_values() {
+    // fractional seconds are kept with all nanoseconds
+    assert_eq!(
+      r#"{"type":"dateAndTime","value":"2022-03-14T10:30:15.000015839+01:00"}"#,
+      round_trip(&feel("2022-03-14T10:30:15.000015839+01:00"))
+    );
+    assert_eq!(r#"{"type":"time","value":"10:30:15.001013633"}"#, round_trip(&feel("10:30:15.001013633")));
+    assert_eq!(
+      r#"{"type":"daysAndTimeDuration","value":"PT1.000126705S"}"#,
+      round_trip(&feel("PT1.000126705S"))
+    );
+    // years below 1000 are formatted with leading zeros
+    let year_999 = Value::DateTime(FeelDateTime::local(999, 12, 31, 23, 59, 59, 0));
+    assert_eq!(r#"{"type":"dateAndTime","value":"0999-12-31T23:59:59"}"#, round_trip(&year_999));
+    assert_eq!(r#"{"type":"date","value":"0001-01-01"}"#, round_trip(&Value::Date(FeelDate::new(1, 1, 1))));
+    // zero offset is not converted to UTC
+    let zero_offset = Value::DateTime(FeelDateTime::offset((2022, 3, 14), (10, 30, 15, 0), 0));
+    assert_eq!(r#"{"type":"dateAndTime","value":"2022-03-14T10:30:15+00:00"}"#, round_trip(&zero_offset));
+  }
+}
@@ -0,0 +1,147 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Locations of `FEEL` expressions in source text.
+
+use crate::AstNode;
+
+/// Span of the source text, `start` and `end` are character offsets, `end` is exclusive.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Span {
+  /// Offset of the first character.
+  pub start: usize,
+  /// Offset of the character after the last one.
+  pub end: usize,
+}
+
+impl Span {
+  /// Creates a new span.
+  pub fn new(start: usize, end: usize) -> Self {
+    Self { start, end }
+  }
+  /// Returns the line and column (both starting from 1) of the beginning of this span in specified source text.
+  pub fn line_column(&self, source: &str) -> (usize, usize) {
+    let mut line = 1;
+    let mut column = 1;
+    for ch in source.chars().take(self.start) {
+      if ch == '\n' {
+        line += 1;
+        column = 1;
+      } else {
+        column += 1;
+      }
+    }
+    (line, column)
+  }
+  /// Returns the fragment of specified source text covered by this span.
+  pub fn text(&self, source: &str) -> String {
+    source.chars().skip(self.start).take(self.end.saturating_sub(self.start)).collect()
+  }
+}
+
+/// Spans of the parsed node and all its descendants.
+///
+/// Spans form a tree having the same shape as the node, the children of the spans
+/// are the spans of the child nodes, in the order returned by [AstNode::children].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AstSpans {
+  /// Span of the node.
+  span: Span,
+  /// Spans of the child nodes.
+  children: Vec<AstSpans>,
+}
+
+impl AstSpans {
+  /// Creates spans of the node having specified span and spans of child nodes.
+  pub fn new(span: Span, children: Vec<AstSpans>) -> Self {
+    Self { span, children }
+  }
+  /// Returns the span of the node.
+  pub fn span(&self) -> Span {
+    self.span
+  }
+  /// Returns the spans of the child nodes.
+  pub fn children(&self) -> &[AstSpans] {
+    &self.children
+  }
+  /// Returns the spans of the child nodes, consuming these spans.
+  pub fn into_children(self) -> Vec<AstSpans> {
+    self.children
+  }
+  /// Returns these spans with the span of the node replaced.
+  pub fn with_span(self, span: Span) -> Self {
+    Self { span, ..self }
+  }
+  /// Visits specified node and all its descendants in pre-order, together with their spans.
+  /// Nodes not having the corresponding spans are not visited.
+  pub fn visit<'a>(&self, node: &'a AstNode, f: &mut impl FnMut(&'a AstNode, Span)) {
+    f(node, self.span);
+    for (child, child_spans) in node.children().into_iter().zip(self.children.iter()) {
+      child_spans.visit(child, f);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_line_column() {
+    let source = "{\n  a: 1,\n  b: a.c\n}";
+    assert_eq!((1, 1), Span::new(0, 1).line_column(source));
+    assert_eq!((2, 3), Span::new(4, 5).line_column(source));
+    assert_eq!((3, 6), Span::new(15, 18).line_column(source));
+    assert_eq!("a.c", Span::new(15, 18).text(source));
+  }
+
+  #[test]
+  fn test_ast_spans() {
+    let node = AstNode::Path(Box::new(AstNode::Name("a".into())), Box::new(AstNode::Name("a".into())));
+    let spans = AstSpans::new(
+      Span::new(0, 3),
+      vec![AstSpans::new(Span::new(0, 1), vec![]), AstSpans::new(Span::new(2, 3), vec![])],
+    );
+    assert_eq!(Span::new(0, 3), spans.span());
+    assert_eq!(2, spans.children().len());
+    let mut visited = vec![];
+    spans.visit(&node, &mut |node, span| visited.push((node.clone(), span)));
+    assert_eq!(
+      vec![
+        (node.clone(), Span::new(0, 3)),
+        (AstNode::Name("a".into()), Span::new(0, 1)),
+        (AstNode::Name("a".into()), Span::new(2, 3))
+      ],
+      visited
+    );
+  }
+}
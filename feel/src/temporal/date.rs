@@ -44,6 +44,7 @@ use std::convert::{TryFrom, TryInto};
 
 lazy_static! {
   static ref RE_DATE: Regex = Regex::new(format!("^{}$", super::DATE_PATTERN).as_str()).unwrap();
+  static ref RE_DATE_EXTENDED: Regex = Regex::new(format!("^{}$", super::EXTENDED_DATE_PATTERN).as_str()).unwrap();
 }
 
 /// FEEL date.
@@ -60,19 +61,25 @@ impl TryFrom<&str> for FeelDate {
   type Error = DmntkError;
   /// Converts string into [FeelDate].
   fn try_from(value: &str) -> Result<Self, Self::Error> {
-    if let Some(captures) = RE_DATE.captures(value) {
-      if let Some(year_match) = captures.name("year") {
-        if let Ok(mut year) = year_match.as_str().parse::<i32>() {
-          if captures.name("sign").is_some() {
-            year = -year;
-          }
-          if let Some(month_match) = captures.name("month") {
-            if let Ok(month) = month_match.as_str().parse::<u8>() {
-              if let Some(day_match) = captures.name("day") {
-                if let Ok(day) = day_match.as_str().parse::<u8>() {
-                  if is_valid_date(year, month, day) {
-                    return Ok(FeelDate(year, month, day));
-                  }
+    parse_date_literal(value, false)
+  }
+}
+
+/// Parses date literal, the year with leading zeros is accepted only in extended form.
+fn parse_date_literal(value: &str, extended: bool) -> Result<FeelDate, DmntkError> {
+  let re_date: &Regex = if extended { &RE_DATE_EXTENDED } else { &RE_DATE };
+  if let Some(captures) = re_date.captures(value) {
+    if let Some(year_match) = captures.name("year") {
+      if let Ok(mut year) = year_match.as_str().parse::<i32>() {
+        if captures.name("sign").is_some() {
+          year = -year;
+        }
+        if let Some(month_match) = captures.name("month") {
+          if let Ok(month) = month_match.as_str().parse::<u8>() {
+            if let Some(day_match) = captures.name("day") {
+              if let Ok(day) = day_match.as_str().parse::<u8>() {
+                if is_valid_date(year, month, day) {
+                  return Ok(FeelDate(year, month, day));
                 }
               }
             }
@@ -80,8 +87,8 @@ impl TryFrom<&str> for FeelDate {
         }
       }
     }
-    Err(invalid_date_literal(value.to_string()))
   }
+  Err(invalid_date_literal(value.to_string()))
 }
 
 impl TryFrom<(FeelNumber, FeelNumber, FeelNumber)> for FeelDate {
@@ -141,6 +148,12 @@ impl FeelDate {
   pub fn new(year: i32, month: u8, day: u8) -> Self {
     Self(year, month, day)
   }
+
+  /// Parses date like `FEEL` literal, accepting also the extended form with year having leading zeros
+  /// (like `0999-12-31`), produced when formatting dates with years below 1000.
+  pub fn parse_extended(text: &str) -> Result<Self, DmntkError> {
+    parse_date_literal(text, true)
+  }
   ///
   pub fn new_opt(year: i32, month: u8, day: u8) -> Option<Self> {
     if is_valid_date(year, month, day) {
@@ -227,6 +240,28 @@ impl FeelDate {
   pub fn as_tuple(&self) -> (i32, u32, u32) {
     (self.0, self.1 as u32, self.2 as u32)
   }
+  /// Returns the date of the next day, or `None` when the next day is out of the valid date range.
+  pub fn next_day(&self) -> Option<Self> {
+    let (year, month, day) = if self.2 < last_day_of_month(self.0, self.1)? {
+      (self.0, self.1, self.2 + 1)
+    } else if self.1 < 12 {
+      (self.0, self.1 + 1, 1)
+    } else {
+      (self.0.checked_add(1)?, 1, 1)
+    };
+    Self::new_opt(year, month, day)
+  }
+  /// Returns the date of the previous day, or `None` when the previous day is out of the valid date range.
+  pub fn previous_day(&self) -> Option<Self> {
+    let (year, month, day) = if self.2 > 1 {
+      (self.0, self.1, self.2 - 1)
+    } else if self.1 > 1 {
+      (self.0, self.1 - 1, last_day_of_month(self.0, self.1 - 1)?)
+    } else {
+      (self.0.checked_sub(1)?, 12, 31)
+    };
+    Self::new_opt(year, month, day)
+  }
 }
 
 ///
@@ -298,7 +333,7 @@ pub mod errors {
 
 #[cfg(test)]
 mod tests {
-  use super::{is_leap_year, is_valid_date, last_day_of_month};
+  use super::{is_leap_year, is_valid_date, last_day_of_month, FeelDate};
 
   #[test]
   fn test_is_valid_date() {
@@ -356,4 +391,18 @@ mod tests {
     assert_eq!(None, last_day_of_month(2020, 13));
     assert_eq!(None, last_day_of_month(2020, 0));
   }
+
+  #[test]
+  fn test_next_and_previous_day() {
+    assert_eq!(Some(FeelDate::new(2021, 2, 1)), FeelDate::new(2021, 1, 31).next_day());
+    assert_eq!(Some(FeelDate::new(2020, 2, 29)), FeelDate::new(2020, 2, 28).next_day());
+    assert_eq!(Some(FeelDate::new(2021, 3, 1)), FeelDate::new(2021, 2, 28).next_day());
+    assert_eq!(Some(FeelDate::new(2022, 1, 1)), FeelDate::new(2021, 12, 31).next_day());
+    assert_eq!(None, FeelDate::new(999_999_999, 12, 31).next_day());
+    assert_eq!(Some(FeelDate::new(2021, 1, 30)), FeelDate::new(2021, 1, 31).previous_day());
+    assert_eq!(Some(FeelDate::new(2020, 2, 29)), FeelDate::new(2020, 3, 1).previous_day());
+    assert_eq!(Some(FeelDate::new(2021, 2, 28)), FeelDate::new(2021, 3, 1).previous_day());
+    assert_eq!(Some(FeelDate::new(2020, 12, 31)), FeelDate::new(2021, 1, 1).previous_day());
+    assert_eq!(None, FeelDate::new(-999_999_999, 1, 1).previous_day());
+  }
 }
@@ -98,6 +98,10 @@ impl FeelDaysAndTimeDuration {
   pub fn as_seconds(&self) -> isize {
     (self.0 / NANOSECONDS_IN_SECOND) as isize
   }
+  /// Returns this duration in nanoseconds with sign.
+  pub fn as_nanoseconds(&self) -> i128 {
+    self.0
+  }
   /// Returns absolute value of the duration.
   pub fn abs(&self) -> Self {
     Self(self.0.abs())
@@ -211,10 +215,7 @@ impl TryFrom<&str> for FeelDaysAndTimeDuration {
         }
       }
       if let Some(fractional_match) = captures.name("fractional") {
-        if let Ok(fractional) = fractional_match.as_str().parse::<f64>() {
-          nanoseconds += (fractional * NANOSECONDS_IN_SECOND as f64).trunc() as i128;
-          is_valid = true;
-        }
+        nanoseconds += super::parse_nanos(fractional_match.as_str()) as i128;
       }
       if captures.name("sign").is_some() {
         nanoseconds = -nanoseconds;
@@ -33,10 +33,11 @@
 //! Date and time utilities.
 
 use crate::temporal::date::{is_valid_date, FeelDate};
+use crate::temporal::dt_duration::FeelDaysAndTimeDuration;
 use crate::temporal::errors::*;
 use crate::temporal::ym_duration::FeelYearsAndMonthsDuration;
 use crate::temporal::zone::FeelZone;
-use chrono::{DateTime, Datelike, FixedOffset, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Timelike};
 use dmntk_common::{DmntkError, Result};
 use regex::Regex;
 use std::cmp::Ordering;
@@ -54,13 +55,17 @@ pub mod zone;
 /// Regular expression pattern for parsing dates.
 const DATE_PATTERN: &str = r#"(?P<sign>-)?(?P<year>[1-9][0-9]{3,8})-(?P<month>[0-9]{2})-(?P<day>[0-9]{2})"#;
 
+/// Regular expression pattern for parsing dates in extended form, accepting also years with leading zeros
+/// (like `0999-12-31`), produced when formatting dates with years below 1000.
+const EXTENDED_DATE_PATTERN: &str = r#"(?P<sign>-)?(?P<year>[0-9]{3,9})-(?P<month>[0-9]{2})-(?P<day>[0-9]{2})"#;
+
 /// Regular expression pattern for parsing time.
 const TIME_PATTERN: &str = r#"(?P<hours>[0-9]{2}):(?P<minutes>[0-9]{2}):(?P<seconds>[0-9]{2})(?P<fractional>\.[0-9]+)?"#;
 
 /// Regular expression patterns for parsing time zones.
 const ZULU_PATTERN: &str = r#"(?P<zulu>[zZ])"#;
 const ZONE_PATTERN: &str = r#"@(?P<zone>[a-zA-Z_/]+)"#;
-const OFFSET_PATTERN: &str = r#"(?P<offSign>[+-])(?P<offHours>[0-9]{2}):(?P<offMinutes>[0-9]{2})(:(?P<offSeconds>[0-9]{2}))?"#;
+const OFFSET_PATTERN: &str = r#"(?P<offSign>[+-])(?P<offHours>[0-9]{2}):(?P<offMinutes>[0-9]{2})(:(?P<offSeconds>[0-9]{2}))?(@(?P<offZone>[a-zA-Z_/]+))?"#;
 
 /// Number of nanoseconds in a second.
 const NANOS_IN_SECOND: u64 = 1_000_000_000;
@@ -70,6 +75,8 @@ lazy_static! {
   static ref RE_DATE: Regex = Regex::new(format!("^{}$", DATE_PATTERN).as_str()).unwrap();
   static ref RE_TIME: Regex = Regex::new(format!("^{}({})?$", TIME_PATTERN, TIME_ZONE_PATTERN.as_str()).as_str()).unwrap();
   static ref RE_DATE_AND_TIME: Regex = Regex::new(format!("^{}T{}({})?$", DATE_PATTERN, TIME_PATTERN, TIME_ZONE_PATTERN.as_str()).as_str()).unwrap();
+  static ref RE_DATE_AND_TIME_EXTENDED: Regex =
+    Regex::new(format!("^{}T{}({})?$", EXTENDED_DATE_PATTERN, TIME_PATTERN, TIME_ZONE_PATTERN.as_str()).as_str()).unwrap();
 }
 
 /// FEEL time.
@@ -91,7 +98,7 @@ impl std::str::FromStr for FeelTime {
   type Err = DmntkError;
   fn from_str(s: &str) -> Result<Self, Self::Err> {
     // parse the time from the provided string
-    let time = parse_time_literal(s)?;
+    let time = parse_time_literal(s, false)?;
     // even if parsing from string was successful, the time may still be invalid,
     // so check the time validity by converting to chrono::DateTime<FixedOffset>
     let _: DateTime<FixedOffset> = time.clone().try_into()?;
@@ -120,6 +127,22 @@ impl TryFrom<FeelTime> for DateTime<FixedOffset> {
 }
 
 impl FeelTime {
+  /// Parses time like `FEEL` literal, accepting also the extended form with both offset and named time zone
+  /// (like `02:30:00+01:00@Europe/Warsaw`), produced by [FeelTime::to_extended_string] for the time
+  /// of the later occurrence of an ambiguous local time.
+  pub fn parse_extended(text: &str) -> Result<Self> {
+    let time = parse_time_literal(text, true)?;
+    let _: DateTime<FixedOffset> = time.clone().try_into()?;
+    Ok(time)
+  }
+
+  /// Returns time like `FEEL` literal, but with the time zone in extended form, retaining
+  /// the offset of the later occurrence of an ambiguous local time.
+  pub fn to_extended_string(&self) -> String {
+    let local = FeelTime(self.0, self.1, self.2, self.3, FeelZone::Local);
+    format!("{}{}", local, self.4.to_extended_string())
+  }
+
   pub fn new_hmso_opt(hour: u8, minute: u8, second: u8, nano: u64, offset: i32) -> Option<Self> {
     if is_valid_time(hour, minute, second) {
       Some(Self(hour, minute, second, nano, FeelZone::new(offset)))
@@ -203,6 +226,10 @@ impl FeelTime {
     self.2
   }
 
+  pub fn nanosecond(&self) -> u64 {
+    self.3
+  }
+
   pub fn feel_time_offset(&self) -> Option<i32> {
     feel_time_offset(&FeelDateTime(FeelDate::today_local(), self.clone()))
   }
@@ -227,33 +254,36 @@ impl TryFrom<&str> for FeelDateTime {
   type Error = DmntkError;
   /// Converts string into [FeelDateTime].
   fn try_from(value: &str) -> Result<Self, Self::Error> {
-    if let Some(captures) = RE_DATE_AND_TIME.captures(value) {
-      if let Some(year_match) = captures.name("year") {
-        if let Ok(mut year) = year_match.as_str().parse::<i32>() {
-          if captures.name("sign").is_some() {
-            year = -year;
-          }
-          if let Some(month_match) = captures.name("month") {
-            if let Ok(month) = month_match.as_str().parse::<u8>() {
-              if let Some(day_match) = captures.name("day") {
-                if let Ok(day) = day_match.as_str().parse::<u8>() {
-                  if let Some(hour_match) = captures.name("hours") {
-                    if let Ok(hour) = hour_match.as_str().parse::<u8>() {
-                      if let Some(min_match) = captures.name("minutes") {
-                        if let Ok(min) = min_match.as_str().parse::<u8>() {
-                          if let Some(sec_match) = captures.name("seconds") {
-                            if let Ok(sec) = sec_match.as_str().parse::<u8>() {
-                              let mut fractional = 0.0;
-                              if let Some(frac_match) = captures.name("fractional") {
-                                if let Ok(frac) = frac_match.as_str().parse::<f64>() {
-                                  fractional = frac;
-                                }
-                              }
-                              let nanos = (fractional * 1e9).trunc() as u64;
-                              if is_valid_date(year, month, day) {
-                                let date = FeelDate::new(year, month, day);
-                                if let Some(zone) = FeelZone::from_captures(&captures) {
-                                  if is_valid_time(hour, min, sec) {
+    parse_date_time_literal(value, false)
+  }
+}
+
+/// Parses date and time literal, the literal with both offset and named time zone
+/// and the year with leading zeros are accepted only in extended form.
+fn parse_date_time_literal(value: &str, extended: bool) -> Result<FeelDateTime> {
+  let re_date_and_time: &Regex = if extended { &RE_DATE_AND_TIME_EXTENDED } else { &RE_DATE_AND_TIME };
+  if let Some(captures) = re_date_and_time.captures(value) {
+    if let Some(year_match) = captures.name("year") {
+      if let Ok(mut year) = year_match.as_str().parse::<i32>() {
+        if captures.name("sign").is_some() {
+          year = -year;
+        }
+        if let Some(month_match) = captures.name("month") {
+          if let Ok(month) = month_match.as_str().parse::<u8>() {
+            if let Some(day_match) = captures.name("day") {
+              if let Ok(day) = day_match.as_str().parse::<u8>() {
+                if let Some(hour_match) = captures.name("hours") {
+                  if let Ok(hour) = hour_match.as_str().parse::<u8>() {
+                    if let Some(min_match) = captures.name("minutes") {
+                      if let Ok(min) = min_match.as_str().parse::<u8>() {
+                        if let Some(sec_match) = captures.name("seconds") {
+                          if let Ok(sec) = sec_match.as_str().parse::<u8>() {
+                            let nanos = captures.name("fractional").map_or(0, |frac_match| parse_nanos(frac_match.as_str()));
+                            if is_valid_date(year, month, day) {
+                              let date = FeelDate::new(year, month, day);
+                              if let Some(zone) = zone_from_captures(&captures, extended) {
+                                if is_valid_time(hour, min, sec) {
+                                  if let Some(zone) = resolve_zone(zone, date.as_tuple(), (hour as u32, min as u32, sec as u32, nanos as u32)) {
                                     let time = FeelTime(hour, min, sec, nanos, zone);
                                     return Ok(FeelDateTime(date, time));
                                   }
@@ -272,8 +302,8 @@ impl TryFrom<&str> for FeelDateTime {
         }
       }
     }
-    Err(err_invalid_date_time_literal(value))
   }
+  Err(err_invalid_date_time_literal(value))
 }
 
 /// Implements `PartialEq` trait for parsing date and time.
@@ -294,6 +324,22 @@ impl std::ops::Sub<&FeelDateTime> for &FeelDateTime {
   }
 }
 
+impl std::ops::Add<&FeelDaysAndTimeDuration> for &FeelDateTime {
+  type Output = Option<FeelDateTime>;
+  /// Adds days and time duration to date and time.
+  fn add(self, rhs: &FeelDaysAndTimeDuration) -> Option<FeelDateTime> {
+    add_nanoseconds(self, rhs.as_nanoseconds())
+  }
+}
+
+impl std::ops::Sub<&FeelDaysAndTimeDuration> for &FeelDateTime {
+  type Output = Option<FeelDateTime>;
+  /// Subtracts days and time duration from date and time.
+  fn sub(self, rhs: &FeelDaysAndTimeDuration) -> Option<FeelDateTime> {
+    add_nanoseconds(self, -rhs.as_nanoseconds())
+  }
+}
+
 impl TryFrom<FeelDateTime> for DateTime<FixedOffset> {
   type Error = DmntkError;
   ///
@@ -305,6 +351,7 @@ impl TryFrom<FeelDateTime> for DateTime<FixedOffset> {
       FeelZone::Local => get_local_offset(me_date_tuple, me_time_tuple),
       FeelZone::Offset(offset) => Some(*offset),
       FeelZone::Zone(zone_name) => get_zone_offset(zone_name, me_date_tuple, me_time_tuple),
+      FeelZone::ZoneOffset(_, offset) => Some(*offset),
     };
     if let Some(me_offset) = me_offset_opt {
       if let Some(me_date) = date_time_offset(me_date_tuple, me_time_tuple, me_offset) {
@@ -336,6 +383,19 @@ impl FeelDateTime {
     Self(FeelDate::new(date.0, date.1, date.2), FeelTime::offset(time.0, time.1, time.2, time.3, offset))
   }
 
+  /// Parses date and time like `FEEL` literal, accepting also the extended form with both offset and named time zone
+  /// (like `2021-10-31T02:30:00+01:00@Europe/Warsaw`), produced by [FeelDateTime::to_extended_string] for the later
+  /// occurrence of an ambiguous local time. The offset must be valid in the named time zone at parsed local date and time.
+  pub fn parse_extended(text: &str) -> Result<Self> {
+    parse_date_time_literal(text, true)
+  }
+
+  /// Returns date and time like `FEEL` literal, but with the time zone in extended form, retaining
+  /// the offset of the later occurrence of an ambiguous local time.
+  pub fn to_extended_string(&self) -> String {
+    format!("{}T{}", self.0, self.1.to_extended_string())
+  }
+
   /// Returns the `Date` part from date and time value.
   pub fn date(&self) -> FeelDate {
     self.0.clone()
@@ -409,6 +469,10 @@ impl FeelDateTime {
     self.1 .2
   }
 
+  pub fn nanosecond(&self) -> u64 {
+    self.1 .3
+  }
+
   pub fn feel_time_offset(&self) -> Option<i32> {
     feel_time_offset(self)
   }
@@ -418,8 +482,27 @@ impl FeelDateTime {
   }
 }
 
-/// Parses time literal.
-fn parse_time_literal(s: &str) -> Result<FeelTime> {
+/// Parses time literal, the literal with both offset and named time zone is accepted only in extended form.
+/// Returns the time zone captured in parsed literal.
+///
+/// The time zone with both offset and named time zone is accepted only in extended form,
+/// where also zero offset (like `+00:00`) is kept as an offset, not converted to UTC.
+fn zone_from_captures(captures: &regex::Captures, extended: bool) -> Option<FeelZone> {
+  match FeelZone::from_captures(captures)? {
+    FeelZone::Utc if extended && captures.name("offSign").is_some() => Some(FeelZone::Offset(0)),
+    FeelZone::ZoneOffset(_, _) if !extended => None,
+    zone => Some(zone),
+  }
+}
+
+/// Converts fractional seconds (like `.123456789`) into the number of nanoseconds,
+/// digits beyond nanoseconds are truncated.
+pub(crate) fn parse_nanos(fractional: &str) -> u64 {
+  let digits = fractional.trim_start_matches('.');
+  digits.chars().chain(std::iter::repeat('0')).take(9).collect::<String>().parse().unwrap_or(0)
+}
+
+fn parse_time_literal(s: &str, extended: bool) -> Result<FeelTime> {
   if let Some(captures) = RE_TIME.captures(s) {
     if let Some(hour_match) = captures.name("hours") {
       if let Ok(hour) = hour_match.as_str().parse::<u8>() {
@@ -427,14 +510,8 @@ fn parse_time_literal(s: &str) -> Result<FeelTime> {
           if let Ok(min) = min_match.as_str().parse::<u8>() {
             if let Some(sec_match) = captures.name("seconds") {
               if let Ok(sec) = sec_match.as_str().parse::<u8>() {
-                let mut fractional = 0.0;
-                if let Some(frac_match) = captures.name("fractional") {
-                  if let Ok(frac) = frac_match.as_str().parse::<f64>() {
-                    fractional = frac;
-                  }
-                }
-                let nanos = (fractional * 1e9).trunc() as u64;
-                if let Some(zone) = FeelZone::from_captures(&captures) {
+                let nanos = captures.name("fractional").map_or(0, |frac_match| parse_nanos(frac_match.as_str()));
+                if let Some(zone) = zone_from_captures(&captures, extended) {
                   if is_valid_time(hour, min, sec) {
                     return Ok(FeelTime(hour, min, sec, nanos, zone));
                   }
@@ -501,6 +578,7 @@ fn compare(me: &FeelDateTime, other: &FeelDateTime) -> Option<Ordering> {
     FeelZone::Local => get_local_offset(me_date_tuple, me_time_tuple),
     FeelZone::Offset(offset) => Some(*offset),
     FeelZone::Zone(zone_name) => get_zone_offset(zone_name, me_date_tuple, me_time_tuple),
+    FeelZone::ZoneOffset(_, offset) => Some(*offset),
   };
   let other_date_tuple = other.0.as_tuple();
   let other_time_tuple = ((other.1).0 as u32, (other.1).1 as u32, (other.1).2 as u32, (other.1).3 as u32);
@@ -509,6 +587,7 @@ fn compare(me: &FeelDateTime, other: &FeelDateTime) -> Option<Ordering> {
     FeelZone::Local => get_local_offset(other_date_tuple, other_time_tuple),
     FeelZone::Offset(offset) => Some(*offset),
     FeelZone::Zone(zone_name) => get_zone_offset(zone_name, other_date_tuple, other_time_tuple),
+    FeelZone::ZoneOffset(_, offset) => Some(*offset),
   };
   if let Some((me_offset, other_offset)) = me_offset_opt.zip(other_offset_opt) {
     let me_date_opt = date_time_offset(me_date_tuple, me_time_tuple, me_offset);
@@ -528,6 +607,7 @@ pub fn subtract(me: &FeelDateTime, other: &FeelDateTime) -> Option<i64> {
     FeelZone::Local => get_local_offset(me_date_tuple, me_time_tuple),
     FeelZone::Offset(offset) => Some(*offset),
     FeelZone::Zone(zone_name) => get_zone_offset(zone_name, me_date_tuple, me_time_tuple),
+    FeelZone::ZoneOffset(_, offset) => Some(*offset),
   };
   let other_date_tuple = other.0.as_tuple();
   let other_time_tuple = ((other.1).0 as u32, (other.1).1 as u32, (other.1).2 as u32, (other.1).3 as u32);
@@ -536,6 +616,7 @@ pub fn subtract(me: &FeelDateTime, other: &FeelDateTime) -> Option<i64> {
     FeelZone::Local => get_local_offset(other_date_tuple, other_time_tuple),
     FeelZone::Offset(offset) => Some(*offset),
     FeelZone::Zone(zone_name) => get_zone_offset(zone_name, other_date_tuple, other_time_tuple),
+    FeelZone::ZoneOffset(_, offset) => Some(*offset),
   };
   if let Some((me_offset, other_offset)) = me_offset_opt.zip(other_offset_opt) {
     let me_date_opt = date_time_offset(me_date_tuple, me_time_tuple, me_offset);
@@ -555,6 +636,7 @@ fn weekday(me: &FeelDateTime) -> Option<u32> {
     FeelZone::Local => get_local_offset(me_date_tuple, me_time_tuple),
     FeelZone::Offset(offset) => Some(*offset),
     FeelZone::Zone(zone_name) => get_zone_offset(zone_name, me_date_tuple, me_time_tuple),
+    FeelZone::ZoneOffset(_, offset) => Some(*offset),
   };
   if let Some(me_offset) = me_offset_opt {
     if let Some(me_date) = date_time_offset(me_date_tuple, me_time_tuple, me_offset) {
@@ -572,6 +654,7 @@ fn feel_time_offset(me: &FeelDateTime) -> Option<i32> {
     FeelZone::Local => None, // in FEEL semantic domain the local offset is treated as none
     FeelZone::Offset(offset) => Some(*offset),
     FeelZone::Zone(zone_name) => get_zone_offset(zone_name, me_date_tuple, me_time_tuple),
+    FeelZone::ZoneOffset(_, offset) => Some(*offset),
   };
   if let Some(me_offset) = me_offset_opt {
     return Some(me_offset);
@@ -580,7 +663,7 @@ fn feel_time_offset(me: &FeelDateTime) -> Option<i32> {
 }
 
 fn feel_time_zone(me: &FeelDateTime) -> Option<String> {
-  if let FeelZone::Zone(zone_name) = &(me.1).4 {
+  if let FeelZone::Zone(zone_name) | FeelZone::ZoneOffset(zone_name, _) = &(me.1).4 {
     return Some(zone_name.clone());
   }
   None
@@ -599,31 +682,115 @@ fn date_time_offset(date: (i32, u32, u32), time: (u32, u32, u32, u32), offset: i
 /// Returns time offset (in seconds) between local time zone
 /// and UTC time zone at specified date and time.
 fn get_local_offset(date: (i32, u32, u32), time: (u32, u32, u32, u32)) -> Option<i32> {
-  if let Some(naive_date) = NaiveDate::from_ymd_opt(date.0, date.1, date.2) {
-    if let Some(naive_time) = NaiveTime::from_hms_nano_opt(time.0, time.1, time.2, time.3) {
-      let naive_date_time = NaiveDateTime::new(naive_date, naive_time);
-      return Some(Local.offset_from_utc_datetime(&naive_date_time).local_minus_utc());
-    }
-  }
-  None
+  resolve_offset(&Local, naive_date_time(date, time)?)
 }
 
 /// Returns time offset (in seconds) between named time zone
 /// and UTC time zone at specified date and time.
 fn get_zone_offset(zone_name: &str, date: (i32, u32, u32), time: (u32, u32, u32, u32)) -> Option<i32> {
-  // try to build UTC date and time from specified values
-  if let LocalResult::Single(utc) = Utc.ymd_opt(date.0, date.1, date.2).and_hms_nano_opt(time.0, time.1, time.2, time.3) {
-    // try parse the time zone specified as text
-    if let Ok(tz) = zone_name.parse::<chrono_tz::Tz>() {
-      // build date and time in parsed time zone
-      let zdt = tz.ymd(date.0, date.1, date.2).and_hms_nano(time.0, time.1, time.2, time.3);
-      // calculate the time offset, the result is a chrono::Duration
-      let offset: chrono::Duration = utc.with_timezone(&tz) - zdt;
-      // return seconds
-      return Some(offset.num_seconds() as i32);
+  resolve_offset(&zone_name.parse::<chrono_tz::Tz>().ok()?, naive_date_time(date, time)?)
+}
+
+/// Returns time offset (in seconds) of the local date and time in specified time zone.
+///
+/// When the local date and time is ambiguous (occurs twice, when clocks are set back),
+/// the offset of the earlier instant is returned. When the local date and time does not exist
+/// (is skipped, when clocks are set forward), the offset valid before the transition is returned,
+/// so the local date and time is shifted forward by the length of the gap.
+fn resolve_offset<Tz: TimeZone>(tz: &Tz, naive: NaiveDateTime) -> Option<i32> {
+  match tz.offset_from_local_datetime(&naive) {
+    LocalResult::Single(offset) => Some(offset.fix().local_minus_utc()),
+    LocalResult::Ambiguous(first, second) => Some(first.fix().local_minus_utc().max(second.fix().local_minus_utc())),
+    LocalResult::None => {
+      // the skipped local time taken with the offset valid before the transition is an instant
+      // after the transition and vice versa, so the offsets at these two instants are the offsets
+      // before and after the transition, the offset before the transition is the smaller one
+      let utc_offset = |offset: i32| -> Option<i32> {
+        let instant = naive.checked_sub_signed(chrono::Duration::seconds(offset as i64))?;
+        Some(tz.offset_from_utc_datetime(&instant).fix().local_minus_utc())
+      };
+      let first = utc_offset(0)?;
+      let second = utc_offset(first)?;
+      let third = utc_offset(second)?;
+      Some(second.min(third))
     }
   }
-  None
+}
+
+/// Resolves the time zone of the local date and time.
+///
+/// Named time zone with explicit offset must have this offset at specified local date and time.
+/// The offset is retained only when it identifies the later of two occurrences of an ambiguous local time,
+/// otherwise it can be derived from the local date and time, and the named time zone is returned.
+fn resolve_zone(zone: FeelZone, date: (i32, u32, u32), time: (u32, u32, u32, u32)) -> Option<FeelZone> {
+  if let FeelZone::ZoneOffset(zone_name, offset) = zone {
+    let tz = zone_name.parse::<chrono_tz::Tz>().ok()?;
+    let naive = naive_date_time(date, time)?;
+    let valid = match tz.offset_from_local_datetime(&naive) {
+      LocalResult::Single(single) => single.fix().local_minus_utc() == offset,
+      LocalResult::Ambiguous(first, second) => first.fix().local_minus_utc() == offset || second.fix().local_minus_utc() == offset,
+      LocalResult::None => false,
+    };
+    if !valid {
+      None
+    } else if resolve_offset(&tz, naive) == Some(offset) {
+      Some(FeelZone::Zone(zone_name))
+    } else {
+      Some(FeelZone::ZoneOffset(zone_name, offset))
+    }
+  } else {
+    Some(zone)
+  }
+}
+
+/// Builds naive date and time from date and time tuples.
+fn naive_date_time(date: (i32, u32, u32), time: (u32, u32, u32, u32)) -> Option<NaiveDateTime> {
+  Some(NaiveDateTime::new(
+    NaiveDate::from_ymd_opt(date.0, date.1, date.2)?,
+    NaiveTime::from_hms_nano_opt(time.0, time.1, time.2, time.3)?,
+  ))
+}
+
+/// Adds specified number of nanoseconds to date and time.
+///
+/// Local date and time, and date and time with UTC or fixed offset are shifted
+/// on the time line without changing the time zone. Date and time in named time zone
+/// is converted to an instant, shifted, and converted back to the local date and time
+/// in the same time zone, so the offset is recalculated when a DST transition is crossed.
+fn add_nanoseconds(me: &FeelDateTime, nanos: i128) -> Option<FeelDateTime> {
+  let duration = chrono::Duration::nanoseconds(i64::try_from(nanos).ok()?);
+  let naive = naive_date_time(me.0.as_tuple(), ((me.1).0 as u32, (me.1).1 as u32, (me.1).2 as u32, (me.1).3 as u32))?;
+  let (shifted, zone) = match &(me.1).4 {
+    FeelZone::Zone(zone_name) | FeelZone::ZoneOffset(zone_name, _) => {
+      let tz = zone_name.parse::<chrono_tz::Tz>().ok()?;
+      let offset = match &(me.1).4 {
+        FeelZone::ZoneOffset(_, offset) => *offset,
+        _ => resolve_offset(&tz, naive)?,
+      };
+      let instant = naive
+        .checked_sub_signed(chrono::Duration::seconds(offset as i64))?
+        .checked_add_signed(duration)?;
+      let zoned = tz.from_utc_datetime(&instant);
+      let shifted_offset = zoned.offset().fix().local_minus_utc();
+      // keep the offset only when it can not be derived from the local date and time
+      let zone = if resolve_offset(&tz, zoned.naive_local()) == Some(shifted_offset) {
+        FeelZone::Zone(zone_name.clone())
+      } else {
+        FeelZone::ZoneOffset(zone_name.clone(), shifted_offset)
+      };
+      (zoned.naive_local(), zone)
+    }
+    other => (naive.checked_add_signed(duration)?, other.clone()),
+  };
+  let date = FeelDate::new_opt(shifted.year(), shifted.month() as u8, shifted.day() as u8)?;
+  let time = FeelTime(
+    shifted.hour() as u8,
+    shifted.minute() as u8,
+    shifted.second() as u8,
+    shifted.nanosecond() as u64,
+    zone,
+  );
+  Some(FeelDateTime(date, time))
 }
 
 /// Converts the number of nanoseconds into textual form, the trailing zeros a stripped.
@@ -647,7 +814,7 @@ fn is_valid_time(hour: u8, minute: u8, second: u8) -> bool {
 
 #[cfg(test)]
 mod tests {
-  use super::{get_local_offset, get_zone_offset, FeelDate, FeelDateTime, FeelTime, FeelZone};
+  use super::{get_local_offset, get_zone_offset, FeelDate, FeelDateTime, FeelDaysAndTimeDuration, FeelTime, FeelZone};
   use std::convert::TryFrom;
 
   const SECONDS_IN_HOUR: i32 = 3_600;
@@ -780,4 +947,74 @@ mod tests {
     // no time change in Honolulu in summer, offset = -10:00
     assert_eq!(Some(-10 * SECONDS_IN_HOUR), get_zone_offset("Pacific/Honolulu", (2020, 6, 8), (8, 0, 0, 0)));
   }
+
+  #[test]
+  fn test_get_zone_offset_dst_transitions() {
+    // skipped local time when clocks are set forward, offset valid before the transition is used
+    assert_eq!(Some(SECONDS_IN_HOUR), get_zone_offset("Europe/Warsaw", (2021, 3, 28), (2, 30, 0, 0)));
+    assert_eq!(Some(2 * SECONDS_IN_HOUR), get_zone_offset("Europe/Warsaw", (2021, 3, 28), (3, 0, 0, 0)));
+    // ambiguous local time when clocks are set back, offset of the earlier instant is used
+    assert_eq!(Some(2 * SECONDS_IN_HOUR), get_zone_offset("Europe/Warsaw", (2021, 10, 31), (2, 30, 0, 0)));
+    assert_eq!(Some(SECONDS_IN_HOUR), get_zone_offset("Europe/Warsaw", (2021, 10, 31), (3, 0, 0, 0)));
+    assert_eq!(Some(-4 * SECONDS_IN_HOUR), get_zone_offset("America/New_York", (2021, 11, 7), (1, 30, 0, 0)));
+    // skipped local time one day after another offset change in the same time zone
+    assert_eq!(Some(SECONDS_IN_HOUR), get_zone_offset("Europe/Vilnius", (1998, 3, 29), (2, 30, 0, 0)));
+    assert_eq!(Some(-7 * SECONDS_IN_HOUR), get_zone_offset("Pacific/Easter", (1982, 3, 14), (3, 30, 0, 0)));
+    assert_eq!(None, get_zone_offset("Europe/Unknown", (2021, 11, 7), (1, 30, 0, 0)));
+  }
+
+  #[test]
+  fn test_parse_zone_with_offset() {
+    // offset of the earlier occurrence of ambiguous time and offsets of unambiguous times are derived from the zone
+    // literals with both offset and named time zone are not valid FEEL literals
+    assert!(FeelDateTime::try_from("2021-10-31T02:30:00+01:00@Europe/Warsaw").is_err());
+    assert!("02:30:00+01:00@Europe/Warsaw".parse::<FeelTime>().is_err());
+    assert!(FeelTime::parse_extended("02:30:00+01:00@Europe/Warsaw").is_ok());
+    let date_time = FeelDateTime::parse_extended("2021-10-31T02:30:00+02:00@Europe/Warsaw").unwrap();
+    assert_eq!(FeelZone::Zone("Europe/Warsaw".to_string()), (date_time.1).4);
+    let date_time = FeelDateTime::parse_extended("2021-06-01T12:00:00+02:00@Europe/Warsaw").unwrap();
+    assert_eq!("2021-06-01T12:00:00@Europe/Warsaw", date_time.to_string());
+    // offset of the later occurrence of ambiguous time is retained
+    let date_time = FeelDateTime::parse_extended("2021-10-31T02:30:00+01:00@Europe/Warsaw").unwrap();
+    assert_eq!(FeelZone::ZoneOffset("Europe/Warsaw".to_string(), SECONDS_IN_HOUR), (date_time.1).4);
+    // offsets not valid in the zone at specified local time are rejected
+    assert!(FeelDateTime::parse_extended("2021-10-31T02:30:00+03:00@Europe/Warsaw").is_err());
+    assert!(FeelDateTime::parse_extended("2021-06-01T12:00:00+01:00@Europe/Warsaw").is_err());
+    assert!(FeelDateTime::parse_extended("2021-03-28T02:30:00+01:00@Europe/Warsaw").is_err());
+    assert!(FeelDateTime::parse_extended("2021-06-01T12:00:00+01:00@Europe/Unknown").is_err());
+  }
+
+  #[test]
+  fn test_add_days_and_time_duration() {
+    let hours = |h: i64| FeelDaysAndTimeDuration::default().second(h * 3_600).build();
+    let start = FeelDateTime::try_from("2021-10-31T01:30:00@Europe/Warsaw").unwrap();
+    let first = (&start + &hours(1)).unwrap();
+    let second = (&start + &hours(2)).unwrap();
+    assert_eq!("2021-10-31T02:30:00@Europe/Warsaw", first.to_string());
+    assert_eq!("2021-10-31T02:30:00@Europe/Warsaw", second.to_string());
+    assert_eq!("2021-10-31T02:30:00@Europe/Warsaw", first.to_extended_string());
+    assert_eq!("2021-10-31T02:30:00+01:00@Europe/Warsaw", second.to_extended_string());
+    assert_eq!(FeelZone::Zone("Europe/Warsaw".to_string()), (first.1).4);
+    assert_eq!(FeelZone::ZoneOffset("Europe/Warsaw".to_string(), SECONDS_IN_HOUR), (second.1).4);
+    assert_eq!(Some(2 * SECONDS_IN_HOUR), first.feel_time_offset());
+    assert_eq!(Some(SECONDS_IN_HOUR), second.feel_time_offset());
+    assert_eq!(Some(true), first.before(&second));
+    assert_eq!(Some(3_600_000_000_000), &second - &first);
+    assert_eq!("2021-10-31T01:30:00@Europe/Warsaw", (&second - &hours(2)).unwrap().to_string());
+    assert_eq!("2021-10-31T03:30:00@Europe/Warsaw", (&second + &hours(1)).unwrap().to_string());
+    // both occurrences of the ambiguous local time are round-tripped through their extended textual form
+    let first_parsed = FeelDateTime::parse_extended(&first.to_extended_string()).unwrap();
+    let second_parsed = FeelDateTime::parse_extended(&second.to_extended_string()).unwrap();
+    assert_eq!((first.1).4, (first_parsed.1).4);
+    assert_eq!((second.1).4, (second_parsed.1).4);
+    assert_eq!(Some(true), first_parsed.equal(&first));
+    assert_eq!(Some(true), second_parsed.equal(&second));
+    assert_eq!(Some(true), first_parsed.before(&second_parsed));
+    let start = FeelDateTime::try_from("2021-03-27T02:30:00@Europe/Warsaw").unwrap();
+    assert_eq!("2021-03-28T03:30:00@Europe/Warsaw", (&start + &hours(24)).unwrap().to_string());
+    let start = FeelDateTime::try_from("2021-03-27T02:30:00").unwrap();
+    assert_eq!("2021-03-28T02:30:00", (&start + &hours(24)).unwrap().to_string());
+    let start = FeelDateTime::try_from("2021-03-27T02:30:00-05:00").unwrap();
+    assert_eq!("2021-03-26T01:30:00-05:00", (&start - &hours(25)).unwrap().to_string());
+  }
 }
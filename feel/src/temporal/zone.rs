@@ -44,6 +44,10 @@ pub enum FeelZone {
   Offset(i32),
   /// Time zone defined as a value from IANA database.
   Zone(String),
+  /// Time zone defined as a value from IANA database with explicit offset,
+  /// used for the later of two occurrences of an ambiguous local time (when clocks are set back).
+  /// The offset is not a part of `FEEL` representation, it is written only in extended form.
+  ZoneOffset(String, i32),
 }
 
 impl std::fmt::Display for FeelZone {
@@ -52,18 +56,34 @@ impl std::fmt::Display for FeelZone {
     match self {
       FeelZone::Utc => write!(f, "Z"),
       FeelZone::Local => write!(f, ""),
-      FeelZone::Offset(offset) => {
-        let hours = offset / 3_600;
-        let minutes = offset.abs().rem(3_600).div(60);
-        let seconds = offset.abs().rem(3_600).rem(60);
-        if seconds > 0 {
-          write!(f, "{:+03}:{:02}:{:02}", hours, minutes, seconds)
-        } else {
-          write!(f, "{:+03}:{:02}", hours, minutes)
-        }
-      }
-      FeelZone::Zone(zone) => write!(f, "@{}", zone),
+      FeelZone::Offset(offset) => write_offset(f, *offset),
+      FeelZone::Zone(zone) | FeelZone::ZoneOffset(zone, _) => write!(f, "@{}", zone),
+    }
+  }
+}
+
+/// Helper for writing the time zone in extended form, with both offset and named time zone.
+struct ExtendedZone<'a>(&'a FeelZone);
+
+impl std::fmt::Display for ExtendedZone<'_> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if let FeelZone::ZoneOffset(_, offset) = self.0 {
+      write_offset(f, *offset)?;
     }
+    write!(f, "{}", self.0)
+  }
+}
+
+/// Writes the offset from UTC in format `+hh:mm` or `+hh:mm:ss`.
+fn write_offset(f: &mut std::fmt::Formatter<'_>, offset: i32) -> std::fmt::Result {
+  let sign = if offset < 0 { '-' } else { '+' };
+  let hours = offset.abs() / 3_600;
+  let minutes = offset.abs().rem(3_600).div(60);
+  let seconds = offset.abs().rem(3_600).rem(60);
+  if seconds > 0 {
+    write!(f, "{}{:02}:{:02}:{:02}", sign, hours, minutes, seconds)
+  } else {
+    write!(f, "{}{:02}:{:02}", sign, hours, minutes)
   }
 }
 
@@ -76,6 +96,11 @@ impl FeelZone {
       Self::Utc
     }
   }
+  /// Returns the time zone in extended form, like `+01:00@Europe/Warsaw`,
+  /// retaining the offset of the later occurrence of an ambiguous local time.
+  pub fn to_extended_string(&self) -> String {
+    ExtendedZone(self).to_string()
+  }
   ///
   pub fn from_captures(captures: &Captures) -> Option<Self> {
     if captures.name("zulu").is_some() {
@@ -99,6 +124,14 @@ impl FeelZone {
                 // the hour magnitude is limited to at most 14
                 return None;
               }
+              if let Some(zone_match) = captures.name("offZone") {
+                // named time zone with explicit offset, identifying one of two occurrences of ambiguous local time
+                return if zone_match.as_str().parse::<chrono_tz::Tz>().is_ok() {
+                  Some(FeelZone::ZoneOffset(zone_match.as_str().to_string(), offset))
+                } else {
+                  None
+                };
+              }
               return Some(FeelZone::new(offset));
             }
           }
@@ -138,6 +171,22 @@ mod tests {
     assert_eq!("Z", FeelZone::Utc.to_string());
   }
 
+  #[test]
+  fn test_format_zone() {
+    assert_eq!("@Europe/Warsaw", FeelZone::Zone("Europe/Warsaw".to_string()).to_string());
+    assert_eq!("@Europe/Warsaw", FeelZone::ZoneOffset("Europe/Warsaw".to_string(), 3_600).to_string());
+    assert_eq!(
+      "+01:00@Europe/Warsaw",
+      FeelZone::ZoneOffset("Europe/Warsaw".to_string(), 3_600).to_extended_string()
+    );
+    assert_eq!(
+      "-00:30@America/St_Johns",
+      FeelZone::ZoneOffset("America/St_Johns".to_string(), -1_800).to_extended_string()
+    );
+    assert_eq!("@Europe/Warsaw", FeelZone::Zone("Europe/Warsaw".to_string()).to_extended_string());
+    assert_eq!("+05:00", FeelZone::Offset(18_000).to_extended_string());
+  }
+
   #[test]
   fn test_format_local() {
     assert_eq!("", FeelZone::Local.to_string());
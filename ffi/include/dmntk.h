@@ -0,0 +1,95 @@
+#ifndef DMNTK_H
+#define DMNTK_H
+
+/* Generated with cbindgen from dmntk-ffi sources, do not edit manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Model loaded from `DMN` definitions, ready for evaluation.
+typedef struct DmntkModel DmntkModel;
+
+// Result of evaluating an invocable, holds either the result in `JSON` format or the error message.
+typedef struct DmntkResult DmntkResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Loads a model from `DMN` definitions in XML format.
+//
+// Returns a handle to the model or `NULL` when loading failed.
+// In case of failure, when `error` is not `NULL`, it is set to the error message
+// that must be released using [dmntk_string_free].
+//
+// # Safety
+//
+// `xml` must be a valid null-terminated string, `error` must be `NULL` or a valid pointer.
+DmntkModel *dmntk_model_load(const char *xml, char **error);
+
+// Returns names of all invocables (decisions, business knowledge models and decision services)
+// defined in the model, as `JSON` array of strings.
+//
+// Returned string must be released using [dmntk_string_free].
+//
+// # Safety
+//
+// `model` must be a valid handle returned by [dmntk_model_load].
+char *dmntk_model_invocables(const DmntkModel *model);
+
+// Evaluates an invocable with specified name using input data in `JSON` format.
+//
+// Always returns a handle to the result, that must be released using [dmntk_result_free].
+//
+// # Safety
+//
+// `model` must be a valid handle returned by [dmntk_model_load],
+// `invocable_name` and `input` must be valid null-terminated strings.
+DmntkResult *dmntk_model_evaluate(const DmntkModel *model, const char *invocable_name, const char *input);
+
+// Returns the evaluation result in `JSON` format, or `NULL` when the evaluation failed.
+//
+// Returned string is owned by the result and is valid until the result is released.
+//
+// # Safety
+//
+// `result` must be a valid handle returned by [dmntk_model_evaluate].
+const char *dmntk_result_json(const DmntkResult *result);
+
+// Returns the error message, or `NULL` when the evaluation succeeded.
+//
+// Returned string is owned by the result and is valid until the result is released.
+//
+// # Safety
+//
+// `result` must be a valid handle returned by [dmntk_model_evaluate].
+const char *dmntk_result_error(const DmntkResult *result);
+
+// Releases the result of evaluation.
+//
+// # Safety
+//
+// `result` must be `NULL` or a valid handle returned by [dmntk_model_evaluate], not released before.
+void dmntk_result_free(DmntkResult *result);
+
+// Releases the model.
+//
+// # Safety
+//
+// `model` must be `NULL` or a valid handle returned by [dmntk_model_load], not released before.
+void dmntk_model_free(DmntkModel *model);
+
+// Releases the string returned by [dmntk_model_load] or [dmntk_model_invocables].
+//
+// # Safety
+//
+// `s` must be `NULL` or a valid string returned by functions listed above, not released before.
+void dmntk_string_free(char *s);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* DMNTK_H */
@@ -0,0 +1,71 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Errors reported by C bindings.
+
+use dmntk_common::DmntkError;
+
+/// Errors reported by C bindings.
+#[derive(Error, Debug)]
+enum FfiError {
+  #[error("argument '{0}' is null")]
+  NullArgument(String),
+  #[error("argument '{0}' is not a valid UTF-8 string")]
+  InvalidUtf8(String),
+  #[error("invocable '{0}' not found")]
+  InvocableNotFound(String),
+  #[error("unexpected failure: {0}")]
+  Panic(String),
+}
+
+impl From<FfiError> for DmntkError {
+  /// Converts C bindings error into [DmntkError].
+  fn from(e: FfiError) -> Self {
+    DmntkError::new("FfiError", &e.to_string())
+  }
+}
+
+pub fn err_null_argument(name: &str) -> DmntkError {
+  FfiError::NullArgument(name.to_string()).into()
+}
+
+pub fn err_invalid_utf8(name: &str) -> DmntkError {
+  FfiError::InvalidUtf8(name.to_string()).into()
+}
+
+pub fn err_invocable_not_found(name: &str) -> DmntkError {
+  FfiError::InvocableNotFound(name.to_string()).into()
+}
+
+pub fn err_panic(reason: &str) -> DmntkError {
+  FfiError::Panic(reason.to_string()).into()
+}
@@ -0,0 +1,68 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Evaluation of `DMN` models independent from C bindings.
+
+use crate::errors::*;
+use dmntk_common::{Jsonify, Result};
+use dmntk_feel::context::FeelContext;
+use dmntk_feel::values::{Value, Values};
+use dmntk_feel::Plain;
+use dmntk_model_evaluator::ModelEvaluator;
+use std::sync::Arc;
+
+/// Loads the model from `DMN` definitions in XML format.
+pub fn load_model(xml: &str) -> Result<Arc<ModelEvaluator>> {
+  let definitions = dmntk_model::parse(xml)?;
+  ModelEvaluator::new(&definitions)
+}
+
+/// Returns names of invocables as `JSON` array.
+pub fn invocables(model_evaluator: &ModelEvaluator) -> String {
+  Values::new(model_evaluator.invocable_names().into_iter().map(Value::String).collect()).jsonify()
+}
+
+/// Evaluates an invocable using input data in `JSON` format, returns the result in `JSON` format.
+///
+/// Input data must be a `JSON` object, empty input is treated as an empty object.
+pub fn evaluate(model_evaluator: &ModelEvaluator, invocable_name: &str, input: &str) -> Result<String> {
+  if !model_evaluator.invocable_names().iter().any(|name| name == invocable_name) {
+    return Err(err_invocable_not_found(invocable_name));
+  }
+  let input_data = if input.trim().is_empty() { Default::default() } else { parse_input(input)? };
+  Ok(model_evaluator.evaluate_invocable(invocable_name, &input_data).jsonify())
+}
+
+/// Parses input data from plain `JSON` object.
+fn parse_input(input: &str) -> Result<FeelContext> {
+  Plain::<FeelContext>::from_json(input).map(|Plain(ctx)| ctx)
+}
@@ -0,0 +1,258 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! C bindings for evaluating `DMN` models in services written in other languages.
+//!
+//! All strings passed to and returned from functions are UTF-8 encoded and null-terminated.
+//! Input data and evaluation results are passed in `JSON` format, input data must be
+//! a plain `JSON` object with entries named like input data of the model. Handles and strings returned from
+//! functions are owned by the caller and must be released using `dmntk_*_free` functions.
+//! Panics never cross the C boundary, they are reported like any other error.
+
+extern crate dmntk_common;
+extern crate dmntk_feel;
+extern crate dmntk_model;
+extern crate dmntk_model_evaluator;
+extern crate serde_json;
+#[macro_use]
+extern crate thiserror;
+
+mod errors;
+mod evaluation;
+
+#[cfg(test)]
+mod tests;
+
+use crate::errors::*;
+use dmntk_common::Result;
+use dmntk_model_evaluator::ModelEvaluator;
+use std::any::Any;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::sync::Arc;
+
+/// Model loaded from `DMN` definitions, ready for evaluation.
+pub struct DmntkModel {
+  model_evaluator: Arc<ModelEvaluator>,
+}
+
+/// Result of evaluating an invocable, holds either the result in `JSON` format or the error message.
+pub struct DmntkResult {
+  json: Option<CString>,
+  error: Option<CString>,
+}
+
+/// Loads a model from `DMN` definitions in XML format.
+///
+/// Returns a handle to the model or `NULL` when loading failed.
+/// In case of failure, when `error` is not `NULL`, it is set to the error message
+/// that must be released using [dmntk_string_free].
+///
+/// # Safety
+///
+/// `xml` must be a valid null-terminated string, `error` must be `NULL` or a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn dmntk_model_load(xml: *const c_char, error: *mut *mut c_char) -> *mut DmntkModel {
+  match catch_panic(|| to_str(xml, "xml").and_then(evaluation::load_model)) {
+    Ok(model_evaluator) => Box::into_raw(Box::new(DmntkModel { model_evaluator })),
+    Err(reason) => {
+      if !error.is_null() {
+        *error = into_c_string(reason.to_string());
+      }
+      ptr::null_mut()
+    }
+  }
+}
+
+/// Returns names of all invocables (decisions, business knowledge models and decision services)
+/// defined in the model, as `JSON` array of strings.
+///
+/// Returned string must be released using [dmntk_string_free].
+///
+/// # Safety
+///
+/// `model` must be a valid handle returned by [dmntk_model_load].
+#[no_mangle]
+pub unsafe extern "C" fn dmntk_model_invocables(model: *const DmntkModel) -> *mut c_char {
+  catch_panic(|| Ok(model.as_ref().map(|model| evaluation::invocables(&model.model_evaluator))))
+    .map_or(ptr::null_mut(), |invocables| invocables.map_or(ptr::null_mut(), into_c_string))
+}
+
+/// Evaluates an invocable with specified name using input data in `JSON` format.
+///
+/// Always returns a handle to the result, that must be released using [dmntk_result_free].
+///
+/// # Safety
+///
+/// `model` must be a valid handle returned by [dmntk_model_load],
+/// `invocable_name` and `input` must be valid null-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn dmntk_model_evaluate(model: *const DmntkModel, invocable_name: *const c_char, input: *const c_char) -> *mut DmntkResult {
+  let result = catch_panic(|| {
+    model
+      .as_ref()
+      .ok_or_else(|| err_null_argument("model"))
+      .and_then(|model| evaluation::evaluate(&model.model_evaluator, to_str(invocable_name, "invocable_name")?, to_str(input, "input")?))
+  });
+  let result = match result {
+    Ok(json) => DmntkResult {
+      json: Some(to_c_string(json)),
+      error: None,
+    },
+    Err(reason) => DmntkResult {
+      json: None,
+      error: Some(to_c_string(reason.to_string())),
+    },
+  };
+  Box::into_raw(Box::new(result))
+}
+
+/// Returns the evaluation result in `JSON` format, or `NULL` when the evaluation failed.
+///
+/// Returned string is owned by the result and is valid until the result is released.
+///
+/// # Safety
+///
+/// `result` must be a valid handle returned by [dmntk_model_evaluate].
+#[no_mangle]
+pub unsafe extern "C" fn dmntk_result_json(result: *const DmntkResult) -> *const c_char {
+  catch_panic(|| {
+    Ok(
+      result
+        .as_ref()
+        .and_then(|result| result.json.as_ref())
+        .map_or(ptr::null(), |json| json.as_ptr()),
+    )
+  })
+  .unwrap_or(ptr::null())
+}
+
+/// Returns the error message, or `NULL` when the evaluation succeeded.
+///
+/// Returned string is owned by the result and is valid until the result is released.
+///
+/// # Safety
+///
+/// `result` must be a valid handle returned by [dmntk_model_evaluate].
+#[no_mangle]
+pub unsafe extern "C" fn dmntk_result_error(result: *const DmntkResult) -> *const c_char {
+  catch_panic(|| {
+    Ok(
+      result
+        .as_ref()
+        .and_then(|result| result.error.as_ref())
+        .map_or(ptr::null(), |error| error.as_ptr()),
+    )
+  })
+  .unwrap_or(ptr::null())
+}
+
+/// Releases the result of evaluation.
+///
+/// # Safety
+///
+/// `result` must be `NULL` or a valid handle returned by [dmntk_model_evaluate], not released before.
+#[no_mangle]
+pub unsafe extern "C" fn dmntk_result_free(result: *mut DmntkResult) {
+  if !result.is_null() {
+    let _ = catch_panic(|| {
+      drop(Box::from_raw(result));
+      Ok(())
+    });
+  }
+}
+
+/// Releases the model.
+///
+/// # Safety
+///
+/// `model` must be `NULL` or a valid handle returned by [dmntk_model_load], not released before.
+#[no_mangle]
+pub unsafe extern "C" fn dmntk_model_free(model: *mut DmntkModel) {
+  if !model.is_null() {
+    let _ = catch_panic(|| {
+      drop(Box::from_raw(model));
+      Ok(())
+    });
+  }
+}
+
+/// Releases the string returned by [dmntk_model_load] or [dmntk_model_invocables].
+///
+/// # Safety
+///
+/// `s` must be `NULL` or a valid string returned by functions listed above, not released before.
+#[no_mangle]
+pub unsafe extern "C" fn dmntk_string_free(s: *mut c_char) {
+  if !s.is_null() {
+    let _ = catch_panic(|| {
+      drop(CString::from_raw(s));
+      Ok(())
+    });
+  }
+}
+
+/// Runs the function, the panic raised inside is converted into an error.
+fn catch_panic<R, F: FnOnce() -> Result<R>>(f: F) -> Result<R> {
+  panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| Err(err_panic(&panic_message(payload.as_ref()))))
+}
+
+/// Returns the message of the panic.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+  if let Some(message) = payload.downcast_ref::<&str>() {
+    message.to_string()
+  } else if let Some(message) = payload.downcast_ref::<String>() {
+    message.clone()
+  } else {
+    "panic".to_string()
+  }
+}
+
+/// Converts null-terminated string into string slice.
+unsafe fn to_str<'a>(s: *const c_char, name: &str) -> Result<&'a str> {
+  if s.is_null() {
+    return Err(err_null_argument(name));
+  }
+  CStr::from_ptr(s).to_str().map_err(|_| err_invalid_utf8(name))
+}
+
+/// Converts string into [CString], interior null characters are removed.
+fn to_c_string(s: String) -> CString {
+  CString::new(s.replace('\0', "")).unwrap_or_default()
+}
+
+/// Converts string into raw null-terminated string owned by the caller.
+fn into_c_string(s: String) -> *mut c_char {
+  to_c_string(s).into_raw()
+}
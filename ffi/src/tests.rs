@@ -0,0 +1,176 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use std::ffi::CStr;
+
+/// Utility function that loads a model from examples.
+fn load(xml: &str) -> *mut DmntkModel {
+  let xml = CString::new(xml).unwrap();
+  unsafe { dmntk_model_load(xml.as_ptr(), ptr::null_mut()) }
+}
+
+/// Utility function that evaluates an invocable and returns the result or error message.
+fn evaluate(model: *const DmntkModel, invocable_name: &str, input: &str) -> std::result::Result<String, String> {
+  let invocable_name = CString::new(invocable_name).unwrap();
+  let input = CString::new(input).unwrap();
+  unsafe {
+    let result = dmntk_model_evaluate(model, invocable_name.as_ptr(), input.as_ptr());
+    let json = dmntk_result_json(result);
+    let error = dmntk_result_error(result);
+    let outcome = if json.is_null() {
+      Err(CStr::from_ptr(error).to_string_lossy().to_string())
+    } else {
+      assert!(error.is_null());
+      Ok(CStr::from_ptr(json).to_string_lossy().to_string())
+    };
+    dmntk_result_free(result);
+    outcome
+  }
+}
+
+#[test]
+fn test_evaluate() {
+  let model = load(dmntk_examples::DMN_2_0001);
+  assert!(!model.is_null());
+  assert_eq!(
+    Ok(r#""Hello John Doe""#.to_string()),
+    evaluate(model, "Greeting Message", r#"{"Full Name": "John Doe"}"#)
+  );
+  assert_eq!(Ok(r#"null"#.to_string()), evaluate(model, "Greeting Message", ""));
+  assert_eq!(Err("FfiError: invocable 'Greeting' not found".to_string()), evaluate(model, "Greeting", "{}"));
+  assert!(evaluate(model, "Greeting Message", "{").is_err());
+  unsafe { dmntk_model_free(model) };
+}
+
+#[test]
+fn test_json_input() {
+  let model = load(dmntk_examples::DMN_2_0001);
+  // plain JSON, not a FEEL context
+  assert_eq!(
+    Ok(r#""Hello John Doe""#.to_string()),
+    evaluate(model, "Greeting Message", r#"{"Full Name": "John Doe", "Age": 1e2}"#)
+  );
+  assert_eq!(Ok(r#"null"#.to_string()), evaluate(model, "Greeting Message", r#"{"Full Name": null}"#));
+  assert_eq!(Ok(r#"null"#.to_string()), evaluate(model, "Greeting Message", "{}"));
+  // FEEL expressions are not evaluated
+  assert_eq!(
+    Err("SerializationError: invalid JSON: key must be a string at line 1 column 2".to_string()),
+    evaluate(model, "Greeting Message", r#"{Full Name: "John" + " Doe"}"#)
+  );
+  assert_eq!(
+    Err("SerializationError: invalid JSON: expected context, actual value is [1, 2]".to_string()),
+    evaluate(model, "Greeting Message", "[1, 2]")
+  );
+  unsafe { dmntk_model_free(model) };
+}
+
+#[test]
+fn test_catch_panic() {
+  assert_eq!(
+    "FfiError: unexpected failure: boom",
+    catch_panic::<(), _>(|| panic!("boom")).unwrap_err().to_string()
+  );
+  assert_eq!(
+    "FfiError: unexpected failure: boom 1",
+    catch_panic::<(), _>(|| panic!("boom {}", 1)).unwrap_err().to_string()
+  );
+  assert_eq!(1, catch_panic(|| Ok(1)).unwrap());
+}
+
+#[test]
+fn test_invocables() {
+  let model = load(dmntk_examples::DMN_3_0014);
+  unsafe {
+    let invocables = dmntk_model_invocables(model);
+    assert_eq!(
+      r#"["Bankrates", "FinancialMetrics", "RankedProducts", "equity36Mo", "monthlyPayment"]"#,
+      CStr::from_ptr(invocables).to_str().unwrap()
+    );
+    dmntk_string_free(invocables);
+    dmntk_model_free(model);
+    assert!(dmntk_model_invocables(ptr::null()).is_null());
+  }
+}
+
+#[test]
+fn test_load_error() {
+  let xml = CString::new("<definitions").unwrap();
+  let mut error = ptr::null_mut();
+  unsafe {
+    let model = dmntk_model_load(xml.as_ptr(), &mut error);
+    assert!(model.is_null());
+    assert!(!error.is_null());
+    assert!(CStr::from_ptr(error).to_str().unwrap().starts_with("ModelParserError"));
+    dmntk_string_free(error);
+    assert!(dmntk_model_load(ptr::null(), ptr::null_mut()).is_null());
+  }
+}
+
+#[test]
+fn test_null_arguments() {
+  unsafe {
+    let result = dmntk_model_evaluate(ptr::null(), ptr::null(), ptr::null());
+    assert_eq!(
+      "FfiError: argument 'model' is null",
+      CStr::from_ptr(dmntk_result_error(result)).to_str().unwrap()
+    );
+    assert!(dmntk_result_json(result).is_null());
+    dmntk_result_free(result);
+    dmntk_result_free(ptr::null_mut());
+    dmntk_model_free(ptr::null_mut());
+    dmntk_string_free(ptr::null_mut());
+  }
+}
+
+#[test]
+fn test_header_is_up_to_date() {
+  let crate_dir = env!("CARGO_MANIFEST_DIR");
+  let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).unwrap();
+  let mut generated = vec![];
+  cbindgen::Builder::new()
+    .with_config(config)
+    .with_src(format!("{}/src/lib.rs", crate_dir))
+    .generate()
+    .unwrap()
+    .write(&mut generated);
+  let header_file_name = format!("{}/include/dmntk.h", crate_dir);
+  if std::env::var("DMNTK_UPDATE_HEADER").is_ok() {
+    std::fs::write(&header_file_name, &generated).unwrap();
+  }
+  let header = std::fs::read_to_string(&header_file_name).unwrap_or_default();
+  assert_eq!(
+    String::from_utf8(generated).unwrap(),
+    header,
+    "C header is outdated, run tests with DMNTK_UPDATE_HEADER=1 to regenerate it"
+  );
+}
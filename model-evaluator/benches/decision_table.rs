@@ -0,0 +1,102 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Benchmarks of evaluating large decision tables.
+
+use dmntk_feel::context::FeelContext;
+use dmntk_feel::values::Value;
+use dmntk_feel::Scope;
+use dmntk_model::model::{DecisionRule, DecisionTable, DecisionTableOrientation, HitPolicy, InputClause, InputEntry, OutputClause, OutputEntry};
+use dmntk_model_evaluator::build_decision_table_evaluator;
+use test::Bencher;
+
+/// Number of rules in benchmarked decision tables.
+const RULE_COUNT: usize = 5000;
+
+/// Builds a pricing decision table, input entries are created by specified function from rule number.
+fn pricing_table(input_entries: fn(usize) -> Vec<String>) -> DecisionTable {
+  DecisionTable {
+    information_item_name: None,
+    input_clauses: ["Category", "Amount"]
+      .iter()
+      .map(|input_expression| InputClause {
+        input_expression: input_expression.to_string(),
+        input_values: None,
+      })
+      .collect(),
+    output_clauses: vec![OutputClause {
+      type_ref: None,
+      name: None,
+      output_values: None,
+      default_output_entry: None,
+    }],
+    annotations: vec![],
+    rules: (0..RULE_COUNT)
+      .map(|i| DecisionRule {
+        id: None,
+        input_entries: input_entries(i).into_iter().map(|text| InputEntry { text }).collect(),
+        output_entries: vec![OutputEntry { text: format!("{}", i) }],
+        annotation_entries: vec![],
+      })
+      .collect(),
+    hit_policy: HitPolicy::Unique,
+    aggregation: None,
+    preferred_orientation: DecisionTableOrientation::RuleAsRow,
+    output_label: None,
+  }
+}
+
+/// Benchmarks the evaluation of specified decision table.
+fn bench_pricing_table(b: &mut Bencher, decision_table: &DecisionTable) {
+  let mut ctx = FeelContext::default();
+  ctx.set_entry(&"Category".into(), Value::String("c7".to_string()));
+  ctx.set_entry(&"Amount".into(), Value::Number(37_775.into()));
+  ctx.set_entry(&"Never".into(), Value::Null(None));
+  let scope: Scope = ctx.into();
+  let evaluator = build_decision_table_evaluator(&scope, decision_table).unwrap();
+  assert_eq!("3777", evaluator(&scope).to_string());
+  b.iter(|| evaluator(&scope));
+}
+
+#[bench]
+fn _0001(b: &mut Bencher) {
+  // input entries are indexed
+  let decision_table = pricing_table(|i| vec![format!(r#""c{}""#, i % 10), format!("[{}..{})", i * 10, i * 10 + 10)]);
+  bench_pricing_table(b, &decision_table);
+}
+
+#[bench]
+fn _0002(b: &mut Bencher) {
+  // input entries contain names, so they are not indexed and all rules are evaluated
+  let decision_table = pricing_table(|i| vec![format!(r#""c{}", Never"#, i % 10), format!("[{}..{}), Never", i * 10, i * 10 + 10)]);
+  bench_pricing_table(b, &decision_table);
+}
@@ -37,3 +37,4 @@ extern crate lazy_static;
 extern crate test;
 
 mod compliance;
+mod decision_table;
@@ -39,10 +39,7 @@ use dmntk_common::Result;
 use dmntk_feel::context::FeelContext;
 use dmntk_feel::values::Value;
 use dmntk_feel::{FeelType, FunctionBody, Name, Scope};
-use dmntk_model::model::{
-  BusinessKnowledgeModel, Context, DecisionTable, Definitions, DmnElement, ExpressionInstance, FunctionDefinition, Invocation, LiteralExpression, NamedElement,
-  Relation, RequiredVariable,
-};
+use dmntk_model::model::{BusinessKnowledgeModel, Definitions, DmnElement, ExpressionInstance, FunctionDefinition, NamedElement, RequiredVariable};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -124,184 +121,26 @@ fn build_business_knowledge_model_evaluator(
   }
   if let Some(expression_instance) = function_definition.body() {
     let scope: Scope = local_context.into();
-    build_expression_instance_evaluator(
+    let evaluator = Arc::new(crate::builders::build_expression_instance_evaluator(
       &scope,
-      &formal_parameters,
       expression_instance,
-      output_variable_name,
-      output_variable_type,
-      &knowledge_requirements,
-    )
+      model_evaluator.backend(),
+      &item_definition_type_evaluator,
+    )?);
+    let function_body = match expression_instance {
+      ExpressionInstance::Context(_) => FunctionBody::Context(evaluator),
+      ExpressionInstance::DecisionTable(_) | ExpressionInstance::FunctionDefinition(_) | ExpressionInstance::Invocation(_) => {
+        FunctionBody::DecisionTable(evaluator)
+      }
+      ExpressionInstance::LiteralExpression(_) | ExpressionInstance::List(_) | ExpressionInstance::Relation(_) => FunctionBody::LiteralExpression(evaluator),
+    };
+    let function = Value::FunctionDefinition(formal_parameters, function_body, output_variable_type);
+    build_evaluator(output_variable_name, function, &knowledge_requirements)
   } else {
     Ok(Box::new(move |_: &FeelContext, _: &ModelEvaluator, _: &mut FeelContext| ()))
   }
 }
 
-///
-fn build_expression_instance_evaluator(
-  scope: &Scope,
-  formal_parameters: &[(Name, FeelType)],
-  expression_instance: &ExpressionInstance,
-  output_variable_name: Name,
-  output_variable_type: FeelType,
-  knowledge_requirements: &[String],
-) -> Result<BusinessKnowledgeModelEvaluatorFn> {
-  match expression_instance {
-    ExpressionInstance::Context(context) => build_context_evaluator(
-      scope,
-      formal_parameters,
-      context,
-      output_variable_name,
-      output_variable_type,
-      knowledge_requirements,
-    ),
-    ExpressionInstance::DecisionTable(decision_table) => build_decision_table_evaluator(
-      scope,
-      formal_parameters,
-      decision_table,
-      output_variable_name,
-      output_variable_type,
-      knowledge_requirements,
-    ),
-    ExpressionInstance::FunctionDefinition(function_definition) => build_function_definition_evaluator(
-      scope,
-      formal_parameters,
-      function_definition,
-      output_variable_name,
-      output_variable_type,
-      knowledge_requirements,
-    ),
-    ExpressionInstance::Invocation(invocation) => build_invocation_evaluator(
-      scope,
-      formal_parameters,
-      invocation,
-      output_variable_name,
-      output_variable_type,
-      knowledge_requirements,
-    ),
-    ExpressionInstance::LiteralExpression(literal_expression) => build_literal_expression_evaluator(
-      scope,
-      formal_parameters,
-      literal_expression,
-      output_variable_name,
-      output_variable_type,
-      knowledge_requirements,
-    ),
-    ExpressionInstance::Relation(relation) => build_relation_evaluator(
-      scope,
-      formal_parameters,
-      relation,
-      output_variable_name,
-      output_variable_type,
-      knowledge_requirements,
-    ),
-  }
-}
-
-///
-fn build_context_evaluator(
-  scope: &Scope,
-  formal_parameters: &[(Name, FeelType)],
-  context: &Context,
-  output_variable_name: Name,
-  output_variable_type: FeelType,
-  knowledge_requirements: &[String],
-) -> Result<BusinessKnowledgeModelEvaluatorFn> {
-  let evaluator = crate::builders::build_context_evaluator(scope, context)?;
-  let function = Value::FunctionDefinition(formal_parameters.to_owned(), FunctionBody::Context(Arc::new(evaluator)), output_variable_type);
-  build_evaluator(output_variable_name, function, knowledge_requirements)
-}
-
-///
-fn build_decision_table_evaluator(
-  scope: &Scope,
-  formal_parameters: &[(Name, FeelType)],
-  decision_table: &DecisionTable,
-  output_variable_name: Name,
-  output_variable_type: FeelType,
-  knowledge_requirements: &[String],
-) -> Result<BusinessKnowledgeModelEvaluatorFn> {
-  let evaluator = crate::builders::build_decision_table_evaluator(scope, decision_table)?;
-  let function = Value::FunctionDefinition(
-    formal_parameters.to_owned(),
-    FunctionBody::DecisionTable(Arc::new(evaluator)),
-    output_variable_type,
-  );
-  build_evaluator(output_variable_name, function, knowledge_requirements)
-}
-
-///
-fn build_function_definition_evaluator(
-  scope: &Scope,
-  formal_parameters: &[(Name, FeelType)],
-  function_definition: &FunctionDefinition,
-  output_variable_name: Name,
-  output_variable_type: FeelType,
-  knowledge_requirements: &[String],
-) -> Result<BusinessKnowledgeModelEvaluatorFn> {
-  let evaluator = crate::builders::build_function_definition_evaluator(scope, function_definition)?;
-  let function = Value::FunctionDefinition(
-    formal_parameters.to_owned(),
-    FunctionBody::DecisionTable(Arc::new(evaluator)),
-    output_variable_type,
-  );
-  build_evaluator(output_variable_name, function, knowledge_requirements)
-}
-
-///
-fn build_invocation_evaluator(
-  scope: &Scope,
-  formal_parameters: &[(Name, FeelType)],
-  invocation: &Invocation,
-  output_variable_name: Name,
-  output_variable_type: FeelType,
-  knowledge_requirements: &[String],
-) -> Result<BusinessKnowledgeModelEvaluatorFn> {
-  let evaluator = crate::builders::build_invocation_evaluator(scope, invocation)?;
-  let function = Value::FunctionDefinition(
-    formal_parameters.to_owned(),
-    FunctionBody::DecisionTable(Arc::new(evaluator)),
-    output_variable_type,
-  );
-  build_evaluator(output_variable_name, function, knowledge_requirements)
-}
-
-///
-fn build_literal_expression_evaluator(
-  scope: &Scope,
-  formal_parameters: &[(Name, FeelType)],
-  literal_expression: &LiteralExpression,
-  output_variable_name: Name,
-  output_variable_type: FeelType,
-  knowledge_requirements: &[String],
-) -> Result<BusinessKnowledgeModelEvaluatorFn> {
-  let evaluator = crate::builders::build_literal_expression_evaluator(scope, literal_expression)?;
-  let function = Value::FunctionDefinition(
-    formal_parameters.to_owned(),
-    FunctionBody::LiteralExpression(Arc::new(evaluator)),
-    output_variable_type,
-  );
-  build_evaluator(output_variable_name, function, knowledge_requirements)
-}
-
-///
-fn build_relation_evaluator(
-  scope: &Scope,
-  formal_parameters: &[(Name, FeelType)],
-  relation: &Relation,
-  output_variable_name: Name,
-  output_variable_type: FeelType,
-  knowledge_requirements: &[String],
-) -> Result<BusinessKnowledgeModelEvaluatorFn> {
-  let evaluator = crate::builders::build_relation_evaluator(scope, relation)?;
-  let function = Value::FunctionDefinition(
-    formal_parameters.to_owned(),
-    FunctionBody::LiteralExpression(Arc::new(evaluator)),
-    output_variable_type,
-  );
-  build_evaluator(output_variable_name, function, knowledge_requirements)
-}
-
 ///
 fn build_evaluator(name: Name, function: Value, knowledge_requirements: &[String]) -> Result<BusinessKnowledgeModelEvaluatorFn> {
   let requirements = knowledge_requirements.to_owned();
@@ -33,14 +33,19 @@
 //! Builder for decision evaluators.
 
 use crate::builders::Variable;
+use crate::decision_cache::{cache_result, cached_result};
 use crate::errors::*;
 use crate::model_evaluator::ModelEvaluator;
+use crate::rule_hits::{assign_rule_hits, rule_hits_count};
+use crate::validation::{is_validating, report_violation};
 use dmntk_common::Result;
 use dmntk_feel::context::FeelContext;
 use dmntk_feel::values::Value;
-use dmntk_feel::{Name, Scope};
+use dmntk_feel::{value_null, Evaluator, FeelType, Name, Scope};
+use dmntk_feel_evaluator::TypeError;
 use dmntk_model::model::{Decision, Definitions, DmnElement, KnowledgeRequirement, NamedElement, RequiredVariable};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, Weak};
 
 /// Type of closure that evaluates a decision.
 ///
@@ -48,42 +53,104 @@ use std::collections::HashMap;
 ///
 type DecisionEvaluatorFn = Box<dyn Fn(&FeelContext, &ModelEvaluator, &mut FeelContext) -> Name + Send + Sync>;
 
-///
-type DecisionEvaluatorEntry = (Variable, DecisionEvaluatorFn);
+/// Decision evaluator entry: output variable, evaluator closure,
+/// inferred result type and type errors detected in decision logic.
+type DecisionEvaluatorEntry = (Variable, DecisionEvaluatorFn, FeelType, Vec<TypeError>);
 
 ///
 #[derive(Default)]
 pub struct DecisionEvaluator {
+  /// Namespace of the model defining evaluated decisions.
+  namespace: String,
   evaluators: HashMap<String, DecisionEvaluatorEntry>,
+  /// Names of input data required by decisions, directly or through required decisions, indexed by decision identifier.
+  required_input_data: HashMap<String, Vec<Name>>,
 }
 
 impl DecisionEvaluator {
   /// Creates a new decision evaluator.
-  pub fn build(&mut self, definitions: &Definitions, model_evaluator: &ModelEvaluator) -> Result<()> {
+  pub fn build(&mut self, definitions: &Definitions, model_evaluator: &Arc<ModelEvaluator>) -> Result<()> {
+    self.namespace = definitions.namespace().to_string();
     for decision in definitions.decisions() {
       let evaluator_entry = build_decision_evaluator(definitions, decision, model_evaluator)?;
       let decision_id = decision.id().as_ref().ok_or_else(err_empty_identifier)?;
       let decision_name = &decision.name().to_string();
+      let required_input_data = collect_required_input_data(definitions, decision)?;
       self.evaluators.insert(decision_id.to_owned(), evaluator_entry);
+      self.required_input_data.insert(decision_id.to_owned(), required_input_data);
       model_evaluator.add_invocable_decision(decision_name, decision_id);
     }
     Ok(())
   }
   /// Evaluates a decision with specified identifier.
+  ///
+  /// When the decision was already evaluated with the same input data in the current request,
+  /// the cached result is placed in evaluated context, without evaluating the decision again.
   pub fn evaluate(&self, decision_id: &str, input_data: &FeelContext, model_evaluator: &ModelEvaluator, evaluated_ctx: &mut FeelContext) -> Option<Name> {
-    self
-      .evaluators
-      .get(decision_id)
-      .map(|evaluator_entry| evaluator_entry.1(input_data, model_evaluator, evaluated_ctx))
+    let evaluator_entry = self.evaluators.get(decision_id)?;
+    if let Some((output_variable_name, value)) = cached_result(&self.namespace, decision_id, input_data) {
+      evaluated_ctx.set_entry(&output_variable_name, value);
+      return Some(output_variable_name);
+    }
+    let output_variable_name = evaluator_entry.1(input_data, model_evaluator, evaluated_ctx);
+    if let Some(value) = evaluated_ctx.get_entry(&output_variable_name) {
+      cache_result(&self.namespace, decision_id, input_data, &output_variable_name, value.clone());
+    }
+    Some(output_variable_name)
   }
   /// Returns the name and type of the output variable of a decision with specified identifier.
   pub fn get_output_variable(&self, decision_id: &str) -> Option<&Variable> {
     self.evaluators.get(decision_id).map(|entry| &entry.0)
   }
+  /// Returns the inferred result type of a decision with specified identifier.
+  ///
+  /// When the output variable has a type reference, the result is coerced to this type,
+  /// so the declared type is returned, otherwise the type inferred from decision logic is returned.
+  pub fn get_result_type(&self, decision_id: &str) -> Option<&FeelType> {
+    self.evaluators.get(decision_id).map(|entry| &entry.2)
+  }
+  /// Returns type errors detected in the logic of a decision with specified identifier.
+  pub fn get_type_errors(&self, decision_id: &str) -> Option<&[TypeError]> {
+    self.evaluators.get(decision_id).map(|entry| entry.3.as_slice())
+  }
+  /// Returns sorted names of input data required by a decision with specified identifier,
+  /// directly or through required decisions.
+  pub fn get_required_input_data(&self, decision_id: &str) -> Option<&[Name]> {
+    self.required_input_data.get(decision_id).map(|names| names.as_slice())
+  }
 }
 
+/// Collects sorted names of input data required by the decision,
+/// walking the graph of information requirements of the decision and all its required decisions.
 ///
-fn build_decision_evaluator(definitions: &Definitions, decision: &Decision, model_evaluator: &ModelEvaluator) -> Result<DecisionEvaluatorEntry> {
+/// The result is a static over-approximation, all input data reachable through information requirements
+/// are collected, regardless of whether their values are used when the decision is evaluated.
+fn collect_required_input_data(definitions: &Definitions, decision: &Decision) -> Result<Vec<Name>> {
+  let mut required_input_data = BTreeSet::new();
+  let mut visited_decisions = HashSet::new();
+  let mut pending_decisions = vec![decision];
+  while let Some(pending_decision) = pending_decisions.pop() {
+    for information_requirement in pending_decision.information_requirements() {
+      if let Some(href) = information_requirement.required_decision() {
+        let required_decision_id: &str = href.into();
+        if visited_decisions.insert(required_decision_id) {
+          if let Some(required_decision) = definitions.decision_by_id(required_decision_id) {
+            pending_decisions.push(required_decision);
+          }
+        }
+      }
+      if let Some(href) = information_requirement.required_input() {
+        if let Some(input_data) = definitions.input_data_by_id(href.into()) {
+          required_input_data.insert(input_data.variable().feel_name().as_ref().ok_or_else(err_empty_feel_name)?.clone());
+        }
+      }
+    }
+  }
+  Ok(required_input_data.into_iter().collect())
+}
+
+///
+fn build_decision_evaluator(definitions: &Definitions, decision: &Decision, model_evaluator: &Arc<ModelEvaluator>) -> Result<DecisionEvaluatorEntry> {
   // acquire all needed evaluators
   let item_definition_type_evaluator = model_evaluator.item_definition_type_evaluator()?;
   let item_definition_context_evaluator = model_evaluator.item_definition_context_evaluator()?;
@@ -94,6 +161,9 @@ fn build_decision_evaluator(definitions: &Definitions, decision: &Decision, mode
   let output_variable_name = output_variable.name.clone();
   // prepare output variable type for this decision
   let output_variable_type = output_variable.feel_type(&item_definition_type_evaluator);
+  // prepare output variable type reference and constraint for validating the result of this decision
+  let output_variable_type_ref = output_variable.type_ref.clone();
+  let output_variable_constraint = output_variable.constraint(definitions);
   // prepare expression instance for this decision
   let expression_instance = decision.decision_logic().as_ref().ok_or_else(err_empty_decision_logic)?;
   let mut ctx = FeelContext::default();
@@ -118,10 +188,18 @@ fn build_decision_evaluator(definitions: &Definitions, decision: &Decision, mode
   }
   // prepare a scope and build expression instance evaluator
   let scope: Scope = ctx.into();
-  let evaluator = crate::builders::build_expression_instance_evaluator(&scope, expression_instance)?;
+  let evaluator =
+    crate::builders::build_expression_instance_evaluator(&scope, expression_instance, model_evaluator.backend(), &item_definition_type_evaluator)?;
+  // infer the result type of this decision
+  let (inferred_type, type_errors) = crate::builders::infer_expression_instance_type(&scope, expression_instance);
+  let result_type = if output_variable_type == FeelType::Any {
+    inferred_type
+  } else {
+    output_variable_type.clone()
+  };
   // prepare references to required knowledge, decisions and input data
   let mut required_knowledge_references: Vec<String> = vec![];
-  let mut required_decision_references: Vec<String> = vec![];
+  let mut required_decision_references: Vec<(String, Name)> = vec![];
   let mut required_input_data_references: Vec<String> = vec![];
   for knowledge_requirement in decision.knowledge_requirements() {
     if let Some(href) = knowledge_requirement.required_knowledge() {
@@ -130,57 +208,87 @@ fn build_decision_evaluator(definitions: &Definitions, decision: &Decision, mode
   }
   for information_requirement in decision.information_requirements() {
     if let Some(href) = information_requirement.required_decision() {
-      required_decision_references.push(href.into())
+      let required_decision_id: &str = href.into();
+      if let Some(required_decision) = definitions.decision_by_id(required_decision_id) {
+        let required_decision_name = required_decision.variable().feel_name().as_ref().ok_or_else(err_empty_feel_name)?.clone();
+        required_decision_references.push((required_decision_id.to_string(), required_decision_name));
+      }
     }
     if let Some(href) = information_requirement.required_input() {
       required_input_data_references.push(href.into())
     }
   }
+  // prepare the name of this decision, assigned to reported rule hits
+  let own_decision_name = decision.name().to_string();
+  // required decisions are evaluated using weak reference to model evaluator, to avoid reference cycles
+  let weak_model_evaluator = Arc::downgrade(model_evaluator);
   // build decision evaluator closure
   let decision_evaluator = Box::new(
     move |input_data_ctx: &FeelContext, model_evaluator: &ModelEvaluator, output_data_ctx: &mut FeelContext| {
       // acquire all evaluators needed
       if let Ok(business_knowledge_model_evaluator) = model_evaluator.business_knowledge_model_evaluator() {
         if let Ok(decision_service_evaluator) = model_evaluator.decision_service_evaluator() {
-          if let Ok(decision_evaluator) = model_evaluator.decision_evaluator() {
-            if let Ok(input_data_evaluator) = model_evaluator.input_data_evaluator() {
-              if let Ok(item_definition_evaluator) = model_evaluator.item_definition_evaluator() {
-                // prepare context containing values from required knowledge and required decisions
-                let mut required_knowledge_ctx: FeelContext = Default::default();
-                // evaluate required knowledge as values from business knowledge models
-                required_knowledge_references.iter().for_each(|business_knowledge_model_identifier| {
-                  business_knowledge_model_evaluator.evaluate(
-                    business_knowledge_model_identifier,
-                    input_data_ctx,
-                    model_evaluator,
-                    &mut required_knowledge_ctx,
-                  )
-                });
-                // evaluate required knowledge as decision service function definitions
-                required_knowledge_references.iter().for_each(|decision_service_id| {
-                  decision_service_evaluator.evaluate_as_function_definition(decision_service_id, input_data_ctx, &mut required_knowledge_ctx)
-                });
-                // evaluate required decisions as values from decisions
-                required_decision_references.iter().for_each(|decision_identifier| {
-                  decision_evaluator.evaluate(decision_identifier, input_data_ctx, model_evaluator, &mut required_knowledge_ctx);
-                });
-                // values from required knowledge may be overridden by input data
-                required_knowledge_ctx.overwrite(input_data_ctx);
-                // prepare context containing values from required input data
-                let mut required_input_ctx: FeelContext = Default::default();
-                let input_data = Value::Context(input_data_ctx.clone());
-                required_input_data_references.iter().for_each(|input_data_id| {
-                  if let Some((name, value)) = input_data_evaluator.evaluate(input_data_id, &input_data, &item_definition_evaluator) {
-                    required_input_ctx.set_entry(&name, value);
+          if let Ok(input_data_evaluator) = model_evaluator.input_data_evaluator() {
+            if let Ok(item_definition_evaluator) = model_evaluator.item_definition_evaluator() {
+              // prepare context containing values from required knowledge and required decisions
+              let mut required_knowledge_ctx: FeelContext = Default::default();
+              // evaluate required knowledge as values from business knowledge models
+              required_knowledge_references.iter().for_each(|business_knowledge_model_identifier| {
+                business_knowledge_model_evaluator.evaluate(
+                  business_knowledge_model_identifier,
+                  input_data_ctx,
+                  model_evaluator,
+                  &mut required_knowledge_ctx,
+                )
+              });
+              // evaluate required knowledge as decision service function definitions
+              required_knowledge_references.iter().for_each(|decision_service_id| {
+                decision_service_evaluator.evaluate_as_function_definition(decision_service_id, input_data_ctx, &mut required_knowledge_ctx)
+              });
+              // values from required knowledge may be overridden by input data
+              required_knowledge_ctx.overwrite(input_data_ctx);
+              // values of required decisions may be overridden by input data too,
+              // otherwise required decisions are evaluated later, only when their values are needed
+              let mut lazy_decisions = vec![];
+              let mut shared_input_data_ctx = None;
+              for (decision_identifier, decision_name) in &required_decision_references {
+                if let Some(value) = input_data_ctx.get_entry(decision_name) {
+                  required_knowledge_ctx.set_entry(decision_name, value.clone());
+                } else {
+                  let input_data_ctx = shared_input_data_ctx.get_or_insert_with(|| Arc::new(input_data_ctx.clone()));
+                  let evaluator = lazy_decision_evaluator(&weak_model_evaluator, decision_identifier, decision_name, input_data_ctx);
+                  lazy_decisions.push((decision_name, evaluator));
+                }
+              }
+              // prepare context containing values from required input data
+              let mut required_input_ctx: FeelContext = Default::default();
+              let input_data = Value::Context(input_data_ctx.clone());
+              required_input_data_references.iter().for_each(|input_data_id| {
+                if let Some((name, value)) = input_data_evaluator.evaluate(input_data_id, &input_data, &item_definition_evaluator) {
+                  required_input_ctx.set_entry(&name, value);
+                }
+              });
+              required_input_ctx.zip(&required_knowledge_ctx);
+              // place the result under the name of the output variable
+              let scope: Scope = required_input_ctx.into();
+              for (decision_name, evaluator) in lazy_decisions {
+                scope.set_lazy_entry(decision_name, evaluator);
+              }
+              let rule_hits_start = rule_hits_count();
+              let decision_result = evaluator(&scope);
+              assign_rule_hits(rule_hits_start, &own_decision_name);
+              let coerced_decision_result = output_variable_type.coerced(&decision_result);
+              // report the result violating the type reference or allowed values of the output variable
+              if is_validating() && !decision_result.is_null() {
+                if let (Some(type_ref), Some(constraint)) = (&output_variable_type_ref, &output_variable_constraint) {
+                  let violated =
+                    coerced_decision_result.is_null() || matches!(item_definition_evaluator.eval(type_ref, &coerced_decision_result), Some(Value::Null(_)));
+                  if violated {
+                    report_violation(format!("decision '{}'", output_variable_name), &decision_result, constraint.clone());
                   }
-                });
-                required_input_ctx.zip(&required_knowledge_ctx);
-                // place the result under the name of the output variable
-                let scope: Scope = required_input_ctx.into();
-                let decision_result = evaluator(&scope);
-                let coerced_decision_result = output_variable_type.coerced(&decision_result);
-                output_data_ctx.set_entry(&output_variable_name, coerced_decision_result);
+                }
               }
+              output_data_ctx.set_entry(&output_variable_name, coerced_decision_result);
             }
           }
         }
@@ -189,8 +297,30 @@ fn build_decision_evaluator(definitions: &Definitions, decision: &Decision, mode
       output_variable_name.clone()
     },
   );
-  // return the output variable, and decision evaluator closure
-  Ok((output_variable, decision_evaluator))
+  // return the output variable, decision evaluator closure, result type and type errors
+  Ok((output_variable, decision_evaluator, result_type, type_errors))
+}
+
+/// Builds an evaluator of the required decision, used to evaluate the decision only when its value is needed.
+fn lazy_decision_evaluator(
+  model_evaluator: &Weak<ModelEvaluator>,
+  decision_id: &str,
+  decision_name: &Name,
+  input_data_ctx: &Arc<FeelContext>,
+) -> Arc<Evaluator> {
+  let model_evaluator = Weak::clone(model_evaluator);
+  let decision_id = decision_id.to_string();
+  let decision_name = decision_name.clone();
+  let input_data_ctx = Arc::clone(input_data_ctx);
+  Arc::new(Box::new(move |_: &Scope| {
+    let mut evaluated_ctx = FeelContext::default();
+    if let Some(model_evaluator) = model_evaluator.upgrade() {
+      if let Ok(decision_evaluator) = model_evaluator.decision_evaluator() {
+        decision_evaluator.evaluate(&decision_id, &input_data_ctx, &model_evaluator, &mut evaluated_ctx);
+      }
+    }
+    evaluated_ctx.get_entry(&decision_name).cloned().unwrap_or_else(|| value_null!())
+  }))
 }
 
 ///
@@ -45,9 +45,9 @@ use std::sync::Arc;
 
 /// Type of closure that evaluates a decision service.
 ///
-/// Fn(input_data, model evaluator, output data)
+/// Fn(input_data, optional names of selected output decisions, model evaluator, output data)
 ///
-type DecisionServiceEvaluatorFn = Box<dyn Fn(&FeelContext, &ModelEvaluator, &mut FeelContext) -> Name + Send + Sync>;
+type DecisionServiceEvaluatorFn = Box<dyn Fn(&FeelContext, Option<&[String]>, &ModelEvaluator, &mut FeelContext) -> Name + Send + Sync>;
 
 ///
 type DecisionServiceEvaluatorEntry = (Variable, DecisionServiceEvaluatorFn, Evaluator);
@@ -64,7 +64,12 @@ impl DecisionServiceEvaluator {
     for decision_service in definitions.decision_services() {
       let decision_service_id = decision_service.id().as_ref().ok_or_else(err_empty_identifier)?;
       let decision_service_name = &decision_service.name().to_string();
-      let evaluator = build_decision_service_evaluator(decision_service, decision_service_id.clone(), Arc::clone(&model_evaluator))?;
+      let evaluator = build_decision_service_evaluator(
+        decision_service,
+        decision_service_id.clone(),
+        decision_service_name.clone(),
+        Arc::clone(&model_evaluator),
+      )?;
       self.evaluators.insert(decision_service_id.to_owned(), evaluator);
       model_evaluator.add_invocable_decision_service(decision_service_name, decision_service_id);
     }
@@ -72,7 +77,22 @@ impl DecisionServiceEvaluator {
   }
   /// Evaluates a decision service with specified identifier.
   pub fn evaluate(&self, id: &str, input_data: &FeelContext, model_evaluator: &ModelEvaluator, output_data: &mut FeelContext) -> Option<Name> {
-    self.evaluators.get(id).map(|entry| entry.1(input_data, model_evaluator, output_data))
+    self.evaluators.get(id).map(|entry| entry.1(input_data, None, model_evaluator, output_data))
+  }
+  /// Evaluates a decision service with specified identifier, only output decisions
+  /// with specified names are evaluated and returned in a context, indexed by decision names.
+  pub fn evaluate_output_decisions(
+    &self,
+    id: &str,
+    input_data: &FeelContext,
+    output_decision_names: &[String],
+    model_evaluator: &ModelEvaluator,
+    output_data: &mut FeelContext,
+  ) -> Option<Name> {
+    self
+      .evaluators
+      .get(id)
+      .map(|entry| entry.1(input_data, Some(output_decision_names), model_evaluator, output_data))
   }
   /// Returns a decision service as function definition with specified identifier.
   pub fn evaluate_as_function_definition(&self, id: &str, input_data: &FeelContext, output_data: &mut FeelContext) {
@@ -89,6 +109,7 @@ impl DecisionServiceEvaluator {
 fn build_decision_service_evaluator(
   decision_service: &DecisionService,
   decision_service_id: String,
+  decision_service_name: String,
   model_evaluator: Arc<ModelEvaluator>,
 ) -> Result<DecisionServiceEvaluatorEntry> {
   // acquire required evaluators
@@ -110,6 +131,11 @@ fn build_decision_service_evaluator(
   let encapsulated_decisions: Vec<String> = decision_service.encapsulated_decisions().iter().map(|href| href.into()).collect();
   // prepare references to output decisions
   let output_decisions: Vec<String> = decision_service.output_decisions().iter().map(|href| href.into()).collect();
+  // prepare names of output decisions, used for selecting output decisions to be evaluated
+  let output_decision_names: Vec<Option<String>> = output_decisions
+    .iter()
+    .map(|decision_id| decision_evaluator.get_output_variable(decision_id).map(|variable| variable.name.to_string()))
+    .collect();
 
   // prepare a container for formal parameters
   let mut formal_parameters = vec![];
@@ -130,11 +156,12 @@ fn build_decision_service_evaluator(
   for decision_id in &input_decisions {
     if let Some(decision_output_variable) = decision_evaluator.get_output_variable(decision_id) {
       let parameter_name = decision_output_variable.name.clone();
+      let parameter_name_clone = parameter_name.clone();
       //let a = model_evaluator.item_definition_type_evaluator()?;
       let parameter_type = decision_output_variable.feel_type(&item_definition_type_evaluator);
       formal_parameters.push((parameter_name, parameter_type));
       let evaluator = build_variable_evaluator(decision_output_variable)?;
-      input_decision_results_evaluators.push(evaluator);
+      input_decision_results_evaluators.push((decision_id.clone(), parameter_name_clone, evaluator));
     }
   }
   // clone the output variable type for later use
@@ -145,27 +172,43 @@ fn build_decision_service_evaluator(
   drop(decision_evaluator);
   // build decision service evaluator closure
   let decision_service_evaluator = Box::new(
-    move |input_data: &FeelContext, model_evaluator: &ModelEvaluator, output_data: &mut FeelContext| {
+    move |input_data: &FeelContext, selected_output_decisions: Option<&[String]>, model_evaluator: &ModelEvaluator, output_data: &mut FeelContext| {
+      // check if all selected output decisions are output decisions of this decision service
+      if let Some(selected_output_decisions) = selected_output_decisions {
+        if let Some(name) = selected_output_decisions
+          .iter()
+          .find(|name| !output_decision_names.iter().flatten().any(|output_decision_name| output_decision_name == *name))
+        {
+          output_data.set_entry(
+            &output_variable_name,
+            value_null!("decision '{}' is not an output decision of decision service '{}'", name, decision_service_name),
+          );
+          return output_variable_name.clone();
+        }
+      }
       // acquire all evaluators needed
       if let Ok(item_definition_evaluator) = model_evaluator.item_definition_evaluator() {
         if let Ok(input_data_evaluator) = model_evaluator.input_data_evaluator() {
           if let Ok(decision_evaluator) = model_evaluator.decision_evaluator() {
-            // evaluate input decisions and store the results in separate context
+            // evaluate input data for encapsulated and output decisions and store them in separate context
+            let mut evaluated_input_data = FeelContext::default();
+            // evaluate input decisions not provided in input data and store the results in separate context,
+            // input decisions provided in input data are not evaluated
             let mut input_decisions_results = FeelContext::default();
-            input_decisions.iter().for_each(|id| {
-              decision_evaluator.evaluate(id, input_data, model_evaluator, &mut input_decisions_results);
+            input_decision_results_evaluators.iter().for_each(|(id, name, _)| {
+              if !input_data.contains_entry(name) {
+                decision_evaluator.evaluate(id, input_data, model_evaluator, &mut input_decisions_results);
+              }
             });
-            // now evaluate input data for encapsulated and output decisions and store them in separate context
-            let mut evaluated_input_data = FeelContext::default();
             // first take values from evaluated input decisions...
             let input_decision_results_value = Value::Context(input_decisions_results);
-            for evaluator in &input_decision_results_evaluators {
+            for (_, _, evaluator) in &input_decision_results_evaluators {
               let (name, value) = evaluator(&input_decision_results_value, &item_definition_evaluator);
               evaluated_input_data.set_entry(&name, value);
             }
             // ...and then take values from provided input data
             let input_data_values = Value::Context(input_data.clone());
-            for evaluator in &input_decision_results_evaluators {
+            for (_, _, evaluator) in &input_decision_results_evaluators {
               let (name, value) = evaluator(&input_data_values, &item_definition_evaluator);
               evaluated_input_data.set_entry(&name, value);
             }
@@ -178,19 +221,39 @@ fn build_decision_service_evaluator(
             // prepare context for evaluated result data for this decision service
             let mut evaluated_ctx = FeelContext::default();
             // acquire decision evaluator
-            // evaluate encapsulated decisions
-            encapsulated_decisions.iter().for_each(|id| {
-              decision_evaluator.evaluate(id, &evaluated_input_data, model_evaluator, &mut evaluated_ctx);
-            });
-            // evaluate output decisions
+            // evaluate encapsulated decisions, when output decisions are selected,
+            // only encapsulated decisions required by selected output decisions are evaluated, when needed
+            if selected_output_decisions.is_none() {
+              encapsulated_decisions.iter().for_each(|id| {
+                decision_evaluator.evaluate(id, &evaluated_input_data, model_evaluator, &mut evaluated_ctx);
+              });
+            }
+            // evaluate output decisions, when output decisions are selected, only selected are evaluated
             let mut output_names = vec![];
-            output_decisions.iter().for_each(|id| {
-              if let Some(output_name) = decision_evaluator.evaluate(id, &evaluated_input_data, model_evaluator, &mut evaluated_ctx) {
-                output_names.push(output_name);
-              }
-            });
+            output_decisions
+              .iter()
+              .zip(output_decision_names.iter())
+              .for_each(|(id, output_decision_name)| {
+                if let Some(selected) = selected_output_decisions {
+                  if !output_decision_name.iter().any(|name| selected.contains(name)) {
+                    return;
+                  }
+                }
+                if let Some(output_name) = decision_evaluator.evaluate(id, &evaluated_input_data, model_evaluator, &mut evaluated_ctx) {
+                  output_names.push(output_name);
+                }
+              });
             // prepare the result from this decision service
-            if output_names.len() == 1 {
+            if selected_output_decisions.is_some() {
+              // the result of selected output decisions is always a context, not coerced to the type of the decision service
+              let mut output_ctx = FeelContext::default();
+              output_names.iter().for_each(|output_name| {
+                if let Some(value) = evaluated_ctx.get_entry(output_name) {
+                  output_ctx.set_entry(output_name, value.to_owned());
+                }
+              });
+              output_data.set_entry(&output_variable_name, Value::Context(output_ctx));
+            } else if output_names.len() == 1 {
               if let Some(value) = evaluated_ctx.get_entry(&output_names[0]) {
                 let single_result = value.to_owned();
                 let coerced_single_result = output_variable_type.coerced(&single_result);
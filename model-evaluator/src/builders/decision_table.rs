@@ -32,10 +32,14 @@
 
 //! Builder for decision table evaluators.
 
+use crate::builders::decision_table_index::DecisionTableIndex;
+use crate::rule_hits::{is_collecting_rule_hits, report_rule_hit, RuleHit};
+use crate::validation::{is_validating, report_violation};
 use dmntk_common::Result;
 use dmntk_feel::context::FeelContext;
 use dmntk_feel::values::{Value, Values};
-use dmntk_feel::{value_null, AstNode, Evaluator, Name, Scope};
+use dmntk_feel::{value_null, AstNode, Evaluator, FeelYearsAndMonthsDuration, Name, Scope};
+use dmntk_feel_evaluator::Backend;
 use dmntk_model::model::{BuiltinAggregator, DecisionTable, HitPolicy};
 use std::cmp::Ordering;
 
@@ -45,6 +49,24 @@ use std::cmp::Ordering;
 struct ParsedRule {
   input_entries_evaluators: Vec<Evaluator>,
   output_entries_evaluators: Vec<Evaluator>,
+  /// Evaluators of output entries not checked against output values, used for validation.
+  unchecked_output_entries_evaluators: Vec<Option<Evaluator>>,
+  /// Optional identifier of the rule, used for reporting rule hits.
+  id: Option<String>,
+  /// Names of rule annotation clauses and texts of annotation entries, used for reporting rule hits.
+  annotations: Vec<(String, String)>,
+}
+
+/// Check of the input value against input values defined in input clause.
+struct InputValuesCheck {
+  /// Text of the input expression.
+  input_expression: String,
+  /// Text of the input values.
+  input_values: String,
+  /// Evaluator of the input expression.
+  input_expression_evaluator: Evaluator,
+  /// Evaluator checking if the value of the input expression is one of the input values.
+  input_values_evaluator: Evaluator,
 }
 
 /// Parsed decision table.
@@ -55,10 +77,18 @@ struct ParsedDecisionTable {
   output_values_evaluators: Vec<Option<Evaluator>>,
   default_output_values_evaluators: Vec<Option<Evaluator>>,
   rules: Vec<ParsedRule>,
+  /// Index narrowing the set of rules evaluated for given input values.
+  index: Option<DecisionTableIndex>,
+  /// Checks of input values, used for validation.
+  input_values_checks: Vec<InputValuesCheck>,
+  /// Names and texts of output values of output clauses, used for validation.
+  output_values_texts: Vec<Option<(String, String)>>,
 }
 
 /// Evaluated rule of a decision table.
 struct EvaluatedRule {
+  /// Index of the rule in the decision table.
+  rule_index: usize,
   matches: bool,
   output_entry_values: Vec<Value>,
 }
@@ -68,7 +98,8 @@ struct EvaluatedRule {
 /// in specified context and results are stored as [Values](Value) in this structure.
 struct EvaluatedDecisionTable {
   component_names: Vec<Name>,
-  output_values: Vec<Value>,
+  /// Output values of each output clause, [None] when output clause has no output values.
+  output_values: Vec<Option<Vec<Value>>>,
   default_output_values: Vec<Value>,
   evaluated_rules: Vec<EvaluatedRule>,
 }
@@ -78,30 +109,36 @@ impl EvaluatedDecisionTable {
   fn get_matching_rules(&self) -> Vec<&EvaluatedRule> {
     self.evaluated_rules.iter().filter(|evaluated_rule| evaluated_rule.matches).collect()
   }
+  /// Returns `true` when none of the output clauses defines output values,
+  /// in such case the priority of matching rules can not be determined.
+  fn has_no_output_values(&self) -> bool {
+    self.output_values.iter().all(Option::is_none)
+  }
   /// Returns all matching rules in decreasing order of priority.
+  ///
+  /// The priority of the rule is decided lexicographically by the outputs of the rule: the position
+  /// of the first output entry value in the output values of the first output, then the position
+  /// of the second output entry value in the output values of the second output, and so on.
+  /// Outputs without output values do not influence the priority. Output entry values not listed
+  /// in output values have the lowest priority. Rules with equal priority remain in rule order.
   fn get_matching_rules_prioritized(&self) -> Vec<&EvaluatedRule> {
     let mut rules: Vec<&EvaluatedRule> = self.evaluated_rules.iter().filter(|v| v.matches).collect();
-    let compare = |x: &&EvaluatedRule, y: &&EvaluatedRule| {
-      for (v1, v2) in x.output_entry_values.iter().zip(y.output_entry_values.iter()) {
-        let index1 = self.output_values.iter().position(|o| o == v1);
-        let index2 = self.output_values.iter().position(|o| o == v2);
-        match (index1, index2) {
-          (Some(ix1), Some(ix2)) => {
-            if ix1 < ix2 {
-              return Ordering::Less;
-            }
-            if ix1 > ix2 {
-              return Ordering::Greater;
-            }
-          }
-          (Some(_), None) => return Ordering::Less,
-          (None, Some(_)) => return Ordering::Greater,
-          _ => {}
-        }
-      }
-      Ordering::Equal
+    let rank = |evaluated_rule: &EvaluatedRule| {
+      evaluated_rule
+        .output_entry_values
+        .iter()
+        .zip(self.output_values.iter())
+        .filter_map(|(value, output_values)| {
+          output_values.as_ref().map(|output_values| {
+            output_values
+              .iter()
+              .position(|output_value| output_value == value)
+              .unwrap_or(output_values.len())
+          })
+        })
+        .collect::<Vec<usize>>()
     };
-    rules.sort_by(compare);
+    rules.sort_by_cached_key(|evaluated_rule| rank(evaluated_rule));
     rules
   }
   /// Returns a result composed from values taken from evaluated output entries.
@@ -127,6 +164,25 @@ impl EvaluatedDecisionTable {
     }
     Value::List(Values::new(values))
   }
+  /// Returns the rules that contributed to the output of the decision table with specified hit policy.
+  fn get_hit_rules(&self, hit_policy: HitPolicy) -> Vec<&EvaluatedRule> {
+    let matching_rules = self.get_matching_rules();
+    match hit_policy {
+      HitPolicy::Unique if matching_rules.len() > 1 => vec![],
+      HitPolicy::Any
+        if matching_rules
+          .iter()
+          .any(|evaluated_rule| evaluated_rule.output_entry_values != matching_rules[0].output_entry_values) =>
+      {
+        vec![]
+      }
+      HitPolicy::Priority | HitPolicy::OutputOrder if self.has_no_output_values() => vec![],
+      HitPolicy::Priority => self.get_matching_rules_prioritized().into_iter().take(1).collect(),
+      HitPolicy::First => matching_rules.into_iter().take(1).collect(),
+      HitPolicy::OutputOrder => self.get_matching_rules_prioritized(),
+      _ => matching_rules,
+    }
+  }
   ///
   fn evaluate_default_output_value(&self) -> Value {
     match self.default_output_values.len() {
@@ -163,6 +219,9 @@ impl EvaluatedDecisionTable {
   }
   ///
   fn evaluate_hit_policy_priority(&self) -> Value {
+    if self.has_no_output_values() {
+      return value_null!("output values are required in decision table with hit policy {}", HitPolicy::Priority);
+    }
     let matching_rules = self.get_matching_rules_prioritized();
     if matching_rules.is_empty() {
       return self.evaluate_default_output_value();
@@ -187,6 +246,9 @@ impl EvaluatedDecisionTable {
   }
   ///
   fn evaluate_hit_policy_output_order(&self) -> Value {
+    if self.has_no_output_values() {
+      return value_null!("output values are required in decision table with hit policy {}", HitPolicy::OutputOrder);
+    }
     let matching_rules = self.get_matching_rules_prioritized();
     if matching_rules.is_empty() {
       return self.evaluate_default_output_value();
@@ -203,6 +265,9 @@ impl EvaluatedDecisionTable {
   }
   ///
   fn evaluate_hit_policy_collect_count(&self) -> Value {
+    if self.component_names.len() > 1 {
+      return value_null!("err_aggregators_not_allowed_for_compound_outputs");
+    }
     let matching_rules = self.get_matching_rules();
     if matching_rules.is_empty() {
       return self.evaluate_default_output_value();
@@ -218,11 +283,7 @@ impl EvaluatedDecisionTable {
     if matching_rules.is_empty() {
       return self.evaluate_default_output_value();
     }
-    let output_values = matching_rules
-      .iter()
-      .map(|evaluated_rule| evaluated_rule.output_entry_values[0].clone())
-      .collect::<Vec<Value>>();
-    dmntk_feel_evaluator::evaluate_sum(output_values)
+    aggregate_sum(&get_aggregated_values(&matching_rules))
   }
   ///
   fn evaluate_hit_policy_collect_min(&self) -> Value {
@@ -233,11 +294,7 @@ impl EvaluatedDecisionTable {
     if matching_rules.is_empty() {
       return self.evaluate_default_output_value();
     }
-    let output_values = matching_rules
-      .iter()
-      .map(|evaluated_rule| evaluated_rule.output_entry_values[0].clone())
-      .collect::<Vec<Value>>();
-    dmntk_feel_evaluator::evaluate_min(output_values)
+    aggregate_extremum(&get_aggregated_values(&matching_rules), "MIN", Ordering::Less)
   }
   ///
   fn evaluate_hit_policy_collect_max(&self) -> Value {
@@ -248,22 +305,116 @@ impl EvaluatedDecisionTable {
     if matching_rules.is_empty() {
       return self.evaluate_default_output_value();
     }
-    let output_values = matching_rules
-      .iter()
-      .map(|evaluated_rule| evaluated_rule.output_entry_values[0].clone())
-      .collect::<Vec<Value>>();
-    dmntk_feel_evaluator::evaluate_max(output_values)
+    aggregate_extremum(&get_aggregated_values(&matching_rules), "MAX", Ordering::Greater)
+  }
+}
+
+/// Returns output values of matching rules to be aggregated, `null` values are not aggregated.
+fn get_aggregated_values<'a>(matching_rules: &[&'a EvaluatedRule]) -> Vec<&'a Value> {
+  matching_rules
+    .iter()
+    .map(|evaluated_rule| &evaluated_rule.output_entry_values[0])
+    .filter(|value| !value.is_null())
+    .collect()
+}
+
+/// Compares two values of the same comparable type.
+/// Returns [None] when the values are not comparable.
+fn compare_values(lhv: &Value, rhv: &Value) -> Option<Ordering> {
+  match (lhv, rhv) {
+    (Value::Number(lh), Value::Number(rh)) => lh.partial_cmp(rh),
+    (Value::String(lh), Value::String(rh)) => Some(lh.cmp(rh)),
+    (Value::Date(lh), Value::Date(rh)) => lh.partial_cmp(rh),
+    (Value::Time(lh), Value::Time(rh)) => ordering(lh.before(rh)?, lh.equal(rh)?),
+    (Value::DateTime(lh), Value::DateTime(rh)) => ordering(lh.before(rh)?, lh.equal(rh)?),
+    (Value::DaysAndTimeDuration(lh), Value::DaysAndTimeDuration(rh)) => lh.partial_cmp(rh),
+    (Value::YearsAndMonthsDuration(lh), Value::YearsAndMonthsDuration(rh)) => lh.partial_cmp(rh),
+    _ => None,
+  }
+}
+
+/// Converts the results of temporal comparison into ordering.
+fn ordering(before: bool, equal: bool) -> Option<Ordering> {
+  Some(if before {
+    Ordering::Less
+  } else if equal {
+    Ordering::Equal
+  } else {
+    Ordering::Greater
+  })
+}
+
+/// Returns the `null` value describing why specified values can not be aggregated.
+fn aggregation_not_defined(aggregator: &str, lhv: &Value, rhv: &Value) -> Value {
+  let (lht, rht) = (lhv.type_of(), rhv.type_of());
+  if lht == rht {
+    value_null!("aggregation {} is not defined for output values of type {}", aggregator, lht)
+  } else {
+    value_null!("aggregation {} is not defined for mixed output types: {} and {}", aggregator, lht, rht)
+  }
+}
+
+/// Returns the minimum or maximum of values, depending on the expected ordering.
+/// All values must have the same comparable type: number, string, date, time,
+/// date and time, days and time duration or years and months duration.
+fn aggregate_extremum(values: &[&Value], aggregator: &str, expected: Ordering) -> Value {
+  if values.is_empty() {
+    return value_null!("no output values to aggregate with {}", aggregator);
+  }
+  let mut result = values[0];
+  if compare_values(result, result).is_none() {
+    return aggregation_not_defined(aggregator, result, result);
+  }
+  for value in values.iter().skip(1) {
+    match compare_values(value, result) {
+      Some(ordering) if ordering == expected => result = value,
+      Some(_) => {}
+      None => return aggregation_not_defined(aggregator, result, value),
+    }
+  }
+  result.clone()
+}
+
+/// Returns the sum of values, all values must be numbers,
+/// days and time durations or years and months durations.
+fn aggregate_sum(values: &[&Value]) -> Value {
+  if values.is_empty() {
+    return value_null!("no output values to aggregate with SUM");
   }
+  let mut sum = values[0].clone();
+  if !matches!(sum, Value::Number(_) | Value::DaysAndTimeDuration(_) | Value::YearsAndMonthsDuration(_)) {
+    return aggregation_not_defined("SUM", &sum, &sum);
+  }
+  for value in values.iter().skip(1) {
+    sum = match (sum, value) {
+      (Value::Number(lh), Value::Number(rh)) => Value::Number(lh + *rh),
+      (Value::DaysAndTimeDuration(lh), Value::DaysAndTimeDuration(rh)) => Value::DaysAndTimeDuration(lh + rh.clone()),
+      (Value::YearsAndMonthsDuration(lh), Value::YearsAndMonthsDuration(rh)) => {
+        Value::YearsAndMonthsDuration(FeelYearsAndMonthsDuration::new_m(lh.as_months() + rh.as_months()))
+      }
+      (lhv, rhv) => return aggregation_not_defined("SUM", &lhv, rhv),
+    };
+  }
+  sum
 }
 
 ///
-fn parse_decision_table(scope: &Scope, decision_table: &DecisionTable) -> Result<ParsedDecisionTable> {
+fn parse_decision_table(scope: &Scope, decision_table: &DecisionTable, backend: Backend) -> Result<ParsedDecisionTable> {
   // parse input expressions and input values
   let mut input_expressions_and_values = vec![];
+  let mut input_values_checks = vec![];
   for input_clause in &decision_table.input_clauses {
     let input_expression = dmntk_feel_parser::parse_expression(scope, &input_clause.input_expression, false)?;
     let input_values = if let Some(input) = &input_clause.input_values {
-      Some(dmntk_feel_parser::parse_unary_tests(scope, input, false)?)
+      let input_values_node = dmntk_feel_parser::parse_unary_tests(scope, input, false)?;
+      let node = AstNode::In(Box::new(input_expression.clone()), Box::new(input_values_node.clone()));
+      input_values_checks.push(InputValuesCheck {
+        input_expression: input_clause.input_expression.clone(),
+        input_values: input.clone(),
+        input_expression_evaluator: dmntk_feel_evaluator::prepare_with(&input_expression, backend)?,
+        input_values_evaluator: dmntk_feel_evaluator::prepare_with(&node, backend)?,
+      });
+      Some(input_values_node)
     } else {
       None
     };
@@ -273,11 +424,21 @@ fn parse_decision_table(scope: &Scope, decision_table: &DecisionTable) -> Result
   let mut component_names = vec![];
   let mut output_values_nodes = vec![];
   let mut default_output_values_nodes = vec![];
+  let mut output_values_texts = vec![];
   for output_clause in &decision_table.output_clauses {
     if let Some(text) = &output_clause.output_values {
       output_values_nodes.push(Some(dmntk_feel_parser::parse_unary_tests(scope, text, false)?));
+      let output_name = output_clause
+        .name
+        .as_ref()
+        .or(decision_table.output_label.as_ref())
+        .or(decision_table.information_item_name.as_ref())
+        .cloned()
+        .unwrap_or_default();
+      output_values_texts.push(Some((output_name, text.clone())));
     } else {
       output_values_nodes.push(None);
+      output_values_texts.push(None);
     }
     if let Some(text) = &output_clause.default_output_entry {
       default_output_values_nodes.push(Some(dmntk_feel_parser::parse_unary_tests(scope, text, false)?));
@@ -290,41 +451,64 @@ fn parse_decision_table(scope: &Scope, decision_table: &DecisionTable) -> Result
   }
   // parse all rules
   let mut parsed_rules = vec![];
+  let mut input_entries_nodes = vec![];
   for rule in &decision_table.rules {
     // parse input clause
     let mut input_entries_evaluators = vec![];
+    let mut rule_input_entries_nodes = vec![];
     for (i, (input_expression, input_values)) in input_expressions_and_values.iter().enumerate() {
       let input_entry_node = dmntk_feel_parser::parse_unary_tests(scope, &rule.input_entries[i].text, false)?;
+      rule_input_entries_nodes.push(input_entry_node.clone());
       if let Some(input_values_node) = input_values {
         let left = AstNode::In(Box::new(input_expression.clone()), Box::new(input_values_node.clone()));
         let right = AstNode::In(Box::new(input_expression.clone()), Box::new(input_entry_node));
         let node = AstNode::And(Box::new(left), Box::new(right));
-        input_entries_evaluators.push(dmntk_feel_evaluator::prepare(&node)?);
+        input_entries_evaluators.push(dmntk_feel_evaluator::prepare_with(&node, backend)?);
       } else {
         let node = AstNode::In(Box::new(input_expression.clone()), Box::new(input_entry_node));
-        input_entries_evaluators.push(dmntk_feel_evaluator::prepare(&node)?);
+        input_entries_evaluators.push(dmntk_feel_evaluator::prepare_with(&node, backend)?);
       }
     }
     // parse output clause
     let mut output_entries_evaluators = vec![];
+    let mut unchecked_output_entries_evaluators = vec![];
     for (i, output_values) in output_values_nodes.iter().enumerate() {
       let output_entry_node = dmntk_feel_parser::parse_expression(scope, &rule.output_entries[i].text, false)?;
       if let Some(output_value_node) = output_values {
+        unchecked_output_entries_evaluators.push(Some(dmntk_feel_evaluator::prepare_with(&output_entry_node, backend)?));
         let node = AstNode::Out(Box::new(output_entry_node), Box::new(output_value_node.clone()));
-        output_entries_evaluators.push(dmntk_feel_evaluator::prepare(&node)?);
+        output_entries_evaluators.push(dmntk_feel_evaluator::prepare_with(&node, backend)?);
       } else {
-        output_entries_evaluators.push(dmntk_feel_evaluator::prepare(&output_entry_node)?);
+        unchecked_output_entries_evaluators.push(None);
+        output_entries_evaluators.push(dmntk_feel_evaluator::prepare_with(&output_entry_node, backend)?);
       }
     }
+    // collect annotations
+    let annotations = decision_table
+      .annotations
+      .iter()
+      .zip(rule.annotation_entries.iter())
+      .map(|(annotation_clause, annotation_entry)| (annotation_clause.name.clone(), annotation_entry.text.clone()))
+      .collect();
     parsed_rules.push(ParsedRule {
       input_entries_evaluators,
       output_entries_evaluators,
-    })
+      unchecked_output_entries_evaluators,
+      id: rule.id.clone(),
+      annotations,
+    });
+    input_entries_nodes.push(rule_input_entries_nodes);
   }
+  // build the index of rules
+  let input_expressions = input_expressions_and_values
+    .into_iter()
+    .map(|(input_expression, _)| input_expression)
+    .collect::<Vec<AstNode>>();
+  let index = DecisionTableIndex::build(&input_expressions, &input_entries_nodes, backend)?;
   let mut output_values_evaluators = vec![];
   for opt_node in output_values_nodes {
     if let Some(node) = opt_node {
-      output_values_evaluators.push(Some(dmntk_feel_evaluator::prepare(&node)?));
+      output_values_evaluators.push(Some(dmntk_feel_evaluator::prepare_with(&node, backend)?));
     } else {
       output_values_evaluators.push(None);
     }
@@ -332,7 +516,7 @@ fn parse_decision_table(scope: &Scope, decision_table: &DecisionTable) -> Result
   let mut default_output_values_evaluators = vec![];
   for opt_node in default_output_values_nodes {
     if let Some(node) = opt_node {
-      default_output_values_evaluators.push(Some(dmntk_feel_evaluator::prepare(&node)?));
+      default_output_values_evaluators.push(Some(dmntk_feel_evaluator::prepare_with(&node, backend)?));
     } else {
       default_output_values_evaluators.push(None);
     }
@@ -342,19 +526,39 @@ fn parse_decision_table(scope: &Scope, decision_table: &DecisionTable) -> Result
     output_values_evaluators,
     default_output_values_evaluators,
     rules: parsed_rules,
+    index,
+    input_values_checks,
+    output_values_texts,
   })
 }
 
 ///
 fn evaluate_parsed_decision_table(scope: &Scope, parsed_decision_table: &ParsedDecisionTable) -> EvaluatedDecisionTable {
-  // evaluate only non-empty output values
-  let mut output_values = vec![];
-  for evaluator in parsed_decision_table.output_values_evaluators.iter().flatten() {
-    let value = evaluator(scope);
-    if let Value::ExpressionList(values) = value {
-      output_values.append(&mut values.as_vec().to_owned());
+  let validating = is_validating();
+  // report input values not being one of input values defined in input clauses
+  if validating {
+    for check in &parsed_decision_table.input_values_checks {
+      let input_value = (check.input_expression_evaluator)(scope);
+      if !input_value.is_null() && !(check.input_values_evaluator)(scope).is_true() {
+        report_violation(
+          format!("decision table input '{}'", check.input_expression),
+          &input_value,
+          format!("input values '{}'", check.input_values),
+        );
+      }
     }
   }
+  // evaluate output values of each output clause
+  let mut output_values = vec![];
+  for opt_evaluator in &parsed_decision_table.output_values_evaluators {
+    output_values.push(opt_evaluator.as_ref().and_then(|evaluator| {
+      if let Value::ExpressionList(values) = evaluator(scope) {
+        Some(values.as_vec().to_owned())
+      } else {
+        None
+      }
+    }));
+  }
   // evaluate only non-empty default output values
   let mut default_output_values = vec![];
   for evaluator in parsed_decision_table.default_output_values_evaluators.iter().flatten() {
@@ -362,23 +566,43 @@ fn evaluate_parsed_decision_table(scope: &Scope, parsed_decision_table: &ParsedD
       default_output_values.append(&mut values.as_vec().to_owned());
     }
   }
-  // evaluate all rules
+  // evaluate all candidate rules, when the index is present, only rules selected by the index are candidates
+  let candidates = parsed_decision_table.index.as_ref().map(|index| index.candidates(scope));
   let mut evaluated_rules = vec![];
-  for parsed_rule in &parsed_decision_table.rules {
-    let mut input_entry_values = vec![];
-    let mut matches = true;
-    for evaluator in &parsed_rule.input_entries_evaluators {
-      let input_value = evaluator(scope);
-      if !input_value.is_true() {
-        matches = false;
-      }
-      input_entry_values.push(input_value);
+  for (rule_index, parsed_rule) in parsed_decision_table.rules.iter().enumerate() {
+    if matches!(&candidates, Some(candidates) if !candidates[rule_index]) {
+      continue;
     }
+    let matches = parsed_rule.input_entries_evaluators.iter().all(|evaluator| evaluator(scope).is_true());
+    // output entries are evaluated only for matching rules
     let mut output_entry_values = vec![];
-    for evaluator in &parsed_rule.output_entries_evaluators {
-      output_entry_values.push(evaluator(scope));
+    if matches {
+      for (i, evaluator) in parsed_rule.output_entries_evaluators.iter().enumerate() {
+        let output_entry_value = evaluator(scope);
+        // report output values not being one of output values defined in output clauses
+        if validating && output_entry_value.is_null() {
+          if let (Some(Some(unchecked_evaluator)), Some(Some((output_name, output_values)))) = (
+            parsed_rule.unchecked_output_entries_evaluators.get(i),
+            parsed_decision_table.output_values_texts.get(i),
+          ) {
+            let unchecked_value = unchecked_evaluator(scope);
+            if !unchecked_value.is_null() {
+              report_violation(
+                format!("decision table output '{}' in rule {}", output_name, rule_index + 1),
+                &unchecked_value,
+                format!("output values '{}'", output_values),
+              );
+            }
+          }
+        }
+        output_entry_values.push(output_entry_value);
+      }
     }
-    evaluated_rules.push(EvaluatedRule { matches, output_entry_values })
+    evaluated_rules.push(EvaluatedRule {
+      rule_index,
+      matches,
+      output_entry_values,
+    })
   }
   EvaluatedDecisionTable {
     component_names: parsed_decision_table.component_names.clone(),
@@ -390,10 +614,27 @@ fn evaluate_parsed_decision_table(scope: &Scope, parsed_decision_table: &ParsedD
 
 ///
 pub fn build_decision_table_evaluator(scope: &Scope, decision_table: &DecisionTable) -> Result<Evaluator> {
+  build_decision_table_evaluator_with_backend(scope, decision_table, Backend::default())
+}
+
+/// Builds decision table evaluator, all expressions in decision table are evaluated using specified backend.
+pub fn build_decision_table_evaluator_with_backend(scope: &Scope, decision_table: &DecisionTable, backend: Backend) -> Result<Evaluator> {
   let hit_policy = decision_table.hit_policy;
-  let parsed_decision_table = parse_decision_table(scope, decision_table)?;
+  let parsed_decision_table = parse_decision_table(scope, decision_table, backend)?;
   Ok(Box::new(move |scope: &Scope| {
     let evaluated_decision_table = evaluate_parsed_decision_table(scope, &parsed_decision_table);
+    // report rules that contributed to the output
+    if is_collecting_rule_hits() {
+      for evaluated_rule in evaluated_decision_table.get_hit_rules(hit_policy) {
+        let parsed_rule = &parsed_decision_table.rules[evaluated_rule.rule_index];
+        report_rule_hit(RuleHit {
+          decision: None,
+          index: evaluated_rule.rule_index + 1,
+          id: parsed_rule.id.clone(),
+          annotations: parsed_rule.annotations.clone(),
+        });
+      }
+    }
     match hit_policy {
       HitPolicy::Unique => evaluated_decision_table.evaluate_hit_policy_unique(),
       HitPolicy::Any => evaluated_decision_table.evaluate_hit_policy_any(),
@@ -419,6 +660,9 @@ mod tests {
   use dmntk_examples::decision_tables::DT_0001;
   use dmntk_feel::values::Value;
   use dmntk_feel::{value_number, FeelNumber};
+  use dmntk_model::model::{
+    BuiltinAggregator, DecisionRule, DecisionTable, DecisionTableOrientation, HitPolicy, InputClause, InputEntry, OutputClause, OutputEntry,
+  };
 
   #[test]
   fn test() {
@@ -427,4 +671,252 @@ mod tests {
     let evaluator = build_decision_table_evaluator(&scope, &decision_table).unwrap();
     assert_eq!(value_number!(10, 2), evaluator(&scope));
   }
+
+  /// Builds a decision table with specified hit policy, input expressions and rules.
+  fn decision_table(hit_policy: HitPolicy, input_expressions: &[&str], rules: Vec<(Vec<String>, String)>) -> DecisionTable {
+    DecisionTable {
+      information_item_name: None,
+      input_clauses: input_expressions
+        .iter()
+        .map(|input_expression| InputClause {
+          input_expression: input_expression.to_string(),
+          input_values: None,
+        })
+        .collect(),
+      output_clauses: vec![OutputClause {
+        type_ref: None,
+        name: None,
+        output_values: None,
+        default_output_entry: None,
+      }],
+      annotations: vec![],
+      rules: rules
+        .into_iter()
+        .map(|(input_entries, output_entry)| DecisionRule {
+          id: None,
+          input_entries: input_entries.into_iter().map(|text| InputEntry { text }).collect(),
+          output_entries: vec![OutputEntry { text: output_entry }],
+          annotation_entries: vec![],
+        })
+        .collect(),
+      hit_policy,
+      aggregation: None,
+      preferred_orientation: DecisionTableOrientation::RuleAsRow,
+      output_label: None,
+    }
+  }
+
+  /// Evaluates decision table in context built from specified input.
+  fn evaluate(decision_table: &DecisionTable, input: &str) -> String {
+    let scope = context(input).into();
+    let evaluator = build_decision_table_evaluator(&scope, decision_table).unwrap();
+    evaluator(&scope).to_string()
+  }
+
+  #[test]
+  fn test_indexed_large_table() {
+    let rules = (0..1000)
+      .map(|i| (vec![format!(r#""c{}""#, i % 10), format!("[{}..{})", i * 10, i * 10 + 10)], format!("{}", i)))
+      .collect();
+    let decision_table = decision_table(HitPolicy::Unique, &["Category", "Amount"], rules);
+    assert_eq!("423", evaluate(&decision_table, r#"{Category: "c3", Amount: 4235}"#));
+    assert_eq!("420", evaluate(&decision_table, r#"{Category: "c0", Amount: 4200}"#));
+    assert_eq!("429", evaluate(&decision_table, r#"{Category: "c9", Amount: 4299.99}"#));
+    assert_eq!(
+      "null(no rules matched, no output value defined)",
+      evaluate(&decision_table, r#"{Category: "c4", Amount: 4235}"#)
+    );
+    assert_eq!(
+      "null(no rules matched, no output value defined)",
+      evaluate(&decision_table, r#"{Category: "c3", Amount: 10000}"#)
+    );
+    assert_eq!(
+      "null(no rules matched, no output value defined)",
+      evaluate(&decision_table, r#"{Category: 3, Amount: 4235}"#)
+    );
+  }
+
+  #[test]
+  fn test_indexed_numeric_entries() {
+    let rules = ["< 100", "<= 50", "> 50", ">= 50", "50", "-50", "[0..50]", "]0..50[", "-", r#""50""#]
+      .iter()
+      .enumerate()
+      .map(|(i, entry)| (vec![entry.to_string()], format!("{}", i)))
+      .collect();
+    let decision_table = decision_table(HitPolicy::RuleOrder, &["Amount"], rules);
+    assert_eq!("[0, 1, 3, 4, 6, 8]", evaluate(&decision_table, r#"{Amount: 50}"#));
+    assert_eq!("[0, 1, 5, 8]", evaluate(&decision_table, r#"{Amount: -50}"#));
+    assert_eq!("[0, 1, 6, 7, 8]", evaluate(&decision_table, r#"{Amount: 0.5}"#));
+    assert_eq!("[2, 3, 8]", evaluate(&decision_table, r#"{Amount: 100}"#));
+    assert_eq!("[8, 9]", evaluate(&decision_table, r#"{Amount: "50"}"#));
+  }
+
+  #[test]
+  fn test_indexed_string_entries() {
+    let rules = [r#""a""#, r#""a","b""#, r#"not("a")"#, "-", r#""b""#, "Other", "1", r#""c""#]
+      .iter()
+      .enumerate()
+      .map(|(i, entry)| (vec![entry.to_string()], format!("{}", i)))
+      .collect();
+    let decision_table = decision_table(HitPolicy::Collect(BuiltinAggregator::List), &["Code"], rules);
+    assert_eq!("[0, 1, 3, 5]", evaluate(&decision_table, r#"{Code: "a", Other: "a"}"#));
+    assert_eq!("[1, 2, 3, 4]", evaluate(&decision_table, r#"{Code: "b", Other: "a"}"#));
+    assert_eq!("[2, 3, 6]", evaluate(&decision_table, r#"{Code: 1, Other: "a"}"#));
+    assert_eq!("[2]", evaluate(&decision_table, r#"{Code: null, Other: "a"}"#));
+  }
+
+  /// Evaluates decision table with specified aggregator, all rules match and have specified outputs.
+  fn aggregate(aggregator: BuiltinAggregator, outputs: &[&str]) -> String {
+    let rules = outputs.iter().map(|output| (vec!["-".to_string()], output.to_string())).collect();
+    let decision_table = decision_table(HitPolicy::Collect(aggregator), &["Code"], rules);
+    evaluate(&decision_table, r#"{Code: 1}"#)
+  }
+
+  #[test]
+  fn test_collect_min_max() {
+    assert_eq!("1", aggregate(BuiltinAggregator::Min, &["3", "1", "2"]));
+    assert_eq!("3", aggregate(BuiltinAggregator::Max, &["3", "1", "2"]));
+    assert_eq!(r#""a""#, aggregate(BuiltinAggregator::Min, &[r#""b""#, r#""a""#, r#""c""#]));
+    assert_eq!(r#""c""#, aggregate(BuiltinAggregator::Max, &[r#""b""#, r#""a""#, r#""c""#]));
+    assert_eq!(
+      "2020-01-01",
+      aggregate(BuiltinAggregator::Min, &[r#"date("2021-05-01")"#, r#"date("2020-01-01")"#])
+    );
+    assert_eq!(
+      "2021-05-01T10:00:00",
+      aggregate(
+        BuiltinAggregator::Max,
+        &[r#"date and time("2021-05-01T08:00:00")"#, r#"date and time("2021-05-01T10:00:00")"#]
+      )
+    );
+    assert_eq!("10:00:00", aggregate(BuiltinAggregator::Max, &[r#"time("08:00:00")"#, r#"time("10:00:00")"#]));
+    assert_eq!("PT12H", aggregate(BuiltinAggregator::Min, &[r#"duration("P1D")"#, r#"duration("PT12H")"#]));
+    assert_eq!("P2Y", aggregate(BuiltinAggregator::Max, &[r#"duration("P2Y")"#, r#"duration("P11M")"#]));
+  }
+
+  #[test]
+  fn test_collect_sum() {
+    assert_eq!("6", aggregate(BuiltinAggregator::Sum, &["3", "1", "2"]));
+    assert_eq!("P1DT12H", aggregate(BuiltinAggregator::Sum, &[r#"duration("P1D")"#, r#"duration("PT12H")"#]));
+    assert_eq!("P2Y11M", aggregate(BuiltinAggregator::Sum, &[r#"duration("P2Y")"#, r#"duration("P11M")"#]));
+    assert_eq!(
+      "null(aggregation SUM is not defined for output values of type string)",
+      aggregate(BuiltinAggregator::Sum, &[r#""a""#, r#""b""#])
+    );
+    assert_eq!(
+      "null(aggregation SUM is not defined for output values of type date)",
+      aggregate(BuiltinAggregator::Sum, &[r#"date("2021-05-01")"#])
+    );
+  }
+
+  #[test]
+  fn test_collect_nulls_and_mixed_types() {
+    assert_eq!("3", aggregate(BuiltinAggregator::Sum, &["1", "null", "2"]));
+    assert_eq!("1", aggregate(BuiltinAggregator::Min, &["null", "1", "2"]));
+    assert_eq!("3", aggregate(BuiltinAggregator::Count, &["1", "null", "2"]));
+    assert_eq!("null(no output values to aggregate with MAX)", aggregate(BuiltinAggregator::Max, &["null"]));
+    assert_eq!(
+      "null(aggregation MIN is not defined for mixed output types: number and string)",
+      aggregate(BuiltinAggregator::Min, &["1", r#""a""#])
+    );
+    assert_eq!(
+      "null(aggregation SUM is not defined for mixed output types: number and days and time duration)",
+      aggregate(BuiltinAggregator::Sum, &["1", r#"duration("P1D")"#])
+    );
+    assert_eq!(
+      "null(aggregation MAX is not defined for output values of type boolean)",
+      aggregate(BuiltinAggregator::Max, &["true", "false"])
+    );
+  }
+
+  #[test]
+  fn test_collect_compound_outputs() {
+    for aggregator in [BuiltinAggregator::Count, BuiltinAggregator::Sum, BuiltinAggregator::Min, BuiltinAggregator::Max] {
+      let mut decision_table = decision_table(HitPolicy::Collect(aggregator), &["Code"], vec![(vec!["-".to_string()], "1".to_string())]);
+      decision_table.output_clauses = ["a", "b"]
+        .iter()
+        .map(|name| OutputClause {
+          type_ref: None,
+          name: Some(name.to_string()),
+          output_values: None,
+          default_output_entry: None,
+        })
+        .collect();
+      decision_table.rules[0].output_entries.push(OutputEntry { text: "2".to_string() });
+      assert_eq!(
+        "null(err_aggregators_not_allowed_for_compound_outputs)",
+        evaluate(&decision_table, r#"{Code: 1}"#)
+      );
+    }
+  }
+
+  /// Builds a decision table with specified hit policy and outputs, all rules match any input.
+  fn prioritized_decision_table(hit_policy: HitPolicy, outputs: &[(&str, Option<&str>)], rules: &[&[&str]]) -> DecisionTable {
+    let mut decision_table = decision_table(hit_policy, &["Code"], rules.iter().map(|_| (vec!["-".to_string()], String::new())).collect());
+    decision_table.output_clauses = outputs
+      .iter()
+      .map(|(name, output_values)| OutputClause {
+        type_ref: None,
+        name: if outputs.len() > 1 { Some(name.to_string()) } else { None },
+        output_values: output_values.map(|text| text.to_string()),
+        default_output_entry: None,
+      })
+      .collect();
+    for (rule, output_entries) in decision_table.rules.iter_mut().zip(rules) {
+      rule.output_entries = output_entries.iter().map(|text| OutputEntry { text: text.to_string() }).collect();
+    }
+    decision_table
+  }
+
+  #[test]
+  fn test_priority_multiple_outputs() {
+    let outputs = [("Status", Some(r#""DECLINED","APPROVED""#)), ("Rate", Some(r#""HIGH","LOW""#))];
+    let rules: &[&[&str]] = &[&[r#""APPROVED""#, r#""HIGH""#], &[r#""DECLINED""#, r#""LOW""#], &[r#""DECLINED""#, r#""HIGH""#]];
+    let decision_table = prioritized_decision_table(HitPolicy::Priority, &outputs, rules);
+    assert_eq!(r#"{Rate: "HIGH", Status: "DECLINED"}"#, evaluate(&decision_table, r#"{Code: 1}"#));
+    let decision_table = prioritized_decision_table(HitPolicy::OutputOrder, &outputs, rules);
+    assert_eq!(
+      r#"[{Rate: "HIGH", Status: "DECLINED"}, {Rate: "LOW", Status: "DECLINED"}, {Rate: "HIGH", Status: "APPROVED"}]"#,
+      evaluate(&decision_table, r#"{Code: 1}"#)
+    );
+  }
+
+  #[test]
+  fn test_priority_output_without_output_values() {
+    // outputs without output values do not influence the priority, rule order is preserved
+    let outputs = [("Status", Some(r#""DECLINED","APPROVED""#)), ("Rate", None)];
+    let rules: &[&[&str]] = &[&[r#""APPROVED""#, r#""A""#], &[r#""DECLINED""#, r#""B""#], &[r#""DECLINED""#, r#""C""#]];
+    let decision_table = prioritized_decision_table(HitPolicy::OutputOrder, &outputs, rules);
+    assert_eq!(
+      r#"[{Rate: "B", Status: "DECLINED"}, {Rate: "C", Status: "DECLINED"}, {Rate: "A", Status: "APPROVED"}]"#,
+      evaluate(&decision_table, r#"{Code: 1}"#)
+    );
+  }
+
+  #[test]
+  fn test_priority_value_not_in_output_values() {
+    // output entry values not listed in output values are null and have the lowest priority
+    let outputs = [("Status", Some(r#""DECLINED","APPROVED""#))];
+    let rules: &[&[&str]] = &[&[r#""UNKNOWN""#], &[r#""APPROVED""#]];
+    let decision_table = prioritized_decision_table(HitPolicy::Priority, &outputs, rules);
+    assert_eq!(r#""APPROVED""#, evaluate(&decision_table, r#"{Code: 1}"#));
+    let decision_table = prioritized_decision_table(HitPolicy::OutputOrder, &outputs, rules);
+    assert_eq!(r#"["APPROVED", null]"#, evaluate(&decision_table, r#"{Code: 1}"#));
+  }
+
+  #[test]
+  fn test_priority_missing_output_values() {
+    let outputs = [("Status", None)];
+    let rules: &[&[&str]] = &[&[r#""APPROVED""#], &[r#""DECLINED""#]];
+    let decision_table = prioritized_decision_table(HitPolicy::Priority, &outputs, rules);
+    assert_eq!(
+      "null(output values are required in decision table with hit policy PRIORITY)",
+      evaluate(&decision_table, r#"{Code: 1}"#)
+    );
+    let decision_table = prioritized_decision_table(HitPolicy::OutputOrder, &outputs, rules);
+    assert_eq!(
+      "null(output values are required in decision table with hit policy OUTPUT ORDER)",
+      evaluate(&decision_table, r#"{Code: 1}"#)
+    );
+  }
 }
@@ -0,0 +1,370 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Index of decision table rules.
+//!
+//! Input entries in form of string literals, numeric literals, unary comparisons
+//! and ranges with numeric literal end points are indexed per input column,
+//! using hash maps (strings) and interval trees (numbers).
+//! The index only narrows the set of candidate rules, all candidate rules
+//! are still fully evaluated, so the semantics of hit policies do not change.
+
+use dmntk_common::Result;
+use dmntk_feel::values::Value;
+use dmntk_feel::{AstNode, Evaluator, FeelNumber, Scope};
+use dmntk_feel_evaluator::Backend;
+use std::collections::HashMap;
+
+/// Minimal number of rules in decision table for building the index.
+const MIN_INDEXED_RULES: usize = 8;
+
+/// Analyzed input entry of a single rule.
+enum IndexedEntry {
+  /// Input entry that matches only the specified strings.
+  Strings(Vec<String>),
+  /// Input entry that matches only the numbers in specified intervals.
+  Intervals(Vec<Interval>),
+  /// Input entry that can not be indexed.
+  Unindexed,
+}
+
+/// End point of an interval.
+#[derive(Copy, Clone)]
+struct EndPoint {
+  /// Value of the end point.
+  value: FeelNumber,
+  /// Flag indicating if the end point belongs to the interval.
+  closed: bool,
+}
+
+/// Interval of numbers, missing end point means unbounded interval.
+#[derive(Copy, Clone)]
+struct Interval {
+  start: Option<EndPoint>,
+  end: Option<EndPoint>,
+}
+
+impl Interval {
+  /// Returns `true` when the start of this interval admits specified number.
+  fn starts_before(&self, n: &FeelNumber) -> bool {
+    match &self.start {
+      Some(start) => start.value < *n || (start.closed && start.value == *n),
+      None => true,
+    }
+  }
+  /// Returns `true` when the end of this interval admits specified number.
+  fn ends_after(&self, n: &FeelNumber) -> bool {
+    ends_after(&self.end, n)
+  }
+  /// Returns `true` when this interval and the other interval have common numbers.
+  fn overlaps(&self, other: &Interval) -> bool {
+    !ends_before(&self.end, &other.start) && !ends_before(&other.end, &self.start)
+  }
+}
+
+/// Returns `true` when no number admitted by specified end point is admitted by specified start point.
+fn ends_before(end: &Option<EndPoint>, start: &Option<EndPoint>) -> bool {
+  match (end, start) {
+    (Some(end), Some(start)) => end.value < start.value || (end.value == start.value && !(end.closed && start.closed)),
+    _ => false,
+  }
+}
+
+/// Returns `true` when specified end point admits specified number.
+fn ends_after(end: &Option<EndPoint>, n: &FeelNumber) -> bool {
+  match end {
+    Some(end) => end.value > *n || (end.closed && end.value == *n),
+    None => true,
+  }
+}
+
+/// Returns the end point that admits more numbers.
+fn max_end(a: Option<EndPoint>, b: Option<EndPoint>) -> Option<EndPoint> {
+  match (a, b) {
+    (Some(a), Some(b)) => {
+      if a.value > b.value || (a.value == b.value && a.closed) {
+        Some(a)
+      } else {
+        Some(b)
+      }
+    }
+    _ => None,
+  }
+}
+
+/// Static interval tree, implicitly balanced over intervals sorted by their start points.
+struct IntervalTree {
+  /// Intervals with rule indexes, sorted by start points.
+  intervals: Vec<(Interval, usize)>,
+  /// Maximal end point in the subtree rooted at the interval with the same index.
+  max_ends: Vec<Option<EndPoint>>,
+}
+
+impl IntervalTree {
+  /// Builds an interval tree from specified intervals with rule indexes.
+  fn new(mut intervals: Vec<(Interval, usize)>) -> Self {
+    intervals.sort_by(|(a, _), (b, _)| match (&a.start, &b.start) {
+      (None, None) => std::cmp::Ordering::Equal,
+      (None, Some(_)) => std::cmp::Ordering::Less,
+      (Some(_), None) => std::cmp::Ordering::Greater,
+      (Some(a), Some(b)) => a
+        .value
+        .partial_cmp(&b.value)
+        .unwrap_or(std::cmp::Ordering::Equal)
+        .then_with(|| b.closed.cmp(&a.closed)),
+    });
+    let mut max_ends = vec![None; intervals.len()];
+    if !intervals.is_empty() {
+      Self::build_max_ends(&intervals, &mut max_ends, 0, intervals.len());
+    }
+    Self { intervals, max_ends }
+  }
+  /// Calculates maximal end points in subtree spanning intervals in range `lo..hi`.
+  fn build_max_ends(intervals: &[(Interval, usize)], max_ends: &mut [Option<EndPoint>], lo: usize, hi: usize) -> Option<EndPoint> {
+    let mid = lo + (hi - lo) / 2;
+    let mut max = intervals[mid].0.end;
+    if lo < mid {
+      max = max_end(max, Self::build_max_ends(intervals, max_ends, lo, mid));
+    }
+    if mid + 1 < hi {
+      max = max_end(max, Self::build_max_ends(intervals, max_ends, mid + 1, hi));
+    }
+    max_ends[mid] = max;
+    max
+  }
+  /// Marks all rules having intervals containing specified number.
+  fn stab(&self, n: &FeelNumber, matched: &mut [bool]) {
+    self.stab_range(n, matched, 0, self.intervals.len())
+  }
+  /// Marks all rules having intervals containing specified number, in subtree spanning intervals in range `lo..hi`.
+  fn stab_range(&self, n: &FeelNumber, matched: &mut [bool], lo: usize, hi: usize) {
+    if lo >= hi {
+      return;
+    }
+    let mid = lo + (hi - lo) / 2;
+    if !ends_after(&self.max_ends[mid], n) {
+      return;
+    }
+    self.stab_range(n, matched, lo, mid);
+    let (interval, rule_index) = &self.intervals[mid];
+    if interval.starts_before(n) {
+      if interval.ends_after(n) {
+        matched[*rule_index] = true;
+      }
+      self.stab_range(n, matched, mid + 1, hi);
+    }
+  }
+}
+
+/// Index of a single input column of decision table.
+struct ColumnIndex {
+  /// Rules indexes by matched strings.
+  strings: HashMap<String, Vec<usize>>,
+  /// Indexes of rules matching only strings.
+  string_rules: Vec<usize>,
+  /// Interval tree with intervals matched by rules.
+  intervals: IntervalTree,
+  /// Indexes of rules matching only numbers.
+  interval_rules: Vec<usize>,
+}
+
+/// Index of decision table rules.
+pub(crate) struct DecisionTableIndex {
+  /// Number of rules in decision table.
+  rule_count: usize,
+  /// Evaluators of input expressions and indexes of input columns.
+  columns: Vec<(Evaluator, ColumnIndex)>,
+}
+
+impl DecisionTableIndex {
+  /// Builds the index from input expressions and parsed input entries of all rules (one vector of input entries per rule).
+  ///
+  /// Returns `None` when decision table has not enough rules or no input entries can be indexed.
+  pub(crate) fn build(input_expressions: &[AstNode], input_entries: &[Vec<AstNode>], backend: Backend) -> Result<Option<Self>> {
+    let rule_count = input_entries.len();
+    if rule_count < MIN_INDEXED_RULES {
+      return Ok(None);
+    }
+    let mut columns = vec![];
+    for (column, input_expression) in input_expressions.iter().enumerate() {
+      let mut strings: HashMap<String, Vec<usize>> = HashMap::new();
+      let mut string_rules = vec![];
+      let mut intervals = vec![];
+      let mut interval_rules = vec![];
+      for (rule_index, rule_input_entries) in input_entries.iter().enumerate() {
+        match rule_input_entries.get(column).map_or(IndexedEntry::Unindexed, analyze_input_entry) {
+          IndexedEntry::Strings(values) => {
+            for value in values {
+              strings.entry(value).or_default().push(rule_index);
+            }
+            string_rules.push(rule_index);
+          }
+          IndexedEntry::Intervals(values) => {
+            intervals.extend(values.into_iter().map(|interval| (interval, rule_index)));
+            interval_rules.push(rule_index);
+          }
+          IndexedEntry::Unindexed => {}
+        }
+      }
+      if !string_rules.is_empty() || !interval_rules.is_empty() {
+        let evaluator = dmntk_feel_evaluator::prepare_with(input_expression, backend)?;
+        let column_index = ColumnIndex {
+          strings,
+          string_rules,
+          intervals: IntervalTree::new(intervals),
+          interval_rules,
+        };
+        columns.push((evaluator, column_index));
+      }
+    }
+    if columns.is_empty() {
+      return Ok(None);
+    }
+    Ok(Some(Self { rule_count, columns }))
+  }
+  /// Returns flags indicating which rules are candidates for matching the input values in specified scope.
+  ///
+  /// A rule is excluded only when the input value has the same type as values indexed
+  /// for this rule's input entry and the input entry does not match this value.
+  pub(crate) fn candidates(&self, scope: &Scope) -> Vec<bool> {
+    let mut candidates = vec![true; self.rule_count];
+    let mut matched = vec![false; self.rule_count];
+    for (evaluator, column_index) in &self.columns {
+      match evaluator(scope) {
+        Value::String(s) => {
+          if let Some(rule_indexes) = column_index.strings.get(&s) {
+            rule_indexes.iter().for_each(|rule_index| matched[*rule_index] = true);
+          }
+          exclude_unmatched(&column_index.string_rules, &mut candidates, &mut matched);
+        }
+        Value::Number(n) => {
+          column_index.intervals.stab(&n, &mut matched);
+          exclude_unmatched(&column_index.interval_rules, &mut candidates, &mut matched);
+        }
+        _ => {}
+      }
+    }
+    candidates
+  }
+}
+
+/// Excludes specified rules from candidates when not matched, clears matched flags.
+fn exclude_unmatched(rule_indexes: &[usize], candidates: &mut [bool], matched: &mut [bool]) {
+  for rule_index in rule_indexes {
+    candidates[*rule_index] &= matched[*rule_index];
+    matched[*rule_index] = false;
+  }
+}
+
+/// Returns `true` when no input value satisfies both parsed input entries,
+/// input entries that can not be analyzed are never disjoint.
+pub(crate) fn are_disjoint(node: &AstNode, other_node: &AstNode) -> bool {
+  match (analyze_input_entry(node), analyze_input_entry(other_node)) {
+    (IndexedEntry::Strings(values), IndexedEntry::Strings(other_values)) => !values.iter().any(|value| other_values.contains(value)),
+    (IndexedEntry::Intervals(intervals), IndexedEntry::Intervals(other_intervals)) => !intervals
+      .iter()
+      .any(|interval| other_intervals.iter().any(|other_interval| interval.overlaps(other_interval))),
+    (IndexedEntry::Strings(_), IndexedEntry::Intervals(_)) | (IndexedEntry::Intervals(_), IndexedEntry::Strings(_)) => true,
+    _ => false,
+  }
+}
+
+/// Analyzes parsed input entry.
+fn analyze_input_entry(node: &AstNode) -> IndexedEntry {
+  let items = match node {
+    AstNode::ExpressionList(items) => items,
+    _ => return IndexedEntry::Unindexed,
+  };
+  if items.iter().all(|item| matches!(item, AstNode::String(_))) {
+    let values = items
+      .iter()
+      .filter_map(|item| match item {
+        AstNode::String(value) => Some(value.clone()),
+        _ => None,
+      })
+      .collect::<Vec<String>>();
+    if !values.is_empty() {
+      return IndexedEntry::Strings(values);
+    }
+  }
+  let intervals = items.iter().map(analyze_interval).collect::<Option<Vec<Interval>>>();
+  match intervals {
+    Some(intervals) if !intervals.is_empty() => IndexedEntry::Intervals(intervals),
+    _ => IndexedEntry::Unindexed,
+  }
+}
+
+/// Analyzes a single unary test, returns the interval of matching numbers.
+fn analyze_interval(node: &AstNode) -> Option<Interval> {
+  let end_point = |node: &AstNode, closed: bool| numeric_literal(node).map(|value| EndPoint { value, closed });
+  match node {
+    AstNode::UnaryLt(rhs) => Some(Interval {
+      start: None,
+      end: Some(end_point(rhs, false)?),
+    }),
+    AstNode::UnaryLe(rhs) => Some(Interval {
+      start: None,
+      end: Some(end_point(rhs, true)?),
+    }),
+    AstNode::UnaryGt(rhs) => Some(Interval {
+      start: Some(end_point(rhs, false)?),
+      end: None,
+    }),
+    AstNode::UnaryGe(rhs) => Some(Interval {
+      start: Some(end_point(rhs, true)?),
+      end: None,
+    }),
+    AstNode::Range(lhs, rhs) => match (lhs.as_ref(), rhs.as_ref()) {
+      (AstNode::IntervalStart(start, start_closed), AstNode::IntervalEnd(end, end_closed)) => Some(Interval {
+        start: Some(end_point(start, *start_closed)?),
+        end: Some(end_point(end, *end_closed)?),
+      }),
+      _ => None,
+    },
+    other => {
+      let value = numeric_literal(other)?;
+      Some(Interval {
+        start: Some(EndPoint { value, closed: true }),
+        end: Some(EndPoint { value, closed: true }),
+      })
+    }
+  }
+}
+
+/// Returns the value of numeric literal, optionally negated.
+fn numeric_literal(node: &AstNode) -> Option<FeelNumber> {
+  match node {
+    AstNode::Numeric(lhs, rhs) => format!("{}.{}", lhs, rhs).parse::<FeelNumber>().ok(),
+    AstNode::Neg(inner) => numeric_literal(inner).map(|value| -value),
+    _ => None,
+  }
+}
@@ -35,6 +35,7 @@
 use crate::builders::item_definition::ItemDefinitionEvaluator;
 use crate::builders::{build_variable_evaluator, Variable, VariableEvaluatorFn};
 use crate::errors::err_empty_identifier;
+use crate::validation::{is_validating, report_violation};
 use dmntk_common::Result;
 use dmntk_feel::values::Value;
 use dmntk_feel::Name;
@@ -48,6 +49,8 @@ type InputDataEvaluatorEntry = (Variable, VariableEvaluatorFn);
 #[derive(Default)]
 pub struct InputDataEvaluator {
   evaluators: HashMap<String, InputDataEvaluatorEntry>,
+  /// Descriptions of constraints on input data values, indexed by input data identifier.
+  constraints: HashMap<String, String>,
 }
 
 impl InputDataEvaluator {
@@ -57,16 +60,29 @@ impl InputDataEvaluator {
       let input_data_id = input_data.id().as_ref().ok_or_else(err_empty_identifier)?;
       let variable = Variable::try_from(input_data.variable())?;
       let evaluator = build_variable_evaluator(&variable)?;
+      if let Some(constraint) = variable.constraint(definitions) {
+        self.constraints.insert(input_data_id.to_owned(), constraint);
+      }
       self.evaluators.insert(input_data_id.to_owned(), (variable, evaluator));
     }
     Ok(())
   }
   /// Evaluates input data with specified identifier.
+  ///
+  /// When validating, a provided value evaluated to `null` is reported as a violation of input data constraints.
   pub fn evaluate(&self, input_data_id: &str, value: &Value, item_definition_evaluator: &ItemDefinitionEvaluator) -> Option<(Name, Value)> {
-    self
+    let (name, evaluated_value) = self
       .evaluators
       .get(input_data_id)
-      .map(|evaluator| evaluator.1(value, item_definition_evaluator))
+      .map(|evaluator| evaluator.1(value, item_definition_evaluator))?;
+    if is_validating() && evaluated_value.is_null() {
+      if let (Some(constraint), Value::Context(ctx)) = (self.constraints.get(input_data_id), value) {
+        if let Some(provided_value) = ctx.get_entry(&name).filter(|provided_value| !provided_value.is_null()) {
+          report_violation(format!("input data '{}'", name), provided_value, constraint.clone());
+        }
+      }
+    }
+    Some((name, evaluated_value))
   }
   /// Returns the name and type of the input variable of input data definition with specified identifier.
   pub fn get_input_variable(&self, input_data_id: &str) -> Option<&Variable> {
@@ -36,6 +36,7 @@ mod business_knowledge_model;
 mod decision;
 mod decision_service;
 pub(crate) mod decision_table;
+pub(crate) mod decision_table_index;
 mod input_data;
 mod input_data_context;
 mod item_definition;
@@ -43,6 +44,7 @@ mod item_definition_context;
 mod item_definition_type;
 
 use crate::errors::*;
+use crate::validation::{report_violation, type_ref_constraint};
 pub use business_knowledge_model::BusinessKnowledgeModelEvaluator;
 pub use decision::DecisionEvaluator;
 pub use decision_service::DecisionServiceEvaluator;
@@ -50,15 +52,18 @@ use dmntk_common::{DmntkError, Result};
 use dmntk_feel::context::FeelContext;
 use dmntk_feel::values::{Value, Values};
 use dmntk_feel::{value_null, Evaluator, FeelType, Name, Scope};
+use dmntk_feel_evaluator::{Backend, TypeError};
 use dmntk_model::model::{
-  Context, DecisionTable, Expression, ExpressionInstance, FunctionDefinition, InformationItem, Invocation, ItemDefinition, ItemDefinitionType,
-  LiteralExpression, NamedElement, Relation,
+  Context, DecisionTable, Definitions, Expression, ExpressionInstance, FunctionDefinition, InformationItem, Invocation, ItemDefinition, ItemDefinitionType,
+  List, LiteralExpression, NamedElement, Relation,
 };
 pub use input_data::InputDataEvaluator;
 pub use input_data_context::InputDataContextEvaluator;
 pub use item_definition::ItemDefinitionEvaluator;
 pub use item_definition_context::ItemDefinitionContextEvaluator;
 pub use item_definition_type::ItemDefinitionTypeEvaluator;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 
 ///
 pub fn information_item_type(type_ref: &str, evaluator: &ItemDefinitionTypeEvaluator) -> Option<FeelType> {
@@ -149,6 +154,17 @@ impl Variable {
       FeelType::Any
     }
   }
+  /// Returns the description of constraints on the value of this variable,
+  /// when the variable has a type reference.
+  fn constraint(&self, definitions: &Definitions) -> Option<String> {
+    self.type_ref.as_ref().map(|type_ref| {
+      let allowed_values = definitions
+        .item_definition_by_name(type_ref)
+        .and_then(|item_definition| item_definition.allowed_values().as_ref())
+        .and_then(|unary_tests| unary_tests.text().as_deref());
+      type_ref_constraint(type_ref, allowed_values)
+    })
+  }
 }
 
 ///
@@ -269,45 +285,114 @@ fn build_variable_evaluator(variable: &Variable) -> Result<VariableEvaluatorFn>
 }
 
 ///
-fn build_expression_instance_evaluator(scope: &Scope, expression_instance: &ExpressionInstance) -> Result<Evaluator> {
+fn build_expression_instance_evaluator(
+  scope: &Scope,
+  expression_instance: &ExpressionInstance,
+  backend: Backend,
+  item_definition_type_evaluator: &ItemDefinitionTypeEvaluator,
+) -> Result<Evaluator> {
   match expression_instance {
-    ExpressionInstance::Context(context) => build_context_evaluator(scope, context),
-    ExpressionInstance::DecisionTable(decision_table) => build_decision_table_evaluator(scope, decision_table),
-    ExpressionInstance::FunctionDefinition(function_definition) => build_function_definition_evaluator(scope, function_definition),
-    ExpressionInstance::Invocation(invocation) => build_invocation_evaluator(scope, invocation),
-    ExpressionInstance::LiteralExpression(literal_expression) => crate::builders::build_literal_expression_evaluator(scope, literal_expression),
-    ExpressionInstance::Relation(relation) => build_relation_evaluator(scope, relation),
+    ExpressionInstance::Context(context) => build_context_evaluator(scope, context, backend, item_definition_type_evaluator),
+    ExpressionInstance::DecisionTable(decision_table) => build_decision_table_evaluator(scope, decision_table, backend),
+    ExpressionInstance::FunctionDefinition(function_definition) => {
+      build_function_definition_evaluator(scope, function_definition, backend, item_definition_type_evaluator)
+    }
+    ExpressionInstance::Invocation(invocation) => build_invocation_evaluator(scope, invocation, backend, item_definition_type_evaluator),
+    ExpressionInstance::LiteralExpression(literal_expression) => build_literal_expression_evaluator(scope, literal_expression, backend),
+    ExpressionInstance::List(list) => build_list_evaluator(scope, list, backend, item_definition_type_evaluator),
+    ExpressionInstance::Relation(relation) => build_relation_evaluator(scope, relation, backend, item_definition_type_evaluator),
+  }
+}
+
+/// Infers the result type of the expression instance, returns inferred type and detected type errors.
+/// Types of expression instances other than literal expressions and contexts are not inferred.
+fn infer_expression_instance_type(scope: &Scope, expression_instance: &ExpressionInstance) -> (FeelType, Vec<TypeError>) {
+  match expression_instance {
+    ExpressionInstance::Context(context) => {
+      let mut entries = BTreeMap::new();
+      let mut errors = vec![];
+      let mut result_type = None;
+      scope.push(FeelContext::default());
+      for context_entry in context.context_entries() {
+        let (feel_type, mut entry_errors) = infer_expression_instance_type(scope, &context_entry.value);
+        errors.append(&mut entry_errors);
+        if let Some(name) = context_entry.variable.as_ref().and_then(|variable| variable.feel_name().as_ref()) {
+          scope.set_entry(name, Value::FeelType(feel_type.clone()));
+          entries.insert(name.clone(), feel_type);
+        } else {
+          result_type = Some(feel_type);
+          break;
+        }
+      }
+      scope.pop();
+      (result_type.unwrap_or(FeelType::Context(entries)), errors)
+    }
+    ExpressionInstance::LiteralExpression(literal_expression) => {
+      if let Some(text) = literal_expression.text() {
+        if let Ok(node) = dmntk_feel_parser::parse_expression(scope, text, false) {
+          return dmntk_feel_evaluator::infer_type(scope, &node);
+        }
+      }
+      (FeelType::Any, vec![])
+    }
+    _ => (FeelType::Any, vec![]),
   }
 }
 
 ///
-fn build_context_evaluator(scope: &Scope, context: &Context) -> Result<Evaluator> {
+fn build_context_evaluator(
+  scope: &Scope,
+  context: &Context,
+  backend: Backend,
+  item_definition_type_evaluator: &ItemDefinitionTypeEvaluator,
+) -> Result<Evaluator> {
   let mut entry_evaluators = vec![];
   scope.push(FeelContext::default());
   for context_entry in context.context_entries() {
     if let Some(variable) = &context_entry.variable {
       let name = variable.feel_name().as_ref().ok_or_else(err_empty_feel_name)?;
-      let evaluator = build_expression_instance_evaluator(scope, &context_entry.value)?;
+      let evaluator = build_expression_instance_evaluator(scope, &context_entry.value, backend, item_definition_type_evaluator)?;
       scope.insert_null(name.clone());
       entry_evaluators.push((Some(name.clone()), evaluator));
     } else {
-      let evaluator = build_expression_instance_evaluator(scope, &context_entry.value)?;
+      let evaluator = build_expression_instance_evaluator(scope, &context_entry.value, backend, item_definition_type_evaluator)?;
       entry_evaluators.push((None, evaluator));
     }
   }
   scope.pop();
+  // when the context has a result entry, then context entries are evaluated only when needed by the result
+  if let Some(result_index) = entry_evaluators.iter().position(|(opt_name, _)| opt_name.is_none()) {
+    let (result_evaluator, entry_evaluators) = {
+      let mut entry_evaluators = entry_evaluators;
+      let (_, result_evaluator) = entry_evaluators.remove(result_index);
+      entry_evaluators.truncate(result_index);
+      let entry_evaluators = entry_evaluators
+        .into_iter()
+        .filter_map(|(opt_name, evaluator)| opt_name.map(|name| (name, Arc::new(evaluator))))
+        .collect::<Vec<(Name, Arc<Evaluator>)>>();
+      (result_evaluator, entry_evaluators)
+    };
+    return Ok(Box::new(move |scope: &Scope| {
+      // each entry is placed in a separate context, so while evaluated, it sees only the preceding entries
+      for (name, evaluator) in &entry_evaluators {
+        scope.push(FeelContext::default());
+        scope.set_lazy_entry(name, Arc::clone(evaluator));
+      }
+      scope.push(FeelContext::default());
+      let value = result_evaluator(scope);
+      for _ in 0..=entry_evaluators.len() {
+        scope.pop();
+      }
+      value
+    }));
+  }
   Ok(Box::new(move |scope: &Scope| {
     let mut evaluated_context = FeelContext::default();
     for (opt_name, evaluator) in &entry_evaluators {
-      match opt_name {
-        Some(name) => {
-          let value = evaluator(scope);
-          scope.set_entry(name, value.clone());
-          evaluated_context.set_entry(name, value);
-        }
-        None => {
-          return evaluator(scope);
-        }
+      if let Some(name) = opt_name {
+        let value = evaluator(scope);
+        scope.set_entry(name, value.clone());
+        evaluated_context.set_entry(name, value);
       }
     }
     Value::Context(evaluated_context)
@@ -315,20 +400,25 @@ fn build_context_evaluator(scope: &Scope, context: &Context) -> Result<Evaluator
 }
 
 ///
-fn build_decision_table_evaluator(scope: &Scope, decision_table: &DecisionTable) -> Result<Evaluator> {
-  let decision_table_evaluator = decision_table::build_decision_table_evaluator(scope, decision_table)?;
+fn build_decision_table_evaluator(scope: &Scope, decision_table: &DecisionTable, backend: Backend) -> Result<Evaluator> {
+  let decision_table_evaluator = decision_table::build_decision_table_evaluator_with_backend(scope, decision_table, backend)?;
   Ok(Box::new(move |scope: &Scope| decision_table_evaluator(scope)))
 }
 
 ///
-fn build_function_definition_evaluator(scope: &Scope, function_definition: &FunctionDefinition) -> Result<Evaluator> {
+fn build_function_definition_evaluator(
+  scope: &Scope,
+  function_definition: &FunctionDefinition,
+  backend: Backend,
+  item_definition_type_evaluator: &ItemDefinitionTypeEvaluator,
+) -> Result<Evaluator> {
   let mut parameters = vec![];
   let body = function_definition.body().as_ref().ok_or_else(err_empty_function_body)?;
-  let function_evaluator = build_expression_instance_evaluator(scope, body)?;
+  let function_evaluator = build_expression_instance_evaluator(scope, body, backend, item_definition_type_evaluator)?;
   for parameter in function_definition.formal_parameters() {
     let name = parameter.feel_name().as_ref().ok_or_else(err_empty_feel_name)?.clone();
     let value_expression = parameter.value_expression().as_ref().ok_or_else(err_empty_value_expression)?;
-    let evaluator = build_expression_instance_evaluator(scope, value_expression)?;
+    let evaluator = build_expression_instance_evaluator(scope, value_expression, backend, item_definition_type_evaluator)?;
     parameters.push((name, evaluator));
   }
   Ok(Box::new(move |scope: &Scope| {
@@ -346,13 +436,18 @@ fn build_function_definition_evaluator(scope: &Scope, function_definition: &Func
 }
 
 ///
-fn build_invocation_evaluator(scope: &Scope, invocation: &Invocation) -> Result<Evaluator> {
+fn build_invocation_evaluator(
+  scope: &Scope,
+  invocation: &Invocation,
+  backend: Backend,
+  item_definition_type_evaluator: &ItemDefinitionTypeEvaluator,
+) -> Result<Evaluator> {
   let mut bindings = vec![];
-  let function_evaluator = build_expression_instance_evaluator(scope, invocation.called_function())?;
+  let function_evaluator = build_expression_instance_evaluator(scope, invocation.called_function(), backend, item_definition_type_evaluator)?;
   for binding in invocation.bindings() {
     if let Some(binding_formula) = binding.binding_formula() {
       let name = binding.parameter().feel_name().as_ref().ok_or_else(err_empty_feel_name)?.clone();
-      let evaluator = build_expression_instance_evaluator(scope, binding_formula)?;
+      let evaluator = build_expression_instance_evaluator(scope, binding_formula, backend, item_definition_type_evaluator)?;
       bindings.push((name, evaluator));
     }
   }
@@ -371,32 +466,67 @@ fn build_invocation_evaluator(scope: &Scope, invocation: &Invocation) -> Result<
 }
 
 ///
-fn build_literal_expression_evaluator(scope: &Scope, literal_expression: &LiteralExpression) -> Result<Evaluator> {
+fn build_literal_expression_evaluator(scope: &Scope, literal_expression: &LiteralExpression, backend: Backend) -> Result<Evaluator> {
   let text = literal_expression.text().as_ref().ok_or_else(err_empty_literal_expression)?;
   let node = dmntk_feel_parser::parse_expression(scope, text, false)?;
-  dmntk_feel_evaluator::prepare(&node)
+  dmntk_feel_evaluator::prepare_with(&node, backend)
 }
 
 ///
-fn build_relation_evaluator(scope: &Scope, relation: &Relation) -> Result<Evaluator> {
+fn build_list_evaluator(scope: &Scope, list: &List, backend: Backend, item_definition_type_evaluator: &ItemDefinitionTypeEvaluator) -> Result<Evaluator> {
+  let mut evaluators = vec![];
+  for element in list.elements() {
+    evaluators.push(build_expression_instance_evaluator(scope, element, backend, item_definition_type_evaluator)?);
+  }
+  Ok(Box::new(move |scope: &Scope| {
+    Value::List(Values::new(evaluators.iter().map(|evaluator| evaluator(scope)).collect()))
+  }))
+}
+
+/// Builds an evaluator of the relation, values in cells are coerced to the types of their columns.
+/// Values that can not be coerced are replaced with `null` describing the row and column of the cell.
+fn build_relation_evaluator(
+  scope: &Scope,
+  relation: &Relation,
+  backend: Backend,
+  item_definition_type_evaluator: &ItemDefinitionTypeEvaluator,
+) -> Result<Evaluator> {
+  let mut columns = vec![];
+  for column in relation.columns() {
+    let name = column.feel_name().as_ref().ok_or_else(err_empty_feel_name)?.clone();
+    let type_ref = column.type_ref().clone();
+    let feel_type = type_ref
+      .as_ref()
+      .and_then(|type_ref| information_item_type(type_ref, item_definition_type_evaluator))
+      .unwrap_or(FeelType::Any);
+    columns.push((name, type_ref, feel_type));
+  }
   let mut rows = vec![];
-  for row in relation.rows() {
+  for (row_index, row) in relation.rows().iter().enumerate() {
     let mut evaluators = vec![];
-    for (i, element) in row.elements().iter().enumerate() {
-      if let Some(column) = relation.columns().get(i) {
-        let name = column.feel_name().as_ref().ok_or_else(err_empty_feel_name)?.clone();
-        let evaluator = build_expression_instance_evaluator(scope, element)?;
-        evaluators.push((name, evaluator));
-      }
+    for ((name, _, _), element) in columns.iter().zip(row.elements()) {
+      let evaluator = build_expression_instance_evaluator(scope, element, backend, item_definition_type_evaluator)
+        .map_err(|reason| err_invalid_relation_cell(row_index + 1, name, reason))?;
+      evaluators.push(evaluator);
     }
     rows.push(evaluators);
   }
   Ok(Box::new(move |scope: &Scope| {
     let mut results = vec![];
-    for row in &rows {
+    for (row_index, row) in rows.iter().enumerate() {
       let mut evaluated_context = FeelContext::default();
-      for (name, evaluator) in row {
-        evaluated_context.set_entry(name, evaluator(scope));
+      for ((name, type_ref, feel_type), evaluator) in columns.iter().zip(row) {
+        let value = evaluator(scope);
+        let coerced_value = feel_type.coerced(&value);
+        if coerced_value.is_null() && !value.is_null() {
+          let element = format!("relation row {} column '{}'", row_index + 1, name);
+          if let Some(type_ref) = type_ref {
+            report_violation(element.clone(), &value, type_ref_constraint(type_ref, None));
+          }
+          evaluated_context.set_entry(name, value_null!("{}: expected type {}, actual value is {}", element, feel_type, value));
+        } else {
+          evaluated_context.set_entry(name, coerced_value);
+        }
       }
       results.push(Value::Context(evaluated_context));
     }
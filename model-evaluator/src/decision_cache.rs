@@ -0,0 +1,142 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Cache of decision results, shared by all decisions evaluated in a single request.
+//!
+//! The cache is active only while evaluating an invocable in [ModelEvaluator](crate::ModelEvaluator),
+//! every decision is then evaluated at most once for the same input data,
+//! no matter how many decisions or decision services require it.
+
+use dmntk_feel::context::FeelContext;
+use dmntk_feel::values::Value;
+use dmntk_feel::Name;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+thread_local! {
+  /// Cache of decision results for the request evaluated in the current thread.
+  static CACHE: RefCell<Option<DecisionCache>> = const { RefCell::new(None) };
+}
+
+/// Entry of the trace of decisions evaluated in a single request.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecisionTraceEntry {
+  /// Decision with specified identifier was evaluated, the result was placed under specified output variable name.
+  Evaluated(String, Name),
+  /// Result of the decision with specified identifier was taken from cache.
+  CacheHit(String, Name),
+}
+
+/// Cache of decision results.
+struct DecisionCache {
+  /// Distinct input data the decisions were evaluated with.
+  inputs: Vec<FeelContext>,
+  /// Results of decisions indexed by model namespace, decision identifier and index of input data.
+  results: HashMap<(String, String, usize), (Name, Value)>,
+  /// Trace of evaluated decisions, when tracing is enabled.
+  trace: Option<Vec<DecisionTraceEntry>>,
+}
+
+impl DecisionCache {
+  /// Returns the index of specified input data, if already present in cache.
+  fn input_index(&self, input_data: &FeelContext) -> Option<usize> {
+    self.inputs.iter().position(|input| input == input_data)
+  }
+}
+
+/// Restores the cache of decision results active before the evaluation, also when the evaluation panics.
+struct CacheRestorer(Option<DecisionCache>);
+
+impl Drop for CacheRestorer {
+  fn drop(&mut self) {
+    let previous = self.0.take();
+    CACHE.with(|cell| cell.replace(previous));
+  }
+}
+
+/// Runs specified function with the cache of decision results active in the current thread.
+///
+/// When the cache is already active (nested evaluation), the function runs using the existing cache
+/// and the returned trace is empty. The trace is recorded only when `traced` is `true`.
+pub(crate) fn with_decision_cache<R, F>(traced: bool, f: F) -> (R, Vec<DecisionTraceEntry>)
+where
+  F: FnOnce() -> R,
+{
+  if CACHE.with(|cell| cell.borrow().is_some()) {
+    return (f(), vec![]);
+  }
+  let cache = DecisionCache {
+    inputs: vec![],
+    results: HashMap::new(),
+    trace: if traced { Some(vec![]) } else { None },
+  };
+  let _restorer = CacheRestorer(CACHE.with(|cell| cell.replace(Some(cache))));
+  let result = f();
+  let trace = CACHE
+    .with(|cell| cell.borrow_mut().as_mut().and_then(|cache| cache.trace.take()))
+    .unwrap_or_default();
+  (result, trace)
+}
+
+/// Returns the cached result of the decision with specified identifier, defined in the model
+/// with specified namespace and evaluated with specified input data.
+pub(crate) fn cached_result(namespace: &str, decision_id: &str, input_data: &FeelContext) -> Option<(Name, Value)> {
+  CACHE.with(|cell| {
+    let mut borrowed = cell.borrow_mut();
+    let cache = borrowed.as_mut()?;
+    let index = cache.input_index(input_data)?;
+    let (name, value) = cache.results.get(&(namespace.to_string(), decision_id.to_string(), index)).cloned()?;
+    if let Some(trace) = cache.trace.as_mut() {
+      trace.push(DecisionTraceEntry::CacheHit(decision_id.to_string(), name.clone()));
+    }
+    Some((name, value))
+  })
+}
+
+/// Stores the result of the decision with specified identifier, defined in the model
+/// with specified namespace and evaluated with specified input data.
+pub(crate) fn cache_result(namespace: &str, decision_id: &str, input_data: &FeelContext, name: &Name, value: Value) {
+  CACHE.with(|cell| {
+    if let Some(cache) = cell.borrow_mut().as_mut() {
+      let index = cache.input_index(input_data).unwrap_or_else(|| {
+        cache.inputs.push(input_data.clone());
+        cache.inputs.len() - 1
+      });
+      cache
+        .results
+        .insert((namespace.to_string(), decision_id.to_string(), index), (name.clone(), value));
+      if let Some(trace) = cache.trace.as_mut() {
+        trace.push(DecisionTraceEntry::Evaluated(decision_id.to_string(), name.clone()));
+      }
+    }
+  });
+}
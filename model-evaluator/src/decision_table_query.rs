@@ -0,0 +1,497 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Reverse queries on decision tables.
+//!
+//! For a desired output value, the rules producing this output are selected
+//! and their input entries are returned as conditions on input expressions.
+//! Conditions of different rules are simplified: rules covered by more general rules
+//! are merged into them, and rules differing only in a single input entry
+//! are merged into one rule listing all tested values of this input entry.
+//! Output entries are evaluated without input values, so rules with output entries
+//! depending on input values never match the desired output.
+//!
+//! With hit policy FIRST, a rule produces its output only when no preceding rule matches,
+//! so the conditions of preceding rules producing other outputs are reported as exclusions,
+//! unless the conditions of both rules are provably disjoint. With hit policies
+//! RULE ORDER, OUTPUT ORDER and COLLECT without aggregation, the rules contributing
+//! the desired output to the resulting list are selected. Hit policy PRIORITY
+//! and COLLECT with aggregation are not supported.
+
+use crate::builders::decision_table_index::are_disjoint;
+use crate::errors::err_unsupported_hit_policy_in_query;
+use dmntk_common::Result;
+use dmntk_feel::context::FeelContext;
+use dmntk_feel::values::Value;
+use dmntk_feel::{AstNode, Name, Scope};
+use dmntk_model::model::{BuiltinAggregator, DecisionTable, HitPolicy};
+use std::fmt;
+
+/// Input conditions under which a decision table produces the desired output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleConditions {
+  /// Numbers of rules producing the desired output under these conditions, starting from 1.
+  pub rules: Vec<usize>,
+  /// Pairs of input expression and unary tests the input value must satisfy, irrelevant inputs are omitted.
+  pub conditions: Vec<(String, String)>,
+  /// Conditions of preceding rules producing other outputs (hit policy FIRST),
+  /// input values satisfying any of these conditions do not produce the desired output.
+  pub exclusions: Vec<Vec<(String, String)>>,
+}
+
+impl fmt::Display for RuleConditions {
+  /// Formats the conditions as a single line of text.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let rules = self.rules.iter().map(|rule| rule.to_string()).collect::<Vec<String>>().join(", ");
+    let conditions = if self.conditions.is_empty() {
+      "any input".to_string()
+    } else {
+      joined_conditions(&self.conditions)
+    };
+    write!(f, "{} {}: {}", if self.rules.len() > 1 { "rules" } else { "rule" }, rules, conditions)?;
+    for exclusion in &self.exclusions {
+      write!(f, ", unless {}", joined_conditions(exclusion))?;
+    }
+    Ok(())
+  }
+}
+
+/// Joins conditions on input expressions into a single line of text.
+fn joined_conditions(conditions: &[(String, String)]) -> String {
+  conditions
+    .iter()
+    .map(|(input_expression, unary_tests)| format!("{} is {}", input_expression, unary_tests))
+    .collect::<Vec<String>>()
+    .join(" and ")
+}
+
+/// Condition on a single input expression.
+#[derive(Clone, PartialEq)]
+enum Condition {
+  /// Any input value satisfies the condition.
+  Irrelevant,
+  /// Input value must satisfy any of the listed unary tests.
+  Positive(Vec<String>),
+  /// Input value must satisfy other unary tests, like negations.
+  Other(String),
+}
+
+impl Condition {
+  /// Returns `true` when this condition is the same as the other condition, regardless of the order of unary tests.
+  fn is_same(&self, other: &Condition) -> bool {
+    match (self, other) {
+      (Condition::Positive(tests), Condition::Positive(other_tests)) => tests.len() == other_tests.len() && tests.iter().all(|test| other_tests.contains(test)),
+      _ => self == other,
+    }
+  }
+}
+
+/// Conditions of one or more rules producing the desired output.
+struct Candidate {
+  rules: Vec<usize>,
+  conditions: Vec<Condition>,
+  /// Indexes of preceding rules producing other outputs, sorted.
+  exclusions: Vec<usize>,
+}
+
+impl Candidate {
+  /// Returns `true` when every input satisfying the conditions of the other candidate satisfies conditions of this candidate.
+  fn covers(&self, other: &Candidate) -> bool {
+    covers(&self.conditions, &other.conditions) && self.exclusions.iter().all(|exclusion| other.exclusions.contains(exclusion))
+  }
+  /// Merges the other candidate differing only in a single positive condition,
+  /// returns `None` when candidates can not be merged.
+  fn merge(&self, other: &Candidate) -> Option<Candidate> {
+    if self.exclusions != other.exclusions {
+      return None;
+    }
+    let mut differing = self
+      .conditions
+      .iter()
+      .zip(other.conditions.iter())
+      .enumerate()
+      .filter(|(_, (condition, other_condition))| !condition.is_same(other_condition));
+    let (index, (condition, other_condition)) = differing.next()?;
+    if differing.next().is_some() {
+      return None;
+    }
+    if let (Condition::Positive(tests), Condition::Positive(other_tests)) = (condition, other_condition) {
+      let mut merged_tests = tests.clone();
+      merged_tests.extend(other_tests.iter().filter(|test| !tests.contains(test)).cloned());
+      let mut conditions = self.conditions.clone();
+      conditions[index] = Condition::Positive(merged_tests);
+      Some(Candidate {
+        rules: merged_rules(&self.rules, &other.rules),
+        conditions,
+        exclusions: self.exclusions.clone(),
+      })
+    } else {
+      None
+    }
+  }
+}
+
+/// Returns `true` when every input satisfying the other conditions satisfies the conditions.
+fn covers(conditions: &[Condition], other_conditions: &[Condition]) -> bool {
+  conditions
+    .iter()
+    .zip(other_conditions.iter())
+    .all(|(condition, other_condition)| *condition == Condition::Irrelevant || condition.is_same(other_condition))
+}
+
+/// Returns sorted rule numbers from both lists.
+fn merged_rules(rules: &[usize], other_rules: &[usize]) -> Vec<usize> {
+  let mut merged = rules.iter().chain(other_rules.iter()).copied().collect::<Vec<usize>>();
+  merged.sort_unstable();
+  merged.dedup();
+  merged
+}
+
+/// Returns the input conditions under which the decision table produces the desired output.
+///
+/// For decision tables with multiple output clauses, the desired output is a context,
+/// and only output components present in this context are compared.
+pub fn query_decision_table(decision_table: &DecisionTable, output: &Value) -> Result<Vec<RuleConditions>> {
+  match decision_table.hit_policy {
+    HitPolicy::Priority => return Err(err_unsupported_hit_policy_in_query(&decision_table.hit_policy)),
+    HitPolicy::Collect(aggregator) if aggregator != BuiltinAggregator::List => return Err(err_unsupported_hit_policy_in_query(&decision_table.hit_policy)),
+    _ => {}
+  }
+  // input expressions being names are placed in scope, so the entries referring them can be parsed
+  let mut ctx = FeelContext::default();
+  for input_clause in &decision_table.input_clauses {
+    ctx.set_null(Name::from(input_clause.input_expression.as_str()));
+  }
+  let scope: Scope = ctx.into();
+  let component_names = decision_table
+    .output_clauses
+    .iter()
+    .map(|output_clause| output_clause.name.as_ref().map(|name| Name::from(name.as_str())))
+    .collect::<Vec<Option<Name>>>();
+  // prepare parsed input entries and conditions of all rules, and select rules producing the desired output
+  let mut rules = vec![];
+  for rule in &decision_table.rules {
+    let mut output_values = vec![];
+    for output_entry in &rule.output_entries {
+      let node = dmntk_feel_parser::parse_expression(&scope, &output_entry.text, false)?;
+      output_values.push(dmntk_feel_evaluator::evaluate(&scope, &node)?);
+    }
+    let mut nodes = vec![];
+    let mut conditions = vec![];
+    for input_entry in &rule.input_entries {
+      let text = input_entry.text.trim().to_string();
+      let node = dmntk_feel_parser::parse_unary_tests(&scope, &text, false)?;
+      conditions.push(match node {
+        AstNode::Irrelevant => Condition::Irrelevant,
+        AstNode::ExpressionList(_) => Condition::Positive(vec![text]),
+        _ => Condition::Other(text),
+      });
+      nodes.push(node);
+    }
+    rules.push((produces_output(&component_names, &output_values, output), nodes, conditions));
+  }
+  let mut candidates = vec![];
+  'rules: for (rule_index, (produces, nodes, conditions)) in rules.iter().enumerate() {
+    if !produces {
+      continue;
+    }
+    // with hit policy FIRST, preceding rules producing other outputs shadow this rule
+    let mut exclusions = vec![];
+    if decision_table.hit_policy == HitPolicy::First {
+      for (other_index, (other_produces, other_nodes, other_conditions)) in rules.iter().enumerate().take(rule_index) {
+        if *other_produces || nodes.iter().zip(other_nodes.iter()).any(|(node, other_node)| are_disjoint(node, other_node)) {
+          continue;
+        }
+        if covers(other_conditions, conditions) {
+          continue 'rules;
+        }
+        exclusions.push(other_index);
+      }
+    }
+    candidates.push(Candidate {
+      rules: vec![rule_index + 1],
+      conditions: conditions.clone(),
+      exclusions,
+    });
+  }
+  simplify(&mut candidates);
+  let input_conditions = |conditions: &[Condition]| -> Vec<(String, String)> {
+    decision_table
+      .input_clauses
+      .iter()
+      .zip(conditions.iter())
+      .filter_map(|(input_clause, condition)| match condition {
+        Condition::Irrelevant => None,
+        Condition::Positive(tests) => Some((input_clause.input_expression.trim().to_string(), tests.join(", "))),
+        Condition::Other(test) => Some((input_clause.input_expression.trim().to_string(), test.clone())),
+      })
+      .collect()
+  };
+  Ok(
+    candidates
+      .into_iter()
+      .map(|candidate| RuleConditions {
+        rules: candidate.rules,
+        conditions: input_conditions(&candidate.conditions),
+        exclusions: candidate.exclusions.iter().map(|index| input_conditions(&rules[*index].2)).collect(),
+      })
+      .collect(),
+  )
+}
+
+/// Returns `true` when output values of a rule produce the desired output.
+fn produces_output(component_names: &[Option<Name>], output_values: &[Value], output: &Value) -> bool {
+  if output_values.len() == 1 {
+    return output_values[0] == *output;
+  }
+  if let Value::Context(output_ctx) = output {
+    output_ctx.get_entries().iter().all(|(name, value)| {
+      match component_names
+        .iter()
+        .position(|component_name| component_name.as_ref() == Some(*name))
+        .and_then(|index| output_values.get(index))
+      {
+        Some(output_value) => output_value == *value,
+        None => false,
+      }
+    })
+  } else {
+    false
+  }
+}
+
+/// Simplifies conditions, until no more candidates can be covered or merged.
+fn simplify(candidates: &mut Vec<Candidate>) {
+  'simplifying: loop {
+    for i in 0..candidates.len() {
+      for j in 0..candidates.len() {
+        if i == j {
+          continue;
+        }
+        if candidates[i].covers(&candidates[j]) {
+          let covered = candidates.remove(j);
+          let index = if j < i { i - 1 } else { i };
+          candidates[index].rules = merged_rules(&candidates[index].rules, &covered.rules);
+          continue 'simplifying;
+        }
+        if let Some(merged) = candidates[i].merge(&candidates[j]) {
+          candidates[i] = merged;
+          candidates.remove(j);
+          continue 'simplifying;
+        }
+      }
+    }
+    break;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use dmntk_model::model::{DecisionRule, DecisionTableOrientation, InputClause, InputEntry, OutputClause, OutputEntry};
+
+  /// Builds a decision table with specified input expressions, output component names and rules.
+  fn decision_table(input_expressions: &[&str], output_names: &[&str], rules: &[(&[&str], &[&str])]) -> DecisionTable {
+    DecisionTable {
+      information_item_name: None,
+      input_clauses: input_expressions
+        .iter()
+        .map(|input_expression| InputClause {
+          input_expression: input_expression.to_string(),
+          input_values: None,
+        })
+        .collect(),
+      output_clauses: output_names
+        .iter()
+        .map(|output_name| OutputClause {
+          type_ref: None,
+          name: if output_names.len() > 1 { Some(output_name.to_string()) } else { None },
+          output_values: None,
+          default_output_entry: None,
+        })
+        .collect(),
+      annotations: vec![],
+      rules: rules
+        .iter()
+        .map(|(input_entries, output_entries)| DecisionRule {
+          id: None,
+          input_entries: input_entries.iter().map(|text| InputEntry { text: text.to_string() }).collect(),
+          output_entries: output_entries.iter().map(|text| OutputEntry { text: text.to_string() }).collect(),
+          annotation_entries: vec![],
+        })
+        .collect(),
+      hit_policy: HitPolicy::First,
+      aggregation: None,
+      preferred_orientation: DecisionTableOrientation::RuleAsRow,
+      output_label: None,
+    }
+  }
+
+  /// Queries the decision table and returns the conditions formatted as text.
+  fn query(decision_table: &DecisionTable, output: &str) -> Vec<String> {
+    let output = dmntk_feel_evaluator::evaluate(
+      &Scope::default(),
+      &dmntk_feel_parser::parse_expression(&Scope::default(), output, false).unwrap(),
+    )
+    .unwrap();
+    query_decision_table(decision_table, &output)
+      .unwrap()
+      .iter()
+      .map(|rule_conditions| rule_conditions.to_string())
+      .collect()
+  }
+
+  fn loan_table() -> DecisionTable {
+    decision_table(
+      &["Credit Score", "Monthly Income"],
+      &["Decision"],
+      &[
+        (&[r#""POOR""#, "-"], &[r#""DECLINED""#]),
+        (&[r#""FAIR""#, "< 1000"], &[r#""DECLINED""#]),
+        (&[r#""FAIR""#, ">= 1000"], &[r#""APPROVED""#]),
+        (&[r#""GOOD""#, "-"], &[r#""APPROVED""#]),
+        (&[r#""POOR""#, "< 500"], &[r#""DECLINED""#]),
+        (&[r#""BAD""#, "-"], &[r#""DECLINED""#]),
+        (&[r#"not("GOOD")"#, "> 10000"], &[r#""REVIEW""#]),
+      ],
+    )
+  }
+
+  #[test]
+  fn test_covered_and_merged_rules() {
+    assert_eq!(
+      vec![
+        r#"rules 1, 5, 6: Credit Score is "POOR", "BAD""#,
+        r#"rule 2: Credit Score is "FAIR" and Monthly Income is < 1000"#
+      ],
+      query(&loan_table(), r#""DECLINED""#)
+    );
+  }
+
+  #[test]
+  fn test_distinct_rules() {
+    assert_eq!(
+      vec![
+        r#"rule 3: Credit Score is "FAIR" and Monthly Income is >= 1000"#,
+        r#"rule 4: Credit Score is "GOOD""#
+      ],
+      query(&loan_table(), r#""APPROVED""#)
+    );
+    assert!(query(&loan_table(), r#""UNKNOWN""#).is_empty());
+  }
+
+  #[test]
+  fn test_first_preceding_rules() {
+    // preceding rules with disjoint conditions are not reported as exclusions
+    assert_eq!(
+      vec![
+        r#"rule 7: Credit Score is not("GOOD") and Monthly Income is > 10000, unless Credit Score is "POOR", unless Credit Score is "FAIR" and Monthly Income is >= 1000, unless Credit Score is "GOOD", unless Credit Score is "BAD""#
+      ],
+      query(&loan_table(), r#""REVIEW""#)
+    );
+  }
+
+  fn discount_table(hit_policy: HitPolicy) -> DecisionTable {
+    let mut decision_table = decision_table(
+      &["Age", "Member"],
+      &["Discount"],
+      &[
+        (&["< 18", "-"], &["10"]),
+        (&["-", "true"], &["20"]),
+        (&["< 18", "false"], &["20"]),
+        (&[">= 65", "-"], &["20"]),
+      ],
+    );
+    decision_table.hit_policy = hit_policy;
+    decision_table
+  }
+
+  #[test]
+  fn test_first_shadowed_rules() {
+    // rule 3 is shadowed by rule 1, rule 2 is partially shadowed by rule 1, rule 4 is disjoint with rule 1
+    assert_eq!(
+      vec!["rule 2: Member is true, unless Age is < 18", "rule 4: Age is >= 65"],
+      query(&discount_table(HitPolicy::First), "20")
+    );
+    assert_eq!(vec!["rule 1: Age is < 18"], query(&discount_table(HitPolicy::First), "10"));
+  }
+
+  #[test]
+  fn test_unique_rules() {
+    assert_eq!(
+      vec!["rule 2: Member is true", "rule 3: Age is < 18 and Member is false", "rule 4: Age is >= 65"],
+      query(&discount_table(HitPolicy::Unique), "20")
+    );
+    assert_eq!(
+      vec!["rule 2: Member is true", "rule 3: Age is < 18 and Member is false", "rule 4: Age is >= 65"],
+      query(&discount_table(HitPolicy::Collect(BuiltinAggregator::List)), "20")
+    );
+  }
+
+  #[test]
+  fn test_unsupported_hit_policies() {
+    let output = Value::Number(20.into());
+    assert_eq!(
+      "ModelEvaluatorError: querying decision tables with hit policy PRIORITY is not supported",
+      query_decision_table(&discount_table(HitPolicy::Priority), &output).unwrap_err().to_string()
+    );
+    assert_eq!(
+      "ModelEvaluatorError: querying decision tables with hit policy COLLECT SUM is not supported",
+      query_decision_table(&discount_table(HitPolicy::Collect(BuiltinAggregator::Sum)), &output)
+        .unwrap_err()
+        .to_string()
+    );
+  }
+
+  #[test]
+  fn test_any_input() {
+    let decision_table = decision_table(&["Age"], &["Category"], &[(&["< 18"], &[r#""CHILD""#]), (&["-"], &[r#""CHILD""#])]);
+    assert_eq!(vec!["rules 1, 2: any input"], query(&decision_table, r#""CHILD""#));
+  }
+
+  #[test]
+  fn test_multiple_outputs() {
+    let decision_table = decision_table(
+      &["Age"],
+      &["Category", "Discount"],
+      &[
+        (&["< 18"], &[r#""CHILD""#, "10"]),
+        (&["[18..65]"], &[r#""ADULT""#, "0"]),
+        (&["> 65"], &[r#""SENIOR""#, "10"]),
+      ],
+    );
+    assert_eq!(vec!["rules 1, 3: Age is < 18, > 65"], query(&decision_table, "{Discount: 10}"));
+    assert_eq!(vec!["rule 2: Age is [18..65]"], query(&decision_table, r#"{Category: "ADULT", Discount: 0}"#));
+    assert!(query(&decision_table, r#"{Category: "ADULT", Discount: 10}"#).is_empty());
+    assert!(query(&decision_table, r#""ADULT""#).is_empty());
+  }
+}
@@ -31,7 +31,8 @@
  */
 
 use dmntk_common::DmntkError;
-use dmntk_feel::FeelType;
+use dmntk_feel::{FeelType, Name};
+use dmntk_model::model::HitPolicy;
 
 /// Errors related to model evaluation.
 #[derive(Error, Debug)]
@@ -62,10 +63,14 @@ pub enum ModelEvaluatorError {
   EmptyFunctionBody,
   #[error("empty value expression")]
   EmptyValueExpression,
+  #[error("invalid cell in relation row {0} column `{1}`: {2}")]
+  InvalidRelationCell(usize, String, String),
   #[error("read lock failed with reason '{0}'")]
   ReadLockFailed(String),
   #[error("write lock failed with reason '{0}'")]
   WriteLockFailed(String),
+  #[error("querying decision tables with hit policy {0} is not supported")]
+  UnsupportedHitPolicyInQuery(String),
 }
 
 impl From<ModelEvaluatorError> for DmntkError {
@@ -126,6 +131,10 @@ pub fn err_empty_value_expression() -> DmntkError {
   ModelEvaluatorError::EmptyValueExpression.into()
 }
 
+pub fn err_invalid_relation_cell(row: usize, column: &Name, reason: impl ToString) -> DmntkError {
+  ModelEvaluatorError::InvalidRelationCell(row, column.to_string(), reason.to_string()).into()
+}
+
 pub fn err_read_lock_failed(reason: impl ToString) -> DmntkError {
   ModelEvaluatorError::ReadLockFailed(reason.to_string()).into()
 }
@@ -133,3 +142,7 @@ pub fn err_read_lock_failed(reason: impl ToString) -> DmntkError {
 pub fn err_write_lock_failed(reason: impl ToString) -> DmntkError {
   ModelEvaluatorError::WriteLockFailed(reason.to_string()).into()
 }
+
+pub fn err_unsupported_hit_policy_in_query(hit_policy: &HitPolicy) -> DmntkError {
+  ModelEvaluatorError::UnsupportedHitPolicyInQuery(hit_policy.to_string()).into()
+}
@@ -43,11 +43,19 @@ extern crate lazy_static;
 extern crate thiserror;
 
 mod builders;
+mod decision_cache;
+mod decision_table_query;
 mod errors;
 mod model_evaluator;
+mod rule_hits;
+mod validation;
 
 #[cfg(test)]
 mod tests;
 
 pub use builders::decision_table::build_decision_table_evaluator;
-pub use model_evaluator::ModelEvaluator;
+pub use decision_cache::DecisionTraceEntry;
+pub use decision_table_query::{query_decision_table, RuleConditions};
+pub use model_evaluator::{ModelEvaluator, PartialEvaluation};
+pub use rule_hits::{collect_rule_hits, RuleHit};
+pub use validation::{ValidationMode, ValidationReport, Violation};
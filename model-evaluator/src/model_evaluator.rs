@@ -34,13 +34,17 @@ use crate::builders::{
   BusinessKnowledgeModelEvaluator, DecisionEvaluator, DecisionServiceEvaluator, InputDataContextEvaluator, InputDataEvaluator, ItemDefinitionContextEvaluator,
   ItemDefinitionEvaluator, ItemDefinitionTypeEvaluator,
 };
+use crate::decision_cache::{with_decision_cache, DecisionTraceEntry};
 use crate::errors::{err_read_lock_failed, err_write_lock_failed};
+use crate::rule_hits::{collect_rule_hits, RuleHit};
+use crate::validation::{with_validation, ValidationMode, ValidationReport};
 use dmntk_common::Result;
 use dmntk_feel::context::FeelContext;
 use dmntk_feel::values::Value;
-use dmntk_feel::{value_null, Name};
+use dmntk_feel::{value_null, FeelType, Name};
+use dmntk_feel_evaluator::{Backend, TypeError};
 use dmntk_model::model::Definitions;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, RwLock, RwLockReadGuard};
 
 ///
@@ -51,9 +55,22 @@ pub enum InvocableType {
   DecisionService(String),
 }
 
+/// Result of the partial evaluation of decisions, when some input data are missing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialEvaluation {
+  /// Results of decisions, for which all required input data were provided, indexed by decision names.
+  pub results: FeelContext,
+  /// Sorted names of missing input data blocking the evaluation of remaining decisions, indexed by decision names.
+  /// Names are taken statically from information requirements, so some of them may not be needed
+  /// for the evaluation of the decision with other present input data values.
+  pub missing_input_data: BTreeMap<String, Vec<String>>,
+}
+
 ///
 #[derive(Default)]
 pub struct ModelEvaluator {
+  /// Backend used for evaluating `FEEL` expressions in this model.
+  backend: Backend,
   /// Input data evaluator.
   input_data_evaluator: RwLock<InputDataEvaluator>,
   /// Input data context evaluator.
@@ -72,12 +89,18 @@ pub struct ModelEvaluator {
   decision_service_evaluator: RwLock<DecisionServiceEvaluator>,
   /// Map of [InvocableType] indexed by invocable (decision, business knowledge model or decision service) name.
   invocable_by_name: RwLock<HashMap<String, InvocableType>>,
+  /// Mode of validating input and output values.
+  validation_mode: RwLock<ValidationMode>,
 }
 
 impl ModelEvaluator {
   /// Creates an instance of [ModelEvaluator].
   pub fn new(definitions: &Definitions) -> Result<Arc<Self>> {
-    let model_evaluator = Arc::new(ModelEvaluator::default());
+    Self::new_with_backend(definitions, Backend::default())
+  }
+  /// Creates an instance of [ModelEvaluator] evaluating `FEEL` expressions using specified backend.
+  pub fn new_with_backend(definitions: &Definitions, backend: Backend) -> Result<Arc<Self>> {
+    let model_evaluator = Arc::new(ModelEvaluator { backend, ..Default::default() });
     model_evaluator
       .input_data_evaluator
       .write()
@@ -120,6 +143,10 @@ impl ModelEvaluator {
       .build(definitions, Arc::clone(&model_evaluator))?;
     Ok(model_evaluator)
   }
+  /// Returns the backend used for evaluating `FEEL` expressions.
+  pub fn backend(&self) -> Backend {
+    self.backend
+  }
   ///
   pub fn input_data_evaluator(&self) -> Result<RwLockReadGuard<InputDataEvaluator>> {
     self.input_data_evaluator.read().map_err(err_read_lock_failed)
@@ -152,8 +179,193 @@ impl ModelEvaluator {
   pub fn decision_evaluator(&self) -> Result<RwLockReadGuard<DecisionEvaluator>> {
     self.decision_evaluator.read().map_err(err_read_lock_failed)
   }
+  /// Returns the mode of validating input and output values.
+  pub fn validation_mode(&self) -> ValidationMode {
+    self.validation_mode.read().map(|validation_mode| *validation_mode).unwrap_or_default()
+  }
+  /// Sets the mode of validating input and output values.
+  pub fn set_validation_mode(&self, validation_mode: ValidationMode) {
+    if let Ok(mut current_validation_mode) = self.validation_mode.write() {
+      *current_validation_mode = validation_mode;
+    }
+  }
   /// Evaluates an invocable with specified name.
+  ///
+  /// Each decision is evaluated at most once for the same input data during this evaluation.
+  /// In strict validation mode, when any value violates its constraints,
+  /// `null` value with the description of violations is returned.
   pub fn evaluate_invocable(&self, invocable_name: &str, input_data: &FeelContext) -> Value {
+    match self.evaluate_invocable_validated(invocable_name, input_data) {
+      Ok(value) => value,
+      Err(report) => value_null!("{}", report),
+    }
+  }
+  /// Evaluates an invocable with specified name, in strict validation mode
+  /// all values violating their constraints are reported in [ValidationReport].
+  pub fn evaluate_invocable_validated(&self, invocable_name: &str, input_data: &FeelContext) -> Result<Value, ValidationReport> {
+    self.validated(|| with_decision_cache(false, || self.evaluate_invocable_by_name(invocable_name, input_data)).0)
+  }
+  /// Evaluates invocables with specified names using the same input data and returns
+  /// a context containing the results of all invocables, indexed by invocable names.
+  ///
+  /// Each decision is evaluated at most once for the same input data, no matter how many invocables require it.
+  /// In strict validation mode, when any value violates its constraints,
+  /// `null` value with the description of violations is returned.
+  pub fn evaluate_invocables(&self, invocable_names: &[String], input_data: &FeelContext) -> Value {
+    match self.evaluate_invocables_validated(invocable_names, input_data) {
+      Ok(value) => value,
+      Err(report) => value_null!("{}", report),
+    }
+  }
+  /// Evaluates invocables with specified names using the same input data, in strict validation mode
+  /// all values violating their constraints are reported in [ValidationReport].
+  pub fn evaluate_invocables_validated(&self, invocable_names: &[String], input_data: &FeelContext) -> Result<Value, ValidationReport> {
+    self.validated(|| {
+      with_decision_cache(false, || {
+        let mut results = FeelContext::default();
+        for invocable_name in invocable_names {
+          results.set_entry(
+            &Name::from(invocable_name.as_str()),
+            self.evaluate_invocable_by_name(invocable_name, input_data),
+          );
+        }
+        Value::Context(results)
+      })
+      .0
+    })
+  }
+  /// Invokes a decision service with specified name, the way process engines invoke decision services.
+  ///
+  /// Values of input decisions of the decision service are taken from input data,
+  /// input decisions provided in input data are not evaluated.
+  /// When the names of output decisions are specified, only these output decisions are evaluated
+  /// and their results are returned in a context, indexed by decision names.
+  /// In strict validation mode, when any value violates its constraints,
+  /// `null` value with the description of violations is returned.
+  pub fn invoke_decision_service(&self, decision_service_name: &str, input_data: &FeelContext, output_decision_names: Option<&[String]>) -> Value {
+    match self.invoke_decision_service_validated(decision_service_name, input_data, output_decision_names) {
+      Ok(value) => value,
+      Err(report) => value_null!("{}", report),
+    }
+  }
+  /// Invokes a decision service with specified name, in strict validation mode
+  /// all values violating their constraints are reported in [ValidationReport].
+  pub fn invoke_decision_service_validated(
+    &self,
+    decision_service_name: &str,
+    input_data: &FeelContext,
+    output_decision_names: Option<&[String]>,
+  ) -> Result<Value, ValidationReport> {
+    self.validated(|| {
+      with_decision_cache(false, || match (self.decision_service_id(decision_service_name), output_decision_names) {
+        (Some(id), Some(output_decision_names)) => self.evaluate_decision_service_output_decisions(&id, input_data, output_decision_names),
+        (Some(id), None) => self.evaluate_decision_service(&id, input_data),
+        (None, _) => value_null!("decision service with name '{}' not found", decision_service_name),
+      })
+      .0
+    })
+  }
+  /// Evaluates selected output decisions of a decision service.
+  fn evaluate_decision_service_output_decisions(&self, id: &str, input_data: &FeelContext, output_decision_names: &[String]) -> Value {
+    if let Ok(decision_service_evaluator) = self.decision_service_evaluator() {
+      let mut evaluated_ctx = FeelContext::default();
+      if let Some(output_variable_name) = decision_service_evaluator.evaluate_output_decisions(id, input_data, output_decision_names, self, &mut evaluated_ctx)
+      {
+        if let Some(output_value) = evaluated_ctx.get_entry(&output_variable_name) {
+          return output_value.clone();
+        }
+      }
+    }
+    value_null!()
+  }
+  /// Returns the identifier of the decision service with specified name.
+  fn decision_service_id(&self, decision_service_name: &str) -> Option<String> {
+    self
+      .invocable_by_name
+      .read()
+      .ok()
+      .and_then(|invocable_by_name| match invocable_by_name.get(decision_service_name) {
+        Some(InvocableType::DecisionService(id)) => Some(id.clone()),
+        _ => None,
+      })
+  }
+  /// Runs specified evaluation, collecting violations of constraints in strict validation mode.
+  fn validated<F>(&self, evaluate: F) -> Result<Value, ValidationReport>
+  where
+    F: FnOnce() -> Value,
+  {
+    if self.validation_mode() == ValidationMode::Lenient {
+      return Ok(evaluate());
+    }
+    match with_validation(evaluate) {
+      (value, violations) if violations.is_empty() => Ok(value),
+      (_, violations) => Err(ValidationReport { violations }),
+    }
+  }
+  /// Evaluates an invocable with specified name and returns the trace of evaluated decisions,
+  /// including decisions which results were taken from cache.
+  pub fn evaluate_invocable_with_trace(&self, invocable_name: &str, input_data: &FeelContext) -> (Value, Vec<DecisionTraceEntry>) {
+    with_decision_cache(true, || self.evaluate_invocable_by_name(invocable_name, input_data))
+  }
+  /// Evaluates an invocable with specified name and returns the rules hit in all evaluated decision tables,
+  /// in order of evaluation, together with their identifiers and annotations.
+  pub fn evaluate_invocable_with_rule_hits(&self, invocable_name: &str, input_data: &FeelContext) -> (Value, Vec<RuleHit>) {
+    collect_rule_hits(|| self.evaluate_invocable(invocable_name, input_data))
+  }
+  /// Evaluates all decisions, for which all required input data are present in specified input data.
+  ///
+  /// For each remaining decision, the names of missing input data required by the decision,
+  /// directly or through required decisions, are returned. Input data with `null` value are considered present.
+  ///
+  /// Required input data are collected statically from the graph of information requirements,
+  /// not from the input data actually accessed during evaluation, so the result is an over-approximation:
+  /// a decision is blocked by every missing input data it requires, even when the missing value
+  /// would not be used for the present input data values (e.g. in a branch of `if` expression not taken).
+  /// Each decision is evaluated at most once for the same input data.
+  pub fn evaluate_partial(&self, input_data: &FeelContext) -> PartialEvaluation {
+    let decisions = self
+      .invocable_by_name
+      .read()
+      .map(|invocable_by_name| {
+        invocable_by_name
+          .iter()
+          .filter_map(|(name, invocable_type)| match invocable_type {
+            InvocableType::Decision(id) => Some((name.clone(), id.clone())),
+            _ => None,
+          })
+          .collect::<Vec<(String, String)>>()
+      })
+      .unwrap_or_default();
+    let mut partial_evaluation = PartialEvaluation {
+      results: FeelContext::default(),
+      missing_input_data: BTreeMap::new(),
+    };
+    with_decision_cache(false, || {
+      for (decision_name, decision_id) in decisions {
+        let missing_input_data = self
+          .decision_evaluator()
+          .map(|decision_evaluator| {
+            decision_evaluator
+              .get_required_input_data(&decision_id)
+              .unwrap_or_default()
+              .iter()
+              .filter(|input_data_name| !input_data.contains_entry(input_data_name))
+              .map(|input_data_name| input_data_name.to_string())
+              .collect::<Vec<String>>()
+          })
+          .unwrap_or_default();
+        if missing_input_data.is_empty() {
+          let value = self.evaluate_decision(&decision_id, input_data);
+          partial_evaluation.results.set_entry(&Name::from(decision_name.as_str()), value);
+        } else {
+          partial_evaluation.missing_input_data.insert(decision_name, missing_input_data);
+        }
+      }
+    });
+    partial_evaluation
+  }
+  /// Evaluates an invocable with specified name, using the decision cache active in the current thread.
+  fn evaluate_invocable_by_name(&self, invocable_name: &str, input_data: &FeelContext) -> Value {
     if let Ok(invocable_by_name) = self.invocable_by_name.read() {
       match invocable_by_name.get(invocable_name) {
         Some(InvocableType::Decision(id)) => {
@@ -174,6 +386,44 @@ impl ModelEvaluator {
       value_null!("write lock failed when acquiring invocable_by_name map")
     }
   }
+  /// Returns sorted names of all invocables (decisions, business knowledge models and decision services).
+  pub fn invocable_names(&self) -> Vec<String> {
+    let mut names = self
+      .invocable_by_name
+      .read()
+      .map(|invocable_by_name| invocable_by_name.keys().cloned().collect::<Vec<String>>())
+      .unwrap_or_default();
+    names.sort();
+    names
+  }
+  /// Returns sorted names of all decisions.
+  pub fn decision_names(&self) -> Vec<String> {
+    let mut names = self
+      .invocable_by_name
+      .read()
+      .map(|invocable_by_name| {
+        invocable_by_name
+          .iter()
+          .filter(|(_, invocable_type)| matches!(invocable_type, InvocableType::Decision(_)))
+          .map(|(name, _)| name.clone())
+          .collect::<Vec<String>>()
+      })
+      .unwrap_or_default();
+    names.sort();
+    names
+  }
+  /// Returns the inferred result type and type errors detected in the logic of the decision with specified name.
+  pub fn decision_type(&self, decision_name: &str) -> Option<(FeelType, Vec<TypeError>)> {
+    let invocable_by_name = self.invocable_by_name.read().ok()?;
+    if let Some(InvocableType::Decision(id)) = invocable_by_name.get(decision_name) {
+      let decision_evaluator = self.decision_evaluator().ok()?;
+      let result_type = decision_evaluator.get_result_type(id)?.clone();
+      let type_errors = decision_evaluator.get_type_errors(id).unwrap_or_default().to_vec();
+      Some((result_type, type_errors))
+    } else {
+      None
+    }
+  }
   ///
   pub fn add_invocable_decision(&self, name: &str, id: &str) {
     if let Ok(mut invocable_by_name) = self.invocable_by_name.write() {
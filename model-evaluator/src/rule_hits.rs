@@ -0,0 +1,127 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Reporting of decision table rules hit during evaluation.
+//!
+//! While rule hits are collected, every evaluated decision table reports the rules
+//! that contributed to its output, according to its hit policy. Each reported rule
+//! carries its index, its optional identifier and the texts of its annotation entries,
+//! so the reasons of the decision may be quoted alongside the output.
+
+use std::cell::RefCell;
+
+thread_local! {
+  /// Rules hit while evaluating decision tables in the current thread.
+  static RULE_HITS: RefCell<Option<Vec<RuleHit>>> = const { RefCell::new(None) };
+}
+
+/// Rule of a decision table that contributed to the output of the decision table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleHit {
+  /// Name of the decision, during evaluation of which the rule was hit.
+  pub decision: Option<String>,
+  /// Index of the rule in the decision table, starting from 1.
+  pub index: usize,
+  /// Optional identifier of the rule.
+  pub id: Option<String>,
+  /// Annotations of the rule, as pairs of rule annotation clause name and annotation entry text.
+  pub annotations: Vec<(String, String)>,
+}
+
+impl RuleHit {
+  /// Returns the text of the annotation entry in rule annotation clause with specified name.
+  pub fn annotation(&self, name: &str) -> Option<&str> {
+    self
+      .annotations
+      .iter()
+      .find(|(clause_name, _)| clause_name == name)
+      .map(|(_, text)| text.as_str())
+  }
+}
+
+/// Restores rule hits collected before the evaluation, also when the evaluation panics.
+struct RuleHitsRestorer(Option<Vec<RuleHit>>);
+
+impl Drop for RuleHitsRestorer {
+  fn drop(&mut self) {
+    let previous = self.0.take();
+    RULE_HITS.with(|cell| cell.replace(previous));
+  }
+}
+
+/// Runs specified function collecting rules hit in decision tables evaluated in the current thread.
+///
+/// When rule hits are already collected (nested evaluation), the function runs
+/// using the existing collection and the returned list of rule hits is empty.
+pub fn collect_rule_hits<R, F>(f: F) -> (R, Vec<RuleHit>)
+where
+  F: FnOnce() -> R,
+{
+  if is_collecting_rule_hits() {
+    return (f(), vec![]);
+  }
+  let _restorer = RuleHitsRestorer(RULE_HITS.with(|cell| cell.replace(Some(vec![]))));
+  let result = f();
+  let rule_hits = RULE_HITS.with(|cell| cell.borrow_mut().as_mut().map(std::mem::take)).unwrap_or_default();
+  (result, rule_hits)
+}
+
+/// Returns `true` when rule hits are collected in the current thread.
+pub(crate) fn is_collecting_rule_hits() -> bool {
+  RULE_HITS.with(|cell| cell.borrow().is_some())
+}
+
+/// Reports a rule hit in the evaluated decision table.
+pub(crate) fn report_rule_hit(rule_hit: RuleHit) {
+  RULE_HITS.with(|cell| {
+    if let Some(rule_hits) = cell.borrow_mut().as_mut() {
+      rule_hits.push(rule_hit);
+    }
+  });
+}
+
+/// Returns the number of rule hits reported so far in the current thread.
+pub(crate) fn rule_hits_count() -> usize {
+  RULE_HITS.with(|cell| cell.borrow().as_ref().map_or(0, |rule_hits| rule_hits.len()))
+}
+
+/// Assigns specified decision name to all rule hits reported after the specified
+/// number of rule hits, that were not assigned to any decision yet.
+pub(crate) fn assign_rule_hits(start: usize, decision_name: &str) {
+  RULE_HITS.with(|cell| {
+    if let Some(rule_hits) = cell.borrow_mut().as_mut() {
+      for rule_hit in rule_hits.iter_mut().skip(start).filter(|rule_hit| rule_hit.decision.is_none()) {
+        rule_hit.decision = Some(decision_name.to_string());
+      }
+    }
+  });
+}
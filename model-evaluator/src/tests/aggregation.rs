@@ -0,0 +1,177 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests of aggregating output values in decision tables with collect hit policy.
+//!
+//! Tests use compatibility example `2_0114` with modified aggregation, type and output entries.
+//! The decision table in this example has four rules, for `NumOfYears` equal to 5 all rules match,
+//! for `NumOfYears` equal to 3 only the first two rules match.
+
+use super::*;
+
+/// Output entries of rules, as defined in the example.
+const OUTPUT_ENTRIES: [&str; 4] = ["98.83", "150.21", "205.43", "64.32"];
+
+/// Returns the example model with specified aggregation, type of the decision and output entries.
+/// When the type is not specified, then the decision is untyped.
+fn model(aggregation: &str, type_ref: Option<&str>, output_entries: [&str; 4]) -> String {
+  let mut model = dmntk_examples::DMN_2_0114.replace(r#"aggregation="MIN""#, &format!(r#"aggregation="{}""#, aggregation));
+  model = match type_ref {
+    Some(type_ref) => model.replace(
+      r#"typeRef="number" name="CarInsurance""#,
+      &format!(r#"typeRef="{}" name="CarInsurance""#, type_ref),
+    ),
+    None => model.replace(r#"typeRef="number" name="CarInsurance""#, r#"name="CarInsurance""#),
+  };
+  model = model.replace(r#"outputLabel="CarInsurance" typeRef="number""#, r#"outputLabel="CarInsurance""#);
+  for (from, to) in OUTPUT_ENTRIES.iter().zip(output_entries) {
+    model = model.replace(&format!("<text>{}</text>", from), &format!("<text>{}</text>", to));
+  }
+  model
+}
+
+/// Evaluates the decision in the model for specified number of years.
+fn eval(model: &str, num_of_years: u8) -> String {
+  build_model_evaluator(model)
+    .evaluate_invocable("CarInsurance", &context(&format!("{{NumOfYears: {}}}", num_of_years)))
+    .to_string()
+}
+
+#[test]
+fn _0001() {
+  // minimum and maximum of dates
+  let dates = [
+    r#"date("2021-05-01")"#,
+    r#"date("2020-01-01")"#,
+    r#"date("2022-12-31")"#,
+    r#"date("2021-01-01")"#,
+  ];
+  assert_eq!("2020-01-01", eval(&model("MIN", Some("date"), dates), 5));
+  assert_eq!("2022-12-31", eval(&model("MAX", Some("date"), dates), 5));
+  assert_eq!("2021-05-01", eval(&model("MAX", Some("date"), dates), 3));
+}
+
+#[test]
+fn _0002() {
+  // minimum and maximum of dates and times, with and without time zone
+  let date_times = [
+    r#"date and time("2021-05-01T08:00:00")"#,
+    r#"date and time("2021-05-01T10:00:00")"#,
+    r#"date and time("2021-05-01T07:59:59")"#,
+    r#"date and time("2021-05-01T09:00:00")"#,
+  ];
+  assert_eq!("2021-05-01T07:59:59", eval(&model("MIN", Some("dateTime"), date_times), 5));
+  assert_eq!("2021-05-01T10:00:00", eval(&model("MAX", Some("dateTime"), date_times), 5));
+  let date_times = [
+    r#"date and time("2021-05-01T08:00:00@Europe/Warsaw")"#,
+    r#"date and time("2021-05-01T07:00:00Z")"#,
+    r#"date and time("2021-05-01T13:00:00+05:00")"#,
+    r#"date and time("2021-05-01T08:30:00+02:00")"#,
+  ];
+  assert_eq!("2021-05-01T08:00:00@Europe/Warsaw", eval(&model("MIN", Some("dateTime"), date_times), 5));
+  assert_eq!("2021-05-01T13:00:00+05:00", eval(&model("MAX", Some("dateTime"), date_times), 5));
+}
+
+#[test]
+fn _0003() {
+  // minimum and maximum of durations
+  let durations = [r#"duration("P1D")"#, r#"duration("PT12H")"#, r#"duration("P1DT1S")"#, r#"duration("PT36H")"#];
+  assert_eq!("PT12H", eval(&model("MIN", Some("dayTimeDuration"), durations), 5));
+  assert_eq!("P1DT12H", eval(&model("MAX", Some("dayTimeDuration"), durations), 5));
+  let durations = [r#"duration("P2Y")"#, r#"duration("P11M")"#, r#"duration("P1Y13M")"#, r#"duration("P1M")"#];
+  assert_eq!("P1M", eval(&model("MIN", Some("yearMonthDuration"), durations), 5));
+  assert_eq!("P2Y1M", eval(&model("MAX", Some("yearMonthDuration"), durations), 5));
+}
+
+#[test]
+fn _0004() {
+  // minimum and maximum of strings
+  let strings = [r#""b""#, r#""a""#, r#""d""#, r#""C""#];
+  assert_eq!(r#""C""#, eval(&model("MIN", Some("string"), strings), 5));
+  assert_eq!(r#""d""#, eval(&model("MAX", Some("string"), strings), 5));
+  assert_eq!(r#""a""#, eval(&model("MIN", Some("string"), strings), 3));
+}
+
+#[test]
+fn _0005() {
+  // sum of durations
+  let durations = [r#"duration("P1D")"#, r#"duration("PT12H")"#, r#"duration("PT1S")"#, r#"duration("-PT1H")"#];
+  assert_eq!("P1DT11H1S", eval(&model("SUM", Some("dayTimeDuration"), durations), 5));
+  assert_eq!("P1DT12H", eval(&model("SUM", Some("dayTimeDuration"), durations), 3));
+  let durations = [r#"duration("P2Y")"#, r#"duration("P11M")"#, r#"duration("P1Y")"#, r#"duration("P1M")"#];
+  assert_eq!("P4Y", eval(&model("SUM", Some("yearMonthDuration"), durations), 5));
+  // days and time durations and years and months durations can not be summed together
+  let durations = [r#"duration("P1D")"#, r#"duration("P1Y")"#, r#"duration("PT1S")"#, r#"duration("P1M")"#];
+  assert_eq!(
+    "null(aggregation SUM is not defined for mixed output types: days and time duration and years and months duration)",
+    eval(&model("SUM", None, durations), 5)
+  );
+}
+
+#[test]
+fn _0006() {
+  // aggregation of multiple outputs is not allowed
+  for aggregation in ["COUNT", "SUM", "MIN", "MAX"] {
+    let model = model(aggregation, None, OUTPUT_ENTRIES)
+      .replace(
+        r#"<output id="output_1"/>"#,
+        r#"<output id="output_1" name="a"/><output id="output_2" name="b"/>"#,
+      )
+      .replace("</outputEntry>", "</outputEntry><outputEntry><text>1</text></outputEntry>");
+    assert_eq!("null(err_aggregators_not_allowed_for_compound_outputs)", eval(&model, 5));
+  }
+}
+
+#[test]
+fn _0007() {
+  // mixed output types give null with the reason
+  let mixed = ["1", r#""a""#, r#"date("2021-05-01")"#, "2"];
+  assert_eq!(
+    "null(aggregation MIN is not defined for mixed output types: number and string)",
+    eval(&model("MIN", None, mixed), 5)
+  );
+  assert_eq!(
+    "null(aggregation MAX is not defined for mixed output types: number and string)",
+    eval(&model("MAX", None, mixed), 5)
+  );
+  assert_eq!(
+    "null(aggregation SUM is not defined for mixed output types: number and string)",
+    eval(&model("SUM", None, mixed), 5)
+  );
+  // null is not rejected by the type of the decision, the reason is retained
+  assert_eq!(
+    "null(aggregation SUM is not defined for mixed output types: number and string)",
+    eval(&model("SUM", Some("number"), mixed), 5)
+  );
+  // count does not depend on output types
+  assert_eq!("4", eval(&model("COUNT", None, mixed), 5));
+}
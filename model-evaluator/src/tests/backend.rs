@@ -0,0 +1,82 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests of model evaluators using bytecode backend.
+
+use super::*;
+use dmntk_feel_evaluator::Backend;
+
+/// Compliance tests evaluated using bytecode backend.
+#[path = "compliance/mod.rs"]
+#[allow(clippy::duplicate_mod)]
+mod compliance;
+
+/// Utility function that builds a model evaluator using bytecode backend, used by compliance tests.
+fn build_model_evaluator(model_content: &str) -> Arc<ModelEvaluator> {
+  build_model_evaluator_with_backend(model_content, Backend::Bytecode)
+}
+
+lazy_static! {
+  static ref MODEL_EVALUATOR_2_0003: Arc<ModelEvaluator> = build_model_evaluator_with_backend(dmntk_examples::DMN_2_0003, Backend::Bytecode);
+  static ref MODEL_EVALUATOR_2_0004: Arc<ModelEvaluator> = build_model_evaluator_with_backend(dmntk_examples::DMN_2_0004, Backend::Bytecode);
+}
+
+#[test]
+fn _0001() {
+  assert_eq!(Backend::Bytecode, MODEL_EVALUATOR_2_0003.backend());
+  let ctx = context(r#"{Employment Status: "EMPLOYED"}"#);
+  assert_decision(&MODEL_EVALUATOR_2_0003, "Employment Status Statement", &ctx, r#""You are EMPLOYED""#);
+}
+
+#[test]
+fn _0002() {
+  let ctx = context(r#"{Employment Status: "RETIRED"}"#);
+  assert_decision(&MODEL_EVALUATOR_2_0003, "Employment Status Statement", &ctx, r#"null(addition err 2)"#);
+}
+
+#[test]
+fn _0003() {
+  let ctx = context(r#"{Age: 18,RiskCategory: "Medium",isAffordable: true}"#);
+  assert_decision(&MODEL_EVALUATOR_2_0004, "Approval Status", &ctx, r#""Approved""#);
+}
+
+#[test]
+fn _0004() {
+  let ctx = context(r#"{Age: 17,RiskCategory: "Medium",isAffordable: true}"#);
+  assert_decision(&MODEL_EVALUATOR_2_0004, "Approval Status", &ctx, r#""Declined""#);
+}
+
+#[test]
+fn _0005() {
+  let ctx = context(r#"{Age: 18,RiskCategory: "High",isAffordable: true}"#);
+  assert_decision(&MODEL_EVALUATOR_2_0004, "Approval Status", &ctx, r#""Declined""#);
+}
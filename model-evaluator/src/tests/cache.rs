@@ -0,0 +1,188 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests of caching decision results during a single evaluation.
+
+use super::*;
+use crate::decision_cache::with_decision_cache;
+use crate::DecisionTraceEntry;
+use dmntk_feel::values::Value;
+use dmntk_feel::Name;
+
+fn evaluated(id: &str, name: &str) -> DecisionTraceEntry {
+  DecisionTraceEntry::Evaluated(id.to_string(), Name::from(name))
+}
+
+fn cache_hit(id: &str, name: &str) -> DecisionTraceEntry {
+  DecisionTraceEntry::CacheHit(id.to_string(), Name::from(name))
+}
+
+#[test]
+fn _0001() {
+  let model_evaluator = build_model_evaluator(DIAMOND_DMN);
+  assert_decision(&model_evaluator, "Top", &context(r#"{Base: 2}"#), "61");
+}
+
+#[test]
+fn _0002() {
+  let model_evaluator = build_model_evaluator(DIAMOND_DMN);
+  let (value, trace) = model_evaluator.evaluate_invocable_with_trace("Top", &context(r#"{Base: 2}"#));
+  assert_eq!("61", value.to_string());
+  assert_eq!(
+    vec![
+      evaluated("_c", "C"),
+      evaluated("_a", "A"),
+      cache_hit("_c", "C"),
+      evaluated("_b", "B"),
+      evaluated("_top", "Top")
+    ],
+    trace
+  );
+}
+
+#[test]
+fn _0003() {
+  // results are not shared between evaluations
+  let model_evaluator = build_model_evaluator(DIAMOND_DMN);
+  let (_, trace) = model_evaluator.evaluate_invocable_with_trace("A", &context(r#"{Base: 2}"#));
+  assert_eq!(vec![evaluated("_c", "C"), evaluated("_a", "A")], trace);
+  let (value, trace) = model_evaluator.evaluate_invocable_with_trace("A", &context(r#"{Base: 3}"#));
+  assert_eq!("31", value.to_string());
+  assert_eq!(vec![evaluated("_c", "C"), evaluated("_a", "A")], trace);
+}
+
+#[test]
+fn _0004() {
+  // results are shared between invocables evaluated in a single request
+  let model_evaluator = build_model_evaluator(DIAMOND_DMN);
+  let invocable_names = vec!["B".to_string(), "A".to_string(), "Top".to_string()];
+  let (value, trace) = with_decision_cache(true, || model_evaluator.evaluate_invocables(&invocable_names, &context(r#"{Base: 2}"#)));
+  assert_eq!("{A: 21, B: 40, Top: 61}", value.to_string());
+  assert_eq!(
+    vec![
+      evaluated("_c", "C"),
+      evaluated("_b", "B"),
+      cache_hit("_c", "C"),
+      evaluated("_a", "A"),
+      cache_hit("_a", "A"),
+      cache_hit("_b", "B"),
+      evaluated("_top", "Top")
+    ],
+    trace
+  );
+}
+
+#[test]
+fn _0005() {
+  let model_evaluator = build_model_evaluator(DIAMOND_DMN);
+  assert_eq!(vec!["A", "B", "C", "Top"], model_evaluator.decision_names());
+  let value = model_evaluator.evaluate_invocables(&model_evaluator.decision_names(), &context(r#"{Base: 1}"#));
+  assert_eq!("{A: 11, B: 20, C: 10, Top: 31}", value.to_string());
+}
+
+#[test]
+fn _0006() {
+  // cache is not left active after the evaluation panicked
+  let model_evaluator = build_model_evaluator(DIAMOND_DMN);
+  let result = std::panic::catch_unwind(|| with_decision_cache(true, || -> Value { panic!("evaluation failed") }));
+  assert!(result.is_err());
+  let (value, trace) = with_decision_cache(true, || model_evaluator.evaluate_invocable("A", &context(r#"{Base: 2}"#)));
+  assert_eq!("21", value.to_string());
+  assert_eq!(vec![evaluated("_c", "C"), evaluated("_a", "A")], trace);
+}
+
+#[test]
+fn _0007() {
+  // results of decisions with the same identifiers are not shared between models
+  let model_evaluator_a = build_model_evaluator(DIAMOND_DMN);
+  let model_evaluator_b = build_model_evaluator(
+    &DIAMOND_DMN
+      .replace("https://dmntk.io/diamond", "https://dmntk.io/diamond-b")
+      .replace("Base * 10", "Base * 100"),
+  );
+  let input_data = context(r#"{Base: 2}"#);
+  let (values, _) = with_decision_cache(false, || {
+    (
+      model_evaluator_a.evaluate_invocable("C", &input_data),
+      model_evaluator_b.evaluate_invocable("C", &input_data),
+    )
+  });
+  assert_eq!("20", values.0.to_string());
+  assert_eq!("200", values.1.to_string());
+}
+
+const DIAMOND_DMN: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<definitions namespace="https://dmntk.io/diamond" name="diamond" id="_3e5f7a9b-1c2d-4e6f-8a0b-2c4d6e8f0a13" xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/">
+    <decision name="Top" id="_top">
+        <variable typeRef="number" name="Top"/>
+        <informationRequirement id="_top_a">
+            <requiredDecision href="#_a"/>
+        </informationRequirement>
+        <informationRequirement id="_top_b">
+            <requiredDecision href="#_b"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>A + B</text>
+        </literalExpression>
+    </decision>
+    <decision name="A" id="_a">
+        <variable typeRef="number" name="A"/>
+        <informationRequirement id="_a_c">
+            <requiredDecision href="#_c"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>C + 1</text>
+        </literalExpression>
+    </decision>
+    <decision name="B" id="_b">
+        <variable typeRef="number" name="B"/>
+        <informationRequirement id="_b_c">
+            <requiredDecision href="#_c"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>C * 2</text>
+        </literalExpression>
+    </decision>
+    <decision name="C" id="_c">
+        <variable typeRef="number" name="C"/>
+        <informationRequirement id="_c_base">
+            <requiredInput href="#_base"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>Base * 10</text>
+        </literalExpression>
+    </decision>
+    <inputData name="Base" id="_base">
+        <variable typeRef="number" name="Base"/>
+    </inputData>
+</definitions>
+"##;
@@ -0,0 +1,190 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests of invoking decision services with provided input decisions and selected output decisions.
+
+use super::*;
+use crate::decision_cache::{with_decision_cache, DecisionTraceEntry};
+use dmntk_feel::Name;
+
+fn names(names: &[&str]) -> Vec<String> {
+  names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn _0001() {
+  let model_evaluator = build_model_evaluator(DECISION_SERVICE_DMN);
+  assert_eq!(
+    r#"{Limit: 4000, Risk: "HIGH"}"#,
+    model_evaluator
+      .invoke_decision_service("Assessment", &context(r#"{Income: 1000, Ratio: 0.7}"#), None)
+      .to_string()
+  );
+}
+
+#[test]
+fn _0002() {
+  // provided value of the input decision is used, input decision is not evaluated
+  let model_evaluator = build_model_evaluator(DECISION_SERVICE_DMN);
+  let (value, trace) = with_decision_cache(true, || {
+    model_evaluator.invoke_decision_service("Assessment", &context(r#"{Income: 1000, Debt: 100, Ratio: 0.7}"#), None)
+  });
+  assert_eq!(r#"{Limit: 4000, Risk: "HIGH"}"#, value.to_string());
+  assert!(!trace
+    .iter()
+    .any(|entry| matches!(entry, DecisionTraceEntry::Evaluated(id, _) | DecisionTraceEntry::CacheHit(id, _) if id == "_ratio")));
+}
+
+#[test]
+fn _0003() {
+  // input decision not provided in input data is null
+  let model_evaluator = build_model_evaluator(DECISION_SERVICE_DMN);
+  assert_eq!(
+    r#"{Limit: 4000, Risk: "LOW"}"#,
+    model_evaluator
+      .invoke_decision_service("Assessment", &context(r#"{Income: 1000, Debt: 900}"#), None)
+      .to_string()
+  );
+}
+
+#[test]
+fn _0004() {
+  // only selected output decision is evaluated, encapsulated decisions are evaluated only when required
+  let model_evaluator = build_model_evaluator(DECISION_SERVICE_DMN);
+  let (value, trace) = with_decision_cache(true, || {
+    model_evaluator.invoke_decision_service("Assessment", &context(r#"{Income: 1000, Ratio: 0.2}"#), Some(&names(&["Risk"])))
+  });
+  assert_eq!(r#"{Risk: "LOW"}"#, value.to_string());
+  assert_eq!(vec![DecisionTraceEntry::Evaluated("_risk".to_string(), Name::from("Risk"))], trace);
+}
+
+#[test]
+fn _0005() {
+  let model_evaluator = build_model_evaluator(DECISION_SERVICE_DMN);
+  assert_eq!(
+    r#"{Limit: 4000}"#,
+    model_evaluator
+      .invoke_decision_service("Assessment", &context(r#"{Income: 1000}"#), Some(&names(&["Limit"])))
+      .to_string()
+  );
+}
+
+#[test]
+fn _0006() {
+  let model_evaluator = build_model_evaluator(DECISION_SERVICE_DMN);
+  assert_eq!(
+    r#"null(decision 'Base Limit' is not an output decision of decision service 'Assessment')"#,
+    model_evaluator
+      .invoke_decision_service("Assessment", &context(r#"{Income: 1000}"#), Some(&names(&["Risk", "Base Limit"])))
+      .to_string()
+  );
+}
+
+#[test]
+fn _0007() {
+  let model_evaluator = build_model_evaluator(DECISION_SERVICE_DMN);
+  assert_eq!(
+    r#"null(decision service with name 'Risk' not found)"#,
+    model_evaluator.invoke_decision_service("Risk", &context(r#"{Income: 1000}"#), None).to_string()
+  );
+}
+
+#[test]
+fn _0008() {
+  // decision service invoked with flat input data evaluates input decisions not provided in input data
+  let model_evaluator = build_model_evaluator(DECISION_SERVICE_DMN);
+  let (value, trace) = with_decision_cache(true, || {
+    model_evaluator.evaluate_invocable("Assessment", &context(r#"{Income: 1000, Debt: 900}"#))
+  });
+  assert_eq!(r#"{Limit: 4000, Risk: "LOW"}"#, value.to_string());
+  assert!(trace
+    .iter()
+    .any(|entry| matches!(entry, DecisionTraceEntry::Evaluated(id, _) if id == "_ratio")));
+}
+
+const DECISION_SERVICE_DMN: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<definitions namespace="https://dmntk.io/decision-service" name="decision service" id="_8d4f2a6c-1b3e-4f5a-9c7d-2e4a6c8f1b35" xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/">
+    <decisionService name="Assessment" id="_assessment">
+        <variable name="Assessment"/>
+        <outputDecision href="#_risk"/>
+        <outputDecision href="#_limit"/>
+        <encapsulatedDecision href="#_base_limit"/>
+        <inputDecision href="#_ratio"/>
+        <inputData href="#_income"/>
+    </decisionService>
+    <decision name="Ratio" id="_ratio">
+        <variable typeRef="number" name="Ratio"/>
+        <informationRequirement id="_ratio_income">
+            <requiredInput href="#_income"/>
+        </informationRequirement>
+        <informationRequirement id="_ratio_debt">
+            <requiredInput href="#_debt"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>Debt / Income</text>
+        </literalExpression>
+    </decision>
+    <decision name="Risk" id="_risk">
+        <variable typeRef="string" name="Risk"/>
+        <informationRequirement id="_risk_ratio">
+            <requiredDecision href="#_ratio"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>if Ratio &gt; 0.5 then "HIGH" else "LOW"</text>
+        </literalExpression>
+    </decision>
+    <decision name="Base Limit" id="_base_limit">
+        <variable typeRef="number" name="Base Limit"/>
+        <informationRequirement id="_base_limit_income">
+            <requiredInput href="#_income"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>Income * 3</text>
+        </literalExpression>
+    </decision>
+    <decision name="Limit" id="_limit">
+        <variable typeRef="number" name="Limit"/>
+        <informationRequirement id="_limit_base_limit">
+            <requiredDecision href="#_base_limit"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>Base Limit + 1000</text>
+        </literalExpression>
+    </decision>
+    <inputData name="Income" id="_income">
+        <variable typeRef="number" name="Income"/>
+    </inputData>
+    <inputData name="Debt" id="_debt">
+        <variable typeRef="number" name="Debt"/>
+    </inputData>
+</definitions>
+"##;
@@ -0,0 +1,172 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests of ordering matching rules in decision tables with priority and output order hit policies.
+//!
+//! Tests use compatibility examples `2_0110` (output order) and `2_0118` (priority) with modified
+//! output values and input entries, both examples define the same decision table with two outputs.
+
+use super::*;
+
+/// Output clause `Rate` without output values, as defined in both examples.
+const RATE_OUTPUT: &str = r#"<output name="Rate" typeRef="string" id="_ee202a75-fdc2-43e0-a9aa-c1d3577f8156"/>"#;
+
+/// Output clause `Rate` with output values, `"Standard"` has higher priority than `"Basic"`.
+const RATE_OUTPUT_WITH_VALUES: &str =
+  r#"<output name="Rate" typeRef="string" id="_ee202a75-fdc2-43e0-a9aa-c1d3577f8156"><outputValues><text>"Standard", "Basic"</text></outputValues></output>"#;
+
+/// Replaces the text in the model, the replaced text must be present in the model.
+fn replace(model: &str, from: &str, to: &str) -> String {
+  assert!(model.contains(from), "text to be replaced not found: {}", from);
+  model.replace(from, to)
+}
+
+/// Modifies the first rule to match any risk category, so the first and the third rule
+/// have equal `Approved/Declined` output for adults in high risk category and differ only in `Rate` output.
+fn with_overlapping_rules(model: &str) -> String {
+  let model = replace(model, r#"<text>"Medium","Low"</text>"#, "<text>-</text>");
+  replace(&model, RATE_OUTPUT, RATE_OUTPUT_WITH_VALUES)
+}
+
+/// Replaces output values of the output clause `Approved/Declined`.
+fn with_output_values(model: &str, output_values: &str) -> String {
+  let start = model.find("<outputValues>").unwrap();
+  let end = model.find("</outputValues>").unwrap();
+  format!("{}<outputValues><text>{}</text>{}", &model[..start], output_values, &model[end..])
+}
+
+/// Removes the type of the decision, output entry values not listed in output values are `null`
+/// and would be rejected by allowed values of the decision type.
+fn untyped(model: &str) -> String {
+  let start = model.find(r#"<variable typeRef=""#).unwrap();
+  let end = start + model[start..].find(r#" name="Approval Status""#).unwrap();
+  format!("{}<variable{}", &model[..start], &model[end..])
+}
+
+/// Removes output values from the output clause `Approved/Declined`.
+fn without_output_values(model: &str) -> String {
+  let start = model.find("<outputValues>").unwrap();
+  let end = model.find("</outputValues>").unwrap() + "</outputValues>".len();
+  format!("{}{}", &model[..start], &model[end..])
+}
+
+#[test]
+fn _0001() {
+  // rules with equal first output are ordered by the second output, not in rule order
+  let model_evaluator = build_model_evaluator(&with_overlapping_rules(dmntk_examples::DMN_2_0110));
+  let ctx = context(r#"{Age: 19, RiskCategory: "High", isAffordable: true}"#);
+  assert_decision(
+    &model_evaluator,
+    "Approval Status",
+    &ctx,
+    r#"[{Approved/Declined: "Approved", Rate: "Standard"}, {Approved/Declined: "Approved", Rate: "Basic"}]"#,
+  );
+  // the first output decides before the second output
+  let ctx = context(r#"{Age: 17, RiskCategory: "High", isAffordable: true}"#);
+  assert_decision(
+    &model_evaluator,
+    "Approval Status",
+    &ctx,
+    r#"[{Approved/Declined: "Approved", Rate: "Standard"}, {Approved/Declined: "Declined", Rate: "Standard"}]"#,
+  );
+}
+
+#[test]
+fn _0002() {
+  // the rule with the highest priority of the second output wins, when the first output is equal
+  let model_evaluator = build_model_evaluator(&with_overlapping_rules(dmntk_examples::DMN_2_0118));
+  let ctx = context(r#"{Age: 19, RiskCategory: "High", isAffordable: true}"#);
+  assert_decision(
+    &model_evaluator,
+    "Approval Status",
+    &ctx,
+    r#"{Approved/Declined: "Approved", Rate: "Standard"}"#,
+  );
+  let ctx = context(r#"{Age: 19, RiskCategory: "Low", isAffordable: true}"#);
+  assert_decision(&model_evaluator, "Approval Status", &ctx, r#"{Approved/Declined: "Approved", Rate: "Basic"}"#);
+}
+
+#[test]
+fn _0003() {
+  // output values reversed, the order of results is reversed
+  let model = with_output_values(dmntk_examples::DMN_2_0110, r#""Declined", "Approved""#);
+  let model_evaluator = build_model_evaluator(&model);
+  let ctx = context(r#"{Age: 17, RiskCategory: "High", isAffordable: true}"#);
+  assert_decision(
+    &model_evaluator,
+    "Approval Status",
+    &ctx,
+    r#"[{Approved/Declined: "Declined", Rate: "Standard"}, {Approved/Declined: "Approved", Rate: "Standard"}]"#,
+  );
+}
+
+#[test]
+fn _0004() {
+  // output entry value not listed in output values is null and has the lowest priority
+  let model_evaluator = build_model_evaluator(&untyped(&with_output_values(dmntk_examples::DMN_2_0110, r#""Declined""#)));
+  let ctx = context(r#"{Age: 17, RiskCategory: "High", isAffordable: true}"#);
+  assert_decision(
+    &model_evaluator,
+    "Approval Status",
+    &ctx,
+    r#"[{Approved/Declined: "Declined", Rate: "Standard"}, {Approved/Declined: null, Rate: "Standard"}]"#,
+  );
+  let model_evaluator = build_model_evaluator(&untyped(&with_output_values(dmntk_examples::DMN_2_0118, r#""Declined""#)));
+  assert_decision(
+    &model_evaluator,
+    "Approval Status",
+    &ctx,
+    r#"{Approved/Declined: "Declined", Rate: "Standard"}"#,
+  );
+  let ctx = context(r#"{Age: 19, RiskCategory: "Low", isAffordable: true}"#);
+  assert_decision(&model_evaluator, "Approval Status", &ctx, r#"{Approved/Declined: null, Rate: "Basic"}"#);
+}
+
+#[test]
+fn _0005() {
+  // output values are required to order the matching rules
+  let model_evaluator = build_model_evaluator(&without_output_values(dmntk_examples::DMN_2_0110));
+  let ctx = context(r#"{Age: 17, RiskCategory: "High", isAffordable: true}"#);
+  assert_decision(
+    &model_evaluator,
+    "Approval Status",
+    &ctx,
+    "null(output values are required in decision table with hit policy OUTPUT ORDER)",
+  );
+  let model_evaluator = build_model_evaluator(&without_output_values(dmntk_examples::DMN_2_0118));
+  assert_decision(
+    &model_evaluator,
+    "Approval Status",
+    &ctx,
+    "null(output values are required in decision table with hit policy PRIORITY)",
+  );
+}
@@ -0,0 +1,193 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests of lazy evaluation of required decisions and context entries.
+
+use super::*;
+use dmntk_feel_evaluator::{evaluate_with_limits, CancellationToken, EvaluationLimits};
+
+/// Evaluates a decision with the number of iterations limited to 10,
+/// the expensive expressions used in the model exceed this limit when evaluated.
+fn evaluate_limited(model_evaluator: &ModelEvaluator, name: &str, input: &str) -> String {
+  let limits = EvaluationLimits {
+    max_iterations: Some(10),
+    ..Default::default()
+  };
+  let input_data = context(input);
+  match evaluate_with_limits(&limits, &CancellationToken::new(), || model_evaluator.evaluate_invocable(name, &input_data)) {
+    Ok(value) => value.to_string(),
+    Err(reason) => reason.to_string(),
+  }
+}
+
+#[test]
+fn _0001() {
+  let model_evaluator = build_model_evaluator(LAZY_DMN);
+  assert_eq!(r#""cheap""#, evaluate_limited(&model_evaluator, "Result", r#"{Flag: true}"#));
+}
+
+#[test]
+fn _0002() {
+  let model_evaluator = build_model_evaluator(LAZY_DMN);
+  assert_eq!(
+    "FeelEvaluatorError: evaluation interrupted: maximum number of iterations 10 exceeded",
+    evaluate_limited(&model_evaluator, "Result", r#"{Flag: false}"#)
+  );
+}
+
+#[test]
+fn _0003() {
+  let model_evaluator = build_model_evaluator(LAZY_DMN);
+  assert_eq!("100", evaluate_limited(&model_evaluator, "Result", r#"{Flag: false, Expensive: 100}"#));
+}
+
+#[test]
+fn _0004() {
+  let model_evaluator = build_model_evaluator(LAZY_DMN);
+  assert_eq!("2", evaluate_limited(&model_evaluator, "Boxed Context", r#"{Flag: true}"#));
+  assert_eq!(
+    "FeelEvaluatorError: evaluation interrupted: maximum number of iterations 10 exceeded",
+    evaluate_limited(&model_evaluator, "Boxed Context", r#"{Flag: false}"#)
+  );
+}
+
+#[test]
+fn _0005() {
+  let model_evaluator = build_model_evaluator(LAZY_DMN);
+  assert_eq!("100", model_evaluator.evaluate_invocable("Result", &context(r#"{Flag: false}"#)).to_string());
+}
+
+#[test]
+fn _0006() {
+  // context entries see only the preceding entries, like when evaluated eagerly
+  let model_evaluator = build_model_evaluator(LAZY_DMN);
+  assert_eq!(
+    "null(context has no value for key 'b')",
+    model_evaluator.evaluate_invocable("Forward Reference", &context(r#"{}"#)).to_string()
+  );
+  assert_eq!("2", model_evaluator.evaluate_invocable("Backward Reference", &context(r#"{}"#)).to_string());
+}
+
+const LAZY_DMN: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<definitions namespace="https://dmntk.io/lazy" name="lazy" id="_6d1c3a8e-2f4b-4e7a-9c5d-3b2a1f0e9d81" xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/">
+    <decision name="Result" id="_1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c51">
+        <variable name="Result"/>
+        <informationRequirement id="_1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c52">
+            <requiredInput href="#_1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c59"/>
+        </informationRequirement>
+        <informationRequirement id="_1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c53">
+            <requiredDecision href="#_1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c54"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>if Flag then "cheap" else Expensive</text>
+        </literalExpression>
+    </decision>
+    <decision name="Expensive" id="_1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c54">
+        <variable typeRef="number" name="Expensive"/>
+        <literalExpression>
+            <text>count(for i in 1..100 return i)</text>
+        </literalExpression>
+    </decision>
+    <decision name="Boxed Context" id="_1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c55">
+        <variable typeRef="number" name="Boxed Context"/>
+        <informationRequirement id="_1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c56">
+            <requiredInput href="#_1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c59"/>
+        </informationRequirement>
+        <context>
+            <contextEntry>
+                <variable name="Costly"/>
+                <literalExpression>
+                    <text>count(for i in 1..100 return i)</text>
+                </literalExpression>
+            </contextEntry>
+            <contextEntry>
+                <variable name="Cheap"/>
+                <literalExpression>
+                    <text>2</text>
+                </literalExpression>
+            </contextEntry>
+            <contextEntry>
+                <literalExpression>
+                    <text>if Flag then Cheap else Costly</text>
+                </literalExpression>
+            </contextEntry>
+        </context>
+    </decision>
+    <decision name="Forward Reference" id="_1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c60">
+        <variable name="Forward Reference"/>
+        <context>
+            <contextEntry>
+                <variable name="a"/>
+                <literalExpression>
+                    <text>b</text>
+                </literalExpression>
+            </contextEntry>
+            <contextEntry>
+                <variable name="b"/>
+                <literalExpression>
+                    <text>1</text>
+                </literalExpression>
+            </contextEntry>
+            <contextEntry>
+                <literalExpression>
+                    <text>a</text>
+                </literalExpression>
+            </contextEntry>
+        </context>
+    </decision>
+    <decision name="Backward Reference" id="_1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c61">
+        <variable name="Backward Reference"/>
+        <context>
+            <contextEntry>
+                <variable name="a"/>
+                <literalExpression>
+                    <text>1</text>
+                </literalExpression>
+            </contextEntry>
+            <contextEntry>
+                <variable name="b"/>
+                <literalExpression>
+                    <text>a + 1</text>
+                </literalExpression>
+            </contextEntry>
+            <contextEntry>
+                <literalExpression>
+                    <text>b</text>
+                </literalExpression>
+            </contextEntry>
+        </context>
+    </decision>
+    <inputData name="Flag" id="_1a2b3c4d-5e6f-4a7b-8c9d-0e1f2a3b4c59">
+        <variable typeRef="boolean" name="Flag"/>
+    </inputData>
+</definitions>
+"##;
@@ -36,7 +36,19 @@ use dmntk_feel::values::Value;
 use dmntk_feel::Scope;
 use std::sync::Arc;
 
+mod aggregation;
+mod backend;
+mod cache;
 mod compliance;
+mod decision_service;
+mod hit_policy;
+mod invocables;
+mod lazy;
+mod partial;
+mod relation;
+mod rule_hits;
+mod type_inference;
+mod validation;
 
 /// Utility function that creates a `FEEL` context from specified input expression.
 pub fn context(input: &str) -> FeelContext {
@@ -58,6 +70,11 @@ fn build_model_evaluator(model_content: &str) -> Arc<ModelEvaluator> {
   ModelEvaluator::new(&dmntk_model::parse(model_content).unwrap()).unwrap()
 }
 
+/// Utility function that builds a model evaluator from XML model definitions using specified backend.
+fn build_model_evaluator_with_backend(model_content: &str, backend: dmntk_feel_evaluator::Backend) -> Arc<ModelEvaluator> {
+  ModelEvaluator::new_with_backend(&dmntk_model::parse(model_content).unwrap(), backend).unwrap()
+}
+
 /// Utility function that evaluates a `Decision` specified by name and compares the result.
 fn assert_decision(model_evaluator: &ModelEvaluator, name: &str, input_data: &FeelContext, expected: &str) {
   let actual = model_evaluator.evaluate_invocable(name, input_data).to_string();
@@ -0,0 +1,134 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests of the partial evaluation of decisions with missing input data.
+
+use super::*;
+use std::collections::BTreeMap;
+
+fn missing(entries: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+  entries
+    .iter()
+    .map(|(decision_name, input_data_names)| (decision_name.to_string(), input_data_names.iter().map(|name| name.to_string()).collect()))
+    .collect()
+}
+
+#[test]
+fn _0001() {
+  let model_evaluator = build_model_evaluator(PARTIAL_DMN);
+  let partial_evaluation = model_evaluator.evaluate_partial(&context(r#"{Age: 30}"#));
+  assert_eq!(r#"{Eligibility: "ELIGIBLE", Greeting: "Hello"}"#, partial_evaluation.results.to_string());
+  assert_eq!(
+    missing(&[("Affordability", &["Monthly Income"]), ("Offer", &["Monthly Income", "Region"])]),
+    partial_evaluation.missing_input_data
+  );
+}
+
+#[test]
+fn _0002() {
+  let model_evaluator = build_model_evaluator(PARTIAL_DMN);
+  let partial_evaluation = model_evaluator.evaluate_partial(&context(r#"{}"#));
+  assert_eq!(r#"{Greeting: "Hello"}"#, partial_evaluation.results.to_string());
+  assert_eq!(
+    missing(&[
+      ("Affordability", &["Monthly Income"]),
+      ("Eligibility", &["Age"]),
+      ("Offer", &["Age", "Monthly Income", "Region"])
+    ]),
+    partial_evaluation.missing_input_data
+  );
+}
+
+#[test]
+fn _0003() {
+  // input data with null value are considered present
+  let model_evaluator = build_model_evaluator(PARTIAL_DMN);
+  let partial_evaluation = model_evaluator.evaluate_partial(&context(r#"{Age: 30, Monthly Income: 5000, Region: null}"#));
+  assert_eq!(
+    r#"{Affordability: "HIGH", Eligibility: "ELIGIBLE", Greeting: "Hello", Offer: "ELIGIBLE HIGH null"}"#,
+    partial_evaluation.results.to_string()
+  );
+  assert!(partial_evaluation.missing_input_data.is_empty());
+}
+
+const PARTIAL_DMN: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<definitions namespace="https://dmntk.io/partial" name="partial" id="_7c9e1a3b-5d7f-4b9d-8e1a-5b7c9e1a3d46" xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/">
+    <decision name="Offer" id="_offer">
+        <variable typeRef="string" name="Offer"/>
+        <informationRequirement id="_offer_eligibility">
+            <requiredDecision href="#_eligibility"/>
+        </informationRequirement>
+        <informationRequirement id="_offer_affordability">
+            <requiredDecision href="#_affordability"/>
+        </informationRequirement>
+        <informationRequirement id="_offer_region">
+            <requiredInput href="#_region"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>Eligibility + " " + Affordability + " " + (if Region = null then "null" else Region)</text>
+        </literalExpression>
+    </decision>
+    <decision name="Eligibility" id="_eligibility">
+        <variable typeRef="string" name="Eligibility"/>
+        <informationRequirement id="_eligibility_age">
+            <requiredInput href="#_age"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>if Age &gt;= 18 then "ELIGIBLE" else "INELIGIBLE"</text>
+        </literalExpression>
+    </decision>
+    <decision name="Affordability" id="_affordability">
+        <variable typeRef="string" name="Affordability"/>
+        <informationRequirement id="_affordability_income">
+            <requiredInput href="#_income"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>if Monthly Income &gt; 3000 then "HIGH" else "LOW"</text>
+        </literalExpression>
+    </decision>
+    <decision name="Greeting" id="_greeting">
+        <variable typeRef="string" name="Greeting"/>
+        <literalExpression>
+            <text>"Hello"</text>
+        </literalExpression>
+    </decision>
+    <inputData name="Age" id="_age">
+        <variable typeRef="number" name="Age"/>
+    </inputData>
+    <inputData name="Monthly Income" id="_income">
+        <variable typeRef="number" name="Monthly Income"/>
+    </inputData>
+    <inputData name="Region" id="_region">
+        <variable typeRef="string" name="Region"/>
+    </inputData>
+</definitions>
+"##;
@@ -0,0 +1,255 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests of relation and list boxed expressions.
+
+use super::*;
+use crate::ValidationMode;
+
+#[test]
+fn _0001() {
+  let model_evaluator = build_model_evaluator(RELATION_DMN);
+  assert_decision(
+    &model_evaluator,
+    "Products",
+    &context(r#"{}"#),
+    r#"[{Dimensions: {x: 1, y: 2}, Name: "Pen", Price: 1.5, Tags: ["blue", "cheap"]}, {Dimensions: {x: 20, y: 30}, Name: "Book", Price: 42, Tags: ["single"]}]"#,
+  );
+}
+
+#[test]
+fn _0002() {
+  let model_evaluator = build_model_evaluator(RELATION_DMN);
+  assert_decision(&model_evaluator, "Numbers", &context(r#"{}"#), r#"[1, {a: 2}, [3, 4], [{n: 5}]]"#);
+}
+
+#[test]
+fn _0003() {
+  let model_evaluator = build_model_evaluator(RELATION_DMN);
+  assert_decision(
+    &model_evaluator,
+    "Mismatch",
+    &context(r#"{}"#),
+    r#"[{Dimensions: null(relation row 1 column 'Dimensions': expected type context<x: number, y: number>, actual value is {x: 1}), Price: null(relation row 1 column 'Price': expected type number, actual value is "free")}, {Dimensions: {x: 3, y: 4}, Price: 7}]"#,
+  );
+}
+
+#[test]
+fn _0004() {
+  let model_evaluator = build_model_evaluator(RELATION_DMN);
+  model_evaluator.set_validation_mode(ValidationMode::Strict);
+  match model_evaluator.evaluate_invocable_validated("Mismatch", &context(r#"{}"#)) {
+    Ok(value) => panic!("expected validation report, actual value is {}", value),
+    Err(report) => assert_eq!(
+      r#"validation failed: relation row 1 column 'Price' value "free" violates type 'number', relation row 1 column 'Dimensions' value {x: 1} violates type 'tPoint'"#,
+      report.to_string()
+    ),
+  }
+}
+
+#[test]
+fn _0005() {
+  let definitions = dmntk_model::parse(INVALID_CELL_DMN).unwrap();
+  match ModelEvaluator::new(&definitions) {
+    Ok(_) => panic!("expected error"),
+    Err(reason) => assert!(
+      reason.to_string().contains("invalid cell in relation row 2 column `Price`"),
+      "unexpected error: {}",
+      reason
+    ),
+  }
+}
+
+const RELATION_DMN: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<definitions namespace="https://dmntk.io/relation" name="relation" id="_3f1b8c2d-6e4a-4d9b-a7c5-2e8f1b3d5a70" xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/">
+    <itemDefinition name="tPoint">
+        <itemComponent name="x">
+            <typeRef>number</typeRef>
+        </itemComponent>
+        <itemComponent name="y">
+            <typeRef>number</typeRef>
+        </itemComponent>
+    </itemDefinition>
+    <itemDefinition name="tTags" isCollection="true">
+        <typeRef>string</typeRef>
+    </itemDefinition>
+    <businessKnowledgeModel name="Double" id="_double">
+        <variable name="Double"/>
+        <encapsulatedLogic>
+            <formalParameter typeRef="number" name="x"/>
+            <literalExpression>
+                <text>x * 2</text>
+            </literalExpression>
+        </encapsulatedLogic>
+    </businessKnowledgeModel>
+    <decision name="Products" id="_products">
+        <variable name="Products"/>
+        <knowledgeRequirement id="_products_double">
+            <requiredKnowledge href="#_double"/>
+        </knowledgeRequirement>
+        <relation>
+            <column name="Name" typeRef="string"/>
+            <column name="Price" typeRef="number"/>
+            <column name="Dimensions" typeRef="tPoint"/>
+            <column name="Tags" typeRef="tTags"/>
+            <row>
+                <literalExpression>
+                    <text>"Pen"</text>
+                </literalExpression>
+                <literalExpression>
+                    <text>1.5</text>
+                </literalExpression>
+                <context>
+                    <contextEntry>
+                        <variable name="x"/>
+                        <literalExpression>
+                            <text>1</text>
+                        </literalExpression>
+                    </contextEntry>
+                    <contextEntry>
+                        <variable name="y"/>
+                        <literalExpression>
+                            <text>x + 1</text>
+                        </literalExpression>
+                    </contextEntry>
+                </context>
+                <list>
+                    <literalExpression>
+                        <text>"blue"</text>
+                    </literalExpression>
+                    <literalExpression>
+                        <text>"cheap"</text>
+                    </literalExpression>
+                </list>
+            </row>
+            <row>
+                <literalExpression>
+                    <text>"Book"</text>
+                </literalExpression>
+                <invocation>
+                    <literalExpression>
+                        <text>Double</text>
+                    </literalExpression>
+                    <binding>
+                        <parameter name="x"/>
+                        <literalExpression>
+                            <text>21</text>
+                        </literalExpression>
+                    </binding>
+                </invocation>
+                <literalExpression>
+                    <text>{x: 20, y: 30}</text>
+                </literalExpression>
+                <literalExpression>
+                    <text>"single"</text>
+                </literalExpression>
+            </row>
+        </relation>
+    </decision>
+    <decision name="Numbers" id="_numbers">
+        <variable name="Numbers"/>
+        <list>
+            <literalExpression>
+                <text>1</text>
+            </literalExpression>
+            <context>
+                <contextEntry>
+                    <variable name="a"/>
+                    <literalExpression>
+                        <text>2</text>
+                    </literalExpression>
+                </contextEntry>
+            </context>
+            <list>
+                <literalExpression>
+                    <text>3</text>
+                </literalExpression>
+                <literalExpression>
+                    <text>4</text>
+                </literalExpression>
+            </list>
+            <relation>
+                <column name="n" typeRef="number"/>
+                <row>
+                    <literalExpression>
+                        <text>5</text>
+                    </literalExpression>
+                </row>
+            </relation>
+        </list>
+    </decision>
+    <decision name="Mismatch" id="_mismatch">
+        <variable name="Mismatch"/>
+        <relation>
+            <column name="Price" typeRef="number"/>
+            <column name="Dimensions" typeRef="tPoint"/>
+            <row>
+                <literalExpression>
+                    <text>"free"</text>
+                </literalExpression>
+                <literalExpression>
+                    <text>{x: 1}</text>
+                </literalExpression>
+            </row>
+            <row>
+                <literalExpression>
+                    <text>7</text>
+                </literalExpression>
+                <literalExpression>
+                    <text>{x: 3, y: 4}</text>
+                </literalExpression>
+            </row>
+        </relation>
+    </decision>
+</definitions>
+"##;
+
+const INVALID_CELL_DMN: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<definitions namespace="https://dmntk.io/invalid-cell" name="invalid cell" id="_5a2c9d4e-7f1b-4c8a-b3e6-9d2f4a6c8e13" xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/">
+    <decision name="Prices" id="_prices">
+        <variable name="Prices"/>
+        <relation>
+            <column name="Price" typeRef="number"/>
+            <row>
+                <literalExpression>
+                    <text>1</text>
+                </literalExpression>
+            </row>
+            <row>
+                <literalExpression>
+                    <text>1 +</text>
+                </literalExpression>
+            </row>
+        </relation>
+    </decision>
+</definitions>
+"##;
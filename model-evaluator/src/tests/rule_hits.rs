@@ -0,0 +1,232 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests of reporting rules hit in decision tables.
+
+use super::*;
+use crate::RuleHit;
+
+fn rule_hit(decision: &str, index: usize, id: &str, reason: &str) -> RuleHit {
+  RuleHit {
+    decision: Some(decision.to_string()),
+    index,
+    id: Some(id.to_string()),
+    annotations: vec![("reason".to_string(), reason.to_string())],
+  }
+}
+
+#[test]
+fn _0001() {
+  let model_evaluator = build_model_evaluator(RULE_HITS_DMN);
+  let (value, rule_hits) = model_evaluator.evaluate_invocable_with_rule_hits("Eligibility", &context(r#"{Age: 15}"#));
+  assert_eq!(r#""INELIGIBLE""#, value.to_string());
+  assert_eq!(vec![rule_hit("Eligibility", 1, "_r1", "Applicant is under age")], rule_hits);
+  assert_eq!(Some("Applicant is under age"), rule_hits[0].annotation("reason"));
+  assert_eq!(None, rule_hits[0].annotation("note"));
+}
+
+#[test]
+fn _0002() {
+  // with the first hit policy only the first matching rule is reported
+  let model_evaluator = build_model_evaluator(RULE_HITS_DMN);
+  let (value, rule_hits) = model_evaluator.evaluate_invocable_with_rule_hits("Eligibility", &context(r#"{Age: 95}"#));
+  assert_eq!(r#""REVIEW""#, value.to_string());
+  assert_eq!(vec![rule_hit("Eligibility", 2, "_r2", "Applicant requires review")], rule_hits);
+}
+
+#[test]
+fn _0003() {
+  // rules hit in required decisions are reported with the name of the required decision
+  let model_evaluator = build_model_evaluator(RULE_HITS_DMN);
+  let (value, rule_hits) = model_evaluator.evaluate_invocable_with_rule_hits("Letter", &context(r#"{Age: 40}"#));
+  assert_eq!(r#""Your application is ELIGIBLE""#, value.to_string());
+  assert_eq!(
+    vec![
+      rule_hit("Eligibility", 3, "_r3", "Applicant meets all criteria"),
+      rule_hit("Discounts", 2, "_d2", "Adult discount"),
+      rule_hit("Discounts", 3, "_d3", ""),
+    ],
+    rule_hits
+  );
+}
+
+#[test]
+fn _0004() {
+  // with the collect hit policy all matching rules are reported
+  let model_evaluator = build_model_evaluator(RULE_HITS_DMN);
+  let (value, rule_hits) = model_evaluator.evaluate_invocable_with_rule_hits("Discounts", &context(r#"{Age: 70}"#));
+  assert_eq!("[5, 10, 2]", value.to_string());
+  assert_eq!(
+    vec![
+      rule_hit("Discounts", 1, "_d1", "Senior discount"),
+      rule_hit("Discounts", 2, "_d2", "Adult discount"),
+      rule_hit("Discounts", 3, "_d3", ""),
+    ],
+    rule_hits
+  );
+}
+
+#[test]
+fn _0005() {
+  // rules are not reported when evaluating without collecting rule hits
+  let model_evaluator = build_model_evaluator(RULE_HITS_DMN);
+  assert_decision(&model_evaluator, "Eligibility", &context(r#"{Age: 15}"#), r#""INELIGIBLE""#);
+  let (_, rule_hits) = model_evaluator.evaluate_invocable_with_rule_hits("Eligibility", &context(r#"{Age: null}"#));
+  assert!(rule_hits.is_empty());
+}
+
+#[test]
+fn _0006() {
+  // rule hits are not collected after the evaluation panicked
+  let result = std::panic::catch_unwind(|| crate::collect_rule_hits(|| -> Value { panic!("evaluation failed") }));
+  assert!(result.is_err());
+  assert!(!crate::rule_hits::is_collecting_rule_hits());
+  let model_evaluator = build_model_evaluator(RULE_HITS_DMN);
+  let (_, rule_hits) = model_evaluator.evaluate_invocable_with_rule_hits("Eligibility", &context(r#"{Age: 15}"#));
+  assert_eq!(vec![rule_hit("Eligibility", 1, "_r1", "Applicant is under age")], rule_hits);
+}
+
+const RULE_HITS_DMN: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<definitions namespace="https://dmntk.io/rule-hits" name="rule-hits" id="_5a7c9e1f-3b5d-4f7a-9c1e-3f5a7c9e1b24" xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/">
+    <decision name="Letter" id="_letter">
+        <variable typeRef="string" name="Letter"/>
+        <informationRequirement id="_letter_eligibility">
+            <requiredDecision href="#_eligibility"/>
+        </informationRequirement>
+        <informationRequirement id="_letter_discounts">
+            <requiredDecision href="#_discounts"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>"Your application is " + Eligibility + (if count(Discounts) &gt; 2 then " with discount" else "")</text>
+        </literalExpression>
+    </decision>
+    <decision name="Eligibility" id="_eligibility">
+        <variable typeRef="string" name="Eligibility"/>
+        <informationRequirement id="_eligibility_age">
+            <requiredInput href="#_age"/>
+        </informationRequirement>
+        <decisionTable hitPolicy="FIRST">
+            <input id="_eligibility_input">
+                <inputExpression typeRef="number">
+                    <text>Age</text>
+                </inputExpression>
+            </input>
+            <output id="_eligibility_output"/>
+            <annotation name="reason"/>
+            <rule id="_r1">
+                <inputEntry>
+                    <text>&lt; 18</text>
+                </inputEntry>
+                <outputEntry>
+                    <text>"INELIGIBLE"</text>
+                </outputEntry>
+                <annotationEntry>
+                    <text>Applicant is under age</text>
+                </annotationEntry>
+            </rule>
+            <rule id="_r2">
+                <inputEntry>
+                    <text>&gt; 90</text>
+                </inputEntry>
+                <outputEntry>
+                    <text>"REVIEW"</text>
+                </outputEntry>
+                <annotationEntry>
+                    <text>Applicant requires review</text>
+                </annotationEntry>
+            </rule>
+            <rule id="_r3">
+                <inputEntry>
+                    <text>&gt;= 18</text>
+                </inputEntry>
+                <outputEntry>
+                    <text>"ELIGIBLE"</text>
+                </outputEntry>
+                <annotationEntry>
+                    <text>Applicant meets all criteria</text>
+                </annotationEntry>
+            </rule>
+        </decisionTable>
+    </decision>
+    <decision name="Discounts" id="_discounts">
+        <variable name="Discounts"/>
+        <informationRequirement id="_discounts_age">
+            <requiredInput href="#_age"/>
+        </informationRequirement>
+        <decisionTable hitPolicy="COLLECT">
+            <input id="_discounts_input">
+                <inputExpression typeRef="number">
+                    <text>Age</text>
+                </inputExpression>
+            </input>
+            <output id="_discounts_output"/>
+            <annotation name="reason"/>
+            <rule id="_d1">
+                <inputEntry>
+                    <text>&gt;= 65</text>
+                </inputEntry>
+                <outputEntry>
+                    <text>5</text>
+                </outputEntry>
+                <annotationEntry>
+                    <text>Senior discount</text>
+                </annotationEntry>
+            </rule>
+            <rule id="_d2">
+                <inputEntry>
+                    <text>&gt;= 18</text>
+                </inputEntry>
+                <outputEntry>
+                    <text>10</text>
+                </outputEntry>
+                <annotationEntry>
+                    <text>Adult discount</text>
+                </annotationEntry>
+            </rule>
+            <rule id="_d3">
+                <inputEntry>
+                    <text>-</text>
+                </inputEntry>
+                <outputEntry>
+                    <text>2</text>
+                </outputEntry>
+                <annotationEntry>
+                    <text/>
+                </annotationEntry>
+            </rule>
+        </decisionTable>
+    </decision>
+    <inputData name="Age" id="_age">
+        <variable typeRef="number" name="Age"/>
+    </inputData>
+</definitions>
+"##;
@@ -0,0 +1,73 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests of result types inferred for decisions.
+
+use super::*;
+use dmntk_feel::FeelType;
+use dmntk_feel_evaluator::TypeError;
+
+/// Builds a model evaluator from example `2_0003` with replaced decision logic and output variable type.
+fn build_model_evaluator_with_logic(text: &str, type_ref: &str) -> Arc<ModelEvaluator> {
+  build_model_evaluator(
+    &dmntk_examples::DMN_2_0003
+      .replace(r#""You are " + Employment Status"#, text)
+      .replace(r#"typeRef="string" name="Employment Status Statement""#, type_ref),
+  )
+}
+
+#[test]
+fn _0001() {
+  let model_evaluator = build_model_evaluator(dmntk_examples::DMN_2_0003);
+  let decision_evaluator = model_evaluator.decision_evaluator().unwrap();
+  assert_eq!(Some(&FeelType::String), decision_evaluator.get_result_type("d_EmploymentStatusStatement"));
+  assert_eq!(Some(&[][..]), decision_evaluator.get_type_errors("d_EmploymentStatusStatement"));
+}
+
+#[test]
+fn _0002() {
+  let model_evaluator = build_model_evaluator_with_logic("string length(Employment Status) * 2", r#"name="Employment Status Statement""#);
+  let decision_evaluator = model_evaluator.decision_evaluator().unwrap();
+  assert_eq!(Some(&FeelType::Number), decision_evaluator.get_result_type("d_EmploymentStatusStatement"));
+  assert_eq!(Some(&[][..]), decision_evaluator.get_type_errors("d_EmploymentStatusStatement"));
+}
+
+#[test]
+fn _0003() {
+  let model_evaluator = build_model_evaluator_with_logic(r#"Employment Status + date("2022-01-01")"#, r#"name="Employment Status Statement""#);
+  let decision_evaluator = model_evaluator.decision_evaluator().unwrap();
+  assert_eq!(Some(&FeelType::Null), decision_evaluator.get_result_type("d_EmploymentStatusStatement"));
+  assert_eq!(
+    Some(&[TypeError::IncompatibleOperands("+".to_string(), FeelType::String, FeelType::Date)][..]),
+    decision_evaluator.get_type_errors("d_EmploymentStatusStatement")
+  );
+}
@@ -0,0 +1,204 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tests of validating input and output values.
+
+use super::*;
+use crate::{ValidationMode, ValidationReport};
+
+fn strict_model_evaluator() -> Arc<ModelEvaluator> {
+  let model_evaluator = build_model_evaluator(VALIDATION_DMN);
+  model_evaluator.set_validation_mode(ValidationMode::Strict);
+  model_evaluator
+}
+
+fn validation_errors(model_evaluator: &ModelEvaluator, name: &str, input: &str) -> Vec<String> {
+  match model_evaluator.evaluate_invocable_validated(name, &context(input)) {
+    Ok(value) => panic!("expected violations, actual value is: {}", value),
+    Err(ValidationReport { violations }) => violations.iter().map(|violation| violation.to_string()).collect(),
+  }
+}
+
+#[test]
+fn _0001() {
+  let model_evaluator = build_model_evaluator(VALIDATION_DMN);
+  assert_eq!(ValidationMode::Lenient, model_evaluator.validation_mode());
+  // the value of input data violating its constraints is silently coerced to null
+  assert_decision(&model_evaluator, "Category", &context(r#"{Age: 150}"#), r#""A""#);
+  assert_eq!(
+    r#""A""#,
+    model_evaluator
+      .evaluate_invocable_validated("Category", &context(r#"{Age: 150}"#))
+      .unwrap()
+      .to_string()
+  );
+}
+
+#[test]
+fn _0002() {
+  let model_evaluator = strict_model_evaluator();
+  assert_decision(&model_evaluator, "Category", &context(r#"{Age: 30}"#), r#""A""#);
+  assert_decision(&model_evaluator, "Risk", &context(r#"{Age: 30}"#), r#""LOW""#);
+  assert_eq!(
+    vec![r#"input data 'Age' value 150 violates type 'tAge' with allowed values '[18..120]'"#],
+    validation_errors(&model_evaluator, "Category", r#"{Age: 150}"#)
+  );
+  assert_eq!(
+    vec![r#"input data 'Age' value "30" violates type 'tAge' with allowed values '[18..120]'"#],
+    validation_errors(&model_evaluator, "Category", r#"{Age: "30"}"#)
+  );
+}
+
+#[test]
+fn _0003() {
+  let model_evaluator = strict_model_evaluator();
+  assert_eq!(
+    vec![r#"decision 'Category' value "C" violates type 'tCategory' with allowed values '"A","B"'"#],
+    validation_errors(&model_evaluator, "Category", r#"{Age: 70}"#)
+  );
+  assert_eq!(
+    vec![r#"decision 'Label' value 70 violates type 'string'"#],
+    validation_errors(&model_evaluator, "Label", r#"{Age: 70}"#)
+  );
+}
+
+#[test]
+fn _0004() {
+  let model_evaluator = strict_model_evaluator();
+  assert_eq!(
+    vec![r#"decision table input 'Age' value 110 violates input values '[0..100]'"#],
+    validation_errors(&model_evaluator, "Risk", r#"{Age: 110}"#)
+  );
+  assert_eq!(
+    vec![r#"decision table output 'Risk' in rule 2 value "MEDIUM" violates output values '"LOW","HIGH"'"#],
+    validation_errors(&model_evaluator, "Risk", r#"{Age: 60}"#)
+  );
+}
+
+#[test]
+fn _0005() {
+  let model_evaluator = strict_model_evaluator();
+  assert_decision(
+    &model_evaluator,
+    "Category",
+    &context(r#"{Age: 150}"#),
+    r#"null(validation failed: input data 'Age' value 150 violates type 'tAge' with allowed values '[18..120]')"#,
+  );
+}
+
+#[test]
+fn _0006() {
+  // violations are not collected after the evaluation panicked
+  let result = std::panic::catch_unwind(|| crate::validation::with_validation(|| -> Value { panic!("evaluation failed") }));
+  assert!(result.is_err());
+  assert!(!crate::validation::is_validating());
+  let model_evaluator = strict_model_evaluator();
+  assert_eq!(
+    vec![r#"input data 'Age' value 150 violates type 'tAge' with allowed values '[18..120]'"#],
+    validation_errors(&model_evaluator, "Category", r#"{Age: 150}"#)
+  );
+}
+
+const VALIDATION_DMN: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<definitions namespace="https://dmntk.io/validation" name="validation" id="_9b8c7d6e-5f4a-4b3c-2d1e-0f9a8b7c6d51" xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/">
+    <itemDefinition name="tAge">
+        <typeRef>number</typeRef>
+        <allowedValues>
+            <text>[18..120]</text>
+        </allowedValues>
+    </itemDefinition>
+    <itemDefinition name="tCategory">
+        <typeRef>string</typeRef>
+        <allowedValues>
+            <text>"A","B"</text>
+        </allowedValues>
+    </itemDefinition>
+    <decision name="Category" id="_category">
+        <variable typeRef="tCategory" name="Category"/>
+        <informationRequirement id="_category_age">
+            <requiredInput href="#_age"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>if Age > 60 then "C" else if Age > 40 then "B" else "A"</text>
+        </literalExpression>
+    </decision>
+    <decision name="Label" id="_label">
+        <variable typeRef="string" name="Label"/>
+        <informationRequirement id="_label_age">
+            <requiredInput href="#_age"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>Age</text>
+        </literalExpression>
+    </decision>
+    <decision name="Risk" id="_risk">
+        <variable name="Risk"/>
+        <informationRequirement id="_risk_age">
+            <requiredInput href="#_age"/>
+        </informationRequirement>
+        <decisionTable outputLabel="Risk">
+            <input id="_risk_input">
+                <inputExpression typeRef="number">
+                    <text>Age</text>
+                </inputExpression>
+                <inputValues>
+                    <text>[0..100]</text>
+                </inputValues>
+            </input>
+            <output id="_risk_output">
+                <outputValues>
+                    <text>"LOW","HIGH"</text>
+                </outputValues>
+            </output>
+            <rule id="_risk_rule_1">
+                <inputEntry id="_risk_rule_1_input">
+                    <text>&lt; 50</text>
+                </inputEntry>
+                <outputEntry id="_risk_rule_1_output">
+                    <text>"LOW"</text>
+                </outputEntry>
+            </rule>
+            <rule id="_risk_rule_2">
+                <inputEntry id="_risk_rule_2_input">
+                    <text>&gt;= 50</text>
+                </inputEntry>
+                <outputEntry id="_risk_rule_2_output">
+                    <text>"MEDIUM"</text>
+                </outputEntry>
+            </rule>
+        </decisionTable>
+    </decision>
+    <inputData name="Age" id="_age">
+        <variable typeRef="tAge" name="Age"/>
+    </inputData>
+</definitions>
+"##;
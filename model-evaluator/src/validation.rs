@@ -0,0 +1,153 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Validation of input and output values against their constraints.
+//!
+//! In strict validation mode, values of input data, results of decisions
+//! and values of decision table inputs and outputs are checked against
+//! their type references, allowed values, input values and output values.
+//! All detected violations are collected during the evaluation of an invocable
+//! and reported in [ValidationReport], instead of silently coerced `null` values.
+
+use dmntk_feel::values::Value;
+use std::cell::RefCell;
+use std::fmt;
+
+thread_local! {
+  /// Violations detected while evaluating an invocable in the current thread.
+  static VIOLATIONS: RefCell<Option<Vec<Violation>>> = const { RefCell::new(None) };
+}
+
+/// Mode of validating input and output values.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ValidationMode {
+  /// Values violating constraints are silently coerced to `null`.
+  Lenient,
+  /// Values violating constraints are reported in [ValidationReport].
+  Strict,
+}
+
+impl Default for ValidationMode {
+  /// Values are validated in lenient mode by default.
+  fn default() -> Self {
+    Self::Lenient
+  }
+}
+
+/// Violation of a constraint by a value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+  /// Description of the element the value belongs to.
+  pub element: String,
+  /// Value violating the constraint.
+  pub value: Value,
+  /// Description of the violated constraint.
+  pub constraint: String,
+}
+
+impl fmt::Display for Violation {
+  /// Formats the violation as a single line of text.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} value {} violates {}", self.element, self.value, self.constraint)
+  }
+}
+
+/// Report of all violations detected while evaluating an invocable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationReport {
+  /// Detected violations, in order of detection.
+  pub violations: Vec<Violation>,
+}
+
+impl fmt::Display for ValidationReport {
+  /// Formats the report as a single line of text.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let violations = self.violations.iter().map(|violation| violation.to_string()).collect::<Vec<String>>();
+    write!(f, "validation failed: {}", violations.join(", "))
+  }
+}
+
+/// Restores violations collected before the evaluation, also when the evaluation panics.
+struct ViolationsRestorer(Option<Vec<Violation>>);
+
+impl Drop for ViolationsRestorer {
+  fn drop(&mut self) {
+    let previous = self.0.take();
+    VIOLATIONS.with(|cell| cell.replace(previous));
+  }
+}
+
+/// Runs specified function collecting violations detected in the current thread.
+///
+/// When violations are already collected (nested evaluation), the function runs
+/// using the existing collection and the returned list of violations is empty.
+pub(crate) fn with_validation<R, F>(f: F) -> (R, Vec<Violation>)
+where
+  F: FnOnce() -> R,
+{
+  if is_validating() {
+    return (f(), vec![]);
+  }
+  let _restorer = ViolationsRestorer(VIOLATIONS.with(|cell| cell.replace(Some(vec![]))));
+  let result = f();
+  let violations = VIOLATIONS.with(|cell| cell.borrow_mut().as_mut().map(std::mem::take)).unwrap_or_default();
+  (result, violations)
+}
+
+/// Returns `true` when violations are collected in the current thread.
+pub(crate) fn is_validating() -> bool {
+  VIOLATIONS.with(|cell| cell.borrow().is_some())
+}
+
+/// Reports a violation, the same violation is reported only once.
+pub(crate) fn report_violation(element: String, value: &Value, constraint: String) {
+  VIOLATIONS.with(|cell| {
+    if let Some(violations) = cell.borrow_mut().as_mut() {
+      let violation = Violation {
+        element,
+        value: value.clone(),
+        constraint,
+      };
+      if !violations.contains(&violation) {
+        violations.push(violation);
+      }
+    }
+  });
+}
+
+/// Returns the description of the constraint defined by type reference and optional allowed values.
+pub(crate) fn type_ref_constraint(type_ref: &str, allowed_values: Option<&str>) -> String {
+  match allowed_values {
+    Some(allowed_values) => format!("type '{}' with allowed values '{}'", type_ref, allowed_values),
+    None => format!("type '{}'", type_ref),
+  }
+}
@@ -871,6 +871,7 @@ impl Import {
 /// - [FunctionDefinition],
 /// - [Invocation],
 /// - [LiteralExpression],
+/// - [List],
 /// - [Relation].
 #[derive(Debug, Clone, PartialEq)]
 pub enum ExpressionInstance {
@@ -879,6 +880,7 @@ pub enum ExpressionInstance {
   FunctionDefinition(Box<FunctionDefinition>),
   Invocation(Box<Invocation>),
   LiteralExpression(Box<LiteralExpression>),
+  List(List),
   Relation(Relation),
 }
 
@@ -2125,6 +2127,8 @@ pub struct RuleAnnotationClause {
 ///
 #[derive(Debug, Clone, PartialEq)]
 pub struct DecisionRule {
+  /// Optional identifier of this decision rule.
+  pub id: Option<String>,
   /// Ordered list of input entries that compose this decision rule.
   pub input_entries: Vec<InputEntry>,
   /// Ordered list of output entries that compose this decision rule.
@@ -40,6 +40,8 @@ use roxmltree::Node;
 
 const NODE_ALLOWED_ANSWERS: &str = "allowedAnswers";
 const NODE_ALLOWED_VALUES: &str = "allowedValues";
+const NODE_ANNOTATION: &str = "annotation";
+const NODE_ANNOTATION_ENTRY: &str = "annotationEntry";
 const NODE_BINDING: &str = "binding";
 const NODE_BUSINESS_KNOWLEDGE_MODEL: &str = "businessKnowledgeModel";
 const NODE_COLUMN: &str = "column";
@@ -86,6 +88,7 @@ const NODE_ITEM_DEFINITION: &str = "itemDefinition";
 const NODE_ITEM_COMPONENT: &str = "itemComponent";
 const NODE_KNOWLEDGE_REQUIREMENT: &str = "knowledgeRequirement";
 const NODE_KNOWLEDGE_SOURCE: &str = "knowledgeSource";
+const NODE_LIST: &str = "list";
 const NODE_LITERAL_EXPRESSION: &str = "literalExpression";
 const NODE_OUTPUT: &str = "output";
 const NODE_OUTPUT_DECISION: &str = "outputDecision";
@@ -511,7 +514,7 @@ impl ModelParser {
     if let Some(context) = self.parse_optional_context(node)? {
       return Ok(Some(ExpressionInstance::Context(context)));
     }
-    if let Some(decision_table) = self.parse_decision_table(node)? {
+    if let Some(decision_table) = self.parse_optional_decision_table(node)? {
       return Ok(Some(ExpressionInstance::DecisionTable(decision_table)));
     }
     if let Some(function_definition) = self.parse_optional_function_definition(node)? {
@@ -523,29 +526,51 @@ impl ModelParser {
     if let Some(literal_expression) = self.parse_optional_literal_expression(node) {
       return Ok(Some(ExpressionInstance::LiteralExpression(Box::new(literal_expression))));
     }
+    if let Some(list) = self.parse_optional_list(node)? {
+      return Ok(Some(ExpressionInstance::List(list)));
+    }
     if let Some(relation) = self.parse_optional_relation(node)? {
       return Ok(Some(ExpressionInstance::Relation(relation)));
     }
     Ok(None)
   }
 
-  fn parse_decision_table(&self, node: &Node) -> Result<Option<DecisionTable>> {
+  /// Parses an expression instance directly from the specified node.
+  /// When the node is not an expression instance (e.g. description), then [None] is returned.
+  fn parse_expression_instance(&self, node: &Node) -> Result<Option<ExpressionInstance>> {
+    Ok(match node.tag_name().name() {
+      NODE_CONTEXT => Some(ExpressionInstance::Context(self.parse_context(node)?)),
+      NODE_DECISION_TABLE => Some(ExpressionInstance::DecisionTable(self.parse_decision_table(node)?)),
+      NODE_FUNCTION_DEFINITION => Some(ExpressionInstance::FunctionDefinition(Box::new(self.parse_function_definition(node)?))),
+      NODE_INVOCATION => Some(ExpressionInstance::Invocation(Box::new(self.parse_invocation(node)?))),
+      NODE_LITERAL_EXPRESSION => Some(ExpressionInstance::LiteralExpression(Box::new(self.parse_literal_expression(node)))),
+      NODE_LIST => Some(ExpressionInstance::List(self.parse_list(node)?)),
+      NODE_RELATION => Some(ExpressionInstance::Relation(self.parse_relation(node)?)),
+      _ => None,
+    })
+  }
+
+  fn parse_optional_decision_table(&self, node: &Node) -> Result<Option<DecisionTable>> {
     if let Some(ref child_node) = node.children().find(|n| n.tag_name().name() == NODE_DECISION_TABLE) {
-      return Ok(Some(DecisionTable {
-        information_item_name: None,
-        input_clauses: self.parse_decision_table_inputs(child_node)?,
-        output_clauses: self.parse_decision_table_outputs(child_node)?,
-        annotations: vec![],
-        rules: self.parse_decision_table_rules(child_node)?,
-        hit_policy: self.parse_hit_policy_attribute(child_node)?,
-        aggregation: None,
-        preferred_orientation: self.parse_preferred_orientation_attribute(child_node)?,
-        output_label: optional_attribute(child_node, ATTR_OUTPUT_LABEL),
-      }));
+      return Ok(Some(self.parse_decision_table(child_node)?));
     }
     Ok(None)
   }
 
+  fn parse_decision_table(&self, node: &Node) -> Result<DecisionTable> {
+    Ok(DecisionTable {
+      information_item_name: None,
+      input_clauses: self.parse_decision_table_inputs(node)?,
+      output_clauses: self.parse_decision_table_outputs(node)?,
+      annotations: self.parse_decision_table_annotations(node)?,
+      rules: self.parse_decision_table_rules(node)?,
+      hit_policy: self.parse_hit_policy_attribute(node)?,
+      aggregation: None,
+      preferred_orientation: self.parse_preferred_orientation_attribute(node)?,
+      output_label: optional_attribute(node, ATTR_OUTPUT_LABEL),
+    })
+  }
+
   fn parse_decision_table_inputs(&self, node: &Node) -> Result<Vec<InputClause>> {
     let mut input_clauses = vec![];
     for ref child_node in node.children().filter(|n| n.tag_name().name() == NODE_INPUT) {
@@ -598,6 +623,16 @@ impl ModelParser {
     })
   }
 
+  fn parse_decision_table_annotations(&self, node: &Node) -> Result<Vec<RuleAnnotationClause>> {
+    let mut annotations = vec![];
+    for ref child_node in node.children().filter(|n| n.tag_name().name() == NODE_ANNOTATION) {
+      annotations.push(RuleAnnotationClause {
+        name: required_attribute(child_node, ATTR_NAME)?,
+      });
+    }
+    Ok(annotations)
+  }
+
   fn parse_decision_table_rules(&self, node: &Node) -> Result<Vec<DecisionRule>> {
     let mut rules = vec![];
     for ref child_node in node.children().filter(|n| n.tag_name().name() == NODE_RULE) {
@@ -608,9 +643,10 @@ impl ModelParser {
 
   fn parse_decision_table_rule(&self, node: &Node) -> Result<DecisionRule> {
     Ok(DecisionRule {
+      id: optional_attribute(node, ATTR_ID),
       input_entries: self.parse_decision_table_input_entries(node)?,
       output_entries: self.parse_decision_table_output_entries(node)?,
-      annotation_entries: vec![],
+      annotation_entries: self.parse_decision_table_annotation_entries(node),
     })
   }
 
@@ -642,15 +678,29 @@ impl ModelParser {
     })
   }
 
+  fn parse_decision_table_annotation_entries(&self, node: &Node) -> Vec<AnnotationEntry> {
+    let mut annotation_entries = vec![];
+    for ref child_node in node.children().filter(|n| n.tag_name().name() == NODE_ANNOTATION_ENTRY) {
+      annotation_entries.push(AnnotationEntry {
+        text: optional_child_optional_content(child_node, NODE_TEXT).unwrap_or_default(),
+      });
+    }
+    annotation_entries
+  }
+
   fn parse_optional_context(&self, node: &Node) -> Result<Option<Context>> {
     if let Some(ref child_node) = node.children().find(|n| n.tag_name().name() == NODE_CONTEXT) {
-      return Ok(Some(Context {
-        context_entries: self.parse_context_entries(child_node)?,
-      }));
+      return Ok(Some(self.parse_context(child_node)?));
     }
     Ok(None)
   }
 
+  fn parse_context(&self, node: &Node) -> Result<Context> {
+    Ok(Context {
+      context_entries: self.parse_context_entries(node)?,
+    })
+  }
+
   fn parse_context_entries(&self, node: &Node) -> Result<Vec<ContextEntry>> {
     let mut context_entries = vec![];
     for ref child_node in node.children().filter(|n| n.tag_name().name() == NODE_CONTEXT_ENTRY) {
@@ -664,14 +714,18 @@ impl ModelParser {
 
   fn parse_optional_invocation(&self, node: &Node) -> Result<Option<Invocation>> {
     if let Some(ref child_node) = node.children().find(|n| n.tag_name().name() == NODE_INVOCATION) {
-      return Ok(Some(Invocation {
-        called_function: self.parse_required_expression_instance(child_node)?,
-        bindings: self.parse_bindings(child_node)?,
-      }));
+      return Ok(Some(self.parse_invocation(child_node)?));
     }
     Ok(None)
   }
 
+  fn parse_invocation(&self, node: &Node) -> Result<Invocation> {
+    Ok(Invocation {
+      called_function: self.parse_required_expression_instance(node)?,
+      bindings: self.parse_bindings(node)?,
+    })
+  }
+
   fn parse_bindings(&self, node: &Node) -> Result<Vec<Binding>> {
     let mut bindings = vec![];
     for ref child_node in node.children().filter(|n| n.tag_name().name() == NODE_BINDING) {
@@ -708,48 +762,65 @@ impl ModelParser {
     }
   }
 
+  fn parse_optional_list(&self, node: &Node) -> Result<Option<List>> {
+    if let Some(ref list_node) = node.children().find(|n| n.tag_name().name() == NODE_LIST) {
+      return Ok(Some(self.parse_list(list_node)?));
+    }
+    Ok(None)
+  }
+
+  /// Parses [List] directly from the specified node, the node may be named `list` or `row`.
+  /// Each element of the list may be any expression instance.
+  fn parse_list(&self, node: &Node) -> Result<List> {
+    let mut elements = vec![];
+    for ref expression_instance_node in node.children().filter(|n| n.is_element()) {
+      if let Some(expression_instance) = self.parse_expression_instance(expression_instance_node)? {
+        elements.push(expression_instance);
+      }
+    }
+    Ok(List {
+      id: optional_attribute(node, ATTR_ID),
+      description: optional_child_optional_content(node, NODE_DESCRIPTION),
+      label: optional_attribute(node, ATTR_LABEL),
+      extension_elements: self.parse_extension_elements(node),
+      extension_attributes: self.parse_extension_attributes(node),
+      type_ref: optional_attribute(node, ATTR_TYPE_REF),
+      elements,
+    })
+  }
+
   fn parse_optional_relation(&self, node: &Node) -> Result<Option<Relation>> {
     if let Some(ref relation_node) = node.children().find(|n| n.tag_name().name() == NODE_RELATION) {
-      let mut columns = vec![];
-      for ref column_node in relation_node.children().filter(|n| n.tag_name().name() == NODE_COLUMN) {
-        columns.push(self.parse_information_item(column_node)?);
-      }
-      let mut rows = vec![];
-      for ref row_node in relation_node.children().filter(|n| n.tag_name().name() == NODE_ROW) {
-        let mut elements = vec![];
-        for ref expression_instance_node in row_node.children() {
-          if expression_instance_node.tag_name().name() == NODE_LITERAL_EXPRESSION {
-            let literal_expression = self.parse_literal_expression(expression_instance_node);
-            elements.push(ExpressionInstance::LiteralExpression(Box::new(literal_expression)));
-          }
-        }
-        if elements.len() != columns.len() {
-          return Err(number_of_elements_in_row_differs_from_number_of_columns());
-        }
-        rows.push(List {
-          id: optional_attribute(row_node, ATTR_ID),
-          description: optional_child_optional_content(row_node, NODE_DESCRIPTION),
-          label: optional_attribute(row_node, ATTR_LABEL),
-          extension_elements: self.parse_extension_elements(row_node),
-          extension_attributes: self.parse_extension_attributes(row_node),
-          type_ref: optional_attribute(row_node, ATTR_TYPE_REF),
-          elements,
-        });
-      }
-      return Ok(Some(Relation {
-        id: optional_attribute(relation_node, ATTR_ID),
-        description: optional_child_optional_content(relation_node, NODE_DESCRIPTION),
-        label: optional_attribute(relation_node, ATTR_LABEL),
-        extension_elements: self.parse_extension_elements(relation_node),
-        extension_attributes: self.parse_extension_attributes(relation_node),
-        type_ref: optional_attribute(relation_node, ATTR_TYPE_REF),
-        rows,
-        columns,
-      }));
+      return Ok(Some(self.parse_relation(relation_node)?));
     }
     Ok(None)
   }
 
+  fn parse_relation(&self, node: &Node) -> Result<Relation> {
+    let mut columns = vec![];
+    for ref column_node in node.children().filter(|n| n.tag_name().name() == NODE_COLUMN) {
+      columns.push(self.parse_information_item(column_node)?);
+    }
+    let mut rows = vec![];
+    for ref row_node in node.children().filter(|n| n.tag_name().name() == NODE_ROW) {
+      let row = self.parse_list(row_node)?;
+      if row.elements.len() != columns.len() {
+        return Err(number_of_elements_in_row_differs_from_number_of_columns());
+      }
+      rows.push(row);
+    }
+    Ok(Relation {
+      id: optional_attribute(node, ATTR_ID),
+      description: optional_child_optional_content(node, NODE_DESCRIPTION),
+      label: optional_attribute(node, ATTR_LABEL),
+      extension_elements: self.parse_extension_elements(node),
+      extension_attributes: self.parse_extension_attributes(node),
+      type_ref: optional_attribute(node, ATTR_TYPE_REF),
+      rows,
+      columns,
+    })
+  }
+
   /// Parses extension elements.
   /// Currently extension elements are ignored and [None] is always returned.
   /// This function is a placeholder for further development.   
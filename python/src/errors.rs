@@ -0,0 +1,60 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Errors reported by Python bindings.
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyTypeError, PyValueError};
+use pyo3::PyErr;
+
+create_exception!(dmntk, DmntkError, PyException, "Error raised when parsing or evaluation fails.");
+
+/// Converts [dmntk_common::DmntkError] into Python `DmntkError` exception.
+pub fn py_error(reason: dmntk_common::DmntkError) -> PyErr {
+  DmntkError::new_err(reason.to_string())
+}
+
+pub fn err_unsupported_type(type_name: &str) -> PyErr {
+  PyTypeError::new_err(format!("Python value of type '{}' can not be converted into FEEL value", type_name))
+}
+
+pub fn err_expected_mapping(type_name: &str) -> PyErr {
+  PyTypeError::new_err(format!("expected mapping, actual value has type '{}'", type_name))
+}
+
+pub fn err_non_string_key(type_name: &str) -> PyErr {
+  PyTypeError::new_err(format!("context keys must be strings, actual key has type '{}'", type_name))
+}
+
+pub fn err_non_finite_number(value: &str) -> PyErr {
+  PyValueError::new_err(format!("non-finite number can not be converted into FEEL value: {}", value))
+}
@@ -0,0 +1,59 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Evaluation of `FEEL` expressions and `DMN` models independent from Python.
+
+use dmntk_common::Result;
+use dmntk_feel::context::FeelContext;
+use dmntk_feel::values::Value;
+use dmntk_feel::Scope;
+use dmntk_model_evaluator::ModelEvaluator;
+use std::sync::Arc;
+
+/// Parses `FEEL` expression and returns the textual representation of its abstract syntax tree.
+pub fn parse_expression(expression: &str, ctx: FeelContext) -> Result<String> {
+  let node = dmntk_feel_parser::parse_expression(&ctx.into(), expression, false)?;
+  Ok(node.to_string().trim_end().to_string())
+}
+
+/// Evaluates `FEEL` expression in specified context.
+pub fn evaluate_expression(expression: &str, ctx: FeelContext) -> Result<Value> {
+  let scope: Scope = ctx.into();
+  let node = dmntk_feel_parser::parse_expression(&scope, expression, false)?;
+  dmntk_feel_evaluator::evaluate(&scope, &node)
+}
+
+/// Builds the model evaluator from definitions in `DMN` XML format.
+pub fn build_model_evaluator(xml: &str) -> Result<Arc<ModelEvaluator>> {
+  let definitions = dmntk_model::parse(xml)?;
+  ModelEvaluator::new(&definitions)
+}
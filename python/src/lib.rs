@@ -0,0 +1,120 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Python bindings for evaluating `FEEL` expressions and `DMN` models.
+//!
+//! Python dictionaries, lists, strings, numbers, booleans, `decimal.Decimal`,
+//! `datetime.date`, `datetime.datetime`, `datetime.time` and `datetime.timedelta`
+//! are converted into corresponding `FEEL` values and results are converted back.
+//! Numbers are always returned as `decimal.Decimal`. Years and months durations,
+//! ranges and other `FEEL` values without a Python counterpart are returned
+//! as their textual `FEEL` representation.
+
+extern crate dmntk_common;
+extern crate dmntk_feel;
+extern crate dmntk_feel_evaluator;
+extern crate dmntk_feel_parser;
+extern crate dmntk_model;
+extern crate dmntk_model_evaluator;
+extern crate pyo3;
+
+mod errors;
+mod evaluator;
+mod values;
+
+#[cfg(test)]
+mod tests;
+
+use crate::errors::{py_error, DmntkError};
+use dmntk_model_evaluator::ModelEvaluator;
+use pyo3::prelude::*;
+use std::sync::Arc;
+
+/// Parses `FEEL` expression and returns the textual representation of its abstract syntax tree.
+/// Names defined in optional `context` mapping are visible while parsing.
+#[pyfunction]
+#[pyo3(signature = (expression, context = None))]
+fn parse(expression: &str, context: Option<&Bound<'_, PyAny>>) -> PyResult<String> {
+  let ctx = values::context_from_py(context)?;
+  evaluator::parse_expression(expression, ctx).map_err(py_error)
+}
+
+/// Evaluates `FEEL` expression in optional `context` mapping and returns the result.
+#[pyfunction]
+#[pyo3(signature = (expression, context = None))]
+fn evaluate(py: Python<'_>, expression: &str, context: Option<&Bound<'_, PyAny>>) -> PyResult<PyObject> {
+  let ctx = values::context_from_py(context)?;
+  let value = evaluator::evaluate_expression(expression, ctx).map_err(py_error)?;
+  values::value_to_py(py, &value)
+}
+
+/// `DMN` model ready for evaluation.
+#[pyclass(frozen)]
+struct Model {
+  model_evaluator: Arc<ModelEvaluator>,
+}
+
+#[pymethods]
+impl Model {
+  /// Creates a model from definitions in `DMN` XML format.
+  #[new]
+  fn new(xml: &str) -> PyResult<Self> {
+    let model_evaluator = evaluator::build_model_evaluator(xml).map_err(py_error)?;
+    Ok(Self { model_evaluator })
+  }
+
+  /// Sorted names of invocables (decisions, business knowledge models and decision services) defined in the model.
+  #[getter]
+  fn invocables(&self) -> Vec<String> {
+    self.model_evaluator.invocable_names()
+  }
+
+  /// Evaluates an invocable (decision, business knowledge model or decision service)
+  /// with input data passed as a mapping, e.g. a dictionary or a row of pandas `DataFrame`.
+  #[pyo3(signature = (invocable_name, input = None))]
+  fn evaluate(&self, py: Python<'_>, invocable_name: &str, input: Option<&Bound<'_, PyAny>>) -> PyResult<PyObject> {
+    let input_data = values::context_from_py(input)?;
+    let value = self.model_evaluator.evaluate_invocable(invocable_name, &input_data);
+    values::value_to_py(py, &value)
+  }
+}
+
+/// Python module `dmntk`.
+#[pymodule]
+#[pyo3(name = "dmntk")]
+fn dmntk_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+  m.add_function(wrap_pyfunction!(parse, m)?)?;
+  m.add_function(wrap_pyfunction!(evaluate, m)?)?;
+  m.add_class::<Model>()?;
+  m.add("DmntkError", m.py().get_type::<DmntkError>())?;
+  Ok(())
+}
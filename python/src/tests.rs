@@ -0,0 +1,162 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::evaluator::*;
+use crate::values::*;
+use dmntk_feel::context::FeelContext;
+use dmntk_feel::values::Value;
+use dmntk_feel::{FeelNumber, Name};
+use pyo3::ffi::c_str;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// Runs Python code with module `dmntk` and specified strings available as global names.
+fn run(code: &std::ffi::CStr, strings: &[(&str, &str)]) {
+  pyo3::prepare_freethreaded_python();
+  Python::with_gil(|py| {
+    let module = PyModule::new(py, "dmntk").unwrap();
+    crate::dmntk_python(&module).unwrap();
+    let globals = PyDict::new(py);
+    globals.set_item("dmntk", module).unwrap();
+    for (name, value) in strings {
+      globals.set_item(name, value).unwrap();
+    }
+    py.run(code, Some(&globals), None).inspect_err(|e| e.print(py)).unwrap();
+  });
+}
+
+#[test]
+fn test_evaluate_expression() {
+  assert_eq!("3", evaluate_expression("1 + 2", FeelContext::default()).unwrap().to_string());
+  let mut ctx = FeelContext::default();
+  ctx.set_entry(&Name::from("Monthly Salary"), Value::Number(FeelNumber::from_string("1200.5")));
+  assert_eq!("14406", evaluate_expression("Monthly Salary * 12", ctx).unwrap().to_string());
+}
+
+#[test]
+fn test_value_round_trip() {
+  pyo3::prepare_freethreaded_python();
+  Python::with_gil(|py| {
+    let obj = py
+      .eval(
+        c_str!("{'a': [1, 2.5, None, True], 'b': __import__('decimal').Decimal('0.1'), 'c': __import__('datetime').timedelta(days=-1, seconds=5)}"),
+        None,
+        None,
+      )
+      .unwrap();
+    let value = value_from_py(&obj).unwrap();
+    assert_eq!(r#"{a: [1, 2.5, null, true], b: 0.1, c: -PT23H59M55S}"#, value.to_string());
+    let back = value_to_py(py, &value).unwrap();
+    assert!(back.bind(py).eq(&obj).unwrap());
+  });
+}
+
+#[test]
+fn test_parse_and_evaluate() {
+  run(
+    c_str!(
+      r#"
+from decimal import Decimal
+assert dmntk.evaluate('1 + 2') == Decimal(3)
+assert dmntk.evaluate('Monthly Salary * 12', {'Monthly Salary': 1200.5}) == Decimal('14406')
+assert dmntk.evaluate('{a: "x", b: [1, 2]}') == {'a': 'x', 'b': [Decimal(1), Decimal(2)]}
+assert dmntk.evaluate('duration("P1Y2M")') == 'P1Y2M'
+assert 'Add' in dmntk.parse('a + b', {'a': 1, 'b': 2})
+try:
+    dmntk.evaluate('1 +')
+    assert False
+except dmntk.DmntkError:
+    pass
+try:
+    dmntk.evaluate('x', {'x': object()})
+    assert False
+except TypeError:
+    pass
+"#
+    ),
+    &[],
+  );
+}
+
+#[test]
+fn test_temporal_values() {
+  run(
+    c_str!(
+      r#"
+from datetime import date, datetime, time, timedelta, timezone
+from zoneinfo import ZoneInfo
+d = date(2022, 3, 14)
+assert dmntk.evaluate('d', {'d': d}) == d
+assert dmntk.evaluate('d.month', {'d': d}) == 3
+dt = datetime(2022, 3, 14, 10, 30, 15, 250000)
+assert dmntk.evaluate('dt', {'dt': dt}) == dt
+dt = datetime(2022, 3, 14, 10, 30, tzinfo=timezone(timedelta(hours=2)))
+assert dmntk.evaluate('dt', {'dt': dt}) == dt
+assert dmntk.evaluate('dt', {'dt': dt}).utcoffset() == timedelta(hours=2)
+dt = datetime(2022, 3, 14, 10, 30, tzinfo=ZoneInfo('Europe/Warsaw'))
+assert dmntk.evaluate('dt', {'dt': dt}).tzinfo == ZoneInfo('Europe/Warsaw')
+assert dmntk.evaluate('date and time("2022-03-14T10:30:00Z")') == datetime(2022, 3, 14, 10, 30, tzinfo=timezone.utc)
+assert dmntk.evaluate('t', {'t': time(8, 15, 30)}) == time(8, 15, 30)
+assert dmntk.evaluate('time("08:15:30+01:00")').utcoffset() == timedelta(hours=1)
+assert dmntk.evaluate('a - b', {'a': dt, 'b': dt - timedelta(hours=3)}) == timedelta(hours=3)
+assert dmntk.evaluate('x + x', {'x': timedelta(minutes=90)}) == timedelta(hours=3)
+"#
+    ),
+    &[],
+  );
+}
+
+#[test]
+fn test_model() {
+  run(
+    c_str!(
+      r#"
+model = dmntk.Model(xml)
+assert model.invocables == ['Greeting Message']
+assert model.evaluate('Greeting Message', {'Full Name': 'John Doe'}) == 'Hello John Doe'
+assert model.evaluate('Greeting Message') is None
+assert model.evaluate('Unknown') is None
+try:
+    dmntk.Model('<definitions')
+    assert False
+except dmntk.DmntkError:
+    pass
+"#
+    ),
+    &[("xml", dmntk_examples::DMN_2_0001)],
+  );
+}
+
+#[test]
+fn test_build_model_evaluator_invalid_xml() {
+  assert!(build_model_evaluator("<definitions").is_err());
+}
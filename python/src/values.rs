@@ -0,0 +1,304 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Conversions between Python objects and `FEEL` values.
+
+use crate::errors::*;
+use dmntk_feel::context::FeelContext;
+use dmntk_feel::values::{Value, Values};
+use dmntk_feel::{FeelDate, FeelDateTime, FeelDaysAndTimeDuration, FeelNumber, FeelTime, Name};
+use pyo3::prelude::*;
+use pyo3::sync::GILOnceCell;
+use pyo3::types::*;
+use std::str::FromStr;
+
+/// Number of nanoseconds in a day.
+const NANOS_IN_DAY: i128 = 86_400_000_000_000;
+
+/// Number of nanoseconds in a second.
+const NANOS_IN_SECOND: i128 = 1_000_000_000;
+
+/// Maximum number of days in Python `timedelta`.
+const MAX_DELTA_DAYS: i128 = 999_999_999;
+
+static DECIMAL: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static ZONE_INFO: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+static TIMEZONE: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+/// Converts Python object into `FEEL` value.
+///
+/// Python `float` values are converted using their shortest decimal representation,
+/// so `0.1` in Python becomes exactly `0.1` in `FEEL`, `NaN` becomes `null`.
+pub fn value_from_py(obj: &Bound<'_, PyAny>) -> PyResult<Value> {
+  let py = obj.py();
+  if obj.is_none() {
+    return Ok(Value::Null(None));
+  }
+  if let Ok(b) = obj.downcast::<PyBool>() {
+    return Ok(Value::Boolean(b.is_true()));
+  }
+  if obj.is_instance_of::<PyInt>() {
+    return Ok(Value::Number(FeelNumber::from_string(&obj.str()?.to_cow()?)));
+  }
+  if let Ok(f) = obj.downcast::<PyFloat>() {
+    let n = f.value();
+    if n.is_nan() {
+      return Ok(Value::Null(None));
+    }
+    if n.is_infinite() {
+      return Err(err_non_finite_number(&n.to_string()));
+    }
+    return Ok(Value::Number(FeelNumber::from_string(&n.to_string())));
+  }
+  if obj.is_instance(DECIMAL.import(py, "decimal", "Decimal")?)? {
+    let text = obj.str()?.to_cow()?.to_string();
+    if !obj.call_method0("is_finite")?.is_truthy()? {
+      if obj.call_method0("is_nan")?.is_truthy()? {
+        return Ok(Value::Null(None));
+      }
+      return Err(err_non_finite_number(&text));
+    }
+    return Ok(Value::Number(FeelNumber::from_string(&text)));
+  }
+  if let Ok(s) = obj.downcast::<PyString>() {
+    return Ok(Value::String(s.to_cow()?.to_string()));
+  }
+  // `datetime` is a subclass of `date`, so it must be checked first
+  if let Ok(dt) = obj.downcast::<PyDateTime>() {
+    return date_time_from_py(dt);
+  }
+  if let Ok(d) = obj.downcast::<PyDate>() {
+    return FeelDate::new_opt(d.get_year(), d.get_month(), d.get_day())
+      .map(Value::Date)
+      .ok_or_else(|| err_unsupported_type("date"));
+  }
+  if let Ok(t) = obj.downcast::<PyTime>() {
+    return time_from_py(t);
+  }
+  if let Ok(delta) = obj.downcast::<PyDelta>() {
+    let seconds = delta.get_days() as i64 * 86_400 + delta.get_seconds() as i64;
+    let nanos = delta.get_microseconds() as i64 * 1_000;
+    return Ok(Value::DaysAndTimeDuration(
+      FeelDaysAndTimeDuration::default().second(seconds).nano(nanos).build(),
+    ));
+  }
+  if let Ok(mapping) = obj.downcast::<PyMapping>() {
+    return Ok(Value::Context(mapping_to_context(mapping)?));
+  }
+  if obj.is_instance_of::<PyList>() || obj.is_instance_of::<PyTuple>() {
+    let items = obj.try_iter()?.map(|item| value_from_py(&item?)).collect::<PyResult<Vec<Value>>>()?;
+    return Ok(Value::List(Values::new(items)));
+  }
+  // pandas `Series` (e.g. a row of `DataFrame`) is converted like a dictionary
+  if obj.hasattr("to_dict")? {
+    return value_from_py(&obj.call_method0("to_dict")?);
+  }
+  // numpy scalars and arrays are converted like Python scalars and lists
+  if obj.hasattr("tolist")? {
+    return value_from_py(&obj.call_method0("tolist")?);
+  }
+  Err(err_unsupported_type(&type_name(obj)))
+}
+
+/// Converts Python mapping into `FEEL` context, `None` is converted into an empty context.
+pub fn context_from_py(obj: Option<&Bound<'_, PyAny>>) -> PyResult<FeelContext> {
+  match obj {
+    None => Ok(FeelContext::default()),
+    Some(obj) if obj.is_none() => Ok(FeelContext::default()),
+    Some(obj) => match value_from_py(obj)? {
+      Value::Context(ctx) => Ok(ctx),
+      _ => Err(err_expected_mapping(&type_name(obj))),
+    },
+  }
+}
+
+/// Converts entries of Python mapping into `FEEL` context.
+fn mapping_to_context(mapping: &Bound<'_, PyMapping>) -> PyResult<FeelContext> {
+  let mut ctx = FeelContext::default();
+  for item in mapping.items()?.iter() {
+    let (key, value) = item.extract::<(Bound<PyAny>, Bound<PyAny>)>()?;
+    let key = key.downcast::<PyString>().map_err(|_| err_non_string_key(&type_name(&key)))?;
+    ctx.set_entry(&Name::from(key.to_cow()?.to_string()), value_from_py(&value)?);
+  }
+  Ok(ctx)
+}
+
+/// Converts Python `datetime` into `FEEL` date and time.
+///
+/// Naive values are converted into local date and time, values with `zoneinfo.ZoneInfo`
+/// keep the name of the time zone, all other time zones are converted into offsets.
+fn date_time_from_py(dt: &Bound<'_, PyDateTime>) -> PyResult<Value> {
+  let date = (dt.get_year(), dt.get_month(), dt.get_day());
+  let time = (dt.get_hour(), dt.get_minute(), dt.get_second(), dt.get_microsecond() as u64 * 1_000);
+  if FeelDate::new_opt(date.0, date.1, date.2).is_none() {
+    return Err(err_unsupported_type("datetime"));
+  }
+  let date_time = match dt.get_tzinfo() {
+    None => FeelDateTime::local(date.0, date.1, date.2, time.0, time.1, time.2, time.3),
+    Some(tz) => {
+      if let Some(zone) = zone_name(&tz)? {
+        let text = format!(
+          "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:09}@{}",
+          date.0, date.1, date.2, time.0, time.1, time.2, time.3, zone
+        );
+        FeelDateTime::try_from(text.as_str()).map_err(py_error)?
+      } else {
+        match utc_offset(&tz.call_method1("utcoffset", (dt,))?)? {
+          Some(offset) => FeelDateTime::offset(date, time, offset),
+          None => FeelDateTime::local(date.0, date.1, date.2, time.0, time.1, time.2, time.3),
+        }
+      }
+    }
+  };
+  Ok(Value::DateTime(date_time))
+}
+
+/// Converts Python `time` into `FEEL` time.
+fn time_from_py(t: &Bound<'_, PyTime>) -> PyResult<Value> {
+  let (hour, minute, second, nanos) = (t.get_hour(), t.get_minute(), t.get_second(), t.get_microsecond() as u64 * 1_000);
+  let time = match t.get_tzinfo() {
+    None => FeelTime::local(hour, minute, second, nanos),
+    Some(tz) => {
+      if let Some(zone) = zone_name(&tz)? {
+        let text = format!("{:02}:{:02}:{:02}.{:09}@{}", hour, minute, second, nanos, zone);
+        FeelTime::from_str(&text).map_err(py_error)?
+      } else {
+        match utc_offset(&tz.call_method1("utcoffset", (t.py().None(),))?)? {
+          Some(offset) => FeelTime::offset(hour, minute, second, nanos, offset),
+          None => FeelTime::local(hour, minute, second, nanos),
+        }
+      }
+    }
+  };
+  Ok(Value::Time(time))
+}
+
+/// Returns the name of the time zone, when specified `tzinfo` is an instance of `zoneinfo.ZoneInfo`.
+fn zone_name(tz: &Bound<'_, PyTzInfo>) -> PyResult<Option<String>> {
+  if tz.is_instance(ZONE_INFO.import(tz.py(), "zoneinfo", "ZoneInfo")?)? {
+    if let Ok(key) = tz.getattr("key")?.downcast::<PyString>() {
+      return Ok(Some(key.to_cow()?.to_string()));
+    }
+  }
+  Ok(None)
+}
+
+/// Converts the result of `tzinfo.utcoffset` into offset in seconds.
+fn utc_offset(offset: &Bound<'_, PyAny>) -> PyResult<Option<i32>> {
+  if let Ok(delta) = offset.downcast::<PyDelta>() {
+    Ok(Some(delta.get_days() * 86_400 + delta.get_seconds()))
+  } else {
+    Ok(None)
+  }
+}
+
+/// Returns the name of the type of Python object.
+fn type_name(obj: &Bound<'_, PyAny>) -> String {
+  obj.get_type().name().map(|name| name.to_string()).unwrap_or_default()
+}
+
+/// Converts `FEEL` value into Python object.
+pub fn value_to_py(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+  Ok(match value {
+    Value::Null(_) => py.None(),
+    Value::Boolean(b) => PyBool::new(py, *b).to_owned().into_any().unbind(),
+    Value::Number(n) => DECIMAL.import(py, "decimal", "Decimal")?.call1((n.to_string(),))?.unbind(),
+    Value::String(s) => text_to_py(py, s),
+    Value::Date(date) => match PyDate::new(py, date.year(), date.month(), date.day()) {
+      Ok(d) => d.into_any().unbind(),
+      Err(_) => text_to_py(py, &date.to_string()),
+    },
+    Value::DateTime(date_time) => {
+      let tz = tz_info(py, date_time.feel_time_zone(), date_time.feel_time_offset())?;
+      let micros = (date_time.nanosecond() / 1_000) as u32;
+      let (year, month, day) = (date_time.year(), date_time.month(), date_time.day());
+      let (hour, minute, second) = (date_time.hour(), date_time.minute(), date_time.second());
+      match PyDateTime::new(py, year, month, day, hour, minute, second, micros, tz.as_ref()) {
+        Ok(dt) => dt.into_any().unbind(),
+        Err(_) => text_to_py(py, &date_time.to_string()),
+      }
+    }
+    Value::Time(time) => {
+      let tz = tz_info(py, time.feel_time_zone(), time.feel_time_offset())?;
+      let micros = (time.nanosecond() / 1_000) as u32;
+      PyTime::new(py, time.hour(), time.minute(), time.second(), micros, tz.as_ref())?
+        .into_any()
+        .unbind()
+    }
+    Value::DaysAndTimeDuration(dt_duration) => {
+      let nanos = dt_duration.as_nanoseconds();
+      let days = nanos.div_euclid(NANOS_IN_DAY);
+      let rest = nanos.rem_euclid(NANOS_IN_DAY);
+      if days.abs() > MAX_DELTA_DAYS {
+        text_to_py(py, &dt_duration.to_string())
+      } else {
+        let seconds = rest / NANOS_IN_SECOND;
+        let micros = (rest % NANOS_IN_SECOND) / 1_000;
+        PyDelta::new(py, days as i32, seconds as i32, micros as i32, false)?.into_any().unbind()
+      }
+    }
+    Value::List(items) => {
+      let list = PyList::empty(py);
+      for item in items.as_vec() {
+        list.append(value_to_py(py, item)?)?;
+      }
+      list.into_any().unbind()
+    }
+    Value::Context(ctx) => {
+      let dict = PyDict::new(py);
+      for (name, entry) in ctx.iter() {
+        dict.set_item(name.to_string(), value_to_py(py, entry)?)?;
+      }
+      dict.into_any().unbind()
+    }
+    // years and months durations, ranges and other values are passed as `FEEL` text
+    other => text_to_py(py, &other.to_string()),
+  })
+}
+
+/// Creates Python `tzinfo` for `FEEL` time zone or offset, local time has no `tzinfo`.
+fn tz_info<'py>(py: Python<'py>, zone: Option<String>, offset: Option<i32>) -> PyResult<Option<Bound<'py, PyTzInfo>>> {
+  let tz = if let Some(zone) = zone {
+    ZONE_INFO.import(py, "zoneinfo", "ZoneInfo")?.call1((zone,))?
+  } else if let Some(offset) = offset {
+    let delta = PyDelta::new(py, 0, offset, 0, true)?;
+    TIMEZONE.import(py, "datetime", "timezone")?.call1((delta,))?
+  } else {
+    return Ok(None);
+  };
+  Ok(Some(tz.downcast_into::<PyTzInfo>()?))
+}
+
+/// Converts text into Python string.
+fn text_to_py(py: Python<'_>, text: &str) -> PyObject {
+  PyString::new(py, text).into_any().unbind()
+}
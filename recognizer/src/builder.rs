@@ -252,6 +252,7 @@ pub fn build(text: &str) -> Result<DecisionTable> {
       annotation_entries.push(annotation_entry);
     }
     rules.push(DecisionRule {
+      id: None,
       input_entries,
       output_entries,
       annotation_entries,
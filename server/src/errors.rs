@@ -42,6 +42,8 @@ enum ServerError {
   EndpointNotFound,
   #[error("missing parameter '{0}'")]
   MissingParameter(String),
+  #[error("invalid parameter '{0}'")]
+  InvalidParameter(String),
   #[error("invalid Base64 encoding")]
   InvalidBase64Encoding,
   #[error("invalid UTF-8 content")]
@@ -68,6 +70,10 @@ pub fn err_missing_parameter(name: &str) -> DmntkError {
   ServerError::MissingParameter(name.to_string()).into()
 }
 
+pub fn err_invalid_parameter(name: &str) -> DmntkError {
+  ServerError::InvalidParameter(name.to_string()).into()
+}
+
 pub fn err_invalid_base64_encoding() -> DmntkError {
   ServerError::InvalidBase64Encoding.into()
 }
@@ -19,8 +19,10 @@ extern crate base64;
 extern crate dmntk_common;
 extern crate dmntk_evaluator;
 extern crate dmntk_feel;
+extern crate dmntk_feel_evaluator;
 extern crate dmntk_feel_parser;
 extern crate dmntk_model;
+extern crate dmntk_model_evaluator;
 extern crate dmntk_workspace;
 extern crate serde;
 extern crate serde_derive;
@@ -32,19 +32,23 @@
 
 use crate::dto::{InputNodeDto, OutputNodeDto, WrappedValue};
 use crate::errors::*;
+use actix_web::error::BlockingError;
 use actix_web::web::Json;
 use actix_web::{error, get, post, web, App, HttpResponse, HttpServer};
 use dmntk_common::{DmntkError, Jsonify, Result};
 use dmntk_feel::context::FeelContext;
 use dmntk_feel::values::Value;
-use dmntk_feel::Scope;
+use dmntk_feel::{FeelType, Name, Scope};
+use dmntk_feel_evaluator::{CancellationToken, EvaluationLimits, TypeError};
 use dmntk_model::model::NamedElement;
+use dmntk_model_evaluator::{RuleConditions, ValidationMode, ValidationReport};
 use dmntk_workspace::Workspace;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::RwLock;
+use std::time::Duration;
 
 const DMNTK_NAME: &str = env!("CARGO_PKG_NAME");
 const DMNTK_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -65,6 +69,85 @@ pub struct ErrorDto {
   details: String,
 }
 
+/// Data transfer object for a violation of constraints.
+#[derive(Serialize)]
+pub struct ViolationDto {
+  /// Element the value belongs to.
+  #[serde(rename = "element")]
+  element: String,
+  /// Value violating the constraint.
+  #[serde(rename = "value")]
+  value: String,
+  /// Violated constraint.
+  #[serde(rename = "constraint")]
+  constraint: String,
+}
+
+/// Data transfer object for a condition on input expression.
+#[derive(Serialize)]
+pub struct ConditionDto {
+  /// Input expression.
+  #[serde(rename = "input")]
+  input_expression: String,
+  /// Unary tests the value of input expression must satisfy.
+  #[serde(rename = "tests")]
+  unary_tests: String,
+}
+
+/// Data transfer object for input conditions under which a decision table produces the desired output.
+#[derive(Serialize)]
+pub struct RuleConditionsDto {
+  /// Numbers of rules producing the desired output.
+  #[serde(rename = "rules")]
+  rules: Vec<usize>,
+  /// Conditions on input expressions.
+  #[serde(rename = "conditions")]
+  conditions: Vec<ConditionDto>,
+  /// Conditions of preceding rules producing other outputs, that must not be satisfied.
+  #[serde(rename = "exclusions")]
+  exclusions: Vec<Vec<ConditionDto>>,
+}
+
+impl From<RuleConditions> for RuleConditionsDto {
+  /// Creates [RuleConditionsDto] from [RuleConditions].
+  fn from(rule_conditions: RuleConditions) -> Self {
+    Self {
+      rules: rule_conditions.rules,
+      conditions: condition_dtos(rule_conditions.conditions),
+      exclusions: rule_conditions.exclusions.into_iter().map(condition_dtos).collect(),
+    }
+  }
+}
+
+/// Creates [ConditionDto]s from pairs of input expression and unary tests.
+fn condition_dtos(conditions: Vec<(String, String)>) -> Vec<ConditionDto> {
+  conditions
+    .into_iter()
+    .map(|(input_expression, unary_tests)| ConditionDto { input_expression, unary_tests })
+    .collect()
+}
+
+/// Data transfer object for the inferred result type of a decision.
+#[derive(Serialize)]
+pub struct DecisionTypeDto {
+  /// Inferred result type of the decision.
+  #[serde(rename = "type")]
+  result_type: String,
+  /// Type errors detected in decision logic.
+  #[serde(rename = "typeErrors")]
+  type_errors: Vec<String>,
+}
+
+impl From<(FeelType, Vec<TypeError>)> for DecisionTypeDto {
+  /// Creates [DecisionTypeDto] from inferred result type and detected type errors.
+  fn from((result_type, type_errors): (FeelType, Vec<TypeError>)) -> Self {
+    Self {
+      result_type: result_type.to_string(),
+      type_errors: type_errors.iter().map(|type_error| type_error.to_string()).collect(),
+    }
+  }
+}
+
 /// Data transfer object for a result.
 #[derive(Serialize)]
 pub struct ResultDto<T> {
@@ -74,12 +157,19 @@ pub struct ResultDto<T> {
   /// Result containing errors.
   #[serde(rename = "errors", skip_serializing_if = "Vec::is_empty")]
   errors: Vec<ErrorDto>,
+  /// Result containing violations of constraints.
+  #[serde(rename = "violations", skip_serializing_if = "Vec::is_empty")]
+  violations: Vec<ViolationDto>,
 }
 
 impl<T> Default for ResultDto<T> {
   /// Creates default result structure.
   fn default() -> Self {
-    Self { data: None, errors: vec![] }
+    Self {
+      data: None,
+      errors: vec![],
+      violations: vec![],
+    }
   }
 }
 
@@ -105,6 +195,22 @@ impl<T> ResultDto<T> {
       ..Default::default()
     }
   }
+  /// Creates [ResultDto] with violations of constraints taken from validation report.
+  pub fn violations(report: &ValidationReport) -> ResultDto<T> {
+    ResultDto {
+      errors: vec![ErrorDto { details: report.to_string() }],
+      violations: report
+        .violations
+        .iter()
+        .map(|violation| ViolationDto {
+          element: violation.element.clone(),
+          value: violation.value.to_string(),
+          constraint: violation.constraint.clone(),
+        })
+        .collect(),
+      ..Default::default()
+    }
+  }
 }
 
 /// System information structure.
@@ -205,6 +311,47 @@ struct EvaluateParams {
   invocable_name: Option<String>,
 }
 
+/// Parameters for evaluating multiple invocables in DMN™ model definitions.
+#[derive(Debug, Deserialize)]
+struct EvaluateInvocablesParams {
+  /// Name of the model.
+  #[serde(rename = "model")]
+  model_name: Option<String>,
+}
+
+/// Parameters for invoking decision service in DMN™ model definitions.
+#[derive(Debug, Deserialize)]
+struct InvokeParams {
+  /// Name of the model.
+  #[serde(rename = "model")]
+  model_name: Option<String>,
+  /// Name of the decision service in model.
+  #[serde(rename = "service")]
+  decision_service_name: Option<String>,
+}
+
+/// Parameters for retrieving the inferred result type of a decision in DMN™ model definitions.
+#[derive(Debug, Deserialize)]
+struct TypeParams {
+  /// Name of the model.
+  #[serde(rename = "model")]
+  model_name: Option<String>,
+  /// Name of the decision in model.
+  #[serde(rename = "decision")]
+  decision_name: Option<String>,
+}
+
+/// Parameters for querying decision table in DMN™ model definitions.
+#[derive(Debug, Deserialize)]
+struct QueryParams {
+  /// Name of the model.
+  #[serde(rename = "model")]
+  model_name: Option<String>,
+  /// Name of the decision, which logic is a decision table.
+  #[serde(rename = "decision")]
+  decision_name: Option<String>,
+}
+
 /// Handler for retrieving system information.
 #[get("/system/info")]
 async fn get_system_info() -> std::io::Result<Json<ResultDto<SystemInfoDto>>> {
@@ -293,22 +440,114 @@ async fn post_tck_evaluate(params: Json<TckEvaluateParams>, data: web::Data<Appl
 /// Handler for evaluating invocable in model.
 ///
 /// Input values may be defined in `JSON` or `FEEL` context format.
-/// Result is always in JSON format.
+/// Result is always in JSON format. In strict validation mode, violations
+/// of constraints are reported with status `422 Unprocessable Entity`.
 #[post("/evaluate/{model}/{invocable}")]
 async fn post_evaluate(params: web::Path<EvaluateParams>, request_body: String, data: web::Data<ApplicationData>) -> HttpResponse {
+  let params = params.into_inner();
+  evaluate_in_thread_pool(data, move |workspace, token| do_evaluate(workspace, &params, &request_body, token)).await
+}
+
+/// Handler for evaluating multiple invocables in model using the same input values.
+///
+/// Request body is a context in `JSON` or `FEEL` format, containing input values in entry `input`
+/// and an optional list of invocable names in entry `invocables`. When no invocable names are specified,
+/// all decisions in model are evaluated. Result is a context containing results of all invocables
+/// indexed by invocable names, always in JSON format.
+#[post("/evaluate/{model}")]
+async fn post_evaluate_invocables(params: web::Path<EvaluateInvocablesParams>, request_body: String, data: web::Data<ApplicationData>) -> HttpResponse {
+  let params = params.into_inner();
+  evaluate_in_thread_pool(data, move |workspace, token| do_evaluate_invocables(workspace, &params, &request_body, token)).await
+}
+
+/// Handler for invoking a decision service, the way process engines invoke decision services.
+///
+/// Request body is a context in `JSON` or `FEEL` format, containing input values in entry `input`
+/// and an optional list of output decision names in entry `outputDecisions`. Values of input decisions
+/// are taken from input values, provided input decisions are not evaluated. When output decision names are specified,
+/// the result is a context containing results of these output decisions, always in JSON format.
+#[post("/invoke/{model}/{service}")]
+async fn post_invoke(params: web::Path<InvokeParams>, request_body: String, data: web::Data<ApplicationData>) -> HttpResponse {
+  let params = params.into_inner();
+  evaluate_in_thread_pool(data, move |workspace, token| do_invoke(workspace, &params, &request_body, token)).await
+}
+
+/// Handler for retrieving the inferred result type of a decision and type errors detected in its logic,
+/// so the callers can validate their requests before evaluating the decision.
+#[get("/type/{model}/{decision}")]
+async fn get_decision_type(params: web::Path<TypeParams>, data: web::Data<ApplicationData>) -> std::io::Result<Json<ResultDto<DecisionTypeDto>>> {
+  if let Ok(workspace) = data.workspace.read() {
+    match do_decision_type(&workspace, &params.into_inner()) {
+      Ok(decision_type) => Ok(Json(ResultDto::data(DecisionTypeDto::from(decision_type)))),
+      Err(reason) => Ok(Json(ResultDto::error(reason))),
+    }
+  } else {
+    Ok(Json(ResultDto::error(err_workspace_read_lock_failed())))
+  }
+}
+
+/// Handler for querying the input conditions under which a decision table produces the desired output.
+///
+/// Request body is a context in `JSON` or `FEEL` format, containing the desired output in entry `output`.
+#[post("/query/{model}/{decision}")]
+async fn post_query(
+  params: web::Path<QueryParams>,
+  request_body: String,
+  data: web::Data<ApplicationData>,
+) -> std::io::Result<Json<ResultDto<Vec<RuleConditionsDto>>>> {
   if let Ok(workspace) = data.workspace.read() {
-    match do_evaluate(&workspace, &params.into_inner(), &request_body) {
-      Ok(value) => HttpResponse::Ok()
-        .content_type("application/json")
-        .body(format!("{{\"data\":{}}}", value.jsonify())),
-      Err(reason) => HttpResponse::Ok()
-        .content_type("application/json")
-        .body(ResultDto::<String>::error(reason).to_string()),
+    match do_query(&workspace, &params.into_inner(), &request_body) {
+      Ok(rule_conditions) => Ok(Json(ResultDto::data(rule_conditions.into_iter().map(RuleConditionsDto::from).collect()))),
+      Err(reason) => Ok(Json(ResultDto::error(reason))),
     }
   } else {
-    HttpResponse::Ok()
+    Ok(Json(ResultDto::error(err_workspace_read_lock_failed())))
+  }
+}
+
+/// Cancels the evaluation when dropped.
+///
+/// The server drops the future of the request handler when the client disconnects,
+/// so the evaluation running in the thread pool is cancelled instead of running to the end.
+struct CancelOnDrop(CancellationToken);
+
+impl Drop for CancelOnDrop {
+  fn drop(&mut self) {
+    self.0.cancel();
+  }
+}
+
+/// Runs the evaluation in the thread pool, the evaluation is cancelled when the client disconnects.
+async fn evaluate_in_thread_pool<F>(data: web::Data<ApplicationData>, f: F) -> HttpResponse
+where
+  F: FnOnce(&Workspace, &CancellationToken) -> Result<Result<Value, ValidationReport>, DmntkError> + Send + 'static,
+{
+  let token = CancellationToken::new();
+  let _cancel_on_drop = CancelOnDrop(token.clone());
+  let result = web::block(move || match data.workspace.read() {
+    Ok(workspace) => f(&workspace, &token),
+    Err(_) => Err(err_workspace_read_lock_failed()),
+  })
+  .await;
+  evaluation_response(match result {
+    Ok(result) => Ok(result),
+    Err(BlockingError::Error(reason)) => Err(reason),
+    Err(BlockingError::Canceled) => Err(err_internal_error("evaluation canceled")),
+  })
+}
+
+/// Prepares the response containing the result of the evaluation.
+fn evaluation_response(result: Result<Result<Value, ValidationReport>, DmntkError>) -> HttpResponse {
+  match result {
+    Ok(Ok(value)) => HttpResponse::Ok()
+      .content_type("application/json")
+      .body(format!("{{\"data\":{}}}", value.jsonify())),
+    Ok(Err(report)) => HttpResponse::UnprocessableEntity()
+      .content_type("application/json")
+      .body(ResultDto::<String>::violations(&report).to_string()),
+    Err(reason) => HttpResponse::Ok()
       .content_type("application/json")
-      .body(ResultDto::<String>::error(err_workspace_read_lock_failed()).to_string())
+      .body(ResultDto::<String>::error(reason).to_string()),
   }
 }
 
@@ -319,7 +558,9 @@ async fn not_found() -> std::io::Result<Json<ResultDto<()>>> {
 
 /// Starts the server.
 pub async fn start_server(opt_host: Option<String>, opt_port: Option<String>, opt_dir: Option<String>) -> std::io::Result<()> {
-  let workspace = Workspace::new(get_workspace_dir(opt_dir));
+  let mut workspace = Workspace::new(get_workspace_dir(opt_dir));
+  workspace.set_limits(get_evaluation_limits());
+  workspace.set_validation_mode(get_validation_mode());
   let application_data = web::Data::new(ApplicationData {
     workspace: RwLock::new(workspace),
   });
@@ -345,6 +586,10 @@ pub async fn start_server(opt_host: Option<String>, opt_port: Option<String>, op
       .service(post_definitions_deploy)
       .service(post_tck_evaluate)
       .service(post_evaluate)
+      .service(post_evaluate_invocables)
+      .service(post_invoke)
+      .service(post_query)
+      .service(get_decision_type)
       .default_service(web::route().to(not_found))
   })
   .bind(address)?
@@ -396,6 +641,39 @@ fn get_server_address(opt_host: Option<String>, opt_port: Option<String>) -> Str
   format!("{}:{}", host, port)
 }
 
+/// Returns the limits applied to each evaluation.
+///
+/// Limits may be controlled using environment variables:
+/// - `DMNTK_MAX_ITERATIONS` for the maximum number of iterations,
+/// - `DMNTK_MAX_RECURSION_DEPTH` for the maximum depth of nested function invocations,
+/// - `DMNTK_MAX_LIST_SIZE` for the maximum size of lists built during evaluation,
+/// - `DMNTK_TIMEOUT` for the maximum duration of the evaluation in milliseconds.
+///
+/// Limits not set using environment variables have default values defined by [EvaluationLimits].
+fn get_evaluation_limits() -> EvaluationLimits {
+  let limit = |name: &str| env::var(name).ok().and_then(|value| usize::from_str(&value).ok());
+  let default_limits = EvaluationLimits::default();
+  EvaluationLimits {
+    max_iterations: limit("DMNTK_MAX_ITERATIONS").or(default_limits.max_iterations),
+    max_recursion_depth: limit("DMNTK_MAX_RECURSION_DEPTH").or(default_limits.max_recursion_depth),
+    max_list_size: limit("DMNTK_MAX_LIST_SIZE").or(default_limits.max_list_size),
+    timeout: limit("DMNTK_TIMEOUT")
+      .map(|millis| Duration::from_millis(millis as u64))
+      .or(default_limits.timeout),
+  }
+}
+
+/// Returns the mode of validating input and output values.
+///
+/// Strict validation mode is enabled by setting environment variable `DMNTK_VALIDATION` to `strict`,
+/// otherwise values violating their constraints are silently coerced to `null`.
+fn get_validation_mode() -> ValidationMode {
+  match env::var("DMNTK_VALIDATION") {
+    Ok(mode) if mode.eq_ignore_ascii_case("strict") => ValidationMode::Strict,
+    _ => ValidationMode::Lenient,
+  }
+}
+
 /// Returns root directory for workspace.
 fn get_workspace_dir(opt_dir: Option<String>) -> Option<PathBuf> {
   let mut dir: Option<String> = None;
@@ -532,14 +810,13 @@ fn do_evaluate_tck(workspace: &Workspace, params: &TckEvaluateParams) -> Result<
   }
 }
 
-/// Evaluates the artifact specified in parameters and returns the result.
+/// Evaluates the artifact specified in parameters and returns the result or the report of violated constraints.
 #[inline(always)]
-fn do_evaluate(workspace: &Workspace, params: &EvaluateParams, input: &str) -> Result<Value, DmntkError> {
+fn do_evaluate(workspace: &Workspace, params: &EvaluateParams, input: &str, token: &CancellationToken) -> Result<Result<Value, ValidationReport>, DmntkError> {
   if let Some(model_name) = &params.model_name {
     if let Some(invocable_name) = &params.invocable_name {
       let input_data = dmntk_evaluator::evaluate_context(&Scope::default(), input)?;
-      let value = workspace.evaluate_invocable(model_name, invocable_name, &input_data)?;
-      Ok(value)
+      workspace.evaluate_invocable_validated(model_name, invocable_name, &input_data, token)
     } else {
       Err(err_missing_parameter("invocable"))
     }
@@ -548,9 +825,102 @@ fn do_evaluate(workspace: &Workspace, params: &EvaluateParams, input: &str) -> R
   }
 }
 
+/// Evaluates multiple invocables in the model specified in parameters and returns the context of results.
+#[inline(always)]
+fn do_evaluate_invocables(
+  workspace: &Workspace,
+  params: &EvaluateInvocablesParams,
+  request: &str,
+  token: &CancellationToken,
+) -> Result<Result<Value, ValidationReport>, DmntkError> {
+  if let Some(model_name) = &params.model_name {
+    let (invocable_names, input_data) = get_invocables_request(request)?;
+    workspace.evaluate_invocables_validated(model_name, invocable_names.as_deref(), &input_data, token)
+  } else {
+    Err(err_missing_parameter("model"))
+  }
+}
+
+/// Invokes the decision service specified in parameters and returns the result or the report of violated constraints.
+#[inline(always)]
+fn do_invoke(workspace: &Workspace, params: &InvokeParams, request: &str, token: &CancellationToken) -> Result<Result<Value, ValidationReport>, DmntkError> {
+  if let Some(model_name) = &params.model_name {
+    if let Some(decision_service_name) = &params.decision_service_name {
+      let (output_decision_names, input_data) = get_names_and_input_request(request, "outputDecisions")?;
+      workspace.invoke_decision_service_validated(model_name, decision_service_name, output_decision_names.as_deref(), &input_data, token)
+    } else {
+      Err(err_missing_parameter("service"))
+    }
+  } else {
+    Err(err_missing_parameter("model"))
+  }
+}
+
+/// Returns the inferred result type and type errors of the decision specified in parameters.
+#[inline(always)]
+fn do_decision_type(workspace: &Workspace, params: &TypeParams) -> Result<(FeelType, Vec<TypeError>), DmntkError> {
+  if let Some(model_name) = &params.model_name {
+    if let Some(decision_name) = &params.decision_name {
+      workspace.decision_type(model_name, decision_name)
+    } else {
+      Err(err_missing_parameter("decision"))
+    }
+  } else {
+    Err(err_missing_parameter("model"))
+  }
+}
+
+/// Queries the decision table specified in parameters and returns the conditions producing the desired output.
+#[inline(always)]
+fn do_query(workspace: &Workspace, params: &QueryParams, request: &str) -> Result<Vec<RuleConditions>, DmntkError> {
+  if let Some(model_name) = &params.model_name {
+    if let Some(decision_name) = &params.decision_name {
+      let request_ctx = dmntk_evaluator::evaluate_context(&Scope::default(), request)?;
+      let output = request_ctx.get_entry(&Name::from("output")).ok_or_else(|| err_missing_parameter("output"))?;
+      workspace.query_decision_table(model_name, decision_name, output)
+    } else {
+      Err(err_missing_parameter("decision"))
+    }
+  } else {
+    Err(err_missing_parameter("model"))
+  }
+}
+
+/// Returns optional invocable names and input data from the request for evaluating multiple invocables.
+fn get_invocables_request(request: &str) -> Result<(Option<Vec<String>>, FeelContext), DmntkError> {
+  get_names_and_input_request(request, "invocables")
+}
+
+/// Returns optional list of names stored in specified entry and input data from the request.
+fn get_names_and_input_request(request: &str, names_entry: &str) -> Result<(Option<Vec<String>>, FeelContext), DmntkError> {
+  let request_ctx = dmntk_evaluator::evaluate_context(&Scope::default(), request)?;
+  let input_data = match request_ctx.get_entry(&Name::from("input")) {
+    Some(Value::Context(input_data)) => input_data.clone(),
+    Some(_) => return Err(err_invalid_parameter("input")),
+    None => return Err(err_missing_parameter("input")),
+  };
+  let names = match request_ctx.get_entry(&Name::from(names_entry)) {
+    Some(Value::List(values)) => {
+      let mut names = vec![];
+      for value in values.as_vec() {
+        if let Value::String(name) = value {
+          names.push(name.clone());
+        } else {
+          return Err(err_invalid_parameter(names_entry));
+        }
+      }
+      Some(names)
+    }
+    Some(Value::Null(_)) | None => None,
+    Some(_) => return Err(err_invalid_parameter(names_entry)),
+  };
+  Ok((names, input_data))
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
+  use dmntk_model_evaluator::Violation;
 
   #[test]
   fn test() {
@@ -559,4 +929,86 @@ mod tests {
       ResultDto::<String>::error(err_internal_error("unknown")).to_string()
     );
   }
+
+  #[test]
+  fn test_invocables_request() {
+    let (invocable_names, input_data) = get_invocables_request(r#"{"invocables": ["A", "B"], "input": {"Age": 40}}"#).unwrap();
+    assert_eq!(Some(vec!["A".to_string(), "B".to_string()]), invocable_names);
+    assert_eq!("{Age: 40}", input_data.to_string());
+    let (invocable_names, input_data) = get_invocables_request(r#"{input: {}}"#).unwrap();
+    assert_eq!(None, invocable_names);
+    assert_eq!("{}", input_data.to_string());
+    assert_eq!(
+      r#"Err(DmntkError("ServerError: missing parameter 'input'"))"#,
+      format!("{:?}", get_invocables_request(r#"{invocables: ["A"]}"#))
+    );
+    assert_eq!(
+      r#"Err(DmntkError("ServerError: invalid parameter 'invocables'"))"#,
+      format!("{:?}", get_invocables_request(r#"{invocables: ["A", 1], input: {}}"#))
+    );
+  }
+
+  #[test]
+  fn test_invoke_request() {
+    let (output_decision_names, input_data) =
+      get_names_and_input_request(r#"{"outputDecisions": ["Risk"], "input": {"Ratio": 0.5}}"#, "outputDecisions").unwrap();
+    assert_eq!(Some(vec!["Risk".to_string()]), output_decision_names);
+    assert_eq!("{Ratio: 0.5}", input_data.to_string());
+    assert_eq!(
+      r#"Err(DmntkError("ServerError: invalid parameter 'outputDecisions'"))"#,
+      format!(
+        "{:?}",
+        get_names_and_input_request(r#"{outputDecisions: "Risk", input: {}}"#, "outputDecisions")
+      )
+    );
+  }
+
+  #[test]
+  fn test_cancel_on_drop() {
+    let token = CancellationToken::new();
+    let cancel_on_drop = CancelOnDrop(token.clone());
+    assert!(!token.is_cancelled());
+    drop(cancel_on_drop);
+    assert!(token.is_cancelled());
+  }
+
+  #[test]
+  fn test_decision_type() {
+    let decision_type = (
+      FeelType::Null,
+      vec![TypeError::IncompatibleOperands("+".to_string(), FeelType::String, FeelType::Date)],
+    );
+    assert_eq!(
+      r#"{"data":{"type":"Null","typeErrors":["incompatible types in '+': string and date"]}}"#,
+      ResultDto::data(DecisionTypeDto::from(decision_type)).to_string()
+    );
+  }
+
+  #[test]
+  fn test_rule_conditions() {
+    let rule_conditions = RuleConditions {
+      rules: vec![1, 5],
+      conditions: vec![("Credit Score".to_string(), r#""POOR", "BAD""#.to_string())],
+      exclusions: vec![vec![("Monthly Income".to_string(), "< 1000".to_string())]],
+    };
+    assert_eq!(
+      r#"{"data":[{"rules":[1,5],"conditions":[{"input":"Credit Score","tests":"\"POOR\", \"BAD\""}],"exclusions":[[{"input":"Monthly Income","tests":"< 1000"}]]}]}"#,
+      ResultDto::data(vec![RuleConditionsDto::from(rule_conditions)]).to_string()
+    );
+  }
+
+  #[test]
+  fn test_violations() {
+    let report = ValidationReport {
+      violations: vec![Violation {
+        element: "input data 'Age'".to_string(),
+        value: Value::String("old".to_string()),
+        constraint: "type 'number'".to_string(),
+      }],
+    };
+    assert_eq!(
+      r#"{"errors":[{"details":"validation failed: input data 'Age' value \"old\" violates type 'number'"}],"violations":[{"element":"input data 'Age'","value":"\"old\"","constraint":"type 'number'"}]}"#,
+      ResultDto::<String>::violations(&report).to_string()
+    );
+  }
 }
@@ -0,0 +1,59 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Errors reported by WebAssembly bindings.
+
+use dmntk_common::DmntkError;
+
+/// Errors reported by WebAssembly bindings.
+#[derive(Error, Debug)]
+enum WasmError {
+  #[error("JavaScript value can not be converted into FEEL value: {0}")]
+  UnsupportedValue(String),
+  #[error("expected JavaScript object, actual value is: {0}")]
+  ExpectedObject(String),
+}
+
+impl From<WasmError> for DmntkError {
+  /// Converts a WebAssembly bindings error into [DmntkError].
+  fn from(e: WasmError) -> Self {
+    DmntkError::new("WasmError", &e.to_string())
+  }
+}
+
+pub fn err_unsupported_value(value: &str) -> DmntkError {
+  WasmError::UnsupportedValue(value.to_string()).into()
+}
+
+pub fn err_expected_object(value: &str) -> DmntkError {
+  WasmError::ExpectedObject(value.to_string()).into()
+}
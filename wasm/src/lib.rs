@@ -0,0 +1,108 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! WebAssembly bindings for evaluating `FEEL` expressions and `DMN` models.
+//!
+//! Values are passed from JavaScript as plain objects, arrays, strings, numbers
+//! and booleans, and results are returned the same way. Temporal values,
+//! ranges and other `FEEL` values without a JavaScript counterpart are
+//! returned as their textual `FEEL` representation.
+
+extern crate dmntk_common;
+extern crate dmntk_feel;
+extern crate dmntk_feel_evaluator;
+extern crate dmntk_feel_parser;
+extern crate dmntk_model;
+extern crate dmntk_model_evaluator;
+extern crate js_sys;
+#[macro_use]
+extern crate thiserror;
+extern crate wasm_bindgen;
+#[cfg(all(test, target_arch = "wasm32"))]
+extern crate wasm_bindgen_test;
+
+mod errors;
+mod evaluator;
+mod values;
+
+#[cfg(test)]
+mod tests;
+
+use dmntk_common::DmntkError;
+use dmntk_model_evaluator::ModelEvaluator;
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+
+/// Parses `FEEL` expression and returns the textual representation of its abstract syntax tree.
+/// Names defined in optional `context` object are visible while parsing.
+#[wasm_bindgen]
+pub fn parse(expression: &str, context: JsValue) -> Result<String, JsValue> {
+  let ctx = values::context_from_js(&context).map_err(js_error)?;
+  evaluator::parse_expression(expression, ctx).map_err(js_error)
+}
+
+/// Evaluates `FEEL` expression in optional `context` object and returns the result.
+#[wasm_bindgen]
+pub fn evaluate(expression: &str, context: JsValue) -> Result<JsValue, JsValue> {
+  let ctx = values::context_from_js(&context).map_err(js_error)?;
+  let value = evaluator::evaluate_expression(expression, ctx).map_err(js_error)?;
+  Ok(values::value_to_js(&value))
+}
+
+/// `DMN` model ready for evaluation.
+#[wasm_bindgen]
+pub struct Model {
+  model_evaluator: Arc<ModelEvaluator>,
+}
+
+#[wasm_bindgen]
+impl Model {
+  /// Creates a model from definitions in `DMN` XML format.
+  #[wasm_bindgen(constructor)]
+  pub fn new(xml: &str) -> Result<Model, JsValue> {
+    let model_evaluator = evaluator::build_model_evaluator(xml).map_err(js_error)?;
+    Ok(Self { model_evaluator })
+  }
+
+  /// Evaluates an invocable (decision, business knowledge model or decision service)
+  /// with input data passed as JavaScript object.
+  pub fn evaluate(&self, invocable_name: &str, input: JsValue) -> Result<JsValue, JsValue> {
+    let input_data = values::context_from_js(&input).map_err(js_error)?;
+    let value = self.model_evaluator.evaluate_invocable(invocable_name, &input_data);
+    Ok(values::value_to_js(&value))
+  }
+}
+
+/// Converts [DmntkError] into JavaScript `Error`.
+fn js_error(reason: DmntkError) -> JsValue {
+  js_sys::Error::new(&reason.to_string()).into()
+}
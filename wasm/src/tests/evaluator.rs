@@ -0,0 +1,64 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::evaluator::*;
+use dmntk_feel::context::FeelContext;
+use dmntk_feel::values::Value;
+use dmntk_feel::{FeelNumber, Name};
+
+#[test]
+fn test_parse_expression() {
+  let ast = parse_expression("1 + 2", FeelContext::default()).unwrap();
+  assert!(ast.contains("Add"));
+  assert!(parse_expression("1 +", FeelContext::default()).is_err());
+}
+
+#[test]
+fn test_evaluate_expression() {
+  assert_eq!("3", evaluate_expression("1 + 2", FeelContext::default()).unwrap().to_string());
+  let mut ctx = FeelContext::default();
+  ctx.set_entry(&Name::from("Monthly Salary"), Value::Number(FeelNumber::from_string("1200.5")));
+  assert_eq!("14406", evaluate_expression("Monthly Salary * 12", ctx).unwrap().to_string());
+}
+
+#[test]
+fn test_evaluate_model() {
+  let model_evaluator = build_model_evaluator(dmntk_examples::DMN_2_0001).unwrap();
+  let mut ctx = FeelContext::default();
+  ctx.set_entry(&Name::from("Full Name"), Value::String("John Doe".to_string()));
+  assert_eq!(r#""Hello John Doe""#, model_evaluator.evaluate_invocable("Greeting Message", &ctx).to_string());
+}
+
+#[test]
+fn test_build_model_evaluator_invalid_xml() {
+  assert!(build_model_evaluator("<definitions").is_err());
+}
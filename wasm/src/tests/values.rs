@@ -0,0 +1,166 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::values::*;
+use dmntk_feel::context::FeelContext;
+use dmntk_feel::values::{Value, Values};
+use dmntk_feel::{FeelDate, FeelNumber, Name};
+use js_sys::{Array, Function, Object, Reflect};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+/// Utility function that converts JavaScript value into `FEEL` value and returns its textual representation.
+fn from_js(value: &JsValue) -> String {
+  value_from_js(value).unwrap().to_string()
+}
+
+/// Utility function that builds JavaScript object from specified entries.
+fn object(entries: &[(&str, JsValue)]) -> JsValue {
+  let object = Object::new();
+  for (name, value) in entries {
+    Reflect::set(&object, &JsValue::from_str(name), value).unwrap();
+  }
+  object.into()
+}
+
+#[wasm_bindgen_test]
+fn test_null_from_js() {
+  assert_eq!("null", from_js(&JsValue::NULL));
+  assert_eq!("null", from_js(&JsValue::UNDEFINED));
+}
+
+#[wasm_bindgen_test]
+fn test_boolean_from_js() {
+  assert_eq!("true", from_js(&JsValue::TRUE));
+  assert_eq!("false", from_js(&JsValue::FALSE));
+}
+
+#[wasm_bindgen_test]
+fn test_number_from_js() {
+  assert_eq!("0.1", from_js(&JsValue::from_f64(0.1)));
+  assert_eq!("-42", from_js(&JsValue::from_f64(-42.0)));
+  assert!(value_from_js(&JsValue::from_f64(f64::NAN)).is_err());
+  assert!(value_from_js(&JsValue::from_f64(f64::INFINITY)).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_string_from_js() {
+  assert_eq!(r#""John Doe""#, from_js(&JsValue::from_str("John Doe")));
+}
+
+#[wasm_bindgen_test]
+fn test_list_from_js() {
+  let array: Array = [JsValue::from_f64(1.0), JsValue::from_str("a"), JsValue::NULL].iter().collect();
+  assert_eq!(r#"[1, "a", null]"#, from_js(&array.into()));
+  assert_eq!("[]", from_js(&Array::new().into()));
+}
+
+#[wasm_bindgen_test]
+fn test_context_from_js() {
+  let value = object(&[
+    ("Full Name", JsValue::from_str("John")),
+    ("Address", object(&[("City", JsValue::from_str("Warsaw"))])),
+  ]);
+  assert_eq!(r#"{Address: {City: "Warsaw"}, Full Name: "John"}"#, from_js(&value));
+  assert_eq!(
+    r#"{Address: {City: "Warsaw"}, Full Name: "John"}"#,
+    context_from_js(&value).unwrap().to_string()
+  );
+  assert_eq!("{}", context_from_js(&JsValue::NULL).unwrap().to_string());
+  assert_eq!("{}", context_from_js(&JsValue::UNDEFINED).unwrap().to_string());
+  assert!(context_from_js(&Array::new().into()).is_err());
+  assert!(context_from_js(&JsValue::from_f64(1.0)).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_function_from_js() {
+  let function = Function::new_no_args("return 1");
+  assert!(value_from_js(&function.into()).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_null_to_js() {
+  assert!(value_to_js(&Value::Null(None)).is_null());
+}
+
+#[wasm_bindgen_test]
+fn test_boolean_to_js() {
+  assert_eq!(Some(true), value_to_js(&Value::Boolean(true)).as_bool());
+}
+
+#[wasm_bindgen_test]
+fn test_number_to_js() {
+  assert_eq!(Some(0.1), value_to_js(&Value::Number(FeelNumber::from_string("0.1"))).as_f64());
+  assert_eq!(Some(-42.0), value_to_js(&Value::Number(FeelNumber::from_string("-42"))).as_f64());
+}
+
+#[wasm_bindgen_test]
+fn test_string_to_js() {
+  assert_eq!(Some("John".to_string()), value_to_js(&Value::String("John".to_string())).as_string());
+}
+
+#[wasm_bindgen_test]
+fn test_list_to_js() {
+  let value = value_to_js(&Value::List(Values::new(vec![Value::Boolean(false), Value::String("a".to_string())])));
+  assert!(Array::is_array(&value));
+  let array = Array::from(&value);
+  assert_eq!(2, array.length());
+  assert_eq!(Some(false), array.get(0).as_bool());
+  assert_eq!(Some("a".to_string()), array.get(1).as_string());
+}
+
+#[wasm_bindgen_test]
+fn test_context_to_js() {
+  let mut ctx = FeelContext::default();
+  ctx.set_entry(&Name::from("Full Name"), Value::String("John".to_string()));
+  ctx.set_entry(&Name::from("Age"), Value::Number(FeelNumber::from_string("49")));
+  let value = value_to_js(&Value::Context(ctx.clone()));
+  assert_eq!(
+    Some("John".to_string()),
+    Reflect::get(&value, &JsValue::from_str("Full Name")).unwrap().as_string()
+  );
+  assert_eq!(Some(49.0), Reflect::get(&value, &JsValue::from_str("Age")).unwrap().as_f64());
+  assert_eq!(ctx, context_from_js(&value).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_other_values_to_js() {
+  let date = Value::Date(FeelDate::new(2022, 3, 14));
+  assert_eq!(Some("2022-03-14".to_string()), value_to_js(&date).as_string());
+  let range = Value::Range(
+    Box::new(Value::Number(FeelNumber::from_string("1"))),
+    true,
+    Box::new(Value::Number(FeelNumber::from_string("5"))),
+    false,
+  );
+  assert_eq!(Some("[1..5)".to_string()), value_to_js(&range).as_string());
+}
@@ -0,0 +1,114 @@
+/*
+ * DMNTK - Decision Model and Notation Toolkit
+ *
+ * MIT license
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ *
+ * Apache license, Version 2.0
+ *
+ * Copyright (c) 2018-2022 Dariusz Depta Engos Software
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Conversions between JavaScript values and `FEEL` values.
+
+use crate::errors::*;
+use dmntk_common::Result;
+use dmntk_feel::context::FeelContext;
+use dmntk_feel::values::{Value, Values};
+use dmntk_feel::{FeelNumber, Name};
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::JsValue;
+
+/// Converts JavaScript value into `FEEL` value.
+///
+/// Numbers are converted using their shortest decimal representation,
+/// so `0.1` in JavaScript becomes exactly `0.1` in `FEEL`.
+pub fn value_from_js(value: &JsValue) -> Result<Value> {
+  if value.is_null() || value.is_undefined() {
+    return Ok(Value::Null(None));
+  }
+  if let Some(b) = value.as_bool() {
+    return Ok(Value::Boolean(b));
+  }
+  if let Some(n) = value.as_f64() {
+    if !n.is_finite() {
+      return Err(err_unsupported_value(&n.to_string()));
+    }
+    return Ok(Value::Number(FeelNumber::from_string(&n.to_string())));
+  }
+  if let Some(s) = value.as_string() {
+    return Ok(Value::String(s));
+  }
+  if Array::is_array(value) {
+    let items = Array::from(value).iter().map(|item| value_from_js(&item)).collect::<Result<Vec<Value>>>()?;
+    return Ok(Value::List(Values::new(items)));
+  }
+  if value.is_object() && !value.is_function() {
+    return Ok(Value::Context(object_to_context(value)?));
+  }
+  Err(err_unsupported_value(&format!("{:?}", value)))
+}
+
+/// Converts JavaScript object into `FEEL` context,
+/// `null` and `undefined` are converted into an empty context.
+pub fn context_from_js(value: &JsValue) -> Result<FeelContext> {
+  if value.is_null() || value.is_undefined() {
+    return Ok(FeelContext::default());
+  }
+  if !value.is_object() || value.is_function() || Array::is_array(value) {
+    return Err(err_expected_object(&format!("{:?}", value)));
+  }
+  object_to_context(value)
+}
+
+/// Converts entries of JavaScript object into `FEEL` context.
+fn object_to_context(value: &JsValue) -> Result<FeelContext> {
+  let mut ctx = FeelContext::default();
+  for key in Object::keys(&Object::from(value.clone())).iter() {
+    let entry = Reflect::get(value, &key).unwrap_or(JsValue::UNDEFINED);
+    let name = Name::from(key.as_string().unwrap_or_default());
+    ctx.set_entry(&name, value_from_js(&entry)?);
+  }
+  Ok(ctx)
+}
+
+/// Converts `FEEL` value into JavaScript value.
+pub fn value_to_js(value: &Value) -> JsValue {
+  match value {
+    Value::Null(_) => JsValue::NULL,
+    Value::Boolean(b) => JsValue::from_bool(*b),
+    Value::Number(n) => JsValue::from_f64(n.to_string().parse().unwrap_or(f64::NAN)),
+    Value::String(s) => JsValue::from_str(s),
+    Value::List(items) => items.as_vec().iter().map(value_to_js).collect::<Array>().into(),
+    Value::Context(ctx) => {
+      let object = Object::new();
+      for (name, entry) in ctx.iter() {
+        let _ = Reflect::set(&object, &JsValue::from_str(&name.to_string()), &value_to_js(entry));
+      }
+      object.into()
+    }
+    // temporal values, ranges and other values are passed as `FEEL` text
+    other => JsValue::from_str(&other.to_string()),
+  }
+}
@@ -33,6 +33,7 @@
 //! Errors reported by workspace.
 
 use dmntk_common::DmntkError;
+use dmntk_model_evaluator::ValidationReport;
 
 /// Errors reported by workspace.
 #[derive(Error, Debug)]
@@ -43,6 +44,14 @@ enum WorkspaceError {
   DefinitionsWithNamespaceAlreadyExist(String),
   #[error("definitions with name '{0}' already exist in workspace")]
   DefinitionsWithNameAlreadyExist(String),
+  #[error("{0}")]
+  ValidationFailed(String),
+  #[error("definitions with name '{0}' not found in workspace")]
+  DefinitionsNotFound(String),
+  #[error("decision '{1}' not found in definitions '{0}'")]
+  DecisionNotFound(String, String),
+  #[error("decision logic of decision '{0}' is not a decision table")]
+  DecisionLogicIsNotDecisionTable(String),
 }
 
 impl From<WorkspaceError> for DmntkError {
@@ -63,3 +72,19 @@ pub fn err_definitions_with_namespace_already_exists(definitions_namespace: &str
 pub fn err_definitions_with_name_already_exists(definitions_name: &str) -> DmntkError {
   WorkspaceError::DefinitionsWithNameAlreadyExist(definitions_name.to_string()).into()
 }
+
+pub fn err_definitions_not_found(definitions_name: &str) -> DmntkError {
+  WorkspaceError::DefinitionsNotFound(definitions_name.to_string()).into()
+}
+
+pub fn err_decision_not_found(definitions_name: &str, decision_name: &str) -> DmntkError {
+  WorkspaceError::DecisionNotFound(definitions_name.to_string(), decision_name.to_string()).into()
+}
+
+pub fn err_decision_logic_is_not_decision_table(decision_name: &str) -> DmntkError {
+  WorkspaceError::DecisionLogicIsNotDecisionTable(decision_name.to_string()).into()
+}
+
+pub fn err_validation_failed(report: &ValidationReport) -> DmntkError {
+  WorkspaceError::ValidationFailed(report.to_string()).into()
+}
@@ -43,13 +43,19 @@
 //! unique inside [Workspace]. In consequence, the same [Definitions] can be accessed using
 //! either a `namespace` or `name` attribute, so there will be an error reported, when two definitions
 //! deployed in a single workspace have the same `namespace` or `name` attributes.
+//!
+//! **Business calendars** loaded from holiday calendar files (`*.holidays`) are named after the file,
+//! e.g. calendar loaded from file `PL.holidays` is named `PL`, and are available for all models
+//! deployed in the same workspace through business calendar functions like `add business days(date, days, "PL")`.
 
 use crate::errors::*;
 use dmntk_common::Result;
 use dmntk_feel::context::FeelContext;
 use dmntk_feel::values::Value;
-use dmntk_model::model::{Definitions, NamedElement};
-use dmntk_model_evaluator::ModelEvaluator;
+use dmntk_feel::FeelType;
+use dmntk_feel_evaluator::{BusinessCalendar, BusinessCalendars, CancellationToken, EvaluationLimits, TypeError};
+use dmntk_model::model::{Definitions, ExpressionInstance, NamedElement};
+use dmntk_model_evaluator::{ModelEvaluator, PartialEvaluation, RuleConditions, ValidationMode, ValidationReport};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -65,6 +71,12 @@ pub struct Workspace {
   definitions_by_name: HashMap<String, Arc<Definitions>>,
   /// Map of [ModelEvaluator] indexed by [Definitions].**name** attribute.
   model_evaluators_by_name: HashMap<String, Arc<ModelEvaluator>>,
+  /// Limits applied to each evaluation of an invocable.
+  limits: EvaluationLimits,
+  /// Mode of validating input and output values, applied to all model evaluators.
+  validation_mode: ValidationMode,
+  /// Business calendars available to all evaluations in this workspace.
+  calendars: Arc<BusinessCalendars>,
 }
 
 impl Workspace {
@@ -76,6 +88,9 @@ impl Workspace {
       definitions_by_namespace: HashMap::new(),
       definitions_by_name: HashMap::new(),
       model_evaluators_by_name: HashMap::new(),
+      limits: EvaluationLimits::default(),
+      validation_mode: ValidationMode::default(),
+      calendars: Arc::new(BusinessCalendars::default()),
     };
     // load and deploy all DMN models from specified directory
     if let Some(dir) = opt_dir {
@@ -130,6 +145,7 @@ impl Workspace {
     for definitions in &self.definitions {
       match ModelEvaluator::new(definitions) {
         Ok(model_evaluator) => {
+          model_evaluator.set_validation_mode(self.validation_mode);
           let name = definitions.name().to_string();
           self.model_evaluators_by_name.insert(name, model_evaluator);
         }
@@ -140,14 +156,164 @@ impl Workspace {
     }
     Ok(())
   }
+  /// Sets the limits applied to each evaluation of an invocable.
+  pub fn set_limits(&mut self, limits: EvaluationLimits) {
+    self.limits = limits;
+  }
+  /// Sets the mode of validating input and output values for all deployed and later deployed model evaluators.
+  pub fn set_validation_mode(&mut self, validation_mode: ValidationMode) {
+    self.validation_mode = validation_mode;
+    for model_evaluator in self.model_evaluators_by_name.values() {
+      model_evaluator.set_validation_mode(validation_mode);
+    }
+  }
   /// Evaluates invocable (decision, business knowledge model or decision service) deployed in workspace.
   pub fn evaluate_invocable(&self, model_name: &str, invocable_name: &str, input_data: &FeelContext) -> Result<Value> {
+    self.evaluate_invocable_with_cancellation(model_name, invocable_name, input_data, &CancellationToken::new())
+  }
+  /// Evaluates invocable deployed in workspace, the evaluation may be cancelled using specified token.
+  ///
+  /// An error is reported when the evaluation exceeds workspace limits or is cancelled,
+  /// or when any value violates its constraints in strict validation mode.
+  pub fn evaluate_invocable_with_cancellation(
+    &self,
+    model_name: &str,
+    invocable_name: &str,
+    input_data: &FeelContext,
+    token: &CancellationToken,
+  ) -> Result<Value> {
+    self
+      .evaluate_invocable_validated(model_name, invocable_name, input_data, token)?
+      .map_err(|report| err_validation_failed(&report))
+  }
+  /// Evaluates invocable deployed in workspace, the evaluation may be cancelled using specified token.
+  ///
+  /// An error is reported when the evaluation exceeds workspace limits or is cancelled.
+  /// In strict validation mode, values violating their constraints are reported in [ValidationReport].
+  pub fn evaluate_invocable_validated(
+    &self,
+    model_name: &str,
+    invocable_name: &str,
+    input_data: &FeelContext,
+    token: &CancellationToken,
+  ) -> Result<Result<Value, ValidationReport>> {
     if let Some(model_evaluator) = self.model_evaluators_by_name.get(model_name) {
-      Ok(model_evaluator.evaluate_invocable(invocable_name, input_data))
+      self.evaluate_with(token, || model_evaluator.evaluate_invocable_validated(invocable_name, input_data))
     } else {
       Err(err_model_evaluator_is_not_deployed(model_name))
     }
   }
+  /// Evaluates multiple invocables deployed in the same model using the same input data,
+  /// when no invocable names are specified, all decisions in the model are evaluated.
+  ///
+  /// Returns a context containing the results of all invocables, indexed by invocable names.
+  /// Each decision is evaluated at most once, no matter how many invocables require it.
+  pub fn evaluate_invocables(&self, model_name: &str, invocable_names: Option<&[String]>, input_data: &FeelContext) -> Result<Value> {
+    self
+      .evaluate_invocables_validated(model_name, invocable_names, input_data, &CancellationToken::new())?
+      .map_err(|report| err_validation_failed(&report))
+  }
+  /// Evaluates multiple invocables deployed in the same model, the evaluation may be cancelled using specified token.
+  ///
+  /// An error is reported when the evaluation exceeds workspace limits or is cancelled.
+  /// In strict validation mode, values violating their constraints are reported in [ValidationReport].
+  pub fn evaluate_invocables_validated(
+    &self,
+    model_name: &str,
+    invocable_names: Option<&[String]>,
+    input_data: &FeelContext,
+    token: &CancellationToken,
+  ) -> Result<Result<Value, ValidationReport>> {
+    if let Some(model_evaluator) = self.model_evaluators_by_name.get(model_name) {
+      let invocable_names = invocable_names.map(|names| names.to_vec()).unwrap_or_else(|| model_evaluator.decision_names());
+      self.evaluate_with(token, || model_evaluator.evaluate_invocables_validated(&invocable_names, input_data))
+    } else {
+      Err(err_model_evaluator_is_not_deployed(model_name))
+    }
+  }
+  /// Invokes a decision service deployed in the model, the way process engines invoke decision services.
+  ///
+  /// Values of input decisions are taken from input data, input decisions provided in input data are not evaluated.
+  /// When output decision names are specified, only these output decisions are evaluated
+  /// and returned in a context, indexed by decision names.
+  pub fn invoke_decision_service(
+    &self,
+    model_name: &str,
+    decision_service_name: &str,
+    output_decision_names: Option<&[String]>,
+    input_data: &FeelContext,
+  ) -> Result<Value> {
+    self
+      .invoke_decision_service_validated(model_name, decision_service_name, output_decision_names, input_data, &CancellationToken::new())?
+      .map_err(|report| err_validation_failed(&report))
+  }
+  /// Invokes a decision service deployed in the model, the invocation may be cancelled using specified token.
+  ///
+  /// An error is reported when the evaluation exceeds workspace limits or is cancelled.
+  /// In strict validation mode, values violating their constraints are reported in [ValidationReport].
+  pub fn invoke_decision_service_validated(
+    &self,
+    model_name: &str,
+    decision_service_name: &str,
+    output_decision_names: Option<&[String]>,
+    input_data: &FeelContext,
+    token: &CancellationToken,
+  ) -> Result<Result<Value, ValidationReport>> {
+    if let Some(model_evaluator) = self.model_evaluators_by_name.get(model_name) {
+      self.evaluate_with(token, || {
+        model_evaluator.invoke_decision_service_validated(decision_service_name, input_data, output_decision_names)
+      })
+    } else {
+      Err(err_model_evaluator_is_not_deployed(model_name))
+    }
+  }
+  /// Evaluates all decisions deployed in the model, for which all required input data are present,
+  /// and reports the names of missing input data blocking the evaluation of remaining decisions.
+  /// Missing input data are collected statically from information requirements of decisions.
+  pub fn evaluate_partial(&self, model_name: &str, input_data: &FeelContext) -> Result<PartialEvaluation> {
+    if let Some(model_evaluator) = self.model_evaluators_by_name.get(model_name) {
+      self.evaluate_with(&CancellationToken::new(), || model_evaluator.evaluate_partial(input_data))
+    } else {
+      Err(err_model_evaluator_is_not_deployed(model_name))
+    }
+  }
+  /// Returns the input conditions under which the decision table defined as the logic
+  /// of the decision with specified name produces the desired output.
+  pub fn query_decision_table(&self, model_name: &str, decision_name: &str, output: &Value) -> Result<Vec<RuleConditions>> {
+    let definitions = self.definitions_by_name.get(model_name).ok_or_else(|| err_definitions_not_found(model_name))?;
+    let decision = definitions
+      .decisions()
+      .into_iter()
+      .find(|decision| decision.name() == decision_name)
+      .ok_or_else(|| err_decision_not_found(model_name, decision_name))?;
+    if let Some(ExpressionInstance::DecisionTable(decision_table)) = decision.decision_logic() {
+      dmntk_model_evaluator::query_decision_table(decision_table, output)
+    } else {
+      Err(err_decision_logic_is_not_decision_table(decision_name))
+    }
+  }
+  /// Returns the inferred result type and type errors detected in the logic of the decision with specified name,
+  /// so the callers can validate their requests before evaluating the decision.
+  pub fn decision_type(&self, model_name: &str, decision_name: &str) -> Result<(FeelType, Vec<TypeError>)> {
+    if let Some(model_evaluator) = self.model_evaluators_by_name.get(model_name) {
+      model_evaluator
+        .decision_type(decision_name)
+        .ok_or_else(|| err_decision_not_found(model_name, decision_name))
+    } else {
+      Err(err_model_evaluator_is_not_deployed(model_name))
+    }
+  }
+  /// Adds a business calendar to workspace, the calendar replaces the calendar with the same name.
+  pub fn add_calendar(&mut self, calendar: BusinessCalendar) {
+    Arc::make_mut(&mut self.calendars).add(calendar);
+  }
+  /// Utility function that runs the evaluation with workspace limits and business calendars.
+  fn evaluate_with<R, F>(&self, token: &CancellationToken, f: F) -> Result<R>
+  where
+    F: FnOnce() -> R,
+  {
+    dmntk_feel_evaluator::evaluate_with_calendars(&self.calendars, || dmntk_feel_evaluator::evaluate_with_limits(&self.limits, token, f))
+  }
   /// Utility function that deletes all definitions in workspace.
   fn clear_definitions(&mut self) {
     self.definitions_by_name.clear();
@@ -163,6 +329,17 @@ impl Workspace {
     for entry in WalkDir::new(dir).into_iter().filter_map(|e| e.ok()) {
       if entry.file_type().is_file() {
         let file_name = entry.file_name().to_string_lossy();
+        if let Some(calendar_name) = file_name.strip_suffix(".holidays") {
+          if let Ok(text) = std::fs::read_to_string(entry.path()) {
+            match BusinessCalendar::parse(calendar_name, &text) {
+              Ok(calendar) => self.add_calendar(calendar),
+              Err(reason) => {
+                // TODO update status report
+                eprintln!("{}", reason);
+              }
+            }
+          }
+        }
         if file_name.ends_with(".dmn") {
           if let Ok(xml) = std::fs::read_to_string(entry.path()) {
             match dmntk_model::parse(&xml) {
@@ -290,4 +467,214 @@ mod tests {
       .unwrap();
     assert_eq!(r#"null(invocable with name 'Good bye message' not found)"#, value.to_string());
   }
+
+  #[test]
+  fn test_load_calendars() {
+    let dir = std::env::temp_dir().join("dmntk-workspace-test-load-calendars");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("WORKSPACE-TEST.holidays"), "--12-25 Christmas Day\n--12-26 Second Day of Christmas\n").unwrap();
+    std::fs::write(dir.join("due-date.dmn"), DUE_DATE_DMN).unwrap();
+    let workspace = Workspace::new(Some(dir.clone()));
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_state(&workspace, (1, 1, 1, 1));
+    let input_data = dmntk_feel_evaluator::evaluate_context(&Scope::default(), r#"{Order Date: date("2022-12-23")}"#).unwrap();
+    let value = workspace.evaluate_invocable("due-date", "Due Date", &input_data).unwrap();
+    assert_eq!("2022-12-28", value.to_string());
+  }
+
+  #[test]
+  fn test_calendars_are_not_shared() {
+    let load = |name: &str, holidays: Option<&str>| {
+      let dir = std::env::temp_dir().join(name);
+      std::fs::create_dir_all(&dir).unwrap();
+      if let Some(holidays) = holidays {
+        std::fs::write(dir.join("WORKSPACE-TEST.holidays"), holidays).unwrap();
+      }
+      std::fs::write(dir.join("due-date.dmn"), DUE_DATE_DMN).unwrap();
+      let workspace = Workspace::new(Some(dir.clone()));
+      std::fs::remove_dir_all(&dir).unwrap();
+      workspace
+    };
+    let workspace_a = load("dmntk-workspace-test-calendars-a", Some("--12-26 Second Day of Christmas\n--12-27 Holiday\n"));
+    let workspace_b = load("dmntk-workspace-test-calendars-b", Some("# no holidays\n"));
+    let workspace_c = load("dmntk-workspace-test-calendars-c", None);
+    let input_data = dmntk_feel_evaluator::evaluate_context(&Scope::default(), r#"{Order Date: date("2022-12-23")}"#).unwrap();
+    let evaluate = |workspace: &Workspace| workspace.evaluate_invocable("due-date", "Due Date", &input_data).unwrap().to_string();
+    assert_eq!("2022-12-29", evaluate(&workspace_a));
+    assert_eq!("2022-12-27", evaluate(&workspace_b));
+    assert_eq!(
+      "null([add business days] business calendar 'WORKSPACE-TEST' is not available)",
+      evaluate(&workspace_c)
+    );
+    assert_eq!("2022-12-29", evaluate(&workspace_a));
+  }
+
+  #[test]
+  fn test_evaluate_with_limits() {
+    let mut workspace = Workspace::new(None);
+    let definitions = dmntk_model::parse(dmntk_examples::DMN_2_0001).unwrap();
+    assert!(workspace.add(definitions).is_ok());
+    assert!(workspace.deploy().is_ok());
+    let input_data = dmntk_feel_evaluator::evaluate_context(&Scope::default(), r#"{Full Name: "John Doe"}"#).unwrap();
+    let token = CancellationToken::new();
+    token.cancel();
+    let result = workspace.evaluate_invocable_with_cancellation("compliance-level-2-test-0001", "Greeting Message", &input_data, &token);
+    assert_eq!(
+      r#"Err(DmntkError("FeelEvaluatorError: evaluation interrupted: evaluation cancelled"))"#,
+      format!("{:?}", result)
+    );
+    workspace.set_limits(EvaluationLimits {
+      max_iterations: Some(0),
+      ..Default::default()
+    });
+    let value = workspace
+      .evaluate_invocable("compliance-level-2-test-0001", "Greeting Message", &input_data)
+      .unwrap();
+    assert_eq!(r#""Hello John Doe""#, value.to_string());
+  }
+
+  #[test]
+  fn test_evaluate_validated() {
+    let mut workspace = Workspace::new(None);
+    let definitions = dmntk_model::parse(dmntk_examples::DMN_2_0003).unwrap();
+    assert!(workspace.add(definitions).is_ok());
+    workspace.set_validation_mode(ValidationMode::Strict);
+    assert!(workspace.deploy().is_ok());
+    let input_data = dmntk_feel_evaluator::evaluate_context(&Scope::default(), r#"{Employment Status: "RETIRED"}"#).unwrap();
+    let token = CancellationToken::new();
+    let report = workspace
+      .evaluate_invocable_validated("compliance-level-2-test-0003", "Employment Status Statement", &input_data, &token)
+      .unwrap()
+      .unwrap_err();
+    assert_eq!(1, report.violations.len());
+    assert_eq!("input data 'Employment Status'", report.violations[0].element);
+    assert_eq!(
+      r#"Err(DmntkError("WorkspaceError: validation failed: input data 'Employment Status' value \"RETIRED\" violates type 'tEmploymentStatus' with allowed values '\"UNEMPLOYED\",\"EMPLOYED\",\"SELF-EMPLOYED\",\"STUDENT\"'"))"#,
+      format!(
+        "{:?}",
+        workspace.evaluate_invocable("compliance-level-2-test-0003", "Employment Status Statement", &input_data)
+      )
+    );
+    workspace.set_validation_mode(ValidationMode::Lenient);
+    let value = workspace
+      .evaluate_invocable("compliance-level-2-test-0003", "Employment Status Statement", &input_data)
+      .unwrap();
+    assert_eq!("null(addition err 2)", value.to_string());
+  }
+
+  #[test]
+  fn test_evaluate_invocables() {
+    let mut workspace = Workspace::new(None);
+    let definitions = dmntk_model::parse(dmntk_examples::DMN_2_0001).unwrap();
+    assert!(workspace.add(definitions).is_ok());
+    assert!(workspace.deploy().is_ok());
+    let input_data = dmntk_feel_evaluator::evaluate_context(&Scope::default(), r#"{Full Name: "John Doe"}"#).unwrap();
+    let value = workspace.evaluate_invocables("compliance-level-2-test-0001", None, &input_data).unwrap();
+    assert_eq!(r#"{Greeting Message: "Hello John Doe"}"#, value.to_string());
+    let invocable_names = vec!["Greeting Message".to_string(), "Good bye message".to_string()];
+    let value = workspace
+      .evaluate_invocables("compliance-level-2-test-0001", Some(&invocable_names), &input_data)
+      .unwrap();
+    assert_eq!(
+      r#"{Good bye message: null(invocable with name 'Good bye message' not found), Greeting Message: "Hello John Doe"}"#,
+      value.to_string()
+    );
+    let result = workspace.evaluate_invocables("compliance-level-2-test-0002", None, &input_data);
+    assert_eq!(Err(err_model_evaluator_is_not_deployed("compliance-level-2-test-0002")), result);
+  }
+
+  #[test]
+  fn test_invoke_decision_service() {
+    let mut workspace = Workspace::new(None);
+    let definitions = dmntk_model::parse(dmntk_examples::DMN_3_0085).unwrap();
+    assert!(workspace.add(definitions).is_ok());
+    assert!(workspace.deploy().is_ok());
+    let input_data = dmntk_feel_evaluator::evaluate_context(
+      &Scope::default(),
+      r#"{decision_003_input_1: "B", decision_003_input_2: "C", inputData_003: "D"}"#,
+    )
+    .unwrap();
+    let value = workspace
+      .invoke_decision_service("compliance-level-3-test-0085", "decisionService_003", None, &input_data)
+      .unwrap();
+    assert_eq!(r#""A B C D""#, value.to_string());
+    let output_decision_names = vec!["decision_003".to_string()];
+    let value = workspace
+      .invoke_decision_service("compliance-level-3-test-0085", "decisionService_003", Some(&output_decision_names), &input_data)
+      .unwrap();
+    assert_eq!(r#"{decision_003: "A B C D"}"#, value.to_string());
+    let result = workspace.invoke_decision_service("compliance-level-3-test-0086", "decisionService_003", None, &input_data);
+    assert_eq!(Err(err_model_evaluator_is_not_deployed("compliance-level-3-test-0086")), result);
+  }
+
+  #[test]
+  fn test_decision_type() {
+    let mut workspace = Workspace::new(None);
+    let definitions = dmntk_model::parse(dmntk_examples::DMN_2_0001).unwrap();
+    assert!(workspace.add(definitions).is_ok());
+    assert!(workspace.deploy().is_ok());
+    assert_eq!(
+      Ok((FeelType::String, vec![])),
+      workspace.decision_type("compliance-level-2-test-0001", "Greeting Message")
+    );
+    assert_eq!(
+      Err(err_decision_not_found("compliance-level-2-test-0001", "Farewell Message")),
+      workspace.decision_type("compliance-level-2-test-0001", "Farewell Message")
+    );
+  }
+
+  #[test]
+  fn test_evaluate_partial() {
+    let mut workspace = Workspace::new(None);
+    let definitions = dmntk_model::parse(dmntk_examples::DMN_2_0001).unwrap();
+    assert!(workspace.add(definitions).is_ok());
+    assert!(workspace.deploy().is_ok());
+    let partial_evaluation = workspace.evaluate_partial("compliance-level-2-test-0001", &FeelContext::default()).unwrap();
+    assert_eq!("{}", partial_evaluation.results.to_string());
+    assert_eq!(
+      Some(&vec!["Full Name".to_string()]),
+      partial_evaluation.missing_input_data.get("Greeting Message")
+    );
+    let input_data = dmntk_feel_evaluator::evaluate_context(&Scope::default(), r#"{Full Name: "John Doe"}"#).unwrap();
+    let partial_evaluation = workspace.evaluate_partial("compliance-level-2-test-0001", &input_data).unwrap();
+    assert_eq!(r#"{Greeting Message: "Hello John Doe"}"#, partial_evaluation.results.to_string());
+    assert!(partial_evaluation.missing_input_data.is_empty());
+  }
+
+  #[test]
+  fn test_query_decision_table() {
+    let mut workspace = Workspace::new(None);
+    let definitions = dmntk_model::parse(dmntk_examples::DMN_2_0001).unwrap();
+    assert!(workspace.add(definitions).is_ok());
+    let output = Value::String("Hello".to_string());
+    assert_eq!(
+      Err(err_definitions_not_found("compliance-level-2-test-0002")),
+      workspace.query_decision_table("compliance-level-2-test-0002", "Greeting Message", &output)
+    );
+    assert_eq!(
+      Err(err_decision_not_found("compliance-level-2-test-0001", "Good bye message")),
+      workspace.query_decision_table("compliance-level-2-test-0001", "Good bye message", &output)
+    );
+    assert_eq!(
+      Err(err_decision_logic_is_not_decision_table("Greeting Message")),
+      workspace.query_decision_table("compliance-level-2-test-0001", "Greeting Message", &output)
+    );
+  }
+
+  const DUE_DATE_DMN: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<definitions namespace="https://dmntk.io/due-date" name="due-date" id="_b4d9ed8c-1bd4-4a3b-9e8e-1c4a4c9d4d42" xmlns="https://www.omg.org/spec/DMN/20191111/MODEL/">
+    <decision name="Due Date" id="_2bb3c0a4-6c3e-4a8c-b3d5-0e0b5b6c7c11">
+        <variable typeRef="date" name="Due Date"/>
+        <informationRequirement id="_5a7e6b1e-0f58-4a6f-a2f1-1d2c7c8a9b01">
+            <requiredInput href="#_8f4c3d2e-6b1a-4c5d-9e8f-7a6b5c4d3e21"/>
+        </informationRequirement>
+        <literalExpression>
+            <text>add business days(Order Date, 2, "WORKSPACE-TEST")</text>
+        </literalExpression>
+    </decision>
+    <inputData name="Order Date" id="_8f4c3d2e-6b1a-4c5d-9e8f-7a6b5c4d3e21">
+        <variable typeRef="date" name="Order Date"/>
+    </inputData>
+</definitions>
+"##;
 }